@@ -1,4 +1,4 @@
-import type { Bot, Match, MatchDetail, Move } from './types';
+import type { Bot, MatchDetail, MatchList, MatchStatus, Move } from './types';
 
 const BASE_URL = '/api';
 
@@ -52,6 +52,104 @@ export interface HeadToHeadMatrix {
   records: HeadToHeadRecord[];
 }
 
+/** One bot's results with a given color */
+export interface ColorRecord {
+  /** Games played with this color */
+  games: number;
+  /** Games won */
+  wins: number;
+  /** Games drawn */
+  draws: number;
+  /** Games lost */
+  losses: number;
+}
+
+/** Detailed results between one pair of bots, from bot_a's side */
+export interface HeadToHeadSummary {
+  /** First bot of the pairing */
+  bot_a: string;
+  /** Second bot of the pairing */
+  bot_b: string;
+  /** Total finished games between the two bots */
+  games: number;
+  /** Games won by bot_a */
+  bot_a_wins: number;
+  /** Games won by bot_b */
+  bot_b_wins: number;
+  /** Drawn games */
+  draws: number;
+  /** Fraction of games drawn */
+  draw_rate: number;
+  /** bot_a's results as white */
+  bot_a_as_white: ColorRecord;
+  /** bot_a's results as black */
+  bot_a_as_black: ColorRecord;
+  /** Average game length in plies */
+  avg_moves: number;
+  /** Last 10 results for bot_a, most recent first (W/D/L) */
+  recent_form: string;
+}
+
+/** Results for an opening detected from stored game moves */
+export interface OpeningPerformance {
+  /** Opening ID from the opening database */
+  opening_id: string;
+  /** Opening name */
+  name: string;
+  /** ECO code, if known */
+  eco: string | null;
+  /** Number of finished games reaching this opening */
+  games_played: number;
+  /** Number of games won by white */
+  white_wins: number;
+  /** Number of drawn games */
+  draws: number;
+  /** Number of games won by black */
+  black_wins: number;
+  /** Average game length in plies */
+  avg_game_length: number;
+}
+
+/** Named opening from the opening database */
+export interface OpeningLine {
+  /** Opening ID */
+  id: string;
+  /** Opening name */
+  name: string;
+  /** ECO code, if known */
+  eco: string | null;
+  /** Moves of the opening in UCI notation */
+  moves: string[];
+  /** FEN of the position after the opening's moves */
+  fen: string;
+}
+
+/** Results of stored games that played a move after the explored line */
+export interface ExplorerMove {
+  /** Move in UCI notation */
+  uci: string;
+  /** Number of finished games that played this move */
+  games: number;
+  /** Number of those games won by white */
+  white_wins: number;
+  /** Number of those games drawn */
+  draws: number;
+  /** Number of those games won by black */
+  black_wins: number;
+  /** White's average score in those games (win 1, draw 0.5) */
+  score: number;
+}
+
+/** Opening explorer view of a line of moves */
+export interface OpeningExplorer {
+  /** The explored line in UCI notation */
+  moves: string[];
+  /** Named openings continuing the line */
+  openings: OpeningLine[];
+  /** Moves played next in stored games, most played first */
+  next_moves: ExplorerMove[];
+}
+
 /** Opening statistics from the database */
 export interface OpeningStats {
   /** ECO code (e.g., "B20") */
@@ -157,13 +255,20 @@ export const api = {
    * Get matches with optional filtering
    * @param params - Optional filter parameters
    * @param params.bot - Filter by bot name
-   * @param params.limit - Maximum number of results
+   * @param params.status - Filter by match status
+   * @param params.limit - Maximum number of results (at most 100)
    * @param params.offset - Pagination offset
-   * @returns List of matches
+   * @returns Page of matches with the total matching count
    */
-  getMatches(params?: { bot?: string; limit?: number; offset?: number }): Promise<Match[]> {
+  getMatches(params?: {
+    bot?: string;
+    status?: MatchStatus;
+    limit?: number;
+    offset?: number;
+  }): Promise<MatchList> {
     const searchParams = new URLSearchParams();
     if (params?.bot) searchParams.set('bot', params.bot);
+    if (params?.status) searchParams.set('status', params.status);
     if (params?.limit) searchParams.set('limit', params.limit.toString());
     if (params?.offset) searchParams.set('offset', params.offset.toString());
 
@@ -192,12 +297,15 @@ export const api = {
   /**
    * Create a new match between two bots
    * @param req - Match creation parameters
+   * @param token - API token for the server's write endpoints
    * @returns Created match details
    */
-  async createMatch(req: CreateMatchRequest): Promise<Match> {
+  async createMatch(req: CreateMatchRequest, token?: string): Promise<Match> {
+    const headers: Record<string, string> = { 'Content-Type': 'application/json' };
+    if (token) headers['Authorization'] = `Bearer ${token}`;
     const response = await fetch(`${BASE_URL}/matches`, {
       method: 'POST',
-      headers: { 'Content-Type': 'application/json' },
+      headers,
       body: JSON.stringify(req),
     });
     if (!response.ok) {
@@ -233,6 +341,36 @@ export const api = {
     return fetchJson('/stats/head-to-head');
   },
 
+  /**
+   * Get detailed head-to-head statistics for one pair of bots
+   * @param botA - Bot whose side results are reported from
+   * @param botB - Opponent bot
+   * @returns Summary of all finished games between the two bots
+   */
+  getHeadToHeadPair(botA: string, botB: string): Promise<HeadToHeadSummary> {
+    return fetchJson(
+      `/stats/head-to-head/${encodeURIComponent(botA)}/${encodeURIComponent(botB)}`
+    );
+  },
+
+  /**
+   * Get results per opening detected from stored games
+   * @returns Opening results, most played first
+   */
+  getOpeningPerformance(): Promise<OpeningPerformance[]> {
+    return fetchJson('/stats/openings');
+  },
+
+  /**
+   * Explore the openings and stored games continuing a line of moves
+   * @param moves - Line of UCI moves from the starting position
+   * @returns Continuing openings and next-move statistics
+   */
+  exploreOpenings(moves: string[]): Promise<OpeningExplorer> {
+    const params = new URLSearchParams({ moves: moves.join(',') });
+    return fetchJson(`/openings/explore?${params}`);
+  },
+
   /**
    * Get available match presets
    * @returns List of preset configurations
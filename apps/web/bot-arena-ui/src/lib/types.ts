@@ -16,6 +16,9 @@ export interface Bot {
   draws: number;
 }
 
+/** Lifecycle states of a match */
+export type MatchStatus = 'pending' | 'running' | 'completed' | 'failed' | 'cancelled';
+
 /** Match between two bots */
 export interface Match {
   /** Unique match ID (UUID) */
@@ -38,8 +41,8 @@ export interface Match {
   started_at: string;
   /** ISO timestamp when match finished */
   finished_at: string | null;
-  /** Match status: pending, in_progress, completed */
-  status: string;
+  /** Match status */
+  status: MatchStatus;
 }
 
 /** Individual game within a match */
@@ -74,6 +77,14 @@ export interface Move {
   stockfish_eval: number | null;
 }
 
+/** A page of matches from `GET /api/matches` */
+export interface MatchList {
+  /** Matches on this page */
+  matches: Match[];
+  /** Total number of matches matching the filter, across all pages */
+  total: number;
+}
+
 /** Match with associated games */
 export interface MatchDetail extends Match {
   /** List of games in this match */
@@ -52,8 +52,8 @@ export function createLiveMatchStore(matchId: string) {
             moves: [...s.moves, {
               ply: s.moves.length + 1,
               uci: msg.uci,
-              san: null,
-              fen_after: '',
+              san: msg.san ?? null,
+              fen_after: msg.fen ?? '',
               bot_eval: msg.centipawns,  // Note: server sends 'centipawns' not 'eval'
               stockfish_eval: null,
             }],
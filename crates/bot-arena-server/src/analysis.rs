@@ -123,6 +123,21 @@ impl EnginePool {
         Ok(result)
     }
 
+    /// Run a blocking job that drives its own engine process.
+    ///
+    /// The job receives the Stockfish path and runs on the blocking thread
+    /// pool while holding one of the pool's permits, so it counts against
+    /// the same concurrency limit as [`analyze`](Self::analyze).
+    pub async fn run_blocking<T, F>(&self, job: F) -> anyhow::Result<T>
+    where
+        F: FnOnce(&str) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let _permit = self.semaphore.acquire().await?;
+        let stockfish_path = self.stockfish_path.clone();
+        Ok(tokio::task::spawn_blocking(move || job(&stockfish_path)).await?)
+    }
+
     /// Get the Stockfish executable path.
     #[cfg(test)]
     pub fn stockfish_path(&self) -> &str {
@@ -185,6 +200,17 @@ impl LazyEnginePool {
         self.get().analyze(fen, depth).await
     }
 
+    /// Run a blocking engine job using the lazy-initialized pool.
+    ///
+    /// See [`EnginePool::run_blocking`].
+    pub async fn run_blocking<T, F>(&self, job: F) -> anyhow::Result<T>
+    where
+        F: FnOnce(&str) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        self.get().run_blocking(job).await
+    }
+
     /// Get the configured Stockfish path.
     pub fn stockfish_path(&self) -> &str {
         &self.stockfish_path
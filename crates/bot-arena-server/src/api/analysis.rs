@@ -1,14 +1,20 @@
 //! Analysis API endpoints.
 
 use axum::{
-    extract::{Query, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     Json,
 };
+use chess_analysis::{AnalysisConfig, AnalyzerError, GameAnalysis, GameAnalyzer, MoveInput};
 use serde::{Deserialize, Serialize};
 
+use crate::models::players_for_game;
+use crate::repo::{AnalysisRepo, MatchRepo};
 use crate::AppState;
 
+/// Deepest search accepted for a whole-game analysis.
+const MAX_GAME_ANALYSIS_DEPTH: u32 = 30;
+
 /// Query parameters for analysis request.
 #[derive(Debug, Deserialize)]
 pub struct AnalysisQuery {
@@ -77,9 +83,148 @@ pub async fn get_analysis(
     }))
 }
 
+/// Query parameters for analyzing a stored game.
+#[derive(Debug, Deserialize)]
+pub struct AnalyzeGameQuery {
+    /// Search depth per position (default: 20).
+    #[serde(default = "default_game_depth")]
+    pub depth: u32,
+}
+
+fn default_game_depth() -> u32 {
+    20
+}
+
+/// The players and result of a stored game.
+struct GameInfo {
+    white: String,
+    black: String,
+    result: String,
+}
+
+/// Load the players and result of a game, or `None` if it doesn't exist.
+fn game_info(state: &AppState, id: &str) -> rusqlite::Result<Option<GameInfo>> {
+    use rusqlite::OptionalExtension;
+
+    let conn = state.db.lock().unwrap();
+    conn.query_row(
+        "SELECT g.game_number, g.result, m.white_bot, m.black_bot
+         FROM games g
+         JOIN matches m ON g.match_id = m.id
+         WHERE g.id = ?1",
+        [id],
+        |row| {
+            let game_number: i32 = row.get(0)?;
+            let result: Option<String> = row.get(1)?;
+            let match_white: String = row.get(2)?;
+            let match_black: String = row.get(3)?;
+            let (white, black) = players_for_game(game_number, match_white, match_black);
+            Ok(GameInfo {
+                white,
+                black,
+                result: result.unwrap_or_else(|| "*".to_string()),
+            })
+        },
+    )
+    .optional()
+}
+
+/// POST /api/games/:id/analyze?depth=20
+///
+/// Analyzes every move of a stored game with Stockfish, stores the result
+/// and returns it. A game already analyzed at the requested depth is
+/// returned from the database without running the engine again.
+///
+/// # Query Parameters
+/// * `depth` - Search depth per position (optional, default: 20, at most 30)
+///
+/// # Errors
+/// * 400 Bad Request - Depth out of range, or the stored moves are invalid
+/// * 404 Not Found - Game doesn't exist
+/// * 503 Service Unavailable - Stockfish not configured
+/// * 500 Internal Server Error - Database or engine failure
+pub async fn analyze_game(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<AnalyzeGameQuery>,
+) -> Result<Json<GameAnalysis>, (StatusCode, String)> {
+    let depth = query.depth;
+    if !(1..=MAX_GAME_ANALYSIS_DEPTH).contains(&depth) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("depth must be between 1 and {}", MAX_GAME_ANALYSIS_DEPTH),
+        ));
+    }
+
+    let db_error = |e: rusqlite::Error| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string());
+    let analyses = AnalysisRepo::new(state.db.clone());
+    if let Some(analysis) = analyses.get(&id, depth).map_err(db_error)? {
+        return Ok(Json(analysis));
+    }
+
+    let info = game_info(&state, &id)
+        .map_err(db_error)?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("Game {} not found", id)))?;
+    let pool = state.engine_pool.clone().ok_or_else(|| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Stockfish not configured".to_string(),
+        )
+    })?;
+
+    let moves: Vec<MoveInput> = MatchRepo::new(state.db.clone())
+        .get_moves(&id)
+        .map_err(db_error)?
+        .into_iter()
+        .map(|m| MoveInput {
+            uci: m.uci,
+            bot_eval_cp: m.bot_eval,
+            bot_eval_mate: None,
+            bot_depth: None,
+            bot_nodes: None,
+            bot_time_ms: None,
+            bot_pv: Vec::new(),
+        })
+        .collect();
+
+    let game_id = id.clone();
+    let analysis = pool
+        .run_blocking(move |stockfish_path| {
+            let config = AnalysisConfig {
+                depth,
+                ..AnalysisConfig::default()
+            };
+            GameAnalyzer::new(stockfish_path, config)?.analyze_game(
+                &game_id,
+                &info.white,
+                &info.black,
+                &moves,
+                &info.result,
+                None,
+            )
+        })
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .map_err(|e| match e {
+            AnalyzerError::InvalidGame(_) => (StatusCode::BAD_REQUEST, e.to_string()),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        })?;
+
+    analyses.save(&id, depth, &analysis).map_err(db_error)?;
+    Ok(Json(analysis))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::analysis::LazyEnginePool;
+    use crate::db::init_db;
+    use crate::ws;
+    use bot_arena::config::ArenaConfig;
+    use chess_analysis::MoveQuality;
+    use std::sync::Arc;
+    #[cfg(unix)]
+    use test_support::FakeEngine;
 
     #[test]
     fn test_default_depth() {
@@ -93,6 +238,123 @@ mod tests {
         assert_eq!(query.depth, 20); // default
     }
 
+    #[test]
+    fn test_analyze_game_query_default_depth() {
+        let query: AnalyzeGameQuery = serde_json::from_str("{}").unwrap();
+        assert_eq!(query.depth, 20);
+    }
+
+    /// State with a seeded game, 1. e4 f6 2. d4 g5, analyzed by `engine`.
+    fn seeded_state(engine: &std::path::Path) -> AppState {
+        let state = AppState {
+            db: init_db(":memory:").unwrap(),
+            ws_broadcast: ws::create_broadcast(),
+            engine_pool: Some(Arc::new(LazyEnginePool::new(
+                engine.to_str().unwrap().to_string(),
+                1,
+            ))),
+            config: Arc::new(ArenaConfig::default()),
+        };
+        let conn = state.db.lock().unwrap();
+        conn.execute_batch(
+            "INSERT INTO bots (name) VALUES ('alpha'), ('beta');
+             INSERT INTO matches (id, white_bot, black_bot, games_total, started_at, status)
+             VALUES ('match1', 'alpha', 'beta', 1, '2025-01-21', 'completed');
+             INSERT INTO games (id, match_id, game_number, result, started_at)
+             VALUES ('game1', 'match1', 0, '1-0', '2025-01-21');
+             INSERT INTO moves (game_id, ply, uci, fen_after) VALUES
+                 ('game1', 1, 'e2e4', ''), ('game1', 2, 'f7f6', ''),
+                 ('game1', 3, 'd2d4', ''), ('game1', 4, 'g7g5', '');",
+        )
+        .unwrap();
+        drop(conn);
+        state
+    }
+
+    /// A [`FakeEngine`] `go` snippet that finds mate for white once black
+    /// plays 2...g5 and otherwise scores every position level.
+    #[cfg(unix)]
+    const MATE_AFTER_G5: &str = r#"case "$position" in
+           *g7g5) echo "info depth 1 score mate 1 nodes 10 pv d1h5" ;;
+           *) echo "info depth 1 score cp 0 nodes 10 pv 0000" ;;
+         esac"#;
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_analyze_game_stores_and_caches_analysis() {
+        let dir = std::env::temp_dir().join(format!("arena-analyze-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let engine = FakeEngine::new("fake-engine.sh")
+            .on_go(MATE_AFTER_G5)
+            .bestmove("0000")
+            .write(&dir);
+        let state = seeded_state(&engine);
+
+        let Json(analysis) = analyze_game(
+            State(state.clone()),
+            Path("game1".to_string()),
+            Query(AnalyzeGameQuery { depth: 8 }),
+        )
+        .await
+        .unwrap();
+        let qualities: Vec<MoveQuality> = analysis.moves.iter().map(|m| m.quality).collect();
+        assert_eq!(qualities.len(), 4);
+        assert_eq!(qualities[3], MoveQuality::Blunder);
+        assert!(qualities[..3].iter().all(|q| *q != MoveQuality::Blunder));
+        assert_eq!(analysis.white_bot, "alpha");
+        assert_eq!(analysis.result, "1-0");
+
+        let stored = AnalysisRepo::new(state.db.clone())
+            .get("game1", 8)
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.moves[3].quality, MoveQuality::Blunder);
+
+        // The same depth is served from the database without the engine
+        std::fs::remove_dir_all(&dir).unwrap();
+        let Json(cached) = analyze_game(
+            State(state.clone()),
+            Path("game1".to_string()),
+            Query(AnalyzeGameQuery { depth: 8 }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(cached.moves[3].quality, MoveQuality::Blunder);
+
+        // A new depth needs the engine again
+        let err = analyze_game(
+            State(state),
+            Path("game1".to_string()),
+            Query(AnalyzeGameQuery { depth: 9 }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.0, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn test_analyze_game_rejects_bad_requests() {
+        let state = seeded_state(std::path::Path::new("/nonexistent/stockfish"));
+
+        let err = analyze_game(
+            State(state.clone()),
+            Path("game1".to_string()),
+            Query(AnalyzeGameQuery { depth: 0 }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.0, StatusCode::BAD_REQUEST);
+
+        let err = analyze_game(
+            State(state),
+            Path("missing".to_string()),
+            Query(AnalyzeGameQuery { depth: 8 }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.0, StatusCode::NOT_FOUND);
+    }
+
     #[test]
     fn test_analysis_response_serialize() {
         let response = AnalysisResponse {
@@ -1,20 +1,28 @@
 //! Export API handlers.
 //!
-//! Provides endpoints for exporting match and game data as downloadable HTML files.
+//! Provides endpoints for exporting match and game data as downloadable HTML
+//! files, and the match list as CSV.
+
+use std::convert::Infallible;
 
 use askama::Template;
 use axum::{
+    body::Body,
     extract::{Path, State},
     http::{header, StatusCode},
     response::{Html, IntoResponse, Response},
 };
+use futures_util::stream;
 
-use crate::repo::MatchRepo;
+use crate::models::players_for_game;
+use crate::repo::{AnalysisRepo, MatchRepo};
 use crate::AppState;
+use bot_arena::pgn::{format_pgn, uci_to_san};
 use bot_arena_server::templates::{
     BoardTemplate, BotExportTemplate, EloPoint, GameExportTemplate, GameSummary,
     MatchExportTemplate,
 };
+use chess_core::Square;
 
 /// Export a match as a standalone HTML file.
 ///
@@ -47,18 +55,15 @@ pub async fn export_match(
         .get_games(&id)
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    // Convert games to summaries
-    // In a match, colors may alternate between games, but for simplicity
-    // we use the match's white/black bots and determine the actual players
-    // based on game_number (odd = normal, even = swapped)
+    // Convert games to summaries; colors alternate between games
     let game_summaries: Vec<GameSummary> = games
         .iter()
         .map(|game| {
-            let (white, black) = if game.game_number % 2 == 1 {
-                (match_info.white_bot.clone(), match_info.black_bot.clone())
-            } else {
-                (match_info.black_bot.clone(), match_info.white_bot.clone())
-            };
+            let (white, black) = players_for_game(
+                game.game_number,
+                match_info.white_bot.clone(),
+                match_info.black_bot.clone(),
+            );
 
             GameSummary {
                 white,
@@ -123,6 +128,70 @@ fn sanitize_filename(s: &str) -> String {
         .collect()
 }
 
+/// Column names of the match CSV export, in order.
+const MATCHES_CSV_HEADER: &str =
+    "match_id,white_bot,black_bot,white_score,black_score,games_total,status,started_at";
+
+/// Quote a CSV field if it contains a delimiter, quote, or line break.
+///
+/// Embedded quotes are doubled, as described in RFC 4180.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Export every match as a CSV file.
+///
+/// The body is streamed one line at a time, starting with a header row.
+///
+/// # Endpoint
+///
+/// `GET /api/export/matches.csv`
+///
+/// # Response
+///
+/// - `200 OK`: CSV file download
+/// - `500 Internal Server Error`: Database error
+pub async fn export_matches_csv(State(state): State<AppState>) -> Result<Response, StatusCode> {
+    let repo = MatchRepo::new(state.db.clone());
+    let matches = repo
+        .list_all()
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let header_row = std::iter::once(format!("{}\r\n", MATCHES_CSV_HEADER));
+    let rows = matches.into_iter().map(|m| {
+        format!(
+            "{},{},{},{},{},{},{},{}\r\n",
+            csv_field(&m.id),
+            csv_field(&m.white_bot),
+            csv_field(&m.black_bot),
+            m.white_score,
+            m.black_score,
+            m.games_total,
+            csv_field(&m.status),
+            csv_field(&m.started_at)
+        )
+    });
+    let lines = stream::iter(header_row.chain(rows).map(Ok::<_, Infallible>));
+
+    let response = (
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"matches.csv\"",
+            ),
+        ],
+        Body::from_stream(lines),
+    )
+        .into_response();
+
+    Ok(response)
+}
+
 /// Starting position FEN for chess.
 const STARTING_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
 
@@ -133,10 +202,55 @@ struct GameQueryResult {
     game_number: i32,
     result: Option<String>,
     opening_name: Option<String>,
+    started_at: String,
     match_white: String,
     match_black: String,
 }
 
+impl GameQueryResult {
+    /// Load a game together with its match's bot names.
+    ///
+    /// Returns `404 Not Found` if the game doesn't exist.
+    fn load(state: &AppState, id: &str) -> Result<Self, StatusCode> {
+        let conn = state
+            .db
+            .lock()
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        // Get game with match info to determine player colors
+        conn.query_row(
+            "SELECT g.id, g.match_id, g.game_number, g.result, g.opening_name, g.started_at,
+                    m.white_bot, m.black_bot
+             FROM games g
+             JOIN matches m ON g.match_id = m.id
+             WHERE g.id = ?1",
+            [id],
+            |row| {
+                Ok(GameQueryResult {
+                    game_id: row.get(0)?,
+                    match_id: row.get(1)?,
+                    game_number: row.get(2)?,
+                    result: row.get(3)?,
+                    opening_name: row.get(4)?,
+                    started_at: row.get(5)?,
+                    match_white: row.get(6)?,
+                    match_black: row.get(7)?,
+                })
+            },
+        )
+        .map_err(|_| StatusCode::NOT_FOUND)
+    }
+
+    /// Returns the `(white, black)` bot names for this game.
+    fn players(&self) -> (String, String) {
+        players_for_game(
+            self.game_number,
+            self.match_white.clone(),
+            self.match_black.clone(),
+        )
+    }
+}
+
 /// Export a game as a standalone HTML file.
 ///
 /// Generates a complete HTML page with the game's board position, move list,
@@ -158,58 +272,15 @@ pub async fn export_game(
     let repo = MatchRepo::new(state.db.clone());
 
     // Query game info with bot names from the match
-    let (game, white_bot, black_bot) = {
-        let conn = state
-            .db
-            .lock()
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-        // Get game with match info to determine player colors
-        let query_result: Option<GameQueryResult> = conn
-            .query_row(
-                "SELECT g.id, g.match_id, g.game_number, g.result, g.opening_name,
-                        m.white_bot, m.black_bot
-                 FROM games g
-                 JOIN matches m ON g.match_id = m.id
-                 WHERE g.id = ?1",
-                [&id],
-                |row| {
-                    Ok(GameQueryResult {
-                        game_id: row.get(0)?,
-                        match_id: row.get(1)?,
-                        game_number: row.get(2)?,
-                        result: row.get(3)?,
-                        opening_name: row.get(4)?,
-                        match_white: row.get(5)?,
-                        match_black: row.get(6)?,
-                    })
-                },
-            )
-            .ok();
-
-        match query_result {
-            Some(qr) => {
-                // Determine actual colors based on game number (odd = normal, even = swapped)
-                let (white, black) = if qr.game_number % 2 == 1 {
-                    (qr.match_white, qr.match_black)
-                } else {
-                    (qr.match_black, qr.match_white)
-                };
-                (
-                    crate::models::Game {
-                        id: qr.game_id,
-                        match_id: qr.match_id,
-                        game_number: qr.game_number,
-                        result: qr.result,
-                        opening_name: qr.opening_name,
-                        pgn: None,
-                    },
-                    white,
-                    black,
-                )
-            }
-            None => return Err(StatusCode::NOT_FOUND),
-        }
+    let query_result = GameQueryResult::load(&state, &id)?;
+    let (white_bot, black_bot) = query_result.players();
+    let game = crate::models::Game {
+        id: query_result.game_id,
+        match_id: query_result.match_id,
+        game_number: query_result.game_number,
+        result: query_result.result,
+        opening_name: query_result.opening_name,
+        pgn: None,
     };
 
     // Get moves for the game
@@ -223,8 +294,13 @@ pub async fn export_game(
         .map(|m| m.fen_after.as_str())
         .unwrap_or(STARTING_FEN);
 
-    // Render the board
-    let board_template = BoardTemplate::from_fen(final_fen);
+    // Render the board with the final move highlighted
+    let last_move = moves.last().and_then(|m| {
+        let from = Square::from_algebraic(m.uci.get(0..2)?)?;
+        let to = Square::from_algebraic(m.uci.get(2..4)?)?;
+        Some((from, to))
+    });
+    let board_template = BoardTemplate::from_fen_with(final_fen, false, last_move);
     let board_svg = board_template
         .render()
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
@@ -238,6 +314,12 @@ pub async fn export_game(
     // Pair the moves for display
     let move_pairs = GameExportTemplate::pair_moves(move_strings);
 
+    // Annotate the moves with their quality if the game has been analyzed
+    let qualities = AnalysisRepo::new(state.db.clone())
+        .latest(&id)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .map(|analysis| analysis.moves.iter().map(|m| m.quality).collect());
+
     // Build the template
     let template = GameExportTemplate {
         white: white_bot.clone(),
@@ -246,6 +328,7 @@ pub async fn export_game(
         opening: game.opening_name.clone(),
         board: board_svg,
         move_pairs,
+        qualities,
     };
 
     // Render the template
@@ -277,6 +360,81 @@ pub async fn export_game(
     Ok(response)
 }
 
+/// Export a game as a PGN file.
+///
+/// Replays the stored UCI moves to produce SAN movetext, so the file can be
+/// opened by any chess tool. Unfinished games get the `*` result token.
+///
+/// # Endpoint
+///
+/// `GET /api/export/game/:id/pgn`
+///
+/// # Response
+///
+/// - `200 OK`: PGN file download
+/// - `404 Not Found`: Game with given ID doesn't exist
+/// - `500 Internal Server Error`: Database error
+pub async fn export_game_pgn(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Response, StatusCode> {
+    let repo = MatchRepo::new(state.db.clone());
+
+    let game = GameQueryResult::load(&state, &id)?;
+    let (white_bot, black_bot) = game.players();
+
+    let uci_moves: Vec<String> = repo
+        .get_moves(&id)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .into_iter()
+        .map(|m| m.uci)
+        .collect();
+
+    let result = game.result.as_deref().unwrap_or("*");
+    // PGN dates use dots (YYYY.MM.DD)
+    let date = game
+        .started_at
+        .get(..10)
+        .map(|d| d.replace('-', "."))
+        .unwrap_or_else(|| "????.??.??".to_string());
+
+    let mut tags = vec![
+        ("Event", "Bot Arena Match".to_string()),
+        ("Site", "Bot Arena".to_string()),
+        ("Date", date),
+        ("Round", game.game_number.to_string()),
+        ("White", white_bot.clone()),
+        ("Black", black_bot.clone()),
+        ("Result", result.to_string()),
+    ];
+    if let Some(opening) = &game.opening_name {
+        tags.push(("Opening", opening.clone()));
+    }
+
+    let pgn = format_pgn(&tags, &uci_to_san(&uci_moves), result);
+
+    let filename = format!(
+        "game_{}_{}_vs_{}.pgn",
+        id,
+        sanitize_filename(&white_bot),
+        sanitize_filename(&black_bot)
+    );
+
+    let response = (
+        [
+            (header::CONTENT_TYPE, "application/x-chess-pgn"),
+            (
+                header::CONTENT_DISPOSITION,
+                &format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        pgn,
+    )
+        .into_response();
+
+    Ok(response)
+}
+
 /// Query result for bot information.
 struct BotQueryResult {
     name: String,
@@ -424,6 +582,7 @@ mod tests {
     use crate::db::init_db;
     use crate::ws;
     use bot_arena::config::ArenaConfig;
+    use chess_analysis::{AnalysisMeta, GameAnalysis, MoveAnalysis, MoveQuality, PlayerStats};
     use http_body_util::BodyExt;
     use std::sync::Arc;
 
@@ -491,8 +650,8 @@ mod tests {
             "komodo",
             "2025-01-21T10:00:00",
         );
-        insert_game(&state, "game1", "match1", 1, Some("1-0"));
-        insert_game(&state, "game2", "match1", 2, Some("0-1"));
+        insert_game(&state, "game1", "match1", 0, Some("1-0"));
+        insert_game(&state, "game2", "match1", 1, Some("0-1"));
 
         let result = export_match(State(state), Path("match1".to_string())).await;
         assert!(result.is_ok());
@@ -528,7 +687,7 @@ mod tests {
             "komodo",
             "2025-01-21T10:00:00",
         );
-        insert_game(&state, "game1", "match1", 1, Some("1-0"));
+        insert_game(&state, "game1", "match1", 0, Some("1-0"));
 
         let result = export_match(State(state), Path("match1".to_string())).await;
         assert!(result.is_ok());
@@ -565,6 +724,81 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    async fn body_string(response: Response) -> String {
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_export_matches_csv() {
+        let state = test_state();
+        setup_test_data(&state);
+        insert_match(
+            &state,
+            "match1",
+            "stockfish",
+            "komodo",
+            "2025-01-21T10:00:00",
+        );
+
+        let response = export_matches_csv(State(state)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let content_type = response.headers().get(header::CONTENT_TYPE).unwrap();
+        assert!(content_type.to_str().unwrap().starts_with("text/csv"));
+        let disposition = response
+            .headers()
+            .get(header::CONTENT_DISPOSITION)
+            .unwrap()
+            .to_str()
+            .unwrap();
+        assert!(disposition.contains("attachment"));
+        assert!(disposition.contains("matches.csv"));
+
+        let csv = body_string(response).await;
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "match_id,white_bot,black_bot,white_score,black_score,games_total,status,started_at",
+                "match1,stockfish,komodo,5.5,4.5,10,completed,2025-01-21T10:00:00",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_matches_csv_quotes_special_characters() {
+        let state = test_state();
+        {
+            let conn = state.db.lock().unwrap();
+            conn.execute("INSERT INTO bots (name) VALUES ('alpha, beta')", [])
+                .unwrap();
+            conn.execute("INSERT INTO bots (name) VALUES ('the \"best\" bot')", [])
+                .unwrap();
+        }
+        insert_match(
+            &state,
+            "match1",
+            "alpha, beta",
+            "the \"best\" bot",
+            "2025-01-21T10:00:00",
+        );
+
+        let csv = body_string(export_matches_csv(State(state)).await.unwrap()).await;
+        let row = csv.lines().nth(1).unwrap();
+        assert_eq!(
+            row,
+            r#"match1,"alpha, beta","the ""best"" bot",5.5,4.5,10,completed,2025-01-21T10:00:00"#
+        );
+    }
+
+    #[test]
+    fn test_csv_field() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+
     #[test]
     fn test_sanitize_filename() {
         assert_eq!(sanitize_filename("stockfish"), "stockfish");
@@ -634,7 +868,7 @@ mod tests {
             &state,
             "game1",
             "match1",
-            1,
+            0,
             Some("1-0"),
             Some("Italian Game"),
         );
@@ -677,7 +911,7 @@ mod tests {
             &state,
             "game1",
             "match1",
-            1,
+            0,
             Some("1-0"),
             Some("Italian Game"),
         );
@@ -706,8 +940,90 @@ mod tests {
         // Check for moves
         assert!(html.contains("e4"));
         assert!(html.contains("e5"));
-        // Check for SVG board
+        // Check for SVG board with the last move, e7e5, highlighted
         assert!(html.contains("<svg"));
+        assert!(html.contains(r#"x="200" y="50" width="50" height="50" class="highlight""#));
+        assert!(html.contains(r#"x="200" y="150" width="50" height="50" class="highlight""#));
+    }
+
+    fn insert_analysis(state: &AppState, game_id: &str, qualities: &[MoveQuality]) {
+        let moves: Vec<MoveAnalysis> = qualities
+            .iter()
+            .map(|&quality| MoveAnalysis {
+                uci: String::new(),
+                san: None,
+                quality,
+                bot_eval: None,
+                bot_depth: None,
+                bot_nodes: None,
+                bot_time_ms: None,
+                bot_pv: Vec::new(),
+                engine_eval_before: None,
+                engine_eval_after: None,
+                engine_best_move: None,
+                engine_pv: Vec::new(),
+                centipawn_loss: None,
+                forced: false,
+            })
+            .collect();
+        let analysis = GameAnalysis {
+            game_id: game_id.to_string(),
+            white_bot: "stockfish".to_string(),
+            black_bot: "komodo".to_string(),
+            opening: None,
+            result: "1-0".to_string(),
+            moves,
+            white_stats: PlayerStats::default(),
+            black_stats: PlayerStats::default(),
+            eval_timeline: Vec::new(),
+            meta: AnalysisMeta::default(),
+            start_fen: None,
+        };
+        let conn = state.db.lock().unwrap();
+        conn.execute(
+            "INSERT INTO game_analyses (game_id, depth, analysis) VALUES (?1, 12, ?2)",
+            [game_id, &serde_json::to_string(&analysis).unwrap()],
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_export_game_marks_analyzed_blunder() {
+        let state = test_state();
+        setup_test_data(&state);
+        insert_match(
+            &state,
+            "match1",
+            "stockfish",
+            "komodo",
+            "2025-01-21T10:00:00",
+        );
+        insert_game(&state, "game1", "match1", 0, Some("1-0"));
+        insert_move(&state, "game1", 1, "e2e4", "e4", "fen1");
+        insert_move(&state, "game1", 2, "f7f6", "f6", "fen2");
+        insert_move(&state, "game1", 3, "d2d4", "d4", "fen3");
+        insert_move(&state, "game1", 4, "g7g5", "g5", "fen4");
+        insert_analysis(
+            &state,
+            "game1",
+            &[
+                MoveQuality::Best,
+                MoveQuality::Good,
+                MoveQuality::Best,
+                MoveQuality::Blunder,
+            ],
+        );
+
+        let response = export_game(State(state), Path("game1".to_string()))
+            .await
+            .unwrap();
+        let html = body_string(response).await;
+
+        assert!(html.contains(r#"g5<span class="glyph">??</span>"#));
+        assert_eq!(html.matches(r#"<span class="glyph">"#).count(), 1);
+        for san in ["e4", "f6", "d4"] {
+            assert!(html.contains(&format!(r#"<span class="move">{san}</span>"#)));
+        }
     }
 
     #[tokio::test]
@@ -721,7 +1037,7 @@ mod tests {
             "komodo",
             "2025-01-21T10:00:00",
         );
-        insert_game(&state, "game1", "match1", 1, None);
+        insert_game(&state, "game1", "match1", 0, None);
 
         let result = export_game(State(state), Path("game1".to_string())).await;
         assert!(result.is_ok());
@@ -737,6 +1053,95 @@ mod tests {
         assert!(html.contains("<svg"));
     }
 
+    #[tokio::test]
+    async fn test_export_game_pgn_known_game() {
+        let state = test_state();
+        setup_test_data(&state);
+        insert_match(
+            &state,
+            "match1",
+            "stockfish",
+            "komodo",
+            "2025-01-21T10:00:00",
+        );
+        insert_game_with_opening(
+            &state,
+            "game1",
+            "match1",
+            0,
+            Some("1-0"),
+            Some("Italian Game"),
+        );
+        // Scholar's mate, stored without SAN
+        for (ply, uci) in ["e2e4", "e7e5", "f1c4", "b8c6", "d1h5", "g8f6", "h5f7"]
+            .iter()
+            .enumerate()
+        {
+            let conn = state.db.lock().unwrap();
+            conn.execute(
+                "INSERT INTO moves (game_id, ply, uci, fen_after) VALUES ('game1', ?1, ?2, '')",
+                rusqlite::params![ply as i32 + 1, uci],
+            )
+            .unwrap();
+        }
+
+        let response = export_game_pgn(State(state), Path("game1".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let disposition = response
+            .headers()
+            .get(header::CONTENT_DISPOSITION)
+            .unwrap()
+            .to_str()
+            .unwrap();
+        assert!(disposition.contains("attachment"));
+        assert!(disposition.contains("game_game1_stockfish_vs_komodo.pgn"));
+
+        let pgn = body_string(response).await;
+        assert!(pgn.contains("[White \"stockfish\"]"));
+        assert!(pgn.contains("[Black \"komodo\"]"));
+        assert!(pgn.contains("[Date \"2025.01.21\"]"));
+        assert!(pgn.contains("[Result \"1-0\"]"));
+        assert!(pgn.contains("[Opening \"Italian Game\"]"));
+
+        let movetext = pgn.split("\n\n").nth(1).unwrap().trim_end();
+        assert_eq!(movetext, "1. e4 e5 2. Bc4 Nc6 3. Qh5 Nf6 4. Qxf7# 1-0");
+    }
+
+    #[tokio::test]
+    async fn test_export_game_pgn_no_moves() {
+        let state = test_state();
+        setup_test_data(&state);
+        insert_match(
+            &state,
+            "match1",
+            "stockfish",
+            "komodo",
+            "2025-01-21T10:00:00",
+        );
+        insert_game_with_opening(&state, "game1", "match1", 0, None, None);
+
+        let response = export_game_pgn(State(state), Path("game1".to_string()))
+            .await
+            .unwrap();
+        let pgn = body_string(response).await;
+
+        let (headers, movetext) = pgn.split_once("\n\n").unwrap();
+        for tag in ["Event", "Site", "Date", "Round", "White", "Black"] {
+            assert!(headers.contains(&format!("[{} \"", tag)), "missing {}", tag);
+        }
+        assert!(headers.contains("[Result \"*\"]"));
+        assert_eq!(movetext.trim_end(), "*");
+    }
+
+    #[tokio::test]
+    async fn test_export_game_pgn_not_found() {
+        let state = test_state();
+        let result = export_game_pgn(State(state), Path("nonexistent".to_string())).await;
+        assert_eq!(result.unwrap_err(), StatusCode::NOT_FOUND);
+    }
+
     #[tokio::test]
     async fn test_export_game_swapped_colors() {
         let state = test_state();
@@ -748,21 +1153,20 @@ mod tests {
             "komodo",
             "2025-01-21T10:00:00",
         );
-        // Game 2 has swapped colors (even game number)
-        insert_game_with_opening(&state, "game2", "match1", 2, Some("1-0"), None);
+        // The second game (game number 1) swaps colors
+        insert_game_with_opening(&state, "game2", "match1", 1, Some("1-0"), None);
 
         let result = export_game(State(state), Path("game2".to_string())).await;
         assert!(result.is_ok());
 
         let response = result.unwrap();
-        let body = response.into_body();
-        let bytes = body.collect().await.unwrap().to_bytes();
-        let html = String::from_utf8(bytes.to_vec()).unwrap();
-
-        // Check Content-Disposition for swapped colors
-        // In game 2 (even), komodo plays white and stockfish plays black
-        assert!(html.contains("komodo"));
-        assert!(html.contains("stockfish"));
+        let disposition = response
+            .headers()
+            .get(header::CONTENT_DISPOSITION)
+            .unwrap()
+            .to_str()
+            .unwrap();
+        assert!(disposition.contains("game_game2_komodo_vs_stockfish.html"));
     }
 
     #[tokio::test]
@@ -776,7 +1180,7 @@ mod tests {
             "komodo",
             "2025-01-21T10:00:00",
         );
-        insert_game_with_opening(&state, "game1", "match1", 1, Some("0-1"), None);
+        insert_game_with_opening(&state, "game1", "match1", 0, Some("0-1"), None);
 
         let result = export_game(State(state), Path("game1".to_string())).await;
         assert!(result.is_ok());
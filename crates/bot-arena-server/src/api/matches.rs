@@ -9,9 +9,15 @@ use axum::{
 use serde::{Deserialize, Serialize};
 
 use crate::models::{Game, Match, Move};
-use crate::repo::{BotRepo, MatchFilter, MatchRepo};
+use crate::repo::{BotRepo, CancelOutcome, MatchFilter, MatchRepo};
 use crate::AppState;
 
+/// Largest page size accepted by `GET /api/matches`.
+pub const MAX_MATCHES_LIMIT: i32 = 100;
+
+/// Match statuses accepted by the `status` filter.
+pub const MATCH_STATUSES: &[&str] = &["pending", "running", "completed", "failed", "cancelled"];
+
 /// Query parameters for listing matches.
 #[derive(Debug, Deserialize)]
 pub struct ListMatchesQuery {
@@ -21,6 +27,17 @@ pub struct ListMatchesQuery {
     pub limit: Option<i32>,
     /// Number of results to skip.
     pub offset: Option<i32>,
+    /// Filter by match status.
+    pub status: Option<String>,
+}
+
+/// A page of matches with the total number matching the filter.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchList {
+    /// Matches on this page.
+    pub matches: Vec<Match>,
+    /// Total number of matches matching the filter, across all pages.
+    pub total: i64,
 }
 
 /// List matches with optional filtering.
@@ -32,27 +49,47 @@ pub struct ListMatchesQuery {
 /// # Query Parameters
 ///
 /// - `bot`: Filter by bot name (optional)
-/// - `limit`: Maximum results (default: 20)
+/// - `status`: Filter by status: `pending`, `running`, `completed`, `failed`, or
+///   `cancelled` (optional)
+/// - `limit`: Maximum results, 1 to 100 (default: 20)
 /// - `offset`: Skip results (default: 0)
 ///
 /// # Response
 ///
-/// - `200 OK`: JSON array of match objects
+/// - `200 OK`: JSON object `{ "matches": [...], "total": N }`
+/// - `400 Bad Request`: Invalid `limit`, `offset`, or `status`
 /// - `500 Internal Server Error`: Database error
 pub async fn list_matches(
     State(state): State<AppState>,
     Query(query): Query<ListMatchesQuery>,
-) -> Result<Json<Vec<Match>>, StatusCode> {
+) -> Result<Json<MatchList>, StatusCode> {
+    let limit = query.limit.unwrap_or(20);
+    let offset = query.offset.unwrap_or(0);
+    if !(1..=MAX_MATCHES_LIMIT).contains(&limit) || offset < 0 {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if let Some(status) = &query.status {
+        if !MATCH_STATUSES.contains(&status.as_str()) {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
+
     let repo = MatchRepo::new(state.db.clone());
     let filter = MatchFilter {
         bot: query.bot,
-        limit: query.limit.unwrap_or(20),
-        offset: query.offset.unwrap_or(0),
+        status: query.status,
+        limit,
+        offset,
     };
 
-    repo.list(filter)
-        .map(Json)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+    let total = repo
+        .count(&filter)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let matches = repo
+        .list(filter)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(MatchList { matches, total }))
 }
 
 /// Match with full game details.
@@ -133,6 +170,60 @@ pub async fn get_game_moves(
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
 }
 
+/// Cancel a pending or running match.
+///
+/// # Endpoint
+///
+/// `POST /api/matches/:id/cancel` (requires the API token)
+///
+/// # Response
+///
+/// - `200 OK`: The pending match is now `cancelled`; JSON match object
+/// - `202 Accepted`: The running match stops after its current game; JSON match object
+/// - `404 Not Found`: Match with given ID doesn't exist
+/// - `409 Conflict`: The match has already finished
+/// - `500 Internal Server Error`: Database error
+pub async fn cancel_match(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<(StatusCode, Json<Match>), StatusCode> {
+    let repo = MatchRepo::new(state.db.clone());
+    let status = match repo
+        .cancel(&id)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    {
+        CancelOutcome::Cancelled => StatusCode::OK,
+        CancelOutcome::Requested => StatusCode::ACCEPTED,
+        CancelOutcome::Finished => return Err(StatusCode::CONFLICT),
+        CancelOutcome::NotFound => return Err(StatusCode::NOT_FOUND),
+    };
+
+    let match_info = repo
+        .get(&id)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    Ok((status, Json(match_info)))
+}
+
+/// Delete a match with its games and moves.
+///
+/// # Endpoint
+///
+/// `DELETE /api/matches/:id` (requires the API token)
+///
+/// # Response
+///
+/// - `204 No Content`: The match was deleted
+/// - `404 Not Found`: Match with given ID doesn't exist
+/// - `500 Internal Server Error`: Database error
+pub async fn delete_match(State(state): State<AppState>, Path(id): Path<String>) -> StatusCode {
+    match MatchRepo::new(state.db.clone()).delete(&id) {
+        Ok(true) => StatusCode::NO_CONTENT,
+        Ok(false) => StatusCode::NOT_FOUND,
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
 /// Request body for creating a new match.
 #[derive(Debug, Deserialize)]
 pub struct CreateMatchRequest {
@@ -301,10 +392,11 @@ mod tests {
             bot: None,
             limit: None,
             offset: None,
+            status: None,
         };
         let result = list_matches(State(state), Query(query)).await;
         assert!(result.is_ok());
-        let Json(matches) = result.unwrap();
+        let Json(MatchList { matches, .. }) = result.unwrap();
         assert!(matches.is_empty());
     }
 
@@ -332,10 +424,11 @@ mod tests {
             bot: None,
             limit: None,
             offset: None,
+            status: None,
         };
         let result = list_matches(State(state), Query(query)).await;
         assert!(result.is_ok());
-        let Json(matches) = result.unwrap();
+        let Json(MatchList { matches, .. }) = result.unwrap();
         assert_eq!(matches.len(), 2);
         // Most recent first
         assert_eq!(matches[0].id, "match2");
@@ -360,10 +453,11 @@ mod tests {
             bot: Some("stockfish".to_string()),
             limit: None,
             offset: None,
+            status: None,
         };
         let result = list_matches(State(state), Query(query)).await;
         assert!(result.is_ok());
-        let Json(matches) = result.unwrap();
+        let Json(MatchList { matches, .. }) = result.unwrap();
         assert_eq!(matches.len(), 1);
         assert_eq!(matches[0].id, "match1");
     }
@@ -393,15 +487,117 @@ mod tests {
             bot: None,
             limit: Some(1),
             offset: Some(1),
+            status: None,
         };
         let result = list_matches(State(state), Query(query)).await;
         assert!(result.is_ok());
-        let Json(matches) = result.unwrap();
+        let Json(MatchList { matches, .. }) = result.unwrap();
         assert_eq!(matches.len(), 1);
         // Skipped match3, got match2
         assert_eq!(matches[0].id, "match2");
     }
 
+    /// Seeds five matches with mixed statuses, oldest first.
+    fn seed_five_matches(state: &AppState) {
+        setup_test_data(state);
+        let statuses = ["pending", "running", "completed", "completed", "failed"];
+        for (i, status) in statuses.iter().enumerate() {
+            let id = format!("match{}", i + 1);
+            let started_at = format!("2025-01-21T1{}:00:00", i);
+            insert_match(state, &id, "stockfish", "komodo", &started_at);
+            state
+                .db
+                .lock()
+                .unwrap()
+                .execute(
+                    "UPDATE matches SET status = ?1 WHERE id = ?2",
+                    [*status, &id],
+                )
+                .unwrap();
+        }
+    }
+
+    async fn list_ids(
+        state: &AppState,
+        limit: Option<i32>,
+        offset: Option<i32>,
+        status: Option<&str>,
+    ) -> Result<(Vec<String>, i64), StatusCode> {
+        let query = ListMatchesQuery {
+            bot: None,
+            limit,
+            offset,
+            status: status.map(str::to_string),
+        };
+        let Json(list) = list_matches(State(state.clone()), Query(query)).await?;
+        Ok((list.matches.into_iter().map(|m| m.id).collect(), list.total))
+    }
+
+    #[tokio::test]
+    async fn test_list_matches_pagination_envelope() {
+        let state = test_state();
+        seed_five_matches(&state);
+
+        let (ids, total) = list_ids(&state, Some(2), None, None).await.unwrap();
+        assert_eq!(ids, vec!["match5", "match4"]);
+        assert_eq!(total, 5);
+
+        let (ids, total) = list_ids(&state, Some(2), Some(2), None).await.unwrap();
+        assert_eq!(ids, vec!["match3", "match2"]);
+        assert_eq!(total, 5);
+
+        let (ids, _) = list_ids(&state, Some(2), Some(4), None).await.unwrap();
+        assert_eq!(ids, vec!["match1"]);
+
+        let (ids, total) = list_ids(&state, Some(2), Some(10), None).await.unwrap();
+        assert!(ids.is_empty());
+        assert_eq!(total, 5);
+    }
+
+    #[tokio::test]
+    async fn test_list_matches_status_filter() {
+        let state = test_state();
+        seed_five_matches(&state);
+
+        let (ids, total) = list_ids(&state, None, None, Some("completed"))
+            .await
+            .unwrap();
+        assert_eq!(ids, vec!["match4", "match3"]);
+        assert_eq!(total, 2);
+
+        let (ids, total) = list_ids(&state, Some(1), Some(1), Some("completed"))
+            .await
+            .unwrap();
+        assert_eq!(ids, vec!["match3"]);
+        assert_eq!(total, 2);
+
+        let (ids, total) = list_ids(&state, None, None, Some("failed")).await.unwrap();
+        assert_eq!(ids, vec!["match5"]);
+        assert_eq!(total, 1);
+    }
+
+    #[tokio::test]
+    async fn test_list_matches_rejects_invalid_params() {
+        let state = test_state();
+        seed_five_matches(&state);
+
+        for (limit, offset, status) in [
+            (Some(101), None, None),
+            (Some(0), None, None),
+            (None, Some(-1), None),
+            (None, None, Some("finished")),
+        ] {
+            let result = list_ids(&state, limit, offset, status).await;
+            assert_eq!(result.unwrap_err(), StatusCode::BAD_REQUEST);
+        }
+
+        // The cap itself is allowed
+        let (ids, _) = list_ids(&state, Some(MAX_MATCHES_LIMIT), None, None)
+            .await
+            .unwrap();
+        assert_eq!(ids.len(), 5);
+    }
+
     #[tokio::test]
     async fn test_get_match_detail_with_games() {
         let state = test_state();
@@ -540,6 +736,169 @@ mod tests {
         assert!(moves.is_empty());
     }
 
+    fn count_rows(state: &AppState, table: &str) -> i64 {
+        state
+            .db
+            .lock()
+            .unwrap()
+            .query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| {
+                row.get(0)
+            })
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_delete_match_removes_games_and_moves() {
+        let state = test_state();
+        setup_test_data(&state);
+
+        insert_match(
+            &state,
+            "match1",
+            "stockfish",
+            "komodo",
+            "2025-01-21T10:00:00",
+        );
+        insert_match(&state, "match2", "komodo", "leela", "2025-01-21T11:00:00");
+        insert_game(&state, "game1", "match1", 1, Some("1-0"));
+        insert_game(&state, "game2", "match1", 2, None);
+        insert_game(&state, "other", "match2", 1, None);
+        insert_move(&state, "game1", 1, "e2e4", "fen1");
+        insert_move(&state, "game2", 1, "d2d4", "fen2");
+        insert_move(&state, "other", 1, "c2c4", "fen3");
+        state
+            .db
+            .lock()
+            .unwrap()
+            .execute(
+                "INSERT INTO elo_history (bot_name, elo_rating, recorded_at, match_id)
+                 VALUES ('stockfish', 1516, '2025-01-21', 'match1')",
+                [],
+            )
+            .unwrap();
+
+        let status = delete_match(State(state.clone()), Path("match1".to_string())).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+
+        assert_eq!(count_rows(&state, "matches"), 1);
+        assert_eq!(count_rows(&state, "games"), 1);
+        assert_eq!(count_rows(&state, "moves"), 1);
+        // Rating history survives without its match
+        let match_id: Option<String> = state
+            .db
+            .lock()
+            .unwrap()
+            .query_row("SELECT match_id FROM elo_history", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(match_id, None);
+
+        let response = get_match_detail(State(state), Path("match1".to_string()))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    fn set_status(state: &AppState, id: &str, status: &str) {
+        state
+            .db
+            .lock()
+            .unwrap()
+            .execute("UPDATE matches SET status = ?1 WHERE id = ?2", [status, id])
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_cancel_pending_match() {
+        let state = test_state();
+        setup_test_data(&state);
+        insert_match(
+            &state,
+            "match1",
+            "stockfish",
+            "komodo",
+            "2025-01-21T10:00:00",
+        );
+
+        let (status, Json(cancelled)) =
+            cancel_match(State(state.clone()), Path("match1".to_string()))
+                .await
+                .unwrap();
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(cancelled.status, "cancelled");
+        assert!(cancelled.finished_at.is_some());
+
+        let (ids, _) = list_ids(&state, None, None, Some("cancelled"))
+            .await
+            .unwrap();
+        assert_eq!(ids, vec!["match1"]);
+        let (ids, _) = list_ids(&state, None, None, Some("pending")).await.unwrap();
+        assert!(ids.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_running_match_sets_flag() {
+        let state = test_state();
+        setup_test_data(&state);
+        insert_match(
+            &state,
+            "match1",
+            "stockfish",
+            "komodo",
+            "2025-01-21T10:00:00",
+        );
+        set_status(&state, "match1", "running");
+
+        let (status, Json(running)) =
+            cancel_match(State(state.clone()), Path("match1".to_string()))
+                .await
+                .unwrap();
+        assert_eq!(status, StatusCode::ACCEPTED);
+        assert_eq!(running.status, "running");
+
+        let requested: bool = state
+            .db
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT cancel_requested FROM matches WHERE id = 'match1'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(requested);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_finished_or_missing_match() {
+        let state = test_state();
+        setup_test_data(&state);
+        insert_match(
+            &state,
+            "match1",
+            "stockfish",
+            "komodo",
+            "2025-01-21T10:00:00",
+        );
+        set_status(&state, "match1", "completed");
+
+        let err = cancel_match(State(state.clone()), Path("match1".to_string()))
+            .await
+            .unwrap_err();
+        assert_eq!(err, StatusCode::CONFLICT);
+
+        let err = cancel_match(State(state), Path("nonexistent".to_string()))
+            .await
+            .unwrap_err();
+        assert_eq!(err, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_delete_match_not_found() {
+        let state = test_state();
+        let status = delete_match(State(state), Path("nonexistent".to_string())).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
     #[tokio::test]
     async fn test_create_match_basic() {
         let state = test_state();
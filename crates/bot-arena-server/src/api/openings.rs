@@ -1,16 +1,20 @@
 //! Opening statistics API.
 //!
-//! Provides endpoints to retrieve chess opening statistics from played games.
+//! Provides endpoints to retrieve chess opening statistics from played games
+//! and to explore the moves played after a given line.
 
 use axum::{
-    extract::State,
+    extract::{Query, State},
     http::{header, StatusCode},
     response::IntoResponse,
     Json,
 };
+use chess_engine::Game;
 use chess_openings::{builtin::builtin_openings, OpeningDatabase};
 use serde::{Deserialize, Serialize};
 
+use crate::models::OpeningExplorer;
+use crate::repo::MatchRepo;
 use crate::AppState;
 
 /// Looks up the ECO code for an opening by name.
@@ -121,6 +125,68 @@ pub async fn list_openings(State(state): State<AppState>) -> impl IntoResponse {
         .into_response()
 }
 
+/// Query parameters for exploring a line of moves.
+#[derive(Debug, Deserialize)]
+pub struct ExploreQuery {
+    /// Comma-separated UCI moves from the starting position; empty for the
+    /// starting position itself.
+    #[serde(default)]
+    pub moves: String,
+}
+
+/// Explore the openings and stored games continuing from a line of moves.
+///
+/// Combines the named openings from the built-in database that extend the
+/// line with the moves played next in finished games and their results.
+///
+/// # Endpoint
+///
+/// `GET /api/openings/explore?moves=e2e4,e7e5`
+///
+/// # Response
+///
+/// - `200 OK`: JSON opening explorer view
+/// - `400 Bad Request`: The line contains an illegal move
+/// - `500 Internal Server Error`: Database error
+pub async fn explore_openings(
+    State(state): State<AppState>,
+    Query(query): Query<ExploreQuery>,
+) -> Result<Json<OpeningExplorer>, (StatusCode, String)> {
+    let moves: Vec<String> = query
+        .moves
+        .split(',')
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    let mut game = Game::new();
+    for uci in &moves {
+        game.make_move_uci(uci).map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("Illegal move {}: {}", uci, e),
+            )
+        })?;
+    }
+
+    let opening_db = OpeningDatabase::with_openings(builtin_openings());
+    let openings = opening_db
+        .continuations_from(&moves)
+        .into_iter()
+        .cloned()
+        .collect();
+    let next_moves = MatchRepo::new(state.db.clone())
+        .next_moves(&moves)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(OpeningExplorer {
+        moves,
+        openings,
+        next_moves,
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -379,4 +445,89 @@ mod tests {
             .expect("Cache-Control header should be present");
         assert_eq!(cache_control, "public, max-age=86400");
     }
+
+    /// Inserts a finished or unfinished game of match1 with the given moves.
+    fn insert_game_with_moves(state: &AppState, id: &str, result: Option<&str>, moves: &str) {
+        let conn = state.db.lock().unwrap();
+        conn.execute(
+            "INSERT INTO games (id, match_id, game_number, result, started_at)
+             VALUES (?1, 'match1', 1, ?2, '2025-01-21')",
+            rusqlite::params![id, result],
+        )
+        .unwrap();
+        for (ply, uci) in (1i32..).zip(moves.split_whitespace()) {
+            conn.execute(
+                "INSERT INTO moves (game_id, ply, uci, fen_after) VALUES (?1, ?2, ?3, 'fen')",
+                rusqlite::params![id, ply, uci],
+            )
+            .unwrap();
+        }
+    }
+
+    fn explore_query(moves: &str) -> Query<ExploreQuery> {
+        Query(ExploreQuery {
+            moves: moves.to_string(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_explore_openings_counts_next_moves() {
+        let state = test_state();
+        {
+            let conn = state.db.lock().unwrap();
+            conn.execute("INSERT INTO bots (name) VALUES ('bot1'), ('bot2')", [])
+                .unwrap();
+            conn.execute(
+                "INSERT INTO matches (id, white_bot, black_bot, games_total, started_at)
+                 VALUES ('match1', 'bot1', 'bot2', 6, '2025-01-21')",
+                [],
+            )
+            .unwrap();
+        }
+        insert_game_with_moves(&state, "g1", Some("1-0"), "e2e4 e7e5 g1f3 b8c6 f1c4");
+        insert_game_with_moves(&state, "g2", Some("1/2-1/2"), "e2e4 e7e5 g1f3 g8f6");
+        insert_game_with_moves(&state, "g3", Some("0-1"), "e2e4 e7e5 f1c4");
+        // Different line, a game ending on the line, and an unfinished game
+        insert_game_with_moves(&state, "g4", Some("1-0"), "e2e4 c7c5 g1f3");
+        insert_game_with_moves(&state, "g5", Some("1-0"), "e2e4 e7e5");
+        insert_game_with_moves(&state, "g6", None, "e2e4 e7e5 g1f3");
+
+        let Json(explorer) = explore_openings(State(state.clone()), explore_query("e2e4,e7e5"))
+            .await
+            .unwrap();
+        assert_eq!(explorer.moves, ["e2e4", "e7e5"]);
+        assert!(explorer.openings.iter().any(|o| o.id == "italian-game"));
+        assert!(explorer
+            .openings
+            .iter()
+            .all(|o| o.moves.len() > 2 && o.moves[..2] == explorer.moves));
+
+        let next: Vec<(&str, i32)> = explorer
+            .next_moves
+            .iter()
+            .map(|m| (m.uci.as_str(), m.games))
+            .collect();
+        assert_eq!(next, [("g1f3", 2), ("f1c4", 1)]);
+        let nf3 = &explorer.next_moves[0];
+        assert_eq!((nf3.white_wins, nf3.draws, nf3.black_wins), (1, 1, 0));
+        assert_eq!(nf3.score, 0.75);
+        assert_eq!(explorer.next_moves[1].score, 0.0);
+
+        // The starting position sees every finished game's first move
+        let Json(start) = explore_openings(State(state), explore_query(""))
+            .await
+            .unwrap();
+        assert_eq!(start.next_moves.len(), 1);
+        assert_eq!(start.next_moves[0].uci, "e2e4");
+        assert_eq!(start.next_moves[0].games, 5);
+    }
+
+    #[tokio::test]
+    async fn test_explore_openings_rejects_illegal_lines() {
+        let state = test_state();
+        let (status, _) = explore_openings(State(state), explore_query("e2e4,e2e4"))
+            .await
+            .unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
 }
@@ -1,8 +1,15 @@
 //! Statistics API endpoints.
 
-use axum::{extract::State, http::StatusCode, Json};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chess_openings::{builtin::builtin_openings, OpeningDatabase};
 use serde::Serialize;
 
+use crate::models::{HeadToHeadSummary, OpeningPerformance};
+use crate::repo::{MatchRepo, StatsRepo};
 use crate::AppState;
 
 /// Record of head-to-head performance between two bots.
@@ -92,6 +99,54 @@ pub async fn head_to_head(
     Ok(Json(HeadToHeadMatrix { bots, records }))
 }
 
+/// Get a detailed head-to-head summary for one pair of bots.
+///
+/// Counts every finished game between the two bots, whichever color each
+/// played. Results and recent form are given from `bot_a`'s side.
+///
+/// # Endpoint
+///
+/// `GET /api/stats/head-to-head/:bot_a/:bot_b`
+///
+/// # Response
+///
+/// - `200 OK`: JSON head-to-head summary (all zeros if the bots never met)
+/// - `500 Internal Server Error`: Database error
+pub async fn head_to_head_pair(
+    State(state): State<AppState>,
+    Path((bot_a, bot_b)): Path<(String, String)>,
+) -> Result<Json<HeadToHeadSummary>, (StatusCode, String)> {
+    let repo = StatsRepo::new(state.db.clone());
+
+    repo.head_to_head(&bot_a, &bot_b)
+        .map(Json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+/// Get per-opening results across all finished games.
+///
+/// Openings are detected by replaying each game's moves against the
+/// built-in opening database.
+///
+/// # Endpoint
+///
+/// `GET /api/stats/openings`
+///
+/// # Response
+///
+/// - `200 OK`: JSON array of opening results, most played first
+/// - `500 Internal Server Error`: Database error
+pub async fn opening_stats(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<OpeningPerformance>>, (StatusCode, String)> {
+    let repo = MatchRepo::new(state.db.clone());
+    let openings = OpeningDatabase::with_openings(builtin_openings());
+
+    repo.opening_stats(&openings)
+        .map(Json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -359,4 +414,93 @@ mod tests {
         assert_eq!(matrix.records.len(), 1);
         assert_eq!(matrix.records[0].black_bot, "leela");
     }
+
+    #[tokio::test]
+    async fn test_head_to_head_pair_never_met() {
+        let state = test_state();
+        setup_bots(&state);
+
+        let result = head_to_head_pair(
+            State(state),
+            Path(("stockfish".to_string(), "leela".to_string())),
+        )
+        .await;
+
+        let Json(summary) = result.expect("Unplayed pairing should not be an error");
+        assert_eq!(summary.games, 0);
+        assert_eq!(summary.bot_a_wins, 0);
+        assert!(summary.recent_form.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_head_to_head_pair_both_orientations() {
+        let state = test_state();
+        setup_bots(&state);
+
+        // Stockfish is white in even-numbered games of match1 and
+        // odd-numbered games of match2
+        insert_match(&state, "match1", "stockfish", "komodo", "completed");
+        insert_game(&state, "game1", "match1", 0, "1-0");
+        insert_game(&state, "game2", "match1", 1, "1-0");
+        insert_match(&state, "match2", "komodo", "stockfish", "completed");
+        insert_game(&state, "game3", "match2", 0, "1/2-1/2");
+        insert_game(&state, "game4", "match2", 1, "0-1");
+
+        let result = head_to_head_pair(
+            State(state),
+            Path(("stockfish".to_string(), "komodo".to_string())),
+        )
+        .await;
+
+        let Json(summary) = result.unwrap();
+        assert_eq!(summary.games, 4);
+        assert_eq!(summary.bot_a_wins, 1);
+        assert_eq!(summary.bot_b_wins, 2);
+        assert_eq!(summary.draws, 1);
+        assert_eq!(summary.bot_a_as_white.wins, 1);
+        assert_eq!(summary.bot_a_as_white.losses, 1);
+        assert_eq!(summary.bot_a_as_black.draws, 1);
+        assert_eq!(summary.bot_a_as_black.losses, 1);
+    }
+
+    fn insert_move(state: &AppState, game_id: &str, ply: i32, uci: &str) {
+        let conn = state.db.lock().unwrap();
+        conn.execute(
+            "INSERT INTO moves (game_id, ply, uci, fen_after) VALUES (?1, ?2, ?3, 'fen')",
+            rusqlite::params![game_id, ply, uci],
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_opening_stats_italian_game() {
+        let state = test_state();
+        setup_bots(&state);
+        insert_match(&state, "match1", "stockfish", "komodo", "completed");
+
+        let italian = ["e2e4", "e7e5", "g1f3", "b8c6", "f1c4"];
+        insert_game(&state, "game1", "match1", 1, "1-0");
+        insert_game(&state, "game2", "match1", 2, "1/2-1/2");
+        for game_id in ["game1", "game2"] {
+            for (i, uci) in italian.iter().enumerate() {
+                insert_move(&state, game_id, i as i32 + 1, uci);
+            }
+        }
+
+        let Json(stats) = opening_stats(State(state)).await.unwrap();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].name, "Italian Game");
+        assert_eq!(stats[0].games_played, 2);
+        assert_eq!(stats[0].white_wins, 1);
+        assert_eq!(stats[0].draws, 1);
+        assert_eq!(stats[0].black_wins, 0);
+        assert_eq!(stats[0].avg_game_length, 5.0);
+    }
+
+    #[tokio::test]
+    async fn test_opening_stats_empty_database() {
+        let state = test_state();
+        let Json(stats) = opening_stats(State(state)).await.unwrap();
+        assert!(stats.is_empty());
+    }
 }
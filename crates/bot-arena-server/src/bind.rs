@@ -0,0 +1,158 @@
+//! Resolution of the address the server listens on.
+//!
+//! The address comes from the `HOST` and `PORT` environment variables and
+//! defaults to `127.0.0.1:3000`. Setting both `TLS_CERT_PATH` and
+//! `TLS_KEY_PATH` serves HTTPS with the given PEM certificate chain and
+//! private key instead of plain HTTP.
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// Address used when `HOST` is not set.
+pub const DEFAULT_HOST: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+/// Port used when `PORT` is not set.
+pub const DEFAULT_PORT: u16 = 3000;
+
+/// Errors in the bind configuration.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum BindError {
+    /// `HOST` is not an IP address.
+    #[error("invalid HOST '{0}': expected an IP address such as 0.0.0.0")]
+    InvalidHost(String),
+    /// `PORT` is not a number between 1 and 65535.
+    #[error("invalid PORT '{0}': expected a number between 1 and 65535")]
+    InvalidPort(String),
+    /// Only one of the TLS paths is set.
+    #[error("TLS needs both TLS_CERT_PATH and TLS_KEY_PATH, but only {0} is set")]
+    IncompleteTls(&'static str),
+}
+
+/// PEM files for serving HTTPS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsPaths {
+    /// Certificate chain.
+    pub cert: PathBuf,
+    /// Private key.
+    pub key: PathBuf,
+}
+
+/// Where and how the server listens.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BindConfig {
+    /// Address to bind to.
+    pub addr: SocketAddr,
+    /// TLS files, or `None` for plain HTTP.
+    pub tls: Option<TlsPaths>,
+}
+
+impl BindConfig {
+    /// Read the bind configuration from the environment.
+    pub fn from_env() -> Result<Self, BindError> {
+        Self::resolve(|name| std::env::var(name).ok())
+    }
+
+    /// Resolve the bind configuration from variables looked up with `var`.
+    ///
+    /// Empty values count as unset.
+    pub fn resolve(var: impl Fn(&str) -> Option<String>) -> Result<Self, BindError> {
+        let var = |name: &str| var(name).filter(|v| !v.trim().is_empty());
+
+        let host = match var("HOST") {
+            Some(host) => host
+                .trim()
+                .parse()
+                .map_err(|_| BindError::InvalidHost(host))?,
+            None => DEFAULT_HOST,
+        };
+        let port = match var("PORT") {
+            Some(port) => match port.trim().parse::<u16>() {
+                Ok(p) if p != 0 => p,
+                _ => return Err(BindError::InvalidPort(port)),
+            },
+            None => DEFAULT_PORT,
+        };
+        let tls = match (var("TLS_CERT_PATH"), var("TLS_KEY_PATH")) {
+            (Some(cert), Some(key)) => Some(TlsPaths {
+                cert: cert.into(),
+                key: key.into(),
+            }),
+            (Some(_), None) => return Err(BindError::IncompleteTls("TLS_CERT_PATH")),
+            (None, Some(_)) => return Err(BindError::IncompleteTls("TLS_KEY_PATH")),
+            (None, None) => None,
+        };
+
+        Ok(Self {
+            addr: SocketAddr::new(host, port),
+            tls,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn resolve(vars: &[(&str, &str)]) -> Result<BindConfig, BindError> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        BindConfig::resolve(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn test_defaults_to_localhost_3000() {
+        let config = resolve(&[]).unwrap();
+        assert_eq!(config.addr, "127.0.0.1:3000".parse().unwrap());
+        assert_eq!(config.tls, None);
+        assert_eq!(resolve(&[("HOST", ""), ("PORT", " ")]).unwrap(), config);
+    }
+
+    #[test]
+    fn test_reads_host_and_port() {
+        let config = resolve(&[("HOST", "0.0.0.0"), ("PORT", "8080")]).unwrap();
+        assert_eq!(config.addr, "0.0.0.0:8080".parse().unwrap());
+
+        let config = resolve(&[("HOST", "::1")]).unwrap();
+        assert_eq!(config.addr, "[::1]:3000".parse().unwrap());
+    }
+
+    #[test]
+    fn test_invalid_host_or_port_is_an_error() {
+        assert_eq!(
+            resolve(&[("HOST", "arena.local")]),
+            Err(BindError::InvalidHost("arena.local".to_string()))
+        );
+        for port in ["http", "0", "70000", "-1"] {
+            assert_eq!(
+                resolve(&[("PORT", port)]),
+                Err(BindError::InvalidPort(port.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_tls_needs_both_paths() {
+        let config =
+            resolve(&[("TLS_CERT_PATH", "cert.pem"), ("TLS_KEY_PATH", "key.pem")]).unwrap();
+        assert_eq!(
+            config.tls,
+            Some(TlsPaths {
+                cert: "cert.pem".into(),
+                key: "key.pem".into(),
+            })
+        );
+
+        assert_eq!(
+            resolve(&[("TLS_CERT_PATH", "cert.pem")]),
+            Err(BindError::IncompleteTls("TLS_CERT_PATH"))
+        );
+        assert_eq!(
+            resolve(&[("TLS_KEY_PATH", "key.pem")]),
+            Err(BindError::IncompleteTls("TLS_KEY_PATH"))
+        );
+    }
+}
@@ -14,6 +14,7 @@ pub type DbPool = Arc<Mutex<Connection>>;
 /// - `matches`: Multi-game series between two bots
 /// - `games`: Individual games within a match
 /// - `moves`: Move-by-move storage with evaluation data
+/// - `game_analyses`: Engine analyses of games, as `GameAnalysis` JSON
 ///
 /// # Arguments
 ///
@@ -51,7 +52,9 @@ pub fn init_db<P: AsRef<Path>>(path: P) -> SqliteResult<DbPool> {
             started_at TEXT NOT NULL,
             finished_at TEXT,
             status TEXT DEFAULT 'pending',
-            worker_id TEXT
+            worker_id TEXT,
+            attempts INTEGER DEFAULT 0,
+            error TEXT
         );
 
         CREATE TABLE IF NOT EXISTS games (
@@ -93,12 +96,46 @@ pub fn init_db<P: AsRef<Path>>(path: P) -> SqliteResult<DbPool> {
         );
 
         CREATE INDEX IF NOT EXISTS idx_elo_history_bot ON elo_history(bot_name);
+
+        CREATE TABLE IF NOT EXISTS game_analyses (
+            game_id TEXT NOT NULL REFERENCES games(id),
+            depth INTEGER NOT NULL,
+            analysis TEXT NOT NULL,
+            created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+            PRIMARY KEY (game_id, depth)
+        );
         ",
     )?;
 
+    // Columns added after the initial schema
+    add_column_if_missing(&conn, "matches", "attempts", "INTEGER DEFAULT 0")?;
+    add_column_if_missing(&conn, "matches", "error", "TEXT")?;
+    add_column_if_missing(&conn, "matches", "cancel_requested", "INTEGER DEFAULT 0")?;
+
     Ok(Arc::new(Mutex::new(conn)))
 }
 
+/// Add a column to an existing table unless it is already there.
+fn add_column_if_missing(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> SqliteResult<()> {
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM pragma_table_info(?1) WHERE name = ?2)",
+        (table, column),
+        |row| row.get(0),
+    )?;
+    if !exists {
+        conn.execute_batch(&format!(
+            "ALTER TABLE {} ADD COLUMN {} {};",
+            table, column, definition
+        ))?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -122,6 +159,7 @@ mod tests {
         assert!(tables.contains(&"games".to_string()));
         assert!(tables.contains(&"moves".to_string()));
         assert!(tables.contains(&"elo_history".to_string()));
+        assert!(tables.contains(&"game_analyses".to_string()));
     }
 
     #[test]
@@ -146,6 +184,45 @@ mod tests {
         assert!(indexes.contains(&"idx_elo_history_bot".to_string()));
     }
 
+    #[test]
+    fn test_init_db_adds_retry_columns_to_old_schema() {
+        let dir = std::env::temp_dir().join(format!("arena-migrate-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("old.db");
+        let _ = std::fs::remove_file(&path);
+        {
+            let conn = Connection::open(&path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE matches (
+                     id TEXT PRIMARY KEY,
+                     white_bot TEXT NOT NULL,
+                     black_bot TEXT NOT NULL,
+                     games_total INTEGER NOT NULL,
+                     started_at TEXT NOT NULL,
+                     status TEXT DEFAULT 'pending',
+                     worker_id TEXT
+                 );
+                 INSERT INTO matches (id, white_bot, black_bot, games_total, started_at)
+                 VALUES ('m1', 'a', 'b', 1, '2025-01-01');",
+            )
+            .unwrap();
+        }
+
+        let db = init_db(&path).expect("Failed to migrate db");
+        let conn = db.lock().unwrap();
+        let (attempts, error): (i32, Option<String>) = conn
+            .query_row(
+                "SELECT attempts, error FROM matches WHERE id = 'm1'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(attempts, 0);
+        assert_eq!(error, None);
+        drop(conn);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn test_init_db_idempotent() {
         // Should be safe to call init_db multiple times on same database
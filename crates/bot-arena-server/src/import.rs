@@ -0,0 +1,313 @@
+//! Import of game files written by the `bot-arena` CLI.
+//!
+//! The CLI saves each game as a JSON file under `data/games/<date>/`. On
+//! startup the server scans that directory and stores every game it has not
+//! seen yet as a one-game match, with its moves and detected opening, so CLI
+//! games show up alongside matches played by the worker.
+
+use crate::db::DbPool;
+use bot_arena::game_runner::{detect_opening, MoveRecord};
+use chess_engine::Game;
+use chess_openings::OpeningDatabase;
+use rusqlite::{Connection, OptionalExtension};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Directory the CLI writes game files to.
+pub const GAMES_DIR: &str = "data/games";
+
+/// A game file as written by `bot_arena::json_output::write_json`.
+#[derive(Debug, Deserialize)]
+struct GameFile {
+    id: String,
+    white: String,
+    black: String,
+    /// "white", "black", or "draw".
+    result: String,
+    moves: Vec<MoveEntry>,
+    created_at: String,
+}
+
+/// One move of a game file.
+#[derive(Debug, Deserialize)]
+struct MoveEntry {
+    uci: String,
+    search_info: Option<SearchInfoEntry>,
+}
+
+/// The parts of a move's search information that the server stores.
+#[derive(Debug, Deserialize)]
+struct SearchInfoEntry {
+    score_cp: Option<i32>,
+    time_ms: Option<u64>,
+}
+
+/// Counts of the game files seen by an import.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ImportSummary {
+    /// Games stored by this import.
+    pub imported: usize,
+    /// Games that were already in the database.
+    pub skipped: usize,
+    /// Files that could not be read or stored.
+    pub failed: usize,
+}
+
+/// Import every JSON game file found under `dir`.
+///
+/// Games are identified by the `id` in their file, so importing the same
+/// directory again skips the games already stored. Files that fail to parse
+/// or to store are logged and counted, and do not stop the import.
+pub fn import_dir(db: &DbPool, dir: &Path, openings: &OpeningDatabase) -> ImportSummary {
+    let mut summary = ImportSummary::default();
+    let mut files = Vec::new();
+    collect_json_files(dir, &mut files);
+    files.sort();
+
+    for path in files {
+        let game = match std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| serde_json::from_str::<GameFile>(&text).map_err(|e| e.to_string()))
+        {
+            Ok(game) => game,
+            Err(e) => {
+                tracing::warn!("Skipping unreadable game file {}: {}", path.display(), e);
+                summary.failed += 1;
+                continue;
+            }
+        };
+
+        let mut conn = db.lock().unwrap();
+        match import_game(&mut conn, &game, openings) {
+            Ok(true) => summary.imported += 1,
+            Ok(false) => summary.skipped += 1,
+            Err(e) => {
+                tracing::warn!("Failed to import game {}: {}", game.id, e);
+                summary.failed += 1;
+            }
+        }
+    }
+
+    summary
+}
+
+/// Recursively collect the `.json` files below `dir`.
+fn collect_json_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_json_files(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "json") {
+            files.push(path);
+        }
+    }
+}
+
+/// Store one game as a completed one-game match.
+///
+/// Returns `Ok(false)` without changes if a game with the same ID exists.
+/// Moves are replayed from the starting position to fill in SAN and FEN;
+/// replay stops at the first illegal move.
+fn import_game(
+    conn: &mut Connection,
+    game: &GameFile,
+    openings: &OpeningDatabase,
+) -> rusqlite::Result<bool> {
+    let exists = conn
+        .query_row("SELECT 1 FROM games WHERE id = ?1", [&game.id], |_| Ok(()))
+        .optional()?
+        .is_some();
+    if exists {
+        return Ok(false);
+    }
+
+    let (result, white_score) = match game.result.as_str() {
+        "white" => ("1-0", 1.0),
+        "black" => ("0-1", 0.0),
+        _ => ("1/2-1/2", 0.5),
+    };
+    let records: Vec<MoveRecord> = game
+        .moves
+        .iter()
+        .map(|m| MoveRecord {
+            uci: m.uci.clone(),
+            search_info: None,
+        })
+        .collect();
+    let opening = detect_opening(&records, openings);
+    let match_id = Uuid::new_v4().to_string();
+
+    let tx = conn.transaction()?;
+    for bot in [&game.white, &game.black] {
+        tx.execute("INSERT OR IGNORE INTO bots (name) VALUES (?1)", [bot])?;
+    }
+    tx.execute(
+        "INSERT INTO matches (id, white_bot, black_bot, games_total, white_score, black_score,
+                              opening_id, started_at, finished_at, status)
+         VALUES (?1, ?2, ?3, 1, ?4, ?5, ?6, ?7, ?7, 'completed')",
+        (
+            &match_id,
+            &game.white,
+            &game.black,
+            white_score,
+            1.0 - white_score,
+            opening.as_ref().map(|o| &o.id),
+            &game.created_at,
+        ),
+    )?;
+    // Game number 0 keeps the match's colors
+    tx.execute(
+        "INSERT INTO games (id, match_id, game_number, result, opening_name, started_at, finished_at)
+         VALUES (?1, ?2, 0, ?3, ?4, ?5, ?5)",
+        (
+            &game.id,
+            &match_id,
+            result,
+            opening.as_ref().map(|o| &o.name),
+            &game.created_at,
+        ),
+    )?;
+
+    let mut replay = Game::new();
+    for (ply, entry) in (1i32..).zip(&game.moves) {
+        if replay.make_move_uci(&entry.uci).is_err() {
+            break;
+        }
+        let san = replay.move_history().last().map(|m| m.san.clone());
+        let info = entry.search_info.as_ref();
+        tx.execute(
+            "INSERT INTO moves (game_id, ply, uci, san, fen_after, bot_eval, time_ms)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            (
+                &game.id,
+                ply,
+                &entry.uci,
+                san,
+                replay.to_fen(),
+                info.and_then(|i| i.score_cp),
+                info.and_then(|i| i.time_ms).map(|t| t as i64),
+            ),
+        )?;
+    }
+    tx.commit()?;
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::init_db;
+    use bot_arena::game_runner::{GameResult, MatchResult, Termination};
+    use bot_arena::json_output::write_json;
+
+    fn game(moves: &[&str], result: MatchResult) -> GameResult {
+        GameResult {
+            moves: moves
+                .iter()
+                .map(|uci| MoveRecord {
+                    uci: uci.to_string(),
+                    search_info: None,
+                })
+                .collect(),
+            result,
+            termination: Termination::Normal,
+            white_name: "alpha".to_string(),
+            black_name: "beta".to_string(),
+            opening: None,
+        }
+    }
+
+    fn move_count(db: &DbPool, game_id: &str) -> i64 {
+        db.lock()
+            .unwrap()
+            .query_row(
+                "SELECT COUNT(*) FROM moves WHERE game_id = ?1 AND san IS NOT NULL",
+                [game_id],
+                |row| row.get(0),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_import_dir_stores_games_once() {
+        let dir = std::env::temp_dir().join(format!("arena-import-{}", Uuid::new_v4()));
+        let day = dir.join("2024-01-15");
+        std::fs::create_dir_all(&day).unwrap();
+        let italian = ["e2e4", "e7e5", "g1f3", "b8c6", "f1c4"];
+        write_json(
+            day.join("game-1.json"),
+            "game-1",
+            &game(&italian, MatchResult::WhiteWins),
+        )
+        .unwrap();
+        write_json(
+            dir.join("game-2.json"),
+            "game-2",
+            &game(&["d2d4", "d7d5"], MatchResult::Draw),
+        )
+        .unwrap();
+
+        let db = init_db(":memory:").unwrap();
+        let openings = OpeningDatabase::with_openings(chess_openings::builtin::builtin_openings());
+        let summary = import_dir(&db, &dir, &openings);
+        assert_eq!(
+            summary,
+            ImportSummary {
+                imported: 2,
+                skipped: 0,
+                failed: 0
+            }
+        );
+        assert_eq!(move_count(&db, "game-1"), 5);
+        assert_eq!(move_count(&db, "game-2"), 2);
+
+        {
+            let conn = db.lock().unwrap();
+            let (game_number, result, opening, status): (i32, String, Option<String>, String) =
+                conn.query_row(
+                    "SELECT g.game_number, g.result, g.opening_name, m.status
+                     FROM games g JOIN matches m ON m.id = g.match_id
+                     WHERE g.id = 'game-1'",
+                    [],
+                    |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+                )
+                .unwrap();
+            assert_eq!(game_number, 0);
+            assert_eq!(result, "1-0");
+            assert_eq!(opening.as_deref(), Some("Italian Game"));
+            assert_eq!(status, "completed");
+        }
+
+        // A second import finds nothing new
+        let again = import_dir(&db, &dir, &openings);
+        assert_eq!(again.imported, 0);
+        assert_eq!(again.skipped, 2);
+        let games: i64 = db
+            .lock()
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM games", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(games, 2);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_import_dir_counts_bad_files() {
+        let dir = std::env::temp_dir().join(format!("arena-import-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("broken.json"), "{ not json").unwrap();
+        std::fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let db = init_db(":memory:").unwrap();
+        let openings = OpeningDatabase::with_openings(Vec::new());
+        assert_eq!(import_dir(&db, &dir, &openings).failed, 1);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
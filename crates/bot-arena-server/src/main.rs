@@ -7,8 +7,10 @@
 
 mod analysis;
 mod api;
+mod bind;
 mod db;
 mod elo;
+mod import;
 mod middleware;
 mod models;
 mod repo;
@@ -16,8 +18,8 @@ mod watcher;
 mod ws;
 
 use axum::middleware as axum_middleware;
-use axum::routing::get;
-use axum::Router;
+use axum::routing::{delete, get, post};
+use axum::{Extension, Router};
 use bot_arena::config::ArenaConfig;
 use db::DbPool;
 use std::net::SocketAddr;
@@ -45,14 +47,36 @@ async fn health() -> &'static str {
     "ok"
 }
 
+/// Reads and parses an environment variable, falling back to `default`.
+fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
 #[tokio::main]
 async fn main() {
     tracing_subscriber::fmt::init();
 
+    let bind = bind::BindConfig::from_env().unwrap_or_else(|e| panic!("{}", e));
+
     // Create data directory if needed
     std::fs::create_dir_all("data").expect("Failed to create data directory");
 
     let db = db::init_db("data/arena.db").expect("Failed to initialize database");
+
+    // Pick up games played with the CLI since the last start
+    let openings =
+        chess_openings::OpeningDatabase::with_openings(chess_openings::builtin::builtin_openings());
+    let imported = import::import_dir(&db, std::path::Path::new(import::GAMES_DIR), &openings);
+    tracing::info!(
+        "Imported {} games from {} ({} already stored, {} failed)",
+        imported.imported,
+        import::GAMES_DIR,
+        imported.skipped,
+        imported.failed
+    );
     let ws_broadcast = ws::create_broadcast();
 
     // Load arena configuration
@@ -91,6 +115,25 @@ async fn main() {
         watcher::watch_moves(db_for_watcher, broadcast_for_watcher).await;
     });
 
+    // Per-client rate limit, configurable from the environment
+    let rate_limit_rps = env_or("RATE_LIMIT_RPS", 20.0);
+    let rate_limit_burst = env_or("RATE_LIMIT_BURST", 40);
+    tracing::info!(
+        "Rate limit: {} requests/second, burst {}",
+        rate_limit_rps,
+        rate_limit_burst
+    );
+    let rate_limiter = middleware::RateLimiter::shared(rate_limit_rps, rate_limit_burst);
+
+    // Bearer token required by write endpoints
+    let api_token = middleware::ApiToken::from_env();
+    if !api_token.is_set() {
+        tracing::warn!(
+            "{} is not set; write endpoints will reject all requests",
+            middleware::auth::API_TOKEN_ENV
+        );
+    }
+
     // CORS layer for cross-origin requests
     let cors = CorsLayer::new()
         .allow_origin(Any)
@@ -109,29 +152,95 @@ async fn main() {
         .route("/api/bots/:name", get(api::bots::get_bot))
         .route(
             "/api/matches",
-            get(api::matches::list_matches).post(api::matches::create_match),
+            get(api::matches::list_matches).merge(
+                post(api::matches::create_match)
+                    .route_layer(axum_middleware::from_fn(middleware::require_api_token)),
+            ),
+        )
+        .route(
+            "/api/matches/:id",
+            get(api::matches::get_match_detail).merge(
+                delete(api::matches::delete_match)
+                    .route_layer(axum_middleware::from_fn(middleware::require_api_token)),
+            ),
+        )
+        .route(
+            "/api/matches/:id/cancel",
+            post(api::matches::cancel_match)
+                .route_layer(axum_middleware::from_fn(middleware::require_api_token)),
         )
-        .route("/api/matches/:id", get(api::matches::get_match_detail))
         .route("/api/games/:id/moves", get(api::matches::get_game_moves))
+        .route(
+            "/api/games/:id/analyze",
+            post(api::analysis::analyze_game)
+                .route_layer(axum_middleware::from_fn(middleware::require_api_token)),
+        )
         .route("/api/export/match/:id", get(api::export::export_match))
         .route("/api/export/game/:id", get(api::export::export_game))
+        .route(
+            "/api/export/game/:id/pgn",
+            get(api::export::export_game_pgn),
+        )
         .route("/api/export/bot/:name", get(api::export::export_bot))
+        .route(
+            "/api/export/matches.csv",
+            get(api::export::export_matches_csv),
+        )
         .route("/api/openings", get(api::openings::list_openings))
+        .route(
+            "/api/openings/explore",
+            get(api::openings::explore_openings),
+        )
         .route("/api/presets", get(api::presets::list_presets))
         .route("/api/stats/head-to-head", get(api::stats::head_to_head))
+        .route(
+            "/api/stats/head-to-head/:bot_a/:bot_b",
+            get(api::stats::head_to_head_pair),
+        )
+        .route("/api/stats/openings", get(api::stats::opening_stats))
         .with_state(state)
         .merge(ws_router)
         .layer(axum_middleware::from_fn(middleware::timing_layer))
+        .layer(axum_middleware::from_fn(middleware::rate_limit))
+        .layer(Extension(rate_limiter))
+        .layer(Extension(api_token))
         .layer(cors)
         .fallback_service(ServeDir::new("static").append_index_html_on_directories(true));
 
-    let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
-    tracing::info!("Server running on http://{}", addr);
-
-    let listener = tokio::net::TcpListener::bind(addr)
-        .await
-        .expect("Failed to bind to address");
-    axum::serve(listener, app).await.expect("Server error");
+    let addr = bind.addr;
+    match bind.tls {
+        Some(tls) => {
+            let rustls = axum_server::tls_rustls::RustlsConfig::from_pem_file(&tls.cert, &tls.key)
+                .await
+                .unwrap_or_else(|e| {
+                    panic!(
+                        "Failed to load TLS certificate {} and key {}: {}",
+                        tls.cert.display(),
+                        tls.key.display(),
+                        e
+                    )
+                });
+            tracing::info!("Server running on https://{}", addr);
+
+            axum_server::bind_rustls(addr, rustls)
+                .serve(app.into_make_service_with_connect_info::<SocketAddr>())
+                .await
+                .expect("Server error");
+        }
+        None => {
+            tracing::info!("Server running on http://{}", addr);
+
+            let listener = tokio::net::TcpListener::bind(addr)
+                .await
+                .unwrap_or_else(|e| panic!("Failed to bind to {}: {}", addr, e));
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+            .expect("Server error");
+        }
+    }
 }
 
 #[cfg(test)]
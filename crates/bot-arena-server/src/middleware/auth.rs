@@ -0,0 +1,174 @@
+//! Bearer-token authentication for mutating endpoints.
+//!
+//! Write routes are wrapped with [`require_api_token`], which checks the
+//! `Authorization: Bearer <token>` header against the server's [`ApiToken`].
+//! Read routes are left public.
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+
+/// Environment variable holding the API token.
+pub const API_TOKEN_ENV: &str = "ARENA_API_TOKEN";
+
+/// The token that clients must present to use write endpoints.
+///
+/// Installed as a request extension. With no token configured, every
+/// authenticated request is rejected.
+#[derive(Debug, Clone, Default)]
+pub struct ApiToken(Option<Arc<str>>);
+
+impl ApiToken {
+    /// Create an API token. Empty tokens are treated as unset.
+    pub fn new(token: Option<String>) -> Self {
+        Self(token.filter(|t| !t.is_empty()).map(Arc::from))
+    }
+
+    /// Read the token from the [`API_TOKEN_ENV`] environment variable.
+    pub fn from_env() -> Self {
+        Self::new(std::env::var(API_TOKEN_ENV).ok())
+    }
+
+    /// Returns true if a token is configured.
+    pub fn is_set(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Returns true if an `Authorization` header value carries this token.
+    pub fn accepts(&self, authorization: &str) -> bool {
+        let Some(expected) = &self.0 else {
+            return false;
+        };
+        let Some(presented) = authorization.strip_prefix("Bearer ") else {
+            return false;
+        };
+        constant_time_eq(presented.trim().as_bytes(), expected.as_bytes())
+    }
+}
+
+/// Compares two byte strings without short-circuiting on the first mismatch.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Middleware that rejects requests without a valid bearer token.
+///
+/// Reads the [`ApiToken`] from the request extensions and returns
+/// `401 Unauthorized` if the `Authorization` header is missing or wrong.
+/// Layer it on individual write routes with `route_layer`:
+///
+/// ```ignore
+/// use axum::{middleware, routing::post, Extension, Router};
+/// use bot_arena_server::middleware::{require_api_token, ApiToken};
+///
+/// let app = Router::new()
+///     .route(
+///         "/api/example",
+///         post(handler).route_layer(middleware::from_fn(require_api_token)),
+///     )
+///     .layer(Extension(ApiToken::from_env()));
+/// ```
+pub async fn require_api_token(request: Request<Body>, next: Next) -> Response {
+    let authorized = match (
+        request.extensions().get::<ApiToken>(),
+        request.headers().get(header::AUTHORIZATION),
+    ) {
+        (Some(token), Some(value)) => value.to_str().is_ok_and(|v| token.accepts(v)),
+        _ => false,
+    };
+
+    if authorized {
+        next.run(request).await
+    } else {
+        (
+            StatusCode::UNAUTHORIZED,
+            [(header::WWW_AUTHENTICATE, "Bearer")],
+            "Missing or invalid API token",
+        )
+            .into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        middleware,
+        routing::{get, post},
+        Extension, Router,
+    };
+    use tower::ServiceExt;
+
+    fn app(token: Option<&str>) -> Router {
+        Router::new()
+            .route(
+                "/write",
+                get(|| async { "read" }).merge(
+                    post(|| async { "written" })
+                        .route_layer(middleware::from_fn(require_api_token)),
+                ),
+            )
+            .layer(Extension(ApiToken::new(token.map(str::to_string))))
+    }
+
+    async fn post_with(app: Router, authorization: Option<&str>) -> Response {
+        let mut request = Request::builder().method("POST").uri("/write");
+        if let Some(value) = authorization {
+            request = request.header(header::AUTHORIZATION, value);
+        }
+        app.oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_missing_token_is_unauthorized() {
+        let response = post_with(app(Some("secret")), None).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(response.headers()[header::WWW_AUTHENTICATE], "Bearer");
+    }
+
+    #[tokio::test]
+    async fn test_wrong_token_is_unauthorized() {
+        let response = post_with(app(Some("secret")), Some("Bearer guess")).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = post_with(app(Some("secret")), Some("Basic secret")).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_correct_token_reaches_handler() {
+        let response = post_with(app(Some("secret")), Some("Bearer secret")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"written");
+    }
+
+    #[tokio::test]
+    async fn test_unconfigured_token_rejects_writes() {
+        let response = post_with(app(None), Some("Bearer ")).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert!(!ApiToken::new(Some(String::new())).is_set());
+    }
+
+    #[tokio::test]
+    async fn test_reads_stay_public() {
+        let response = app(Some("secret"))
+            .oneshot(
+                Request::builder()
+                    .uri("/write")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -1,5 +1,9 @@
 //! Middleware components for the Bot Arena server.
 
+pub mod auth;
+pub mod rate_limit;
 pub mod timing;
 
+pub use auth::{require_api_token, ApiToken};
+pub use rate_limit::{rate_limit, RateLimiter};
 pub use timing::timing_layer;
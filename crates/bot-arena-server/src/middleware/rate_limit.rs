@@ -0,0 +1,228 @@
+//! Per-client rate limiting middleware.
+//!
+//! Each client IP gets a token bucket that refills at a steady rate up to a
+//! burst capacity. A request spends one token; when the bucket is empty the
+//! request is rejected with `429 Too Many Requests` and a `Retry-After`
+//! header saying how long until a token is available.
+
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::{header, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Number of tracked clients above which idle buckets are pruned.
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// A token bucket for a single client.
+#[derive(Debug, Clone, Copy)]
+struct TokenBucket {
+    /// Tokens currently available.
+    tokens: f64,
+    /// When `tokens` was last brought up to date.
+    updated: Instant,
+}
+
+/// Token-bucket rate limiter keyed by client IP.
+///
+/// Install it as a request extension and add [`rate_limit`] as middleware:
+///
+/// ```ignore
+/// use axum::{middleware, Extension, Router};
+/// use bot_arena_server::middleware::{rate_limit, RateLimiter};
+///
+/// let app = Router::new()
+///     .route("/api/example", get(handler))
+///     .layer(middleware::from_fn(rate_limit))
+///     .layer(Extension(RateLimiter::shared(10.0, 20)));
+/// ```
+#[derive(Debug)]
+pub struct RateLimiter {
+    requests_per_second: f64,
+    burst: f64,
+    buckets: Mutex<HashMap<IpAddr, TokenBucket>>,
+}
+
+impl RateLimiter {
+    /// Create a limiter allowing `requests_per_second` sustained requests per
+    /// client, with bursts of up to `burst` requests.
+    pub fn new(requests_per_second: f64, burst: u32) -> Self {
+        Self {
+            requests_per_second,
+            burst: f64::from(burst.max(1)),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Create a limiter wrapped in an [`Arc`], ready to use as an extension.
+    pub fn shared(requests_per_second: f64, burst: u32) -> Arc<Self> {
+        Arc::new(Self::new(requests_per_second, burst))
+    }
+
+    /// Try to spend a token for `client` at time `now`.
+    ///
+    /// Returns `Err` with the time until the next token is available if the
+    /// client's bucket is empty.
+    pub fn check(&self, client: IpAddr, now: Instant) -> Result<(), Duration> {
+        let mut buckets = self.buckets.lock().unwrap();
+
+        if buckets.len() > PRUNE_THRESHOLD {
+            // Buckets that have refilled completely behave like new ones
+            buckets.retain(|_, bucket| self.refilled(bucket, now) < self.burst);
+        }
+
+        let bucket = buckets.entry(client).or_insert(TokenBucket {
+            tokens: self.burst,
+            updated: now,
+        });
+        bucket.tokens = self.refilled(bucket, now);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else if self.requests_per_second > 0.0 {
+            let wait = (1.0 - bucket.tokens) / self.requests_per_second;
+            Err(Duration::from_secs_f64(wait))
+        } else {
+            Err(Duration::MAX)
+        }
+    }
+
+    /// Returns the tokens `bucket` would hold at `now`.
+    fn refilled(&self, bucket: &TokenBucket, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        (bucket.tokens + elapsed * self.requests_per_second).min(self.burst)
+    }
+}
+
+/// Middleware that rejects clients exceeding their request rate.
+///
+/// Reads the [`RateLimiter`] from the request extensions and the client IP
+/// from [`ConnectInfo`], so the server must be started with
+/// `into_make_service_with_connect_info::<SocketAddr>()`. Requests without a
+/// known address share a single bucket. If no limiter is installed, requests
+/// pass through unchanged.
+pub async fn rate_limit(request: Request<Body>, next: Next) -> Response {
+    let Some(limiter) = request.extensions().get::<Arc<RateLimiter>>().cloned() else {
+        return next.run(request).await;
+    };
+
+    let client = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip())
+        .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+
+    match limiter.check(client, Instant::now()) {
+        Ok(()) => next.run(request).await,
+        Err(wait) => {
+            let retry_after = wait.as_secs_f64().ceil().clamp(1.0, u32::MAX as f64) as u32;
+            tracing::debug!(client = %client, retry_after, "Rate limit exceeded");
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(header::RETRY_AFTER, retry_after.to_string())],
+                "Too many requests",
+            )
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{middleware, routing::get, Extension, Router};
+    use tower::ServiceExt;
+
+    fn client(last: u8) -> IpAddr {
+        IpAddr::V4(Ipv4Addr::new(10, 0, 0, last))
+    }
+
+    #[test]
+    fn test_burst_allowed_then_rejected() {
+        let limiter = RateLimiter::new(1.0, 3);
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert!(limiter.check(client(1), now).is_ok());
+        }
+        let wait = limiter.check(client(1), now).unwrap_err();
+        assert!((wait.as_secs_f64() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_bucket_refills_over_time() {
+        let limiter = RateLimiter::new(2.0, 2);
+        let start = Instant::now();
+
+        assert!(limiter.check(client(1), start).is_ok());
+        assert!(limiter.check(client(1), start).is_ok());
+        assert!(limiter.check(client(1), start).is_err());
+
+        // Half a second at 2 requests/second refills one token
+        let later = start + Duration::from_millis(500);
+        assert!(limiter.check(client(1), later).is_ok());
+        assert!(limiter.check(client(1), later).is_err());
+
+        // Refill is capped at the burst size
+        let much_later = later + Duration::from_secs(60);
+        assert!(limiter.check(client(1), much_later).is_ok());
+        assert!(limiter.check(client(1), much_later).is_ok());
+        assert!(limiter.check(client(1), much_later).is_err());
+    }
+
+    #[test]
+    fn test_clients_have_separate_buckets() {
+        let limiter = RateLimiter::new(1.0, 1);
+        let now = Instant::now();
+
+        assert!(limiter.check(client(1), now).is_ok());
+        assert!(limiter.check(client(1), now).is_err());
+        assert!(limiter.check(client(2), now).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_middleware_returns_429() {
+        let app: Router = Router::new()
+            .route("/test", get(|| async { "ok" }))
+            .layer(middleware::from_fn(rate_limit))
+            .layer(Extension(RateLimiter::shared(0.001, 1)));
+
+        let request = || Request::builder().uri("/test").body(Body::empty()).unwrap();
+
+        let response = app.clone().oneshot(request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app.oneshot(request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        let retry_after: u64 = response.headers()[header::RETRY_AFTER]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(retry_after >= 1);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_middleware_without_limiter_passes_through() {
+        let app: Router = Router::new()
+            .route("/test", get(|| async { "ok" }))
+            .layer(middleware::from_fn(rate_limit));
+
+        for _ in 0..5 {
+            let response = app
+                .clone()
+                .oneshot(Request::builder().uri("/test").body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+    }
+}
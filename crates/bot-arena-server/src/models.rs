@@ -86,7 +86,7 @@ pub struct Match {
     pub started_at: String,
     /// When the match finished (if complete).
     pub finished_at: Option<String>,
-    /// Match status (pending, running, completed, failed).
+    /// Match status (pending, running, completed, failed, cancelled).
     pub status: String,
     /// Worker ID processing this match (if assigned).
     pub worker_id: Option<String>,
@@ -99,7 +99,7 @@ pub struct Game {
     pub id: String,
     /// Match this game belongs to.
     pub match_id: String,
-    /// Game number within the match (1-indexed).
+    /// Game number within the match (0-indexed).
     pub game_number: i32,
     /// Game result (1-0, 0-1, 1/2-1/2, or None if in progress).
     pub result: Option<String>,
@@ -109,6 +109,19 @@ pub struct Game {
     pub pgn: Option<String>,
 }
 
+/// Returns the `(white, black)` players of game `game_number` of a match
+/// whose first game is played by `match_white` and `match_black`.
+///
+/// The worker alternates colors each game: even games keep the match's
+/// colors and odd games swap them.
+pub fn players_for_game<T>(game_number: i32, match_white: T, match_black: T) -> (T, T) {
+    if game_number % 2 == 0 {
+        (match_white, match_black)
+    } else {
+        (match_black, match_white)
+    }
+}
+
 /// A single move in a game.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Move {
@@ -126,10 +139,106 @@ pub struct Move {
     pub stockfish_eval: Option<i32>,
 }
 
+/// Aggregated results for an opening reached in stored games.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OpeningPerformance {
+    /// Opening ID from the opening database.
+    pub opening_id: String,
+    /// Name of the opening.
+    pub name: String,
+    /// ECO code, if the opening has one.
+    pub eco: Option<String>,
+    /// Number of finished games that reached this opening.
+    pub games_played: i32,
+    /// Number of games won by white.
+    pub white_wins: i32,
+    /// Number of drawn games.
+    pub draws: i32,
+    /// Number of games won by black.
+    pub black_wins: i32,
+    /// Average game length in plies.
+    pub avg_game_length: f64,
+}
+
+/// Results of the games that played a move after a given line.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExplorerMove {
+    /// The move in UCI notation.
+    pub uci: String,
+    /// Number of finished games that played this move.
+    pub games: i32,
+    /// Number of those games won by white.
+    pub white_wins: i32,
+    /// Number of those games drawn.
+    pub draws: i32,
+    /// Number of those games won by black.
+    pub black_wins: i32,
+    /// White's average score in those games (win 1, draw 0.5).
+    pub score: f64,
+}
+
+/// Opening explorer view of a line of moves from the starting position.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OpeningExplorer {
+    /// The line explored, as UCI moves.
+    pub moves: Vec<String>,
+    /// Named openings that continue the line.
+    pub openings: Vec<chess_openings::Opening>,
+    /// Moves played next in stored games, most played first.
+    pub next_moves: Vec<ExplorerMove>,
+}
+
+/// One bot's results with a given color in a head-to-head pairing.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ColorRecord {
+    /// Number of games played with this color.
+    pub games: i32,
+    /// Number of those games won.
+    pub wins: i32,
+    /// Number of those games drawn.
+    pub draws: i32,
+    /// Number of those games lost.
+    pub losses: i32,
+}
+
+/// Aggregated results between two bots across all their finished games.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HeadToHeadSummary {
+    /// First bot of the pairing; wins and recent form are from its side.
+    pub bot_a: String,
+    /// Second bot of the pairing.
+    pub bot_b: String,
+    /// Total number of finished games between the two bots.
+    pub games: i32,
+    /// Number of games won by `bot_a`.
+    pub bot_a_wins: i32,
+    /// Number of games won by `bot_b`.
+    pub bot_b_wins: i32,
+    /// Number of drawn games.
+    pub draws: i32,
+    /// Fraction of games drawn, or 0 with no games.
+    pub draw_rate: f64,
+    /// Results of `bot_a` when playing white.
+    pub bot_a_as_white: ColorRecord,
+    /// Results of `bot_a` when playing black.
+    pub bot_a_as_black: ColorRecord,
+    /// Average number of moves per game, in plies.
+    pub avg_moves: f64,
+    /// The last 10 results for `bot_a`, most recent first (e.g. `"WDLWW"`).
+    pub recent_form: String,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_players_for_game_alternates_from_game_zero() {
+        assert_eq!(players_for_game(0, "a", "b"), ("a", "b"));
+        assert_eq!(players_for_game(1, "a", "b"), ("b", "a"));
+        assert_eq!(players_for_game(2, "a", "b"), ("a", "b"));
+    }
+
     #[test]
     fn test_win_rate_no_games() {
         let bot = Bot {
@@ -0,0 +1,136 @@
+//! Analysis repository for engine analyses of stored games.
+
+use crate::db::DbPool;
+use chess_analysis::GameAnalysis;
+use rusqlite::{OptionalExtension, Result as SqliteResult};
+
+/// Repository for game analyses.
+///
+/// Each game can have one analysis per search depth, stored as
+/// [`GameAnalysis`] JSON.
+pub struct AnalysisRepo {
+    db: DbPool,
+}
+
+impl AnalysisRepo {
+    /// Create a new analysis repository with the given database pool.
+    pub fn new(db: DbPool) -> Self {
+        Self { db }
+    }
+
+    /// Get the stored analysis of a game at a given search depth.
+    ///
+    /// Returns `None` if the game has not been analyzed at that depth, or
+    /// if the stored analysis no longer parses.
+    pub fn get(&self, game_id: &str, depth: u32) -> SqliteResult<Option<GameAnalysis>> {
+        let conn = self.db.lock().unwrap();
+        let json: Option<String> = conn
+            .query_row(
+                "SELECT analysis FROM game_analyses WHERE game_id = ?1 AND depth = ?2",
+                (game_id, depth),
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(json.and_then(|json| serde_json::from_str(&json).ok()))
+    }
+
+    /// Store the analysis of a game at a given search depth, replacing any
+    /// earlier analysis at the same depth.
+    pub fn save(&self, game_id: &str, depth: u32, analysis: &GameAnalysis) -> SqliteResult<()> {
+        let json = serde_json::to_string(analysis)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let conn = self.db.lock().unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO game_analyses (game_id, depth, analysis) VALUES (?1, ?2, ?3)",
+            (game_id, depth, json),
+        )?;
+        Ok(())
+    }
+
+    /// Get the deepest stored analysis of a game.
+    ///
+    /// Returns `None` if the game has not been analyzed. A stored analysis
+    /// that no longer parses is treated as missing.
+    pub fn latest(&self, game_id: &str) -> SqliteResult<Option<GameAnalysis>> {
+        let conn = self.db.lock().unwrap();
+        let json: Option<String> = conn
+            .query_row(
+                "SELECT analysis FROM game_analyses WHERE game_id = ?1
+                 ORDER BY depth DESC LIMIT 1",
+                [game_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(json.and_then(|json| serde_json::from_str(&json).ok()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::init_db;
+
+    use chess_analysis::{AnalysisMeta, PlayerStats};
+
+    fn db_with_game() -> DbPool {
+        let db = init_db(":memory:").unwrap();
+        db.lock()
+            .unwrap()
+            .execute_batch(
+                "INSERT INTO bots (name) VALUES ('a'), ('b');
+                 INSERT INTO matches (id, white_bot, black_bot, games_total, started_at, status)
+                 VALUES ('match1', 'a', 'b', 1, '2025-01-21', 'completed');
+                 INSERT INTO games (id, match_id, game_number, started_at)
+                 VALUES ('game1', 'match1', 1, '2025-01-21');",
+            )
+            .unwrap();
+        db
+    }
+
+    fn analysis(result: &str) -> GameAnalysis {
+        GameAnalysis {
+            game_id: "game1".to_string(),
+            white_bot: "a".to_string(),
+            black_bot: "b".to_string(),
+            opening: None,
+            result: result.to_string(),
+            moves: Vec::new(),
+            white_stats: PlayerStats::default(),
+            black_stats: PlayerStats::default(),
+            eval_timeline: Vec::new(),
+            meta: AnalysisMeta::default(),
+            start_fen: None,
+        }
+    }
+
+    #[test]
+    fn test_save_and_get_by_depth() {
+        let repo = AnalysisRepo::new(db_with_game());
+        repo.save("game1", 14, &analysis("1-0")).unwrap();
+        repo.save("game1", 10, &analysis("0-1")).unwrap();
+
+        assert_eq!(repo.get("game1", 10).unwrap().unwrap().result, "0-1");
+        assert!(repo.get("game1", 12).unwrap().is_none());
+        assert_eq!(repo.latest("game1").unwrap().unwrap().result, "1-0");
+
+        // Saving the same depth again replaces the earlier analysis
+        repo.save("game1", 14, &analysis("1/2-1/2")).unwrap();
+        assert_eq!(repo.get("game1", 14).unwrap().unwrap().result, "1/2-1/2");
+    }
+
+    #[test]
+    fn test_latest_missing_or_unparsable_is_none() {
+        let db = db_with_game();
+        let repo = AnalysisRepo::new(db.clone());
+        assert!(repo.latest("game1").unwrap().is_none());
+
+        db.lock()
+            .unwrap()
+            .execute(
+                "INSERT INTO game_analyses (game_id, depth, analysis) VALUES ('game1', 10, '{}')",
+                [],
+            )
+            .unwrap();
+        assert!(repo.latest("game1").unwrap().is_none());
+    }
+}
@@ -1,9 +1,12 @@
 //! Match repository for database operations.
 
 use crate::db::DbPool;
-use crate::models::{Game, Match, Move};
+use crate::models::{ExplorerMove, Game, Match, Move, OpeningPerformance};
+use bot_arena::game_runner::{detect_opening, MoveRecord};
+use chess_openings::OpeningDatabase;
 use rusqlite::OptionalExtension;
 use rusqlite::Result as SqliteResult;
+use std::collections::HashMap;
 use uuid::Uuid;
 
 /// Repository for match database operations.
@@ -16,16 +19,32 @@ pub struct MatchRepo {
 pub struct MatchFilter {
     /// Filter by bot name (matches where bot is white or black).
     pub bot: Option<String>,
+    /// Filter by match status (e.g. "pending", "completed").
+    pub status: Option<String>,
     /// Maximum number of results to return.
     pub limit: i32,
     /// Number of results to skip.
     pub offset: i32,
 }
 
+/// What a cancellation request did to a match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CancelOutcome {
+    /// The match was pending and is now `cancelled`.
+    Cancelled,
+    /// The match is running; its worker stops it after the current game.
+    Requested,
+    /// The match had already finished and was left unchanged.
+    Finished,
+    /// No match has the given ID.
+    NotFound,
+}
+
 impl Default for MatchFilter {
     fn default() -> Self {
         Self {
             bot: None,
+            status: None,
             limit: 20,
             offset: 0,
         }
@@ -62,39 +81,78 @@ impl MatchRepo {
         Ok(id)
     }
 
+    /// Builds the `WHERE` clause and its parameters for a filter.
+    ///
+    /// Parameters are numbered from `?1`.
+    fn filter_clause(filter: &MatchFilter) -> (String, Vec<String>) {
+        let mut conditions = Vec::new();
+        let mut params = Vec::new();
+
+        if let Some(bot) = &filter.bot {
+            params.push(bot.clone());
+            conditions.push(format!(
+                "(white_bot = ?{0} OR black_bot = ?{0})",
+                params.len()
+            ));
+        }
+        if let Some(status) = &filter.status {
+            params.push(status.clone());
+            conditions.push(format!("status = ?{}", params.len()));
+        }
+
+        let clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+        (clause, params)
+    }
+
     /// List matches with optional filtering.
     ///
     /// Results are ordered by start time (most recent first).
     pub fn list(&self, filter: MatchFilter) -> SqliteResult<Vec<Match>> {
         let conn = self.db.lock().unwrap();
 
-        let sql = if filter.bot.is_some() {
-            "SELECT id, white_bot, black_bot, games_total, white_score, black_score,
-                    opening_id, movetime_ms, started_at, finished_at, status, worker_id
-             FROM matches
-             WHERE white_bot = ?1 OR black_bot = ?1
-             ORDER BY started_at DESC LIMIT ?2 OFFSET ?3"
-        } else {
+        let (clause, mut params) = Self::filter_clause(&filter);
+        let sql = format!(
             "SELECT id, white_bot, black_bot, games_total, white_score, black_score,
                     opening_id, movetime_ms, started_at, finished_at, status, worker_id
              FROM matches
-             ORDER BY started_at DESC LIMIT ?1 OFFSET ?2"
-        };
+             {}
+             ORDER BY started_at DESC LIMIT ?{} OFFSET ?{}",
+            clause,
+            params.len() + 1,
+            params.len() + 2
+        );
+        params.push(filter.limit.to_string());
+        params.push(filter.offset.to_string());
 
-        let mut stmt = conn.prepare(sql)?;
+        let mut stmt = conn.prepare(&sql)?;
+        let matches = stmt.query_map(rusqlite::params_from_iter(params), Self::map_row)?;
 
-        let matches = if let Some(ref bot) = filter.bot {
-            stmt.query_map(
-                [bot, &filter.limit.to_string(), &filter.offset.to_string()],
-                Self::map_row,
-            )?
-        } else {
-            stmt.query_map(
-                [&filter.limit.to_string(), &filter.offset.to_string()],
-                Self::map_row,
-            )?
-        };
+        Ok(matches.filter_map(|r| r.ok()).collect())
+    }
+
+    /// Count matches matching a filter, ignoring its limit and offset.
+    pub fn count(&self, filter: &MatchFilter) -> SqliteResult<i64> {
+        let conn = self.db.lock().unwrap();
+
+        let (clause, params) = Self::filter_clause(filter);
+        let sql = format!("SELECT COUNT(*) FROM matches {}", clause);
+        conn.query_row(&sql, rusqlite::params_from_iter(params), |row| row.get(0))
+    }
+
+    /// List every match, oldest first.
+    pub fn list_all(&self) -> SqliteResult<Vec<Match>> {
+        let conn = self.db.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, white_bot, black_bot, games_total, white_score, black_score,
+                    opening_id, movetime_ms, started_at, finished_at, status, worker_id
+             FROM matches ORDER BY started_at, id",
+        )?;
 
+        let matches = stmt.query_map([], Self::map_row)?;
         Ok(matches.filter_map(|r| r.ok()).collect())
     }
 
@@ -111,6 +169,71 @@ impl MatchRepo {
         stmt.query_row([id], Self::map_row).optional()
     }
 
+    /// Cancel a match.
+    ///
+    /// A pending match becomes `cancelled` at once, so no worker claims it.
+    /// A running match is flagged with `cancel_requested`; the worker playing
+    /// it checks the flag between games and then marks it `cancelled`.
+    pub fn cancel(&self, id: &str) -> SqliteResult<CancelOutcome> {
+        let conn = self.db.lock().unwrap();
+
+        let cancelled = conn.execute(
+            "UPDATE matches SET status = 'cancelled', finished_at = ?2
+             WHERE id = ?1 AND status = 'pending'",
+            (id, chrono::Utc::now().to_rfc3339()),
+        )?;
+        if cancelled > 0 {
+            return Ok(CancelOutcome::Cancelled);
+        }
+
+        let requested = conn.execute(
+            "UPDATE matches SET cancel_requested = 1 WHERE id = ?1 AND status = 'running'",
+            [id],
+        )?;
+        if requested > 0 {
+            return Ok(CancelOutcome::Requested);
+        }
+
+        let exists = conn
+            .query_row("SELECT 1 FROM matches WHERE id = ?1", [id], |_| Ok(()))
+            .optional()?
+            .is_some();
+        Ok(if exists {
+            CancelOutcome::Finished
+        } else {
+            CancelOutcome::NotFound
+        })
+    }
+
+    /// Delete a match together with its games, their moves and their
+    /// stored analyses.
+    ///
+    /// Elo history entries recorded for the match are kept, with their
+    /// match reference cleared. Everything happens in one transaction.
+    /// Returns `false` if the match doesn't exist.
+    pub fn delete(&self, id: &str) -> SqliteResult<bool> {
+        let mut conn = self.db.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        tx.execute(
+            "DELETE FROM moves WHERE game_id IN (SELECT id FROM games WHERE match_id = ?1)",
+            [id],
+        )?;
+        tx.execute(
+            "DELETE FROM game_analyses WHERE game_id IN (SELECT id FROM games WHERE match_id = ?1)",
+            [id],
+        )?;
+        tx.execute("DELETE FROM games WHERE match_id = ?1", [id])?;
+        tx.execute(
+            "UPDATE elo_history SET match_id = NULL WHERE match_id = ?1",
+            [id],
+        )?;
+        let deleted = tx.execute("DELETE FROM matches WHERE id = ?1", [id])?;
+
+        tx.commit()?;
+        Ok(deleted > 0)
+    }
+
     /// Get all games for a match.
     ///
     /// Games are ordered by game number.
@@ -165,6 +288,176 @@ impl MatchRepo {
         Ok(moves)
     }
 
+    /// Aggregate results per opening across all finished games.
+    ///
+    /// Each game's stored moves are replayed through [`detect_opening`] to
+    /// find the opening it reached. Games whose moves don't match any opening
+    /// in `openings` are skipped. Results are ordered by games played
+    /// (descending), then by name.
+    pub fn opening_stats(
+        &self,
+        openings: &OpeningDatabase,
+    ) -> SqliteResult<Vec<OpeningPerformance>> {
+        // Replaying every game is slow, so release the connection first
+        let rows: Vec<(String, String, String)> = {
+            let conn = self.db.lock().unwrap();
+            let mut stmt = conn.prepare(
+                "SELECT g.id, g.result, m.uci
+                 FROM games g
+                 JOIN moves m ON m.game_id = g.id
+                 WHERE g.result IS NOT NULL
+                 ORDER BY g.id, m.ply",
+            )?;
+            let rows = stmt.query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })?;
+            rows.filter_map(|r| r.ok()).collect()
+        };
+
+        // Collect each game's moves in ply order
+        let mut games: Vec<(String, Vec<MoveRecord>)> = Vec::new();
+        let mut current_id: Option<String> = None;
+        for (game_id, result, uci) in rows {
+            if current_id.as_deref() != Some(game_id.as_str()) {
+                games.push((result, Vec::new()));
+                current_id = Some(game_id);
+            }
+            if let Some((_, moves)) = games.last_mut() {
+                moves.push(MoveRecord {
+                    uci,
+                    search_info: None,
+                });
+            }
+        }
+
+        let mut by_opening: HashMap<String, (OpeningPerformance, usize)> = HashMap::new();
+        for (result, moves) in &games {
+            let Some(opening) = detect_opening(moves, openings) else {
+                continue;
+            };
+
+            let (stats, total_plies) = by_opening.entry(opening.id.clone()).or_insert_with(|| {
+                (
+                    OpeningPerformance {
+                        opening_id: opening.id,
+                        name: opening.name,
+                        eco: opening.eco,
+                        games_played: 0,
+                        white_wins: 0,
+                        draws: 0,
+                        black_wins: 0,
+                        avg_game_length: 0.0,
+                    },
+                    0,
+                )
+            });
+
+            stats.games_played += 1;
+            *total_plies += moves.len();
+            match result.as_str() {
+                "1-0" => stats.white_wins += 1,
+                "0-1" => stats.black_wins += 1,
+                "1/2-1/2" => stats.draws += 1,
+                _ => {}
+            }
+        }
+
+        let mut stats: Vec<OpeningPerformance> = by_opening
+            .into_values()
+            .map(|(mut stats, total_plies)| {
+                stats.avg_game_length = total_plies as f64 / stats.games_played as f64;
+                stats
+            })
+            .collect();
+        stats.sort_by(|a, b| {
+            b.games_played
+                .cmp(&a.games_played)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        Ok(stats)
+    }
+
+    /// Count the moves played right after `prefix` in finished games.
+    ///
+    /// `prefix` is a line of UCI moves from the starting position. Every
+    /// finished game whose stored moves start with it contributes the move
+    /// at the next ply, together with its result. Results are ordered by
+    /// games played (descending), then by move.
+    pub fn next_moves(&self, prefix: &[String]) -> SqliteResult<Vec<ExplorerMove>> {
+        let conn = self.db.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT g.id, g.result, m.uci
+             FROM games g
+             JOIN moves m ON m.game_id = g.id
+             WHERE g.result IS NOT NULL AND m.ply <= ?1
+             ORDER BY g.id, m.ply",
+        )?;
+
+        let rows = stmt
+            .query_map([prefix.len() as i64 + 1], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })?
+            .filter_map(|r| r.ok());
+
+        // Collect each game's opening moves in ply order
+        let mut games: Vec<(String, Vec<String>)> = Vec::new();
+        let mut current_id: Option<String> = None;
+        for (game_id, result, uci) in rows {
+            if current_id.as_deref() != Some(game_id.as_str()) {
+                games.push((result, Vec::new()));
+                current_id = Some(game_id);
+            }
+            if let Some((_, moves)) = games.last_mut() {
+                moves.push(uci);
+            }
+        }
+
+        let mut by_move: HashMap<String, ExplorerMove> = HashMap::new();
+        for (result, moves) in games {
+            if moves.len() <= prefix.len() || moves[..prefix.len()] != *prefix {
+                continue;
+            }
+
+            let uci = &moves[prefix.len()];
+            let stats = by_move.entry(uci.clone()).or_insert_with(|| ExplorerMove {
+                uci: uci.clone(),
+                games: 0,
+                white_wins: 0,
+                draws: 0,
+                black_wins: 0,
+                score: 0.0,
+            });
+            stats.games += 1;
+            match result.as_str() {
+                "1-0" => stats.white_wins += 1,
+                "0-1" => stats.black_wins += 1,
+                "1/2-1/2" => stats.draws += 1,
+                _ => {}
+            }
+        }
+
+        let mut next_moves: Vec<ExplorerMove> = by_move
+            .into_values()
+            .map(|mut stats| {
+                stats.score =
+                    (stats.white_wins as f64 + stats.draws as f64 / 2.0) / stats.games as f64;
+                stats
+            })
+            .collect();
+        next_moves.sort_by(|a, b| b.games.cmp(&a.games).then_with(|| a.uci.cmp(&b.uci)));
+
+        Ok(next_moves)
+    }
+
     fn map_row(row: &rusqlite::Row) -> rusqlite::Result<Match> {
         Ok(Match {
             id: row.get(0)?,
@@ -326,6 +619,89 @@ mod tests {
         assert_eq!(matches[1].id, "match1");
     }
 
+    /// Seeds five matches with mixed statuses, oldest first.
+    fn seed_five_matches(db: &DbPool) {
+        setup_test_data(db);
+        let statuses = ["pending", "running", "completed", "completed", "failed"];
+        for (i, status) in statuses.iter().enumerate() {
+            let id = format!("match{}", i + 1);
+            let started_at = format!("2025-01-21T1{}:00:00", i);
+            insert_match(db, &id, "stockfish", "komodo", &started_at);
+            db.lock()
+                .unwrap()
+                .execute(
+                    "UPDATE matches SET status = ?1 WHERE id = ?2",
+                    [*status, &id],
+                )
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_list_matches_limit_offset_slices() {
+        let db = init_db(":memory:").unwrap();
+        seed_five_matches(&db);
+        let repo = MatchRepo::new(db);
+
+        let page = |limit, offset| -> Vec<String> {
+            let filter = MatchFilter {
+                limit,
+                offset,
+                ..Default::default()
+            };
+            repo.list(filter)
+                .unwrap()
+                .into_iter()
+                .map(|m| m.id)
+                .collect()
+        };
+
+        assert_eq!(page(2, 0), vec!["match5", "match4"]);
+        assert_eq!(page(2, 2), vec!["match3", "match2"]);
+        assert_eq!(page(2, 4), vec!["match1"]);
+        assert!(page(2, 6).is_empty());
+        assert_eq!(repo.count(&MatchFilter::default()).unwrap(), 5);
+    }
+
+    #[test]
+    fn test_list_matches_with_status_filter() {
+        let db = init_db(":memory:").unwrap();
+        seed_five_matches(&db);
+        let repo = MatchRepo::new(db);
+
+        let filter = MatchFilter {
+            status: Some("completed".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(repo.count(&filter).unwrap(), 2);
+        let ids: Vec<String> = repo
+            .list(filter)
+            .unwrap()
+            .into_iter()
+            .map(|m| m.id)
+            .collect();
+        assert_eq!(ids, vec!["match4", "match3"]);
+
+        // Status and bot filters combine
+        let filter = MatchFilter {
+            bot: Some("leela".to_string()),
+            status: Some("completed".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(repo.count(&filter).unwrap(), 0);
+        assert!(repo.list(filter).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_list_all_matches_oldest_first() {
+        let db = init_db(":memory:").unwrap();
+        seed_five_matches(&db);
+        let repo = MatchRepo::new(db);
+
+        let ids: Vec<String> = repo.list_all().unwrap().into_iter().map(|m| m.id).collect();
+        assert_eq!(ids, vec!["match1", "match2", "match3", "match4", "match5"]);
+    }
+
     #[test]
     fn test_get_match_exists() {
         let db = init_db(":memory:").unwrap();
@@ -415,6 +791,32 @@ mod tests {
         assert_eq!(moves[1].fen_after, fen2);
     }
 
+    #[test]
+    fn test_delete_match_with_stored_analysis() {
+        let db = init_db(":memory:").unwrap();
+        setup_test_data(&db);
+        insert_match(&db, "match1", "stockfish", "komodo", "2025-01-21T10:00:00");
+        insert_game(&db, "game1", "match1", 0, Some("1-0"));
+        insert_move(&db, "game1", 1, "e2e4", "fen1");
+        db.lock()
+            .unwrap()
+            .execute(
+                "INSERT INTO game_analyses (game_id, depth, analysis) VALUES ('game1', 12, '{}')",
+                [],
+            )
+            .unwrap();
+
+        let repo = MatchRepo::new(db.clone());
+        assert!(repo.delete("match1").unwrap());
+        assert!(repo.get("match1").unwrap().is_none());
+        let analyses: i64 = db
+            .lock()
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM game_analyses", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(analyses, 0);
+    }
+
     #[test]
     fn test_get_moves_empty() {
         let db = init_db(":memory:").unwrap();
@@ -594,4 +996,63 @@ mod tests {
         assert_eq!(match_info.status, "pending");
         assert_eq!(match_info.opening_id, Some("sicilian".to_string()));
     }
+
+    /// Inserts a finished game with the given UCI moves.
+    fn insert_game_with_moves(db: &DbPool, id: &str, game_number: i32, result: &str, moves: &str) {
+        insert_game(db, id, "match1", game_number, Some(result));
+        for (i, uci) in moves.split_whitespace().enumerate() {
+            insert_move(db, id, i as i32 + 1, uci, "fen");
+        }
+    }
+
+    #[test]
+    fn test_opening_stats_aggregates_results() {
+        let db = init_db(":memory:").unwrap();
+        setup_test_data(&db);
+        insert_match(&db, "match1", "stockfish", "komodo", "2025-01-21T10:00:00");
+
+        // Two Italian Games with different results, one Sicilian
+        insert_game_with_moves(&db, "game1", 1, "1-0", "e2e4 e7e5 g1f3 b8c6 f1c4 h7h6");
+        insert_game_with_moves(
+            &db,
+            "game2",
+            2,
+            "1/2-1/2",
+            "e2e4 e7e5 g1f3 b8c6 f1c4 a7a6 d2d3 h7h6",
+        );
+        insert_game_with_moves(&db, "game3", 3, "0-1", "e2e4 c7c5");
+
+        let repo = MatchRepo::new(db);
+        let openings = OpeningDatabase::with_openings(chess_openings::builtin::builtin_openings());
+        let stats = repo.opening_stats(&openings).unwrap();
+
+        let italian = stats
+            .iter()
+            .find(|s| s.opening_id == "italian-game")
+            .expect("Italian Game should be detected");
+        assert_eq!(italian.games_played, 2);
+        assert_eq!(italian.white_wins, 1);
+        assert_eq!(italian.draws, 1);
+        assert_eq!(italian.black_wins, 0);
+        assert_eq!(italian.avg_game_length, 7.0);
+        assert_eq!(italian.eco.as_deref(), Some("C50"));
+
+        // Most played opening first
+        assert_eq!(stats[0].opening_id, "italian-game");
+        assert_eq!(stats.iter().map(|s| s.games_played).sum::<i32>(), 3);
+    }
+
+    #[test]
+    fn test_opening_stats_skips_unfinished_games() {
+        let db = init_db(":memory:").unwrap();
+        setup_test_data(&db);
+        insert_match(&db, "match1", "stockfish", "komodo", "2025-01-21T10:00:00");
+
+        insert_game(&db, "game1", "match1", 1, None);
+        insert_move(&db, "game1", 1, "e2e4", "fen");
+
+        let repo = MatchRepo::new(db);
+        let openings = OpeningDatabase::with_openings(chess_openings::builtin::builtin_openings());
+        assert!(repo.opening_stats(&openings).unwrap().is_empty());
+    }
 }
@@ -1,7 +1,11 @@
 //! Repository modules for database operations.
 
+pub mod analyses;
 pub mod bots;
 pub mod matches;
+pub mod stats;
 
+pub use analyses::AnalysisRepo;
 pub use bots::BotRepo;
-pub use matches::{MatchFilter, MatchRepo};
+pub use matches::{CancelOutcome, MatchFilter, MatchRepo};
+pub use stats::StatsRepo;
@@ -0,0 +1,258 @@
+//! Statistics repository for aggregate queries across matches and games.
+
+use crate::db::DbPool;
+use crate::models::{ColorRecord, HeadToHeadSummary};
+use rusqlite::Result as SqliteResult;
+
+/// Number of results included in [`HeadToHeadSummary::recent_form`].
+const RECENT_FORM_GAMES: usize = 10;
+
+/// Repository for statistics queries.
+pub struct StatsRepo {
+    db: DbPool,
+}
+
+impl StatsRepo {
+    /// Create a new statistics repository with the given database pool.
+    pub fn new(db: DbPool) -> Self {
+        Self { db }
+    }
+
+    /// Summarize all finished games between `bot_a` and `bot_b`.
+    ///
+    /// Games from matches in either color orientation are included. Within a
+    /// match colors alternate, with the match's white bot playing white in
+    /// even-numbered games. A pairing with no games returns all zeros.
+    pub fn head_to_head(&self, bot_a: &str, bot_b: &str) -> SqliteResult<HeadToHeadSummary> {
+        let conn = self.db.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT m.white_bot, g.game_number, g.result,
+                    (SELECT COUNT(*) FROM moves mv WHERE mv.game_id = g.id)
+             FROM games g
+             JOIN matches m ON g.match_id = m.id
+             WHERE g.result IS NOT NULL
+               AND ((m.white_bot = ?1 AND m.black_bot = ?2)
+                 OR (m.white_bot = ?2 AND m.black_bot = ?1))
+             ORDER BY m.started_at DESC, g.game_number DESC",
+        )?;
+
+        let rows = stmt
+            .query_map([bot_a, bot_b], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, i32>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, i64>(3)?,
+                ))
+            })?
+            .filter_map(|r| r.ok());
+
+        let mut summary = HeadToHeadSummary {
+            bot_a: bot_a.to_string(),
+            bot_b: bot_b.to_string(),
+            games: 0,
+            bot_a_wins: 0,
+            bot_b_wins: 0,
+            draws: 0,
+            draw_rate: 0.0,
+            bot_a_as_white: ColorRecord::default(),
+            bot_a_as_black: ColorRecord::default(),
+            avg_moves: 0.0,
+            recent_form: String::new(),
+        };
+        let mut total_plies = 0;
+
+        for (match_white, game_number, result, plies) in rows {
+            let a_is_white = (match_white == bot_a) == (game_number % 2 == 0);
+            let a_won = match result.as_str() {
+                "1-0" => Some(a_is_white),
+                "0-1" => Some(!a_is_white),
+                _ => None,
+            };
+
+            let record = if a_is_white {
+                &mut summary.bot_a_as_white
+            } else {
+                &mut summary.bot_a_as_black
+            };
+            record.games += 1;
+            let form = match a_won {
+                Some(true) => {
+                    record.wins += 1;
+                    summary.bot_a_wins += 1;
+                    'W'
+                }
+                Some(false) => {
+                    record.losses += 1;
+                    summary.bot_b_wins += 1;
+                    'L'
+                }
+                None => {
+                    record.draws += 1;
+                    summary.draws += 1;
+                    'D'
+                }
+            };
+
+            if (summary.games as usize) < RECENT_FORM_GAMES {
+                summary.recent_form.push(form);
+            }
+            summary.games += 1;
+            total_plies += plies;
+        }
+
+        if summary.games > 0 {
+            summary.draw_rate = f64::from(summary.draws) / f64::from(summary.games);
+            summary.avg_moves = total_plies as f64 / f64::from(summary.games);
+        }
+
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::init_db;
+
+    fn setup_bots(db: &DbPool) {
+        let conn = db.lock().unwrap();
+        for name in ["stockfish", "komodo", "leela"] {
+            conn.execute("INSERT INTO bots (name) VALUES (?1)", [name])
+                .unwrap();
+        }
+    }
+
+    fn insert_match(db: &DbPool, id: &str, white: &str, black: &str, started_at: &str) {
+        let conn = db.lock().unwrap();
+        conn.execute(
+            "INSERT INTO matches (id, white_bot, black_bot, games_total, started_at, status)
+             VALUES (?1, ?2, ?3, 10, ?4, 'completed')",
+            [id, white, black, started_at],
+        )
+        .unwrap();
+    }
+
+    fn insert_game(db: &DbPool, id: &str, match_id: &str, game_number: i32, result: Option<&str>) {
+        let conn = db.lock().unwrap();
+        conn.execute(
+            "INSERT INTO games (id, match_id, game_number, result, started_at)
+             VALUES (?1, ?2, ?3, ?4, '2025-01-21')",
+            rusqlite::params![id, match_id, game_number, result],
+        )
+        .unwrap();
+    }
+
+    fn insert_moves(db: &DbPool, game_id: &str, count: i32) {
+        let conn = db.lock().unwrap();
+        for ply in 1..=count {
+            conn.execute(
+                "INSERT INTO moves (game_id, ply, uci, fen_after) VALUES (?1, ?2, 'e2e4', 'fen')",
+                rusqlite::params![game_id, ply],
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_head_to_head_no_games_returns_zeros() {
+        let db = init_db(":memory:").unwrap();
+        setup_bots(&db);
+        let repo = StatsRepo::new(db);
+
+        let summary = repo.head_to_head("stockfish", "komodo").unwrap();
+        assert_eq!(summary.bot_a, "stockfish");
+        assert_eq!(summary.bot_b, "komodo");
+        assert_eq!(summary.games, 0);
+        assert_eq!(summary.draw_rate, 0.0);
+        assert_eq!(summary.avg_moves, 0.0);
+        assert_eq!(summary.bot_a_as_white, ColorRecord::default());
+        assert!(summary.recent_form.is_empty());
+    }
+
+    #[test]
+    fn test_head_to_head_aggregates_both_orientations() {
+        let db = init_db(":memory:").unwrap();
+        setup_bots(&db);
+
+        // Older match with stockfish as the match's white bot:
+        // game 0 stockfish white and wins, game 1 stockfish black and draws
+        insert_match(&db, "m1", "stockfish", "komodo", "2025-01-20T10:00:00");
+        insert_game(&db, "m1-0", "m1", 0, Some("1-0"));
+        insert_game(&db, "m1-1", "m1", 1, Some("1/2-1/2"));
+        insert_moves(&db, "m1-0", 40);
+        insert_moves(&db, "m1-1", 60);
+
+        // Newer match with komodo as the match's white bot:
+        // game 0 komodo white and wins, game 1 stockfish white and wins
+        insert_match(&db, "m2", "komodo", "stockfish", "2025-01-21T10:00:00");
+        insert_game(&db, "m2-0", "m2", 0, Some("1-0"));
+        insert_game(&db, "m2-1", "m2", 1, Some("1-0"));
+        insert_moves(&db, "m2-0", 30);
+        insert_moves(&db, "m2-1", 70);
+
+        // Unfinished games and other pairings are ignored
+        insert_game(&db, "m2-2", "m2", 2, None);
+        insert_match(&db, "m3", "stockfish", "leela", "2025-01-22T10:00:00");
+        insert_game(&db, "m3-0", "m3", 0, Some("1-0"));
+
+        let repo = StatsRepo::new(db);
+        let summary = repo.head_to_head("stockfish", "komodo").unwrap();
+
+        assert_eq!(summary.games, 4);
+        assert_eq!(summary.bot_a_wins, 2);
+        assert_eq!(summary.bot_b_wins, 1);
+        assert_eq!(summary.draws, 1);
+        assert_eq!(summary.draw_rate, 0.25);
+        assert_eq!(summary.avg_moves, 50.0);
+        assert_eq!(
+            summary.bot_a_as_white,
+            ColorRecord {
+                games: 2,
+                wins: 2,
+                draws: 0,
+                losses: 0,
+            }
+        );
+        assert_eq!(
+            summary.bot_a_as_black,
+            ColorRecord {
+                games: 2,
+                wins: 0,
+                draws: 1,
+                losses: 1,
+            }
+        );
+        // Most recent first: m2-1, m2-0, m1-1, m1-0
+        assert_eq!(summary.recent_form, "WLDW");
+
+        // Swapping the pair flips the perspective
+        let flipped = repo.head_to_head("komodo", "stockfish").unwrap();
+        assert_eq!(flipped.bot_a_wins, 1);
+        assert_eq!(flipped.bot_b_wins, 2);
+        assert_eq!(flipped.bot_a_as_black.losses, 2);
+        assert_eq!(flipped.recent_form, "LWDL");
+    }
+
+    #[test]
+    fn test_head_to_head_recent_form_keeps_last_ten() {
+        let db = init_db(":memory:").unwrap();
+        setup_bots(&db);
+        insert_match(&db, "m1", "stockfish", "komodo", "2025-01-20T10:00:00");
+        // Stockfish wins the first two games, then every game is drawn
+        for n in 0..12 {
+            let result = match n {
+                0 => "1-0",
+                1 => "0-1",
+                _ => "1/2-1/2",
+            };
+            insert_game(&db, &format!("m1-{}", n), "m1", n, Some(result));
+        }
+
+        let repo = StatsRepo::new(db);
+        let summary = repo.head_to_head("stockfish", "komodo").unwrap();
+        assert_eq!(summary.games, 12);
+        assert_eq!(summary.bot_a_wins, 2);
+        assert_eq!(summary.recent_form, "DDDDDDDDDD");
+    }
+}
@@ -4,20 +4,33 @@
 //! that can be embedded in HTML exports.
 
 use askama::Template;
+use chess_core::Square;
 
 /// A piece to render on the board.
 ///
 /// Represents a single chess piece with its position and Unicode symbol.
+/// Rows and columns are counted from the top left corner of the rendered
+/// board, so they depend on its orientation.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PieceView {
-    /// Row index (0-7, where 0 is the 8th rank).
+    /// Row index (0-7, where 0 is the 8th rank, or the 1st when flipped).
     pub row: usize,
-    /// Column index (0-7, where 0 is the a-file).
+    /// Column index (0-7, where 0 is the a-file, or the h-file when flipped).
     pub col: usize,
     /// Unicode chess piece symbol.
     pub symbol: char,
 }
 
+/// A highlighted square on the board, in the same row and column
+/// coordinates as [`PieceView`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SquareView {
+    /// Row index from the top of the rendered board (0-7).
+    pub row: usize,
+    /// Column index from the left of the rendered board (0-7).
+    pub col: usize,
+}
+
 /// SVG chess board template.
 ///
 /// Renders an 8x8 chess board with pieces positioned according to the FEN notation.
@@ -26,6 +39,8 @@ pub struct PieceView {
 pub struct BoardTemplate {
     /// Pieces to render on the board.
     pub pieces: Vec<PieceView>,
+    /// Squares shaded to mark the last move.
+    pub highlights: Vec<SquareView>,
 }
 
 impl BoardTemplate {
@@ -48,17 +63,57 @@ impl BoardTemplate {
     /// ```
     #[must_use]
     pub fn from_fen(fen: &str) -> Self {
+        Self::from_fen_with(fen, false, None)
+    }
+
+    /// Create a board from a FEN position string, choosing the orientation
+    /// and marking the last move.
+    ///
+    /// # Arguments
+    ///
+    /// * `fen` - A FEN string (only the piece placement part is used).
+    /// * `flipped` - Render from black's side, with the 1st rank at the top.
+    /// * `last_move` - From and to squares to shade, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bot_arena_server::templates::BoardTemplate;
+    /// use chess_core::Square;
+    ///
+    /// let e2 = Square::from_algebraic("e2").unwrap();
+    /// let e4 = Square::from_algebraic("e4").unwrap();
+    /// let board = BoardTemplate::from_fen_with(
+    ///     "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1",
+    ///     true,
+    ///     Some((e2, e4)),
+    /// );
+    /// assert_eq!(board.highlights.len(), 2);
+    /// ```
+    #[must_use]
+    pub fn from_fen_with(fen: &str, flipped: bool, last_move: Option<(Square, Square)>) -> Self {
+        // Maps board coordinates from white's side to the rendered corner
+        let orient = |row: usize, col: usize| {
+            if flipped {
+                (7 - row, 7 - col)
+            } else {
+                (row, col)
+            }
+        };
+
         let mut pieces = Vec::new();
         let board_part = fen.split_whitespace().next().unwrap_or("");
 
-        for (row, rank) in board_part.split('/').enumerate() {
+        for (row, rank) in board_part.split('/').enumerate().take(8) {
             let mut col = 0;
             for c in rank.chars() {
                 if let Some(skip) = c.to_digit(10) {
                     col += skip as usize;
                 } else {
-                    let symbol = Self::piece_to_symbol(c);
+                    // Pieces off the board in a malformed FEN are skipped
+                    let symbol = Self::piece_to_symbol(c).filter(|_| col < 8);
                     if let Some(symbol) = symbol {
+                        let (row, col) = orient(row, col);
                         pieces.push(PieceView { row, col, symbol });
                     }
                     col += 1;
@@ -66,7 +121,22 @@ impl BoardTemplate {
             }
         }
 
-        Self { pieces }
+        let highlights = last_move
+            .map(|(from, to)| {
+                [from, to]
+                    .into_iter()
+                    .map(|square| {
+                        let (row, col) = orient(
+                            7 - square.rank().index() as usize,
+                            square.file().index() as usize,
+                        );
+                        SquareView { row, col }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self { pieces, highlights }
     }
 
     /// Convert a FEN piece character to its Unicode chess symbol.
@@ -149,6 +219,78 @@ mod tests {
         assert_eq!(black_king.col, 4);
     }
 
+    #[test]
+    fn test_last_move_is_highlighted() {
+        let e2 = Square::from_algebraic("e2").unwrap();
+        let e4 = Square::from_algebraic("e4").unwrap();
+        let board = BoardTemplate::from_fen_with(
+            "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1",
+            false,
+            Some((e2, e4)),
+        );
+        assert_eq!(
+            board.highlights,
+            vec![SquareView { row: 6, col: 4 }, SquareView { row: 4, col: 4 }]
+        );
+
+        let html = board.render().unwrap();
+        assert!(
+            html.contains(r#"<rect x="200" y="300" width="50" height="50" class="highlight" />"#)
+        );
+        assert!(
+            html.contains(r#"<rect x="200" y="200" width="50" height="50" class="highlight" />"#)
+        );
+
+        // No last move, no highlights
+        let plain = BoardTemplate::from_fen("8/8/8/8/8/8/8/8 w - - 0 1");
+        assert!(!plain.render().unwrap().contains("class=\"highlight\""));
+    }
+
+    #[test]
+    fn test_flipped_board_reverses_ranks_and_files() {
+        let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        let board = BoardTemplate::from_fen_with(fen, true, None);
+        assert_eq!(board.pieces.len(), 32);
+
+        // White's pieces are at the top, black's at the bottom
+        let white_king = board
+            .pieces
+            .iter()
+            .find(|p| p.symbol == '\u{2654}')
+            .expect("White king should exist");
+        assert_eq!((white_king.row, white_king.col), (0, 3));
+        let black_king = board
+            .pieces
+            .iter()
+            .find(|p| p.symbol == '\u{265A}')
+            .expect("Black king should exist");
+        assert_eq!((black_king.row, black_king.col), (7, 3));
+
+        // Every piece sits on the mirror image of its unflipped square
+        let mut unflipped: Vec<(usize, usize, char)> = BoardTemplate::from_fen(fen)
+            .pieces
+            .iter()
+            .map(|p| (7 - p.row, 7 - p.col, p.symbol))
+            .collect();
+        let mut flipped: Vec<(usize, usize, char)> = board
+            .pieces
+            .iter()
+            .map(|p| (p.row, p.col, p.symbol))
+            .collect();
+        unflipped.sort();
+        flipped.sort();
+        assert_eq!(flipped, unflipped);
+
+        // Highlights follow the orientation
+        let e2 = Square::from_algebraic("e2").unwrap();
+        let e4 = Square::from_algebraic("e4").unwrap();
+        let board = BoardTemplate::from_fen_with(fen, true, Some((e2, e4)));
+        assert_eq!(
+            board.highlights,
+            vec![SquareView { row: 1, col: 3 }, SquareView { row: 3, col: 3 }]
+        );
+    }
+
     #[test]
     fn test_piece_to_symbol() {
         assert_eq!(BoardTemplate::piece_to_symbol('K'), Some('\u{2654}'));
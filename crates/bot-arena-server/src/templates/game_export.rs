@@ -4,13 +4,15 @@
 //! as standalone HTML pages with a visual board and move list.
 
 use askama::Template;
+use chess_analysis::MoveQuality;
 
 /// Game export HTML template.
 ///
 /// Renders a single chess game as a standalone HTML page with:
 /// - Game information (players, result, opening)
 /// - Visual chess board showing the final position
-/// - Full move list in standard notation
+/// - Full move list in standard notation, annotated with move-quality
+///   glyphs when the game has been analyzed
 #[derive(Template)]
 #[template(path = "export_game.html")]
 pub struct GameExportTemplate {
@@ -26,9 +28,37 @@ pub struct GameExportTemplate {
     pub board: String,
     /// Move pairs for display (white_move, optional black_move).
     pub move_pairs: Vec<(String, Option<String>)>,
+    /// Quality of each move by ply, from an analysis of the game.
+    pub qualities: Option<Vec<MoveQuality>>,
 }
 
 impl GameExportTemplate {
+    /// Returns the glyph to show after the move at `ply` (0 for white's
+    /// first move), if the game was analyzed and the move earns one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bot_arena_server::templates::GameExportTemplate;
+    /// use chess_analysis::MoveQuality;
+    ///
+    /// let template = GameExportTemplate {
+    ///     white: "a".into(),
+    ///     black: "b".into(),
+    ///     result: "*".into(),
+    ///     opening: None,
+    ///     board: String::new(),
+    ///     move_pairs: vec![("e4".into(), Some("f6".into()))],
+    ///     qualities: Some(vec![MoveQuality::Best, MoveQuality::Mistake]),
+    /// };
+    /// assert_eq!(template.glyph(0), None);
+    /// assert_eq!(template.glyph(1), Some("?"));
+    /// ```
+    #[must_use]
+    pub fn glyph(&self, ply: usize) -> Option<&'static str> {
+        self.qualities.as_ref()?.get(ply)?.glyph()
+    }
+
     /// Convert a flat list of moves into pairs (white_move, black_move).
     ///
     /// Takes a sequential list of moves and groups them into pairs for display
@@ -115,6 +145,7 @@ mod tests {
             opening: Some("Italian Game".into()),
             board: "<svg></svg>".into(),
             move_pairs: vec![("e4".into(), Some("e5".into()))],
+            qualities: None,
         };
         let html = template.render().unwrap();
         assert!(html.contains("minimax"));
@@ -133,6 +164,7 @@ mod tests {
             opening: None,
             board: "<svg></svg>".into(),
             move_pairs: vec![],
+            qualities: None,
         };
         let html = template.render().unwrap();
         assert!(html.contains("bot_a"));
@@ -153,6 +185,7 @@ mod tests {
                 ("Nf3".into(), Some("d6".into())),
                 ("d4".into(), Some("cxd4".into())),
             ],
+            qualities: None,
         };
         let html = template.render().unwrap();
         assert!(html.contains("stockfish"));
@@ -163,6 +196,34 @@ mod tests {
         assert!(html.contains("Nf3"));
     }
 
+    #[test]
+    fn test_game_export_shows_quality_glyphs() {
+        let template = GameExportTemplate {
+            white: "a".into(),
+            black: "b".into(),
+            result: "1-0".into(),
+            opening: None,
+            board: "<svg></svg>".into(),
+            move_pairs: vec![
+                ("e4".into(), Some("f6".into())),
+                ("d4".into(), Some("g5".into())),
+                ("Qh5#".into(), None),
+            ],
+            qualities: Some(vec![
+                MoveQuality::Best,
+                MoveQuality::Inaccuracy,
+                MoveQuality::Good,
+                MoveQuality::Blunder,
+                MoveQuality::Best,
+            ]),
+        };
+        let html = template.render().unwrap();
+        assert!(html.contains(r#"f6<span class="glyph">?!</span>"#));
+        assert!(html.contains(r#"g5<span class="glyph">??</span>"#));
+        assert_eq!(html.matches(r#"<span class="glyph">"#).count(), 2);
+        assert!(html.contains(r#"<span class="move">d4</span>"#));
+    }
+
     #[test]
     fn test_template_contains_required_elements() {
         let template = GameExportTemplate {
@@ -172,6 +233,7 @@ mod tests {
             opening: None,
             board: "<svg></svg>".into(),
             move_pairs: vec![],
+            qualities: None,
         };
         let html = template.render().unwrap();
         // Check HTML structure
@@ -192,6 +254,7 @@ mod tests {
             opening: None,
             board: "<svg></svg>".into(),
             move_pairs: vec![],
+            qualities: None,
         };
         let html = template.render().unwrap();
         // Askama should escape HTML special characters
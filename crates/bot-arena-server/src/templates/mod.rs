@@ -8,7 +8,7 @@ pub mod bot_export;
 pub mod game_export;
 pub mod match_export;
 
-pub use board::{BoardTemplate, PieceView};
+pub use board::{BoardTemplate, PieceView, SquareView};
 pub use bot_export::{BotExportTemplate, EloPoint};
 pub use game_export::GameExportTemplate;
 pub use match_export::{GameSummary, MatchExportTemplate};
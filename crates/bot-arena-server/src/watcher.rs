@@ -5,9 +5,58 @@
 
 use crate::db::DbPool;
 use crate::ws::{WsBroadcast, WsMessage};
+use chess_engine::Game;
+use rusqlite::Connection;
 use std::collections::HashMap;
 use tokio::time::{interval, Duration};
 
+/// A move row as read from the database.
+struct StoredMove {
+    game_id: String,
+    ply: i32,
+    uci: String,
+    san: Option<String>,
+    fen_after: String,
+    match_id: String,
+}
+
+/// Replays `uci_moves` from the starting position and returns the SAN of
+/// the last move and the FEN after it.
+///
+/// Returns `None` if there are no moves or any move is illegal.
+pub fn replay_last_move(uci_moves: &[String]) -> Option<(String, String)> {
+    let mut game = Game::new();
+    for uci in uci_moves {
+        game.make_move_uci(uci).ok()?;
+    }
+    let san = game.move_history().last()?.san.clone();
+    Some((san, game.to_fen()))
+}
+
+/// Returns the SAN and FEN after a stored move.
+///
+/// Uses the stored `san`/`fen_after` columns when present and otherwise
+/// reconstructs them by replaying the game's moves up to `ply`.
+fn move_details(conn: &Connection, stored: &StoredMove) -> (Option<String>, Option<String>) {
+    let fen = (!stored.fen_after.is_empty()).then(|| stored.fen_after.clone());
+    if stored.san.is_some() && fen.is_some() {
+        return (stored.san.clone(), fen);
+    }
+
+    let uci_moves: Vec<String> = conn
+        .prepare("SELECT uci FROM moves WHERE game_id = ?1 AND ply <= ?2 ORDER BY ply")
+        .and_then(|mut stmt| {
+            stmt.query_map((&stored.game_id, stored.ply), |row| row.get(0))
+                .map(|rows| rows.filter_map(|r| r.ok()).collect())
+        })
+        .unwrap_or_default();
+
+    match replay_last_move(&uci_moves) {
+        Some((san, replayed_fen)) => (stored.san.clone().or(Some(san)), fen.or(Some(replayed_fen))),
+        None => (stored.san.clone(), fen),
+    }
+}
+
 /// Watches the database for new moves and broadcasts them via WebSocket.
 ///
 /// This function runs indefinitely, polling the database every 100ms for new moves
@@ -23,6 +72,10 @@ use tokio::time::{interval, Duration};
 /// The watcher tracks the last seen ply for each game and only broadcasts moves
 /// that are newer than the previously seen ply. This ensures each move is only
 /// broadcast once even if it appears in multiple polling cycles.
+///
+/// Each broadcast includes the move in SAN and the FEN after it, so clients
+/// can render the board without another request. Moves stored without
+/// these are reconstructed by replaying the game.
 pub async fn watch_moves(db: DbPool, broadcast: WsBroadcast) {
     let mut last_move_plies: HashMap<String, i32> = HashMap::new();
     let mut ticker = interval(Duration::from_millis(100));
@@ -30,42 +83,52 @@ pub async fn watch_moves(db: DbPool, broadcast: WsBroadcast) {
     loop {
         ticker.tick().await;
 
-        let new_moves = {
-            let conn = match db.lock() {
-                Ok(c) => c,
-                Err(_) => continue,
-            };
-
-            let mut stmt = match conn.prepare(
-                "SELECT m.game_id, m.ply, m.uci, g.match_id
-                 FROM moves m
-                 JOIN games g ON m.game_id = g.id
-                 ORDER BY m.rowid DESC
-                 LIMIT 100",
-            ) {
-                Ok(s) => s,
-                Err(_) => continue,
-            };
-
-            let moves: Vec<(String, i32, String, String)> = stmt
-                .query_map([], |row| {
-                    Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
-                })
-                .map(|rows| rows.filter_map(|r| r.ok()).collect())
-                .unwrap_or_default();
+        let conn = match db.lock() {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
 
-            moves
+        let mut stmt = match conn.prepare(
+            "SELECT m.game_id, m.ply, m.uci, m.san, m.fen_after, g.match_id
+             FROM moves m
+             JOIN games g ON m.game_id = g.id
+             ORDER BY m.rowid DESC
+             LIMIT 100",
+        ) {
+            Ok(s) => s,
+            Err(_) => continue,
         };
 
-        for (game_id, ply, uci, match_id) in new_moves {
-            let last_ply = last_move_plies.get(&game_id).copied().unwrap_or(-1);
-            if ply > last_ply {
-                last_move_plies.insert(game_id.clone(), ply);
+        let new_moves: Vec<StoredMove> = stmt
+            .query_map([], |row| {
+                Ok(StoredMove {
+                    game_id: row.get(0)?,
+                    ply: row.get(1)?,
+                    uci: row.get(2)?,
+                    san: row.get(3)?,
+                    fen_after: row.get(4)?,
+                    match_id: row.get(5)?,
+                })
+            })
+            .map(|rows| rows.filter_map(|r| r.ok()).collect())
+            .unwrap_or_default();
+        drop(stmt);
+
+        for stored in new_moves {
+            let last_ply = last_move_plies.get(&stored.game_id).copied().unwrap_or(-1);
+            if stored.ply > last_ply {
+                last_move_plies.insert(stored.game_id.clone(), stored.ply);
+
+                let (san, fen) = move_details(&conn, &stored);
 
                 // Broadcast to WebSocket clients
                 let _ = broadcast.send(WsMessage::Move {
-                    match_id,
-                    uci,
+                    match_id: stored.match_id,
+                    game_id: stored.game_id,
+                    ply: stored.ply,
+                    uci: stored.uci,
+                    san,
+                    fen,
                     centipawns: None,
                 });
             }
@@ -129,11 +192,21 @@ mod tests {
         match result {
             Ok(Ok(WsMessage::Move {
                 match_id,
+                game_id,
+                ply,
                 uci,
+                fen,
                 centipawns,
+                ..
             })) => {
                 assert_eq!(match_id, "match1");
+                assert_eq!(game_id, "game1");
+                assert_eq!(ply, 1);
                 assert_eq!(uci, "e2e4");
+                assert_eq!(
+                    fen.as_deref(),
+                    Some("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1")
+                );
                 assert!(centipawns.is_none());
             }
             _ => panic!("Expected Move message within timeout"),
@@ -276,4 +349,72 @@ mod tests {
         // Different game
         assert_eq!(last_move_plies.get("game2").copied().unwrap_or(-1), -1);
     }
+
+    #[tokio::test]
+    async fn test_watch_moves_reconstructs_missing_san_and_fen() {
+        let db = init_db(":memory:").expect("Failed to init db");
+        let broadcast = create_broadcast();
+        let mut rx = broadcast.subscribe();
+
+        // The worker stores moves without SAN or FEN
+        {
+            let conn = db.lock().unwrap();
+            conn.execute("INSERT INTO bots (name) VALUES (?)", ["white_bot"])
+                .unwrap();
+            conn.execute("INSERT INTO bots (name) VALUES (?)", ["black_bot"])
+                .unwrap();
+            conn.execute(
+                "INSERT INTO matches (id, white_bot, black_bot, games_total, started_at) VALUES (?, ?, ?, ?, ?)",
+                ["match1", "white_bot", "black_bot", "1", "2025-01-21"],
+            )
+            .unwrap();
+            conn.execute(
+                "INSERT INTO games (id, match_id, game_number, started_at) VALUES (?, ?, ?, ?)",
+                ["game1", "match1", "1", "2025-01-21"],
+            )
+            .unwrap();
+            for (ply, uci) in [("0", "e2e4"), ("1", "e7e5"), ("2", "g1f3")] {
+                conn.execute(
+                    "INSERT INTO moves (game_id, ply, uci, fen_after) VALUES (?, ?, ?, '')",
+                    ["game1", ply, uci],
+                )
+                .unwrap();
+            }
+        }
+
+        let db_clone = db.clone();
+        let broadcast_clone = broadcast.clone();
+        let watcher_handle = tokio::spawn(async move {
+            watch_moves(db_clone, broadcast_clone).await;
+        });
+
+        let result = tokio::time::timeout(Duration::from_secs(1), rx.recv()).await;
+        match result {
+            Ok(Ok(WsMessage::Move { ply, san, fen, .. })) => {
+                assert_eq!(ply, 2);
+                assert_eq!(san.as_deref(), Some("Nf3"));
+                assert_eq!(
+                    fen.as_deref(),
+                    Some("rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2")
+                );
+            }
+            _ => panic!("Expected Move message within timeout"),
+        }
+
+        watcher_handle.abort();
+    }
+
+    #[test]
+    fn test_replay_last_move() {
+        let moves = vec!["e2e4".to_string(), "d7d5".to_string(), "e4d5".to_string()];
+        let (san, fen) = replay_last_move(&moves).unwrap();
+        assert_eq!(san, "exd5");
+        assert_eq!(
+            fen,
+            "rnbqkbnr/ppp1pppp/8/3P4/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 2"
+        );
+
+        assert!(replay_last_move(&[]).is_none());
+        assert!(replay_last_move(&["e2e5".to_string()]).is_none());
+    }
 }
@@ -36,8 +36,20 @@ pub enum WsMessage {
     Move {
         /// The match ID.
         match_id: String,
+        /// The game ID.
+        #[serde(default)]
+        game_id: String,
+        /// The ply number of the move within the game.
+        #[serde(default)]
+        ply: i32,
         /// The move in UCI notation (e.g., "e2e4").
         uci: String,
+        /// The move in SAN notation (e.g., "e4"), if known.
+        #[serde(default)]
+        san: Option<String>,
+        /// The FEN of the position after the move, if known.
+        #[serde(default)]
+        fen: Option<String>,
         /// Optional centipawn evaluation of the position.
         centipawns: Option<i32>,
     },
@@ -156,7 +168,11 @@ mod tests {
     fn test_ws_message_move_serialization() {
         let msg = WsMessage::Move {
             match_id: "123".to_string(),
+            game_id: "g1".to_string(),
+            ply: 1,
             uci: "e2e4".to_string(),
+            san: None,
+            fen: None,
             centipawns: Some(30),
         };
 
@@ -167,11 +183,35 @@ mod tests {
         assert!(json.contains("\"centipawns\":30"));
     }
 
+    #[test]
+    fn test_ws_message_move_board_fields() {
+        let msg = WsMessage::Move {
+            match_id: "123".to_string(),
+            game_id: "game-1".to_string(),
+            ply: 1,
+            uci: "e2e4".to_string(),
+            san: Some("e4".to_string()),
+            fen: Some("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1".to_string()),
+            centipawns: None,
+        };
+
+        let json = serde_json::to_string(&msg).unwrap();
+        assert!(json.contains("\"game_id\":\"game-1\""));
+        assert!(json.contains("\"ply\":1"));
+        assert!(json.contains("\"san\":\"e4\""));
+        assert!(json
+            .contains("\"fen\":\"rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1\""));
+    }
+
     #[test]
     fn test_ws_message_move_without_centipawns() {
         let msg = WsMessage::Move {
             match_id: "456".to_string(),
+            game_id: "g1".to_string(),
+            ply: 1,
             uci: "d7d5".to_string(),
+            san: None,
+            fen: None,
             centipawns: None,
         };
 
@@ -263,8 +303,13 @@ mod tests {
             WsMessage::Move {
                 match_id,
                 uci,
+                san,
+                fen,
                 centipawns,
+                ..
             } => {
+                assert!(san.is_none());
+                assert!(fen.is_none());
                 assert_eq!(match_id, "m1");
                 assert_eq!(uci, "e2e4");
                 assert_eq!(centipawns, Some(15));
@@ -292,7 +337,11 @@ mod tests {
 
         let msg = WsMessage::Move {
             match_id: "match-1".to_string(),
+            game_id: "g1".to_string(),
+            ply: 1,
             uci: "e2e4".to_string(),
+            san: None,
+            fen: None,
             centipawns: None,
         };
 
@@ -320,7 +369,11 @@ mod tests {
         for i in 0..150 {
             let _ = tx.send(WsMessage::Move {
                 match_id: format!("match-{}", i),
+                game_id: "g1".to_string(),
+                ply: 1,
                 uci: "e2e4".to_string(),
+                san: None,
+                fen: None,
                 centipawns: None,
             });
         }
@@ -424,7 +477,11 @@ mod tests {
     fn test_get_match_id_helper() {
         let move_msg = WsMessage::Move {
             match_id: "m1".to_string(),
+            game_id: "g1".to_string(),
+            ply: 1,
             uci: "e2e4".to_string(),
+            san: None,
+            fen: None,
             centipawns: None,
         };
         assert_eq!(get_match_id(&move_msg), Some("m1"));
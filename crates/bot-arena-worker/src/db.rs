@@ -1,25 +1,32 @@
 //! Database operations for the worker.
 //!
 //! This module provides database connectivity and match claiming functionality
-//! for the worker. The `claim_match` function will be used in the worker loop
-//! implementation (next phase).
+//! for the worker.
 
-use crate::elo;
-use rusqlite::{Connection, OptionalExtension, Result as SqliteResult};
+use crate::elo::{self, EloConfig};
+use rusqlite::{Connection, OptionalExtension, Result as SqliteResult, TransactionBehavior};
 use std::path::Path;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 /// Thread-safe database connection pool.
 pub type DbPool = Arc<Mutex<Connection>>;
 
+/// How long a connection waits for another worker's write lock.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Opens a connection to the SQLite database.
 ///
+/// Several workers may share the database; a connection waits up to
+/// [`BUSY_TIMEOUT`] for another worker's write to finish.
+///
 /// # Errors
 ///
 /// Returns an error if the database cannot be opened or configured.
 pub fn connect(path: &Path) -> SqliteResult<DbPool> {
     let conn = Connection::open(path)?;
     conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+    conn.busy_timeout(BUSY_TIMEOUT)?;
     Ok(Arc::new(Mutex::new(conn)))
 }
 
@@ -44,8 +51,10 @@ pub struct PendingMatch {
 
 /// Atomically claim a pending match.
 ///
-/// This function finds the oldest pending match and atomically updates its status
-/// to 'running' while recording the worker ID and start time.
+/// This function finds the oldest pending match and updates its status to
+/// 'running' while recording the worker ID and start time. The lookup and the
+/// update are a single `UPDATE ... RETURNING` statement, so two workers can
+/// never claim the same match, even through separate connections.
 ///
 /// # Arguments
 ///
@@ -55,62 +64,28 @@ pub struct PendingMatch {
 /// # Returns
 ///
 /// * `Ok(Some(PendingMatch))` - Successfully claimed a match
-/// * `Ok(None)` - No pending matches available or claim failed due to race condition
+/// * `Ok(None)` - No pending matches available
 /// * `Err(_)` - Database error
 pub fn claim_match(db: &DbPool, worker_id: &str) -> SqliteResult<Option<PendingMatch>> {
     let conn = db.lock().unwrap();
-
-    // Find and claim in one transaction
-    conn.execute_batch("BEGIN IMMEDIATE;")?;
-
-    let result: SqliteResult<Option<PendingMatch>> = (|| {
-        let mut stmt = conn.prepare(
-            "SELECT id, white_bot, black_bot, games_total, movetime_ms, opening_id
-             FROM matches
-             WHERE status = 'pending'
-             ORDER BY rowid ASC
-             LIMIT 1",
-        )?;
-
-        let match_opt = stmt
-            .query_row([], |row| {
-                Ok(PendingMatch {
-                    id: row.get(0)?,
-                    white_bot: row.get(1)?,
-                    black_bot: row.get(2)?,
-                    games_total: row.get(3)?,
-                    movetime_ms: row.get(4)?,
-                    opening_id: row.get(5)?,
-                })
+    conn.query_row(
+        "UPDATE matches SET status = 'running', worker_id = ?1, started_at = datetime('now')
+         WHERE id = (SELECT id FROM matches WHERE status = 'pending' ORDER BY rowid ASC LIMIT 1)
+           AND status = 'pending'
+         RETURNING id, white_bot, black_bot, games_total, movetime_ms, opening_id",
+        [worker_id],
+        |row| {
+            Ok(PendingMatch {
+                id: row.get(0)?,
+                white_bot: row.get(1)?,
+                black_bot: row.get(2)?,
+                games_total: row.get(3)?,
+                movetime_ms: row.get(4)?,
+                opening_id: row.get(5)?,
             })
-            .optional()?;
-
-        if let Some(ref m) = match_opt {
-            let updated = conn.execute(
-                "UPDATE matches SET status = 'running', worker_id = ?, started_at = datetime('now')
-                 WHERE id = ? AND status = 'pending'",
-                (&worker_id, &m.id),
-            )?;
-
-            if updated == 0 {
-                // Race condition - another worker claimed it
-                return Ok(None);
-            }
-        }
-
-        Ok(match_opt)
-    })();
-
-    match result {
-        Ok(m) => {
-            conn.execute_batch("COMMIT;")?;
-            Ok(m)
-        }
-        Err(e) => {
-            let _ = conn.execute_batch("ROLLBACK;");
-            Err(e)
-        }
-    }
+        },
+    )
+    .optional()
 }
 
 /// Create a game record.
@@ -192,6 +167,41 @@ pub fn finish_game(db: &DbPool, game_id: &str, result: &str) -> SqliteResult<()>
     Ok(())
 }
 
+/// Load the finished games already saved for a match.
+///
+/// A match released mid-way through keeps the games it completed, so a
+/// worker resuming it continues from the next game number.
+///
+/// # Arguments
+///
+/// * `db` - Database connection pool
+/// * `match_id` - ID of the match
+///
+/// # Returns
+///
+/// The saved results ordered by game number.
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub fn finished_games(db: &DbPool, match_id: &str) -> SqliteResult<Vec<GameResult>> {
+    let conn = db.lock().unwrap();
+    let mut stmt = conn.prepare(
+        "SELECT game_number, result FROM games
+         WHERE match_id = ?1 AND result IS NOT NULL
+         ORDER BY game_number",
+    )?;
+    let games = stmt
+        .query_map([match_id], |row| {
+            Ok(GameResult {
+                game_num: row.get(0)?,
+                result: row.get(1)?,
+            })
+        })?
+        .collect();
+    games
+}
+
 /// Release a match back to pending (e.g., on shutdown).
 ///
 /// Sets the match status back to 'pending' and clears the worker_id,
@@ -216,29 +226,111 @@ pub fn release_match(db: &DbPool, match_id: &str, worker_id: &str) -> SqliteResu
     Ok(())
 }
 
-/// Mark a match as failed with an error message.
+/// Returns true if cancelling the match was requested while it was running.
 ///
-/// Updates the match status to 'failed' and logs the error message.
-/// The error message is not stored in the database (no column exists for it)
-/// but is logged via tracing for debugging purposes.
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub fn cancel_requested(db: &DbPool, match_id: &str) -> SqliteResult<bool> {
+    let conn = db.lock().unwrap();
+    conn.query_row(
+        "SELECT COALESCE(cancel_requested, 0) FROM matches WHERE id = ?1",
+        [match_id],
+        |row| row.get(0),
+    )
+    .optional()
+    .map(|requested| requested.unwrap_or(false))
+}
+
+/// Mark a running match as cancelled.
+///
+/// Only changes the match if it is currently claimed by this worker. Games
+/// already saved are kept.
+///
+/// # Errors
+///
+/// Returns an error if the database update fails.
+pub fn cancel_match(db: &DbPool, match_id: &str, worker_id: &str) -> SqliteResult<()> {
+    let conn = db.lock().unwrap();
+    conn.execute(
+        "UPDATE matches SET status = 'cancelled', finished_at = datetime('now')
+         WHERE id = ?1 AND worker_id = ?2 AND status = 'running'",
+        (match_id, worker_id),
+    )?;
+    Ok(())
+}
+
+/// Default number of times a match is attempted before it is left failed.
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+/// What happened to a match after a failed attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailOutcome {
+    /// The match went back to 'pending' to be retried.
+    Requeued {
+        /// Attempts made so far.
+        attempts: u32,
+    },
+    /// The match ran out of attempts and is now 'failed'.
+    Failed {
+        /// Attempts made in total.
+        attempts: u32,
+    },
+}
+
+/// Record a failed attempt at a match.
+///
+/// Stores the error message and increments the match's `attempts` counter.
+/// While fewer than `max_attempts` attempts have been made the match is
+/// re-queued: its status goes back to 'pending' and the worker is cleared so
+/// any worker can claim it again. Otherwise the status becomes 'failed' and
+/// `finished_at` is set.
 ///
 /// # Arguments
 ///
 /// * `db` - Database connection pool
-/// * `match_id` - ID of the match to mark as failed
+/// * `match_id` - ID of the match that failed
 /// * `error` - Error message describing the failure
+/// * `max_attempts` - Total attempts allowed before giving up
 ///
 /// # Errors
 ///
 /// Returns an error if the database update fails.
-pub fn fail_match(db: &DbPool, match_id: &str, error: &str) -> SqliteResult<()> {
+pub fn fail_match(
+    db: &DbPool,
+    match_id: &str,
+    error: &str,
+    max_attempts: u32,
+) -> SqliteResult<FailOutcome> {
     let conn = db.lock().unwrap();
-    conn.execute(
-        "UPDATE matches SET status = 'failed', finished_at = datetime('now') WHERE id = ?1",
-        [match_id],
+    let attempts: u32 = conn.query_row(
+        "UPDATE matches SET attempts = attempts + 1, error = ?2 WHERE id = ?1
+         RETURNING attempts",
+        (match_id, error),
+        |row| row.get(0),
     )?;
-    tracing::error!(match_id = %match_id, error = %error, "Match marked as failed");
-    Ok(())
+
+    if attempts < max_attempts {
+        conn.execute(
+            "UPDATE matches SET status = 'pending', worker_id = NULL WHERE id = ?1",
+            [match_id],
+        )?;
+        tracing::warn!(
+            match_id = %match_id,
+            error = %error,
+            attempts,
+            max_attempts,
+            "Match attempt failed, re-queued"
+        );
+        Ok(FailOutcome::Requeued { attempts })
+    } else {
+        conn.execute(
+            "UPDATE matches SET status = 'failed', finished_at = datetime('now') WHERE id = ?1",
+            [match_id],
+        )?;
+        tracing::error!(match_id = %match_id, error = %error, attempts, "Match marked as failed");
+        Ok(FailOutcome::Failed { attempts })
+    }
 }
 
 /// Finish a match with final scores.
@@ -285,11 +377,19 @@ pub struct GameResult {
 /// Colors alternate each game: even-numbered games have white_bot as white,
 /// odd-numbered games have white_bot as black.
 ///
+/// Each game uses the K-factor for the number of games the bot had played
+/// before it, so a bot leaves its provisional period partway through a
+/// match. The anchor bot, if one of the two, keeps its fixed rating.
+///
+/// The ratings are read and written in one immediate transaction, so
+/// concurrent updates from other workers cannot interleave with it.
+///
 /// # Arguments
 ///
 /// * `db` - Database connection pool
 /// * `match_id` - Match ID to get bot names
 /// * `game_results` - Vector of game results
+/// * `config` - K-factor and anchor settings
 ///
 /// # Errors
 ///
@@ -298,8 +398,10 @@ pub fn update_elo_ratings(
     db: &DbPool,
     match_id: &str,
     game_results: &[GameResult],
+    config: &EloConfig,
 ) -> SqliteResult<()> {
-    let conn = db.lock().unwrap();
+    let mut conn = db.lock().unwrap();
+    let conn = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
 
     // Get bot names from match
     let (white_bot, black_bot): (String, String) = conn.query_row(
@@ -308,24 +410,25 @@ pub fn update_elo_ratings(
         |row| Ok((row.get(0)?, row.get(1)?)),
     )?;
 
-    // Get current ratings
-    let white_rating: i32 = conn.query_row(
-        "SELECT elo_rating FROM bots WHERE name = ?1",
-        [&white_bot],
-        |row| row.get(0),
-    )?;
+    // Get current ratings and game counts
+    let rating_query = "SELECT elo_rating, games_played FROM bots WHERE name = ?1";
+    let (white_rating, white_games): (i32, u32) =
+        conn.query_row(rating_query, [&white_bot], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })?;
+    let (black_rating, black_games): (i32, u32) =
+        conn.query_row(rating_query, [&black_bot], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })?;
 
-    let black_rating: i32 = conn.query_row(
-        "SELECT elo_rating FROM bots WHERE name = ?1",
-        [&black_bot],
-        |row| row.get(0),
-    )?;
+    let white_anchor = config.anchored_rating(&white_bot);
+    let black_anchor = config.anchored_rating(&black_bot);
 
     // Update Elo for each game
-    let mut new_white_rating = white_rating;
-    let mut new_black_rating = black_rating;
+    let mut new_white_rating = white_anchor.unwrap_or(white_rating);
+    let mut new_black_rating = black_anchor.unwrap_or(black_rating);
 
-    for game in game_results {
+    for (played, game) in (0u32..).zip(game_results) {
         let (white_actual, black_actual) = match game.result.as_str() {
             "1-0" => (1.0, 0.0),
             "0-1" => (0.0, 1.0),
@@ -333,19 +436,30 @@ pub fn update_elo_ratings(
         };
 
         // Colors alternate each game
-        if game.game_num % 2 == 0 {
+        let (white_bot_actual, black_bot_actual) = if game.game_num % 2 == 0 {
             // Even games: white_bot plays white
-            let new_w = elo::new_rating(new_white_rating, new_black_rating, white_actual);
-            let new_b = elo::new_rating(new_black_rating, new_white_rating, black_actual);
-            new_white_rating = new_w;
-            new_black_rating = new_b;
+            (white_actual, black_actual)
         } else {
             // Odd games: white_bot plays black
-            let new_w = elo::new_rating(new_white_rating, new_black_rating, black_actual);
-            let new_b = elo::new_rating(new_black_rating, new_white_rating, white_actual);
-            new_white_rating = new_w;
-            new_black_rating = new_b;
-        }
+            (black_actual, white_actual)
+        };
+
+        let white_k = config.k_for(white_games + played);
+        let black_k = config.k_for(black_games + played);
+        let new_w = elo::new_rating(
+            new_white_rating,
+            new_black_rating,
+            white_bot_actual,
+            white_k,
+        );
+        let new_b = elo::new_rating(
+            new_black_rating,
+            new_white_rating,
+            black_bot_actual,
+            black_k,
+        );
+        new_white_rating = white_anchor.unwrap_or(new_w);
+        new_black_rating = black_anchor.unwrap_or(new_b);
     }
 
     // Update database
@@ -359,17 +473,15 @@ pub fn update_elo_ratings(
         (new_black_rating, game_results.len() as i64, &black_bot),
     )?;
 
-    Ok(())
+    conn.commit()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    fn setup_test_db() -> DbPool {
-        let conn = Connection::open_in_memory().unwrap();
-        conn.execute_batch(
-            "CREATE TABLE bots (
+    /// Tables used by the worker, as created by the server.
+    const TEST_SCHEMA: &str = "CREATE TABLE bots (
                  name TEXT PRIMARY KEY,
                  elo_rating INTEGER DEFAULT 1500,
                  games_played INTEGER DEFAULT 0
@@ -383,16 +495,111 @@ mod tests {
                  opening_id TEXT,
                  status TEXT DEFAULT 'pending',
                  worker_id TEXT,
-                 started_at TEXT
+                 started_at TEXT,
+                 attempts INTEGER DEFAULT 0,
+                 error TEXT,
+                 cancel_requested INTEGER DEFAULT 0
              );
-             INSERT INTO bots (name) VALUES ('bot1'), ('bot2');
-             INSERT INTO matches (id, white_bot, black_bot, games_total)
-             VALUES ('match1', 'bot1', 'bot2', 10);",
+             INSERT INTO bots (name) VALUES ('bot1'), ('bot2');";
+
+    fn setup_test_db() -> DbPool {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(TEST_SCHEMA).unwrap();
+        conn.execute(
+            "INSERT INTO matches (id, white_bot, black_bot, games_total)
+             VALUES ('match1', 'bot1', 'bot2', 10)",
+            [],
         )
         .unwrap();
         Arc::new(Mutex::new(conn))
     }
 
+    /// Creates a database file with `matches` pending matches and returns
+    /// its path, so that several connections can share it.
+    fn setup_file_db(dir: &Path, matches: usize) -> std::path::PathBuf {
+        let path = dir.join("arena.db");
+        let conn = Connection::open(&path).unwrap();
+        conn.execute_batch(TEST_SCHEMA).unwrap();
+        for i in 0..matches {
+            conn.execute(
+                "INSERT INTO matches (id, white_bot, black_bot, games_total)
+                 VALUES (?1, 'bot1', 'bot2', 1)",
+                [format!("match{}", i)],
+            )
+            .unwrap();
+        }
+        path
+    }
+
+    #[test]
+    fn test_claim_match_hammered_from_many_threads_succeeds_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = setup_file_db(dir.path(), 1);
+
+        let barrier = Arc::new(std::sync::Barrier::new(8));
+        let threads: Vec<_> = (0..8)
+            .map(|n| {
+                let db = connect(&path).unwrap();
+                let barrier = Arc::clone(&barrier);
+                std::thread::spawn(move || {
+                    barrier.wait();
+                    claim_match(&db, &format!("worker{}", n)).unwrap()
+                })
+            })
+            .collect();
+
+        let winners: Vec<PendingMatch> = threads
+            .into_iter()
+            .filter_map(|t| t.join().unwrap())
+            .collect();
+        assert_eq!(winners.len(), 1);
+        assert_eq!(winners[0].id, "match0");
+
+        let db = connect(&path).unwrap();
+        let (status, worker): (String, String) = db
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT status, worker_id FROM matches WHERE id = 'match0'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(status, "running");
+        assert!(worker.starts_with("worker"));
+    }
+
+    #[test]
+    fn test_concurrent_workers_never_claim_the_same_match() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = setup_file_db(dir.path(), 20);
+
+        // Each worker has its own connection, as separate processes would
+        let workers: Vec<_> = (0..2)
+            .map(|n| {
+                let db = connect(&path).unwrap();
+                std::thread::spawn(move || {
+                    let worker_id = format!("worker{}", n);
+                    let mut claimed = Vec::new();
+                    while let Some(m) = claim_match(&db, &worker_id).unwrap() {
+                        claimed.push(m.id);
+                    }
+                    claimed
+                })
+            })
+            .collect();
+
+        let mut claimed: Vec<String> = workers
+            .into_iter()
+            .flat_map(|w| w.join().unwrap())
+            .collect();
+        claimed.sort();
+        let total = claimed.len();
+        claimed.dedup();
+        assert_eq!(total, 20);
+        assert_eq!(claimed.len(), 20);
+    }
+
     #[test]
     fn test_claim_match_success() {
         let db = setup_test_db();
@@ -429,6 +636,51 @@ mod tests {
         assert_eq!(status, "running");
     }
 
+    #[test]
+    fn test_cancelled_match_is_not_claimed() {
+        let db = setup_test_db();
+        db.lock()
+            .unwrap()
+            .execute(
+                "UPDATE matches SET status = 'cancelled' WHERE id = 'match1'",
+                [],
+            )
+            .unwrap();
+
+        assert!(claim_match(&db, "worker1").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_cancel_requested_and_cancel_match() {
+        let db = setup_test_db();
+        db.lock()
+            .unwrap()
+            .execute("ALTER TABLE matches ADD COLUMN finished_at TEXT", [])
+            .unwrap();
+        claim_match(&db, "worker1").unwrap().unwrap();
+        assert!(!cancel_requested(&db, "match1").unwrap());
+        assert!(!cancel_requested(&db, "missing").unwrap());
+
+        db.lock()
+            .unwrap()
+            .execute(
+                "UPDATE matches SET cancel_requested = 1 WHERE id = 'match1'",
+                [],
+            )
+            .unwrap();
+        assert!(cancel_requested(&db, "match1").unwrap());
+
+        // Another worker cannot cancel this worker's match
+        cancel_match(&db, "match1", "worker2").unwrap();
+        let (status, ..) = match_state(&db);
+        assert_eq!(status, "running");
+
+        cancel_match(&db, "match1", "worker1").unwrap();
+        let (status, ..) = match_state(&db);
+        assert_eq!(status, "cancelled");
+        assert!(claim_match(&db, "worker1").unwrap().is_none());
+    }
+
     #[test]
     fn test_create_and_finish_game() {
         let db = setup_test_db();
@@ -452,6 +704,33 @@ mod tests {
         assert_eq!(result, "1-0");
     }
 
+    #[test]
+    fn test_finished_games() {
+        let db = setup_test_db();
+        {
+            let conn = db.lock().unwrap();
+            conn.execute_batch(
+                "CREATE TABLE games (id TEXT PRIMARY KEY, match_id TEXT, game_number INTEGER, result TEXT, started_at TEXT);",
+            )
+            .unwrap();
+        }
+
+        create_game(&db, "match1-1", "match1", 1).unwrap();
+        finish_game(&db, "match1-1", "1/2-1/2").unwrap();
+        create_game(&db, "match1-0", "match1", 0).unwrap();
+        finish_game(&db, "match1-0", "1-0").unwrap();
+        create_game(&db, "match1-2", "match1", 2).unwrap();
+        create_game(&db, "other-0", "other", 0).unwrap();
+        finish_game(&db, "other-0", "0-1").unwrap();
+
+        let games = finished_games(&db, "match1").unwrap();
+        let games: Vec<(i32, &str)> = games
+            .iter()
+            .map(|g| (g.game_num, g.result.as_str()))
+            .collect();
+        assert_eq!(games, vec![(0, "1-0"), (1, "1/2-1/2")]);
+    }
+
     #[test]
     fn test_insert_move() {
         let db = setup_test_db();
@@ -524,7 +803,7 @@ mod tests {
             result: "1-0".to_string(),
         }];
 
-        update_elo_ratings(&db, "match1", &results).unwrap();
+        update_elo_ratings(&db, "match1", &results, &EloConfig::default()).unwrap();
 
         let conn = db.lock().unwrap();
         let (bot1_elo, bot1_games): (i32, i32) = conn
@@ -566,7 +845,7 @@ mod tests {
             },
         ];
 
-        update_elo_ratings(&db, "match1", &results).unwrap();
+        update_elo_ratings(&db, "match1", &results, &EloConfig::default()).unwrap();
 
         let conn = db.lock().unwrap();
         let (bot1_elo, bot1_games): (i32, i32) = conn
@@ -600,7 +879,7 @@ mod tests {
             result: "1/2-1/2".to_string(),
         }];
 
-        update_elo_ratings(&db, "match1", &results).unwrap();
+        update_elo_ratings(&db, "match1", &results, &EloConfig::default()).unwrap();
 
         let conn = db.lock().unwrap();
         let bot1_elo: i32 = conn
@@ -629,7 +908,7 @@ mod tests {
 
         let results: Vec<GameResult> = vec![];
 
-        update_elo_ratings(&db, "match1", &results).unwrap();
+        update_elo_ratings(&db, "match1", &results, &EloConfig::default()).unwrap();
 
         let conn = db.lock().unwrap();
         let bot1_elo: i32 = conn
@@ -699,7 +978,8 @@ mod tests {
         claim_match(&db, "worker-1").unwrap();
 
         // Mark it as failed
-        fail_match(&db, "match1", "Test engine error").unwrap();
+        let outcome = fail_match(&db, "match1", "Test engine error", 1).unwrap();
+        assert_eq!(outcome, FailOutcome::Failed { attempts: 1 });
 
         // Verify status is 'failed' and finished_at is set
         let conn = db.lock().unwrap();
@@ -724,7 +1004,7 @@ mod tests {
         }
 
         // Fail a match directly without claiming (edge case)
-        fail_match(&db, "match1", "Configuration error").unwrap();
+        fail_match(&db, "match1", "Configuration error", 1).unwrap();
 
         // Verify status is 'failed'
         let conn = db.lock().unwrap();
@@ -737,4 +1017,136 @@ mod tests {
             .unwrap();
         assert_eq!(status, "failed");
     }
+
+    fn match_state(db: &DbPool) -> (String, u32, Option<String>, Option<String>) {
+        db.lock()
+            .unwrap()
+            .query_row(
+                "SELECT status, attempts, error, worker_id FROM matches WHERE id = 'match1'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_fail_match_requeues_until_attempts_exhausted() {
+        let db = setup_test_db();
+        {
+            let conn = db.lock().unwrap();
+            conn.execute("ALTER TABLE matches ADD COLUMN finished_at TEXT", [])
+                .unwrap();
+        }
+
+        for attempt in 1..3 {
+            let claimed = claim_match(&db, "worker-1").unwrap();
+            assert_eq!(claimed.unwrap().id, "match1");
+
+            let error = format!("engine crashed ({})", attempt);
+            let outcome = fail_match(&db, "match1", &error, 3).unwrap();
+            assert_eq!(outcome, FailOutcome::Requeued { attempts: attempt });
+
+            let (status, attempts, stored_error, worker) = match_state(&db);
+            assert_eq!(status, "pending");
+            assert_eq!(attempts, attempt);
+            assert_eq!(stored_error.as_deref(), Some(error.as_str()));
+            assert_eq!(worker, None);
+        }
+
+        // The third failure uses up the last attempt
+        claim_match(&db, "worker-2").unwrap().unwrap();
+        let outcome = fail_match(&db, "match1", "engine crashed (3)", 3).unwrap();
+        assert_eq!(outcome, FailOutcome::Failed { attempts: 3 });
+
+        let (status, attempts, error, _) = match_state(&db);
+        assert_eq!(status, "failed");
+        assert_eq!(attempts, 3);
+        assert_eq!(error.as_deref(), Some("engine crashed (3)"));
+
+        // A failed match is no longer handed out
+        assert!(claim_match(&db, "worker-1").unwrap().is_none());
+    }
+
+    fn bot_rating(db: &DbPool, name: &str) -> i32 {
+        db.lock()
+            .unwrap()
+            .query_row(
+                "SELECT elo_rating FROM bots WHERE name = ?1",
+                [name],
+                |row| row.get(0),
+            )
+            .unwrap()
+    }
+
+    fn wins_for_white_bot(games: i32) -> Vec<GameResult> {
+        (0..games)
+            .map(|game_num| GameResult {
+                game_num,
+                result: if game_num % 2 == 0 { "1-0" } else { "0-1" }.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_update_elo_ratings_anchor_never_changes() {
+        let db = setup_test_db();
+        let config = EloConfig {
+            anchor: Some(("bot2".to_string(), 2000)),
+            ..EloConfig::default()
+        };
+
+        // bot2 is anchored at 2000 and loses every game
+        update_elo_ratings(&db, "match1", &wins_for_white_bot(6), &config).unwrap();
+        assert_eq!(bot_rating(&db, "bot2"), 2000);
+
+        // Beating a 2000-rated anchor is worth more than beating a 1500 bot
+        let bot1 = bot_rating(&db, "bot1");
+        assert!(bot1 > 1500 + 6 * 16, "{}", bot1);
+
+        update_elo_ratings(&db, "match1", &wins_for_white_bot(2), &config).unwrap();
+        assert_eq!(bot_rating(&db, "bot2"), 2000);
+    }
+
+    #[test]
+    fn test_update_elo_ratings_provisional_k_factor() {
+        let db = setup_test_db();
+        db.lock()
+            .unwrap()
+            .execute("UPDATE bots SET games_played = 100 WHERE name = 'bot2'", [])
+            .unwrap();
+        let config = EloConfig {
+            k_factor: 10.0,
+            provisional_k_factor: 40.0,
+            provisional_games: 30,
+            anchor: None,
+        };
+
+        // Same single result from equal ratings: the new bot1 uses K=40
+        // while the established bot2 uses K=10
+        update_elo_ratings(&db, "match1", &wins_for_white_bot(1), &config).unwrap();
+        let bot1_gain = bot_rating(&db, "bot1") - 1500;
+        let bot2_loss = 1500 - bot_rating(&db, "bot2");
+        assert_eq!(bot1_gain, 20);
+        assert_eq!(bot2_loss, 5);
+    }
+
+    #[test]
+    fn test_update_elo_ratings_leaves_provisional_period_mid_match() {
+        let db = setup_test_db();
+        db.lock()
+            .unwrap()
+            .execute("UPDATE bots SET games_played = 29", [])
+            .unwrap();
+        let config = EloConfig {
+            k_factor: 10.0,
+            provisional_k_factor: 40.0,
+            provisional_games: 30,
+            anchor: None,
+        };
+
+        // First game at K=40 (+20), second at K=10 against the now
+        // 1480-rated bot2 (+4 rather than +18 at K=40)
+        update_elo_ratings(&db, "match1", &wins_for_white_bot(2), &config).unwrap();
+        assert_eq!(bot_rating(&db, "bot1"), 1524);
+    }
 }
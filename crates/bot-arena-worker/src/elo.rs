@@ -1,9 +1,72 @@
 //! Elo rating calculation.
 //!
 //! This module provides functions for calculating Elo ratings after games.
-//! The K-factor of 32 is used, which is standard for most chess rating systems.
+//! The K-factor defaults to 32, which is standard for most chess rating
+//! systems, and can be raised for provisional bots with few games. One bot
+//! can be anchored to a fixed rating to pin the scale.
 
-const K_FACTOR: f64 = 32.0;
+/// Default K-factor for all bots.
+pub const DEFAULT_K_FACTOR: f64 = 32.0;
+
+/// Default number of games a bot is provisional for.
+pub const DEFAULT_PROVISIONAL_GAMES: u32 = 30;
+
+/// Rating update settings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EloConfig {
+    /// K-factor for established bots.
+    pub k_factor: f64,
+    /// K-factor for bots with fewer than `provisional_games` games.
+    pub provisional_k_factor: f64,
+    /// Number of games after which a bot is established.
+    pub provisional_games: u32,
+    /// Bot whose rating is fixed, and the rating it is fixed at.
+    pub anchor: Option<(String, i32)>,
+}
+
+impl Default for EloConfig {
+    fn default() -> Self {
+        Self {
+            k_factor: DEFAULT_K_FACTOR,
+            provisional_k_factor: DEFAULT_K_FACTOR,
+            provisional_games: DEFAULT_PROVISIONAL_GAMES,
+            anchor: None,
+        }
+    }
+}
+
+impl EloConfig {
+    /// Returns the K-factor for a bot that has played `games_played` games.
+    pub fn k_for(&self, games_played: u32) -> f64 {
+        if games_played < self.provisional_games {
+            self.provisional_k_factor
+        } else {
+            self.k_factor
+        }
+    }
+
+    /// Returns the fixed rating of `bot` if it is the anchor.
+    pub fn anchored_rating(&self, bot: &str) -> Option<i32> {
+        self.anchor
+            .as_ref()
+            .filter(|(name, _)| name == bot)
+            .map(|(_, rating)| *rating)
+    }
+}
+
+/// Parse an anchor given as `NAME=RATING`.
+pub fn parse_anchor(s: &str) -> Result<(String, i32), String> {
+    let (name, rating) = s
+        .rsplit_once('=')
+        .ok_or_else(|| format!("expected NAME=RATING, got '{}'", s))?;
+    if name.is_empty() {
+        return Err(format!("missing bot name in '{}'", s));
+    }
+    let rating = rating
+        .parse()
+        .map_err(|_| format!("invalid rating '{}'", rating))?;
+    Ok((name.to_string(), rating))
+}
 
 /// Calculate expected score for player A against player B.
 fn expected_score(rating_a: i32, rating_b: i32) -> f64 {
@@ -16,9 +79,10 @@ fn expected_score(rating_a: i32, rating_b: i32) -> f64 {
 /// * `rating` - Current rating
 /// * `opponent_rating` - Opponent's rating
 /// * `actual` - Actual score (1.0 = win, 0.5 = draw, 0.0 = loss)
-pub fn new_rating(rating: i32, opponent_rating: i32, actual: f64) -> i32 {
+/// * `k_factor` - Maximum rating change for a single game
+pub fn new_rating(rating: i32, opponent_rating: i32, actual: f64, k_factor: f64) -> i32 {
     let expected = expected_score(rating, opponent_rating);
-    let new = rating as f64 + K_FACTOR * (actual - expected);
+    let new = rating as f64 + k_factor * (actual - expected);
     new.round() as i32
 }
 
@@ -48,26 +112,57 @@ mod tests {
 
     #[test]
     fn test_new_rating_win() {
-        let new = new_rating(1500, 1500, 1.0);
+        let new = new_rating(1500, 1500, 1.0, DEFAULT_K_FACTOR);
         assert_eq!(new, 1516);
     }
 
     #[test]
     fn test_new_rating_loss() {
-        let new = new_rating(1500, 1500, 0.0);
+        let new = new_rating(1500, 1500, 0.0, DEFAULT_K_FACTOR);
         assert_eq!(new, 1484);
     }
 
     #[test]
     fn test_new_rating_draw() {
-        let new = new_rating(1500, 1500, 0.5);
+        let new = new_rating(1500, 1500, 0.5, DEFAULT_K_FACTOR);
         assert_eq!(new, 1500);
     }
 
     #[test]
     fn test_new_rating_upset_win() {
         // Lower rated player wins
-        let new = new_rating(1300, 1500, 1.0);
+        let new = new_rating(1300, 1500, 1.0, DEFAULT_K_FACTOR);
         assert!(new > 1320); // Bigger gain for upset
     }
+
+    #[test]
+    fn test_new_rating_scales_with_k_factor() {
+        assert_eq!(new_rating(1500, 1500, 1.0, 40.0), 1520);
+        assert_eq!(new_rating(1500, 1500, 1.0, 10.0), 1505);
+    }
+
+    #[test]
+    fn test_k_for_provisional_and_established() {
+        let config = EloConfig {
+            k_factor: 10.0,
+            provisional_k_factor: 40.0,
+            provisional_games: 30,
+            anchor: None,
+        };
+        assert_eq!(config.k_for(0), 40.0);
+        assert_eq!(config.k_for(29), 40.0);
+        assert_eq!(config.k_for(30), 10.0);
+        assert_eq!(EloConfig::default().k_for(0), DEFAULT_K_FACTOR);
+    }
+
+    #[test]
+    fn test_parse_anchor() {
+        assert_eq!(
+            parse_anchor("stockfish=2800"),
+            Ok(("stockfish".to_string(), 2800))
+        );
+        assert!(parse_anchor("stockfish").is_err());
+        assert!(parse_anchor("=2800").is_err());
+        assert!(parse_anchor("stockfish=strong").is_err());
+    }
 }
@@ -32,6 +32,292 @@ struct Args {
     /// Directory containing bot executables
     #[arg(long, default_value = "bots")]
     bots_dir: PathBuf,
+
+    /// Elo K-factor for established bots
+    #[arg(long, default_value_t = elo::DEFAULT_K_FACTOR)]
+    k_factor: f64,
+
+    /// Elo K-factor for provisional bots (defaults to --k-factor)
+    #[arg(long)]
+    provisional_k_factor: Option<f64>,
+
+    /// Number of games a bot stays provisional for
+    #[arg(long, default_value_t = elo::DEFAULT_PROVISIONAL_GAMES)]
+    provisional_games: u32,
+
+    /// Number of times a failing match is attempted before it is left failed
+    #[arg(long, default_value_t = db::DEFAULT_MAX_ATTEMPTS)]
+    max_attempts: u32,
+
+    /// Number of matches played in parallel
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+    workers: u16,
+
+    /// Bot whose rating is fixed, as NAME=RATING (e.g. stockfish=2800)
+    #[arg(long, value_parser = elo::parse_anchor)]
+    anchor: Option<(String, i32)>,
+}
+
+impl Args {
+    /// Elo settings from the command line.
+    fn elo_config(&self) -> elo::EloConfig {
+        elo::EloConfig {
+            k_factor: self.k_factor,
+            provisional_k_factor: self.provisional_k_factor.unwrap_or(self.k_factor),
+            provisional_games: self.provisional_games,
+            anchor: self.anchor.clone(),
+        }
+    }
+}
+
+/// Settings and shared state for the match-processing tasks.
+struct Worker {
+    /// Database connection shared by all tasks.
+    db: db::DbPool,
+    /// Runner that plays the claimed matches.
+    runner: MatchRunner,
+    /// Set once a shutdown was requested.
+    shutdown: Arc<AtomicBool>,
+    /// Elo settings applied after each match.
+    elo_config: elo::EloConfig,
+    /// Attempts allowed before a failing match is left failed.
+    max_attempts: u32,
+    /// How long to wait before polling again when no match is pending.
+    poll_interval: Duration,
+}
+
+impl Worker {
+    /// Claims and plays matches as `worker_id` until a shutdown is requested.
+    ///
+    /// Rating updates from concurrent tasks are serialized by the shared
+    /// database connection.
+    fn run(&self, worker_id: &str) {
+        let Worker {
+            db,
+            runner,
+            shutdown,
+            elo_config,
+            max_attempts,
+            poll_interval,
+        } = self;
+        let (max_attempts, poll_interval) = (*max_attempts, *poll_interval);
+
+        // Track the currently running match ID for graceful shutdown.
+        // The match runner stops the game in progress once the flag is set, and
+        // the interrupted match is released here so another worker can finish it.
+        let mut current_match_id: Option<String> = None;
+
+        // Main worker loop
+        loop {
+            // Check for shutdown
+            if shutdown.load(Ordering::SeqCst) {
+                if let Some(ref match_id) = current_match_id {
+                    tracing::info!("Releasing match {} due to shutdown", match_id);
+                    if let Err(e) = db::release_match(db, match_id, worker_id) {
+                        tracing::error!("Failed to release match: {}", e);
+                    }
+                }
+                break;
+            }
+
+            match db::claim_match(db, worker_id) {
+                Ok(Some(pending)) => {
+                    // Track current match for graceful shutdown release.
+                    // This is intentionally set before run_match so it can be released
+                    // if a shutdown signal interrupts the match.
+                    // Note: Rust warns about unused assignment because it doesn't track
+                    // the value being read across loop iterations at shutdown check.
+                    #[allow(unused_assignments)]
+                    {
+                        current_match_id = Some(pending.id.clone());
+                    }
+                    tracing::info!(
+                        "Claimed match: {} ({} vs {})",
+                        pending.id,
+                        pending.white_bot,
+                        pending.black_bot
+                    );
+
+                    // Games saved before an earlier shutdown are kept and skipped
+                    let mut game_results =
+                        db::finished_games(db, &pending.id).unwrap_or_else(|e| {
+                            tracing::error!("Failed to load games for match {}: {}", pending.id, e);
+                            Vec::new()
+                        });
+                    let first_game = game_results.len() as i32;
+                    if first_game > 0 {
+                        tracing::info!("Resuming match {} at game {}", pending.id, first_game);
+                    }
+
+                    // A cancellation requested through the API stops the match
+                    // between games
+                    let cancelled = || db::cancel_requested(db, &pending.id).unwrap_or(false);
+
+                    match runner.run_match(&pending, first_game, shutdown, cancelled) {
+                        Ok(results) => {
+                            for (game_num, (game_id, result)) in (first_game..).zip(&results) {
+                                // Create game record
+                                if let Err(e) = db::create_game(db, game_id, &pending.id, game_num)
+                                {
+                                    tracing::error!("Failed to create game {}: {}", game_id, e);
+                                    continue;
+                                }
+
+                                // Insert all moves
+                                for (ply, move_record) in result.moves.iter().enumerate() {
+                                    let _ = db::insert_move(
+                                        db,
+                                        game_id,
+                                        ply as i32,
+                                        &move_record.uci,
+                                        None, // SAN not available from MoveRecord
+                                        "",   // FEN not available
+                                    );
+                                }
+
+                                let game_result_str = match result.result {
+                                    MatchResult::WhiteWins => "1-0",
+                                    MatchResult::BlackWins => "0-1",
+                                    MatchResult::Draw => "1/2-1/2",
+                                };
+
+                                // Collect game result for scoring and Elo update
+                                game_results.push(db::GameResult {
+                                    game_num,
+                                    result: game_result_str.to_string(),
+                                });
+
+                                let _ = db::finish_game(db, game_id, game_result_str);
+                                tracing::info!("Game {} finished: {}", game_id, game_result_str);
+                            }
+
+                            // A cancelled match keeps its completed games but is not
+                            // scored or rated
+                            if cancelled() && (game_results.len() as i32) < pending.games_total {
+                                match db::cancel_match(db, &pending.id, worker_id) {
+                                    Ok(()) => tracing::info!(
+                                        "Match {} cancelled after {} of {} games",
+                                        pending.id,
+                                        game_results.len(),
+                                        pending.games_total
+                                    ),
+                                    Err(e) => {
+                                        tracing::error!(
+                                            "Failed to cancel match {}: {}",
+                                            pending.id,
+                                            e
+                                        )
+                                    }
+                                }
+                                current_match_id = None;
+                                continue;
+                            }
+
+                            // A shutdown stopped the match early: the completed games
+                            // stay saved and the match is released at the top of the loop
+                            if shutdown.load(Ordering::SeqCst)
+                                && (game_results.len() as i32) < pending.games_total
+                            {
+                                tracing::info!(
+                                    "Match {} interrupted after {} of {} games",
+                                    pending.id,
+                                    game_results.len(),
+                                    pending.games_total
+                                );
+                                continue;
+                            }
+
+                            let (white_score, black_score) = match_scores(&game_results);
+
+                            // Finish the match
+                            if let Err(e) =
+                                db::finish_match(db, &pending.id, white_score, black_score)
+                            {
+                                tracing::error!("Failed to finish match {}: {}", pending.id, e);
+                            } else {
+                                tracing::info!(
+                                    "Match {} completed: {} - {}",
+                                    pending.id,
+                                    white_score,
+                                    black_score
+                                );
+                            }
+
+                            // Update Elo ratings
+                            if let Err(e) =
+                                db::update_elo_ratings(db, &pending.id, &game_results, elo_config)
+                            {
+                                tracing::error!(
+                                    "Failed to update Elo ratings for match {}: {}",
+                                    pending.id,
+                                    e
+                                );
+                            } else {
+                                tracing::info!("Elo ratings updated for match {}", pending.id);
+                            }
+
+                            // Clear current match after successful completion
+                            current_match_id = None;
+                        }
+                        Err(e) => {
+                            tracing::error!("Match {} failed: {}", pending.id, e);
+                            // Record the attempt; the match is retried until it
+                            // runs out of attempts
+                            match db::fail_match(db, &pending.id, &e.to_string(), max_attempts) {
+                                Ok(db::FailOutcome::Requeued { attempts }) => tracing::info!(
+                                    "Match {} re-queued after attempt {}/{}",
+                                    pending.id,
+                                    attempts,
+                                    max_attempts
+                                ),
+                                Ok(db::FailOutcome::Failed { attempts }) => tracing::error!(
+                                    "Match {} failed after {} attempts",
+                                    pending.id,
+                                    attempts
+                                ),
+                                Err(db_err) => {
+                                    tracing::error!("Failed to mark match as failed: {}", db_err)
+                                }
+                            }
+                            current_match_id = None;
+                        }
+                    }
+                }
+                Ok(None) => {
+                    // No pending matches, wait before polling again
+                    std::thread::sleep(poll_interval);
+                }
+                Err(e) => {
+                    tracing::error!("Database error: {}", e);
+                    std::thread::sleep(poll_interval);
+                }
+            }
+        }
+    }
+}
+
+/// Totals the match points for `white_bot` and `black_bot`.
+///
+/// Colors alternate: in even-numbered games `white_bot` plays white, in
+/// odd-numbered games `black_bot` does.
+fn match_scores(game_results: &[db::GameResult]) -> (f64, f64) {
+    let mut white_score = 0.0;
+    let mut black_score = 0.0;
+    for game in game_results {
+        let (white, black) = match game.result.as_str() {
+            "1-0" => (1.0, 0.0),
+            "0-1" => (0.0, 1.0),
+            _ => (0.5, 0.5),
+        };
+        if game.game_num % 2 == 0 {
+            white_score += white;
+            black_score += black;
+        } else {
+            white_score += black;
+            black_score += white;
+        }
+    }
+    (white_score, black_score)
 }
 
 #[tokio::main]
@@ -43,13 +329,16 @@ async fn main() -> anyhow::Result<()> {
     tracing::info!("Database: {:?}", args.db);
     tracing::info!("Poll interval: {}ms", args.poll_interval);
     tracing::info!("Bots directory: {:?}", args.bots_dir);
+    tracing::info!("Parallel matches: {}", args.workers);
+    let elo_config = args.elo_config();
+    if let Some((bot, rating)) = &elo_config.anchor {
+        tracing::info!("Anchoring {} at {} Elo", bot, rating);
+    }
 
     let db = db::connect(&args.db)?;
     let worker_id = uuid::Uuid::new_v4().to_string();
     tracing::info!("Worker ID: {}", worker_id);
 
-    let runner = MatchRunner::new(&args.bots_dir);
-
     // Shutdown flag
     let shutdown = Arc::new(AtomicBool::new(false));
     let shutdown_clone = shutdown.clone();
@@ -61,154 +350,30 @@ async fn main() -> anyhow::Result<()> {
         shutdown_clone.store(true, Ordering::SeqCst);
     });
 
-    // Track the currently running match ID for graceful shutdown.
-    // Note: The shutdown check only runs between loop iterations, so if a match
-    // is in progress during shutdown, it will complete before the worker exits.
-    // The match ID is tracked so it can be released if shutdown occurs after
-    // claiming but before the match starts (edge case).
-    let mut current_match_id: Option<String> = None;
-
-    // Main worker loop
-    loop {
-        // Check for shutdown
-        if shutdown.load(Ordering::SeqCst) {
-            if let Some(ref match_id) = current_match_id {
-                tracing::info!("Releasing match {} due to shutdown", match_id);
-                if let Err(e) = db::release_match(&db, match_id, &worker_id) {
-                    tracing::error!("Failed to release match: {}", e);
-                }
-            }
-            break;
-        }
-
-        match db::claim_match(&db, &worker_id) {
-            Ok(Some(pending)) => {
-                // Track current match for graceful shutdown release.
-                // This is intentionally set before run_match so it can be released
-                // if a shutdown signal is received during match execution.
-                // Note: Rust warns about unused assignment because it doesn't track
-                // the value being read across loop iterations at shutdown check.
-                #[allow(unused_assignments)]
-                {
-                    current_match_id = Some(pending.id.clone());
-                }
-                tracing::info!(
-                    "Claimed match: {} ({} vs {})",
-                    pending.id,
-                    pending.white_bot,
-                    pending.black_bot
-                );
-
-                match runner.run_match(&pending) {
-                    Ok(results) => {
-                        let mut white_score = 0.0;
-                        let mut black_score = 0.0;
-                        let mut game_results = Vec::new();
-
-                        for (game_num, (game_id, result)) in results.iter().enumerate() {
-                            // Create game record
-                            if let Err(e) =
-                                db::create_game(&db, game_id, &pending.id, game_num as i32)
-                            {
-                                tracing::error!("Failed to create game {}: {}", game_id, e);
-                                continue;
-                            }
-
-                            // Insert all moves
-                            for (ply, move_record) in result.moves.iter().enumerate() {
-                                let _ = db::insert_move(
-                                    &db,
-                                    game_id,
-                                    ply as i32,
-                                    &move_record.uci,
-                                    None, // SAN not available from MoveRecord
-                                    "",   // FEN not available
-                                );
-                            }
-
-                            // Calculate scores (considering color alternation)
-                            // In even-numbered games, white_bot plays white
-                            // In odd-numbered games, black_bot plays white
-                            let game_result_str = match result.result {
-                                MatchResult::WhiteWins => {
-                                    if game_num % 2 == 0 {
-                                        white_score += 1.0;
-                                    } else {
-                                        black_score += 1.0;
-                                    }
-                                    "1-0"
-                                }
-                                MatchResult::BlackWins => {
-                                    if game_num % 2 == 0 {
-                                        black_score += 1.0;
-                                    } else {
-                                        white_score += 1.0;
-                                    }
-                                    "0-1"
-                                }
-                                MatchResult::Draw => {
-                                    white_score += 0.5;
-                                    black_score += 0.5;
-                                    "1/2-1/2"
-                                }
-                            };
-
-                            // Collect game result for Elo update
-                            game_results.push(db::GameResult {
-                                game_num: game_num as i32,
-                                result: game_result_str.to_string(),
-                            });
-
-                            let _ = db::finish_game(&db, game_id, game_result_str);
-                            tracing::info!("Game {} finished: {}", game_id, game_result_str);
-                        }
-
-                        // Finish the match
-                        if let Err(e) = db::finish_match(&db, &pending.id, white_score, black_score)
-                        {
-                            tracing::error!("Failed to finish match {}: {}", pending.id, e);
-                        } else {
-                            tracing::info!(
-                                "Match {} completed: {} - {}",
-                                pending.id,
-                                white_score,
-                                black_score
-                            );
-                        }
+    let worker = Worker {
+        db,
+        runner: MatchRunner::new(&args.bots_dir),
+        shutdown,
+        elo_config,
+        max_attempts: args.max_attempts,
+        poll_interval: Duration::from_millis(args.poll_interval),
+    };
+    let worker = Arc::new(worker);
 
-                        // Update Elo ratings
-                        if let Err(e) = db::update_elo_ratings(&db, &pending.id, &game_results) {
-                            tracing::error!(
-                                "Failed to update Elo ratings for match {}: {}",
-                                pending.id,
-                                e
-                            );
-                        } else {
-                            tracing::info!("Elo ratings updated for match {}", pending.id);
-                        }
-
-                        // Clear current match after successful completion
-                        current_match_id = None;
-                    }
-                    Err(e) => {
-                        tracing::error!("Match {} failed: {}", pending.id, e);
-                        // Mark match as failed in database
-                        if let Err(db_err) = db::fail_match(&db, &pending.id, &e.to_string()) {
-                            tracing::error!("Failed to mark match as failed: {}", db_err);
-                        }
-                        current_match_id = None;
-                    }
-                }
-            }
-            Ok(None) => {
-                // No pending matches, wait before polling again
-                tokio::time::sleep(Duration::from_millis(args.poll_interval)).await;
-            }
-            Err(e) => {
-                tracing::error!("Database error: {}", e);
-                tokio::time::sleep(Duration::from_millis(args.poll_interval)).await;
-            }
-        }
+    // Each task claims and plays its own matches under its own worker ID
+    let tasks: Vec<_> = (0..args.workers)
+        .map(|n| {
+            let worker = Arc::clone(&worker);
+            let task_id = if args.workers > 1 {
+                format!("{}-{}", worker_id, n)
+            } else {
+                worker_id.clone()
+            };
+            tokio::task::spawn_blocking(move || worker.run(&task_id))
+        })
+        .collect();
+    for task in tasks {
+        task.await?;
     }
 
     tracing::info!("Worker shutdown complete");
@@ -6,8 +6,11 @@
 
 use crate::db::PendingMatch;
 use bot_arena::game_runner::{GameError, GameResult, GameRunner};
+use bot_arena::time_control::TimeControl;
 use bot_arena::uci_client::UciClient;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 /// Executes matches between UCI chess engines.
 ///
@@ -32,15 +35,26 @@ impl MatchRunner {
         }
     }
 
-    /// Runs a complete match consisting of multiple games.
+    /// Runs a match consisting of multiple games.
     ///
-    /// Executes all games specified by the match parameters, alternating colors
-    /// between games to ensure fairness. Each game result is paired with a
-    /// unique game ID.
+    /// Executes the games specified by the match parameters, starting at game
+    /// number `first_game` so that a resumed match skips games that were
+    /// already saved. Colors alternate between games to ensure fairness.
+    /// Each game result is paired with a unique game ID.
+    ///
+    /// Once `shutdown` is set the game in progress stops after the current
+    /// move, its engines are sent `quit`, and the games completed so far are
+    /// returned. The interrupted game is not included.
+    ///
+    /// `cancelled` is checked before each game; once it returns true no
+    /// further games are started and the games completed so far are returned.
     ///
     /// # Arguments
     ///
     /// * `pending` - The match parameters including bot names, game count, and time control.
+    /// * `first_game` - Number of the first game to play.
+    /// * `shutdown` - Flag requesting that the match stop early.
+    /// * `cancelled` - Returns true once the match has been cancelled.
     ///
     /// # Returns
     ///
@@ -58,14 +72,21 @@ impl MatchRunner {
     pub fn run_match(
         &self,
         pending: &PendingMatch,
+        first_game: i32,
+        shutdown: &Arc<AtomicBool>,
+        cancelled: impl Fn() -> bool,
     ) -> Result<Vec<(String, GameResult)>, GameError> {
         let white_path = self.bots_dir.join(&pending.white_bot);
         let black_path = self.bots_dir.join(&pending.black_bot);
-        let time_control = format!("movetime {}", pending.movetime_ms);
+        let time_control = TimeControl::MoveTime(pending.movetime_ms.max(0) as u64);
 
         let mut results = Vec::new();
 
-        for game_num in 0..pending.games_total {
+        for game_num in first_game.max(0)..pending.games_total {
+            if shutdown.load(Ordering::SeqCst) || cancelled() {
+                break;
+            }
+
             let game_id = format!("{}-{}", pending.id, game_num);
 
             // Alternate colors each game for fairness
@@ -78,10 +99,14 @@ impl MatchRunner {
             let white = UciClient::spawn(w_path)?;
             let black = UciClient::spawn(b_path)?;
 
-            let mut runner = GameRunner::new(white, black, time_control.clone(), vec![])?;
+            let mut runner = GameRunner::new(white, black, time_control.clone(), vec![])?
+                .with_shutdown(Arc::clone(shutdown));
 
-            let result = runner.play_game()?;
-            results.push((game_id, result));
+            match runner.play_game() {
+                Ok(result) => results.push((game_id, result)),
+                Err(GameError::Interrupted) => break,
+                Err(e) => return Err(e),
+            }
         }
 
         Ok(results)
@@ -91,6 +116,8 @@ impl MatchRunner {
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(unix)]
+    use test_support::fake_engine;
 
     #[test]
     fn test_match_runner_new() {
@@ -117,10 +144,114 @@ mod tests {
             opening_id: None,
         };
 
-        let result = runner.run_match(&pending);
+        let result = runner.run_match(&pending, 0, &Arc::new(AtomicBool::new(false)), || false);
         assert!(result.is_err());
     }
 
+    /// A [`fake_engine`] `go` snippet that plays 1. e4 e5 and then has no
+    /// move, so every game ends after two plies. Each `go` is appended to
+    /// `log`.
+    #[cfg(unix)]
+    fn play_e4_e5(log: &std::path::Path) -> String {
+        format!(
+            "echo go >> '{}'; sleep 0.2\n\
+             case \"$position\" in\n\
+             *e7e5) echo 'bestmove 0000' ;;\n\
+             *e2e4) echo 'bestmove e7e5' ;;\n\
+             *) echo 'bestmove e2e4' ;;\n\
+             esac",
+            log.display()
+        )
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_shutdown_mid_match_returns_completed_games() {
+        let dir = tempfile::tempdir().unwrap();
+        let white_log = dir.path().join("white.log");
+        let black_log = dir.path().join("black.log");
+        fake_engine(dir.path(), "white", &play_e4_e5(&white_log));
+        fake_engine(dir.path(), "black", &play_e4_e5(&black_log));
+
+        let pending = PendingMatch {
+            id: "m".to_string(),
+            white_bot: "white".to_string(),
+            black_bot: "black".to_string(),
+            games_total: 10,
+            movetime_ms: 10,
+            opening_id: None,
+        };
+
+        // The black bot's first move in game 1 is its second `go`: game 0 is
+        // complete by then and game 1 is in progress
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&shutdown);
+        let watcher = std::thread::spawn(move || {
+            while std::fs::read_to_string(&black_log).map_or(0, |log| log.lines().count()) < 2 {
+                std::thread::sleep(std::time::Duration::from_millis(5));
+            }
+            flag.store(true, Ordering::SeqCst);
+        });
+
+        let results = MatchRunner::new(dir.path())
+            .run_match(&pending, 0, &shutdown, || false)
+            .unwrap();
+        watcher.join().unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "m-0");
+        assert_eq!(results[0].1.moves.len(), 2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_cancel_stops_match_between_games() {
+        let dir = tempfile::tempdir().unwrap();
+        let white_log = dir.path().join("white.log");
+        let black_log = dir.path().join("black.log");
+        fake_engine(dir.path(), "white", &play_e4_e5(&white_log));
+        fake_engine(dir.path(), "black", &play_e4_e5(&black_log));
+
+        let pending = PendingMatch {
+            id: "m".to_string(),
+            white_bot: "white".to_string(),
+            black_bot: "black".to_string(),
+            games_total: 10,
+            movetime_ms: 10,
+            opening_id: None,
+        };
+
+        // Cancelled once the first game is over
+        let checks = std::cell::Cell::new(0);
+        let results = MatchRunner::new(dir.path())
+            .run_match(&pending, 0, &Arc::new(AtomicBool::new(false)), || {
+                checks.set(checks.get() + 1);
+                checks.get() > 1
+            })
+            .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "m-0");
+    }
+
+    #[test]
+    fn test_run_match_starts_at_first_game() {
+        let pending = PendingMatch {
+            id: "m".to_string(),
+            white_bot: "missing".to_string(),
+            black_bot: "missing".to_string(),
+            games_total: 2,
+            movetime_ms: 10,
+            opening_id: None,
+        };
+
+        // All games are already played, so no engine is spawned
+        let results = MatchRunner::new("/nonexistent/path")
+            .run_match(&pending, 2, &Arc::new(AtomicBool::new(false)), || false)
+            .unwrap();
+        assert!(results.is_empty());
+    }
+
     #[test]
     fn test_time_control_format() {
         // Verify time control string format by checking string formatting
@@ -0,0 +1,270 @@
+//! Resign and draw adjudication for engine games.
+//!
+//! Games between engines often continue long after the outcome is clear.
+//! [`adjudicate`] inspects the scores the engines reported for each move and
+//! ends the game early once both engines agree on the result.
+
+use serde::{Deserialize, Serialize};
+
+use crate::game_runner::{MatchResult, MoveRecord};
+
+/// Full move number after which draws may be adjudicated.
+pub const DRAW_ADJUDICATION_MIN_MOVE: usize = 40;
+
+/// Centipawn value used for mate scores.
+const MATE_CP: i32 = 100_000;
+
+/// Thresholds for ending games early by adjudication.
+///
+/// Scores are compared from white's point of view, so both engines must
+/// agree on the evaluation for a rule to trigger. A move count of 0
+/// disables the corresponding rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdjudicationConfig {
+    /// Score (in centipawns) at which the losing side resigns.
+    #[serde(default = "default_resign_threshold_cp")]
+    pub resign_threshold_cp: i32,
+    /// Number of consecutive plies the score must stay past the resign threshold.
+    #[serde(default = "default_resign_moves")]
+    pub resign_moves: usize,
+    /// Maximum absolute score (in centipawns) considered a dead draw.
+    #[serde(default = "default_draw_threshold_cp")]
+    pub draw_threshold_cp: i32,
+    /// Number of consecutive plies the score must stay within the draw threshold.
+    #[serde(default = "default_draw_moves")]
+    pub draw_moves: usize,
+}
+
+fn default_resign_threshold_cp() -> i32 {
+    1000
+}
+
+fn default_resign_moves() -> usize {
+    6
+}
+
+fn default_draw_threshold_cp() -> i32 {
+    10
+}
+
+fn default_draw_moves() -> usize {
+    16
+}
+
+impl Default for AdjudicationConfig {
+    fn default() -> Self {
+        Self {
+            resign_threshold_cp: default_resign_threshold_cp(),
+            resign_moves: default_resign_moves(),
+            draw_threshold_cp: default_draw_threshold_cp(),
+            draw_moves: default_draw_moves(),
+        }
+    }
+}
+
+/// Returns the score reported for a move from white's point of view.
+///
+/// Engines report scores relative to the side to move, which is white for
+/// even plies and black for odd plies. Returns `None` if no score was reported.
+fn white_score(ply: usize, record: &MoveRecord) -> Option<i32> {
+    let info = record.search_info.as_ref()?;
+    let score = match (info.score_mate, info.score_cp) {
+        (Some(mate), _) if mate > 0 => MATE_CP - mate,
+        (Some(mate), _) => -MATE_CP - mate,
+        (None, Some(cp)) => cp,
+        (None, None) => return None,
+    };
+    Some(if ply.is_multiple_of(2) { score } else { -score })
+}
+
+/// Decides whether a game can be adjudicated after the given moves.
+///
+/// - **Resignation**: if the last `resign_moves` plies all scored at least
+///   `resign_threshold_cp` in favour of one side, that side wins.
+/// - **Draw**: once past move [`DRAW_ADJUDICATION_MIN_MOVE`], if the last
+///   `draw_moves` plies all scored within `draw_threshold_cp` of zero, the
+///   game is drawn.
+///
+/// Moves without a reported score (such as book moves) break a streak.
+pub fn adjudicate(moves: &[MoveRecord], config: &AdjudicationConfig) -> Option<MatchResult> {
+    let scores = |count: usize| -> Option<Vec<i32>> {
+        if count == 0 || moves.len() < count {
+            return None;
+        }
+        let start = moves.len() - count;
+        moves[start..]
+            .iter()
+            .enumerate()
+            .map(|(i, record)| white_score(start + i, record))
+            .collect()
+    };
+
+    if let Some(recent) = scores(config.resign_moves) {
+        if recent.iter().all(|&s| s >= config.resign_threshold_cp) {
+            return Some(MatchResult::WhiteWins);
+        }
+        if recent.iter().all(|&s| s <= -config.resign_threshold_cp) {
+            return Some(MatchResult::BlackWins);
+        }
+    }
+
+    if moves.len() >= DRAW_ADJUDICATION_MIN_MOVE * 2 {
+        if let Some(recent) = scores(config.draw_moves) {
+            if recent.iter().all(|s| s.abs() <= config.draw_threshold_cp) {
+                return Some(MatchResult::Draw);
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uci_client::SearchInfo;
+
+    /// Builds a move with a centipawn score from the mover's point of view.
+    fn scored(cp: i32) -> MoveRecord {
+        MoveRecord {
+            uci: "e2e4".to_string(),
+            search_info: Some(SearchInfo {
+                score_cp: Some(cp),
+                ..Default::default()
+            }),
+        }
+    }
+
+    fn unscored() -> MoveRecord {
+        MoveRecord {
+            uci: "e2e4".to_string(),
+            search_info: None,
+        }
+    }
+
+    /// Builds plies from scores given from white's point of view.
+    fn white_view(scores: &[i32]) -> Vec<MoveRecord> {
+        scores
+            .iter()
+            .enumerate()
+            .map(|(ply, &cp)| scored(if ply.is_multiple_of(2) { cp } else { -cp }))
+            .collect()
+    }
+
+    /// Builds `count` plies that all score `white_cp` from white's point of view.
+    fn plies(count: usize, white_cp: i32) -> Vec<MoveRecord> {
+        white_view(&vec![white_cp; count])
+    }
+
+    fn config() -> AdjudicationConfig {
+        AdjudicationConfig {
+            resign_threshold_cp: 500,
+            resign_moves: 4,
+            draw_threshold_cp: 10,
+            draw_moves: 6,
+        }
+    }
+
+    #[test]
+    fn test_default_config() {
+        let config = AdjudicationConfig::default();
+        assert_eq!(config.resign_threshold_cp, 1000);
+        assert_eq!(config.resign_moves, 6);
+        assert_eq!(config.draw_threshold_cp, 10);
+        assert_eq!(config.draw_moves, 16);
+    }
+
+    #[test]
+    fn test_resign_black_losing() {
+        let moves = plies(4, 600);
+        assert_eq!(adjudicate(&moves, &config()), Some(MatchResult::WhiteWins));
+    }
+
+    #[test]
+    fn test_resign_white_losing() {
+        let moves = plies(10, -800);
+        assert_eq!(adjudicate(&moves, &config()), Some(MatchResult::BlackWins));
+    }
+
+    #[test]
+    fn test_resign_requires_consecutive_plies() {
+        assert_eq!(adjudicate(&plies(3, 600), &config()), None);
+
+        // A ply below the threshold resets the streak
+        let mut scores = vec![600, 600, 600, 100, 600, 600, 600];
+        assert_eq!(adjudicate(&white_view(&scores), &config()), None);
+
+        scores.push(600);
+        assert_eq!(
+            adjudicate(&white_view(&scores), &config()),
+            Some(MatchResult::WhiteWins)
+        );
+    }
+
+    #[test]
+    fn test_resign_requires_both_engines_to_agree() {
+        // White thinks it is winning, black thinks it is fine
+        let moves: Vec<MoveRecord> = (0..6).map(|_| scored(600)).collect();
+        assert_eq!(adjudicate(&moves, &config()), None);
+    }
+
+    #[test]
+    fn test_resign_with_mate_scores() {
+        let mate = |n: i32| MoveRecord {
+            uci: "e2e4".to_string(),
+            search_info: Some(SearchInfo {
+                score_mate: Some(n),
+                ..Default::default()
+            }),
+        };
+        // White sees mate in 3, black sees itself getting mated
+        let moves = vec![mate(3), mate(-3), mate(2), mate(-2)];
+        assert_eq!(adjudicate(&moves, &config()), Some(MatchResult::WhiteWins));
+    }
+
+    #[test]
+    fn test_unscored_moves_break_streak() {
+        let mut moves = plies(6, -800);
+        moves[3] = unscored();
+        assert_eq!(adjudicate(&moves, &config()), None);
+    }
+
+    #[test]
+    fn test_draw_after_move_forty() {
+        let moves = plies(80, 5);
+        assert_eq!(adjudicate(&moves, &config()), Some(MatchResult::Draw));
+    }
+
+    #[test]
+    fn test_no_draw_before_move_forty() {
+        let moves = plies(79, 0);
+        assert_eq!(adjudicate(&moves, &config()), None);
+    }
+
+    #[test]
+    fn test_no_draw_when_score_leaves_threshold() {
+        let mut moves = plies(80, 0);
+        moves.push(scored(-50));
+        moves.extend(plies(5, 0));
+        assert_eq!(adjudicate(&moves, &config()), None);
+    }
+
+    #[test]
+    fn test_zero_move_count_disables_rule() {
+        let config = AdjudicationConfig {
+            resign_moves: 0,
+            draw_moves: 0,
+            ..config()
+        };
+        assert_eq!(adjudicate(&plies(10, 900), &config), None);
+        assert_eq!(adjudicate(&plies(100, 0), &config), None);
+    }
+
+    #[test]
+    fn test_config_deserialize_partial() {
+        let config: AdjudicationConfig = toml::from_str("resign_moves = 10").unwrap();
+        assert_eq!(config.resign_moves, 10);
+        assert_eq!(config.resign_threshold_cp, 1000);
+        assert_eq!(config.draw_moves, 16);
+    }
+}
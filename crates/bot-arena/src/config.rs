@@ -3,6 +3,7 @@
 //! This module provides types and functions for loading and managing
 //! arena configuration from TOML files.
 
+use crate::adjudication::AdjudicationConfig;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -33,7 +34,7 @@ pub enum ConfigError {
 pub struct BotConfig {
     /// Path to the bot executable.
     pub path: PathBuf,
-    /// Time control string (e.g., "movetime 500").
+    /// Time control string (e.g., "movetime 500", "40/300", or "5+3").
     /// Defaults to "movetime 500" if not specified.
     #[serde(default = "default_time_control")]
     pub time_control: String,
@@ -59,7 +60,7 @@ pub struct PresetConfig {
     /// Defaults to empty (use standard starting position).
     #[serde(default)]
     pub openings: Vec<String>,
-    /// Time control string for the match.
+    /// Time control string for the match (see [`crate::time_control`]).
     /// Defaults to "movetime 500" if not specified.
     #[serde(default = "default_time_control")]
     pub time_control: String,
@@ -118,6 +119,9 @@ pub struct ArenaConfig {
     /// Configuration for Stockfish analysis engine pool.
     #[serde(default)]
     pub analysis: AnalysisConfig,
+    /// Resign/draw adjudication thresholds. Games are played to the end if unset.
+    #[serde(default)]
+    pub adjudication: Option<AdjudicationConfig>,
 }
 
 impl ArenaConfig {
@@ -372,4 +376,23 @@ pool_size = 8
         assert_eq!(deserialized.pool_size, analysis.pool_size);
         assert_eq!(deserialized.stockfish_path, analysis.stockfish_path);
     }
+
+    #[test]
+    fn test_adjudication_config_section() {
+        let toml_content = r#"
+[adjudication]
+resign_threshold_cp = 800
+draw_moves = 20
+"#;
+
+        let config: ArenaConfig = toml::from_str(toml_content).unwrap();
+        let adjudication = config.adjudication.unwrap();
+        assert_eq!(adjudication.resign_threshold_cp, 800);
+        assert_eq!(adjudication.draw_moves, 20);
+        // Unspecified fields fall back to defaults
+        assert_eq!(adjudication.resign_moves, 6);
+
+        let config: ArenaConfig = toml::from_str("").unwrap();
+        assert!(config.adjudication.is_none());
+    }
 }
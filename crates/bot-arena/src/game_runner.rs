@@ -4,9 +4,15 @@
 //! between two UCI-compatible chess engines, handling the complete game loop
 //! from initialization to result determination.
 
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
 use chess_core::Color;
 use chess_engine::{Game, GameResult as EngineResult};
 
+use crate::adjudication::{adjudicate, AdjudicationConfig};
+use crate::time_control::TimeControl;
 use crate::uci_client::{SearchInfo, UciClient, UciError};
 use thiserror::Error;
 
@@ -22,6 +28,9 @@ pub enum GameError {
     /// An engine returned an invalid or illegal move.
     #[error("Invalid move: {0}")]
     InvalidMove(String),
+    /// The game was stopped early because a shutdown was requested.
+    #[error("Game interrupted by shutdown")]
+    Interrupted,
 }
 
 /// A single move with its associated search information.
@@ -79,6 +88,45 @@ pub struct GameResult {
     pub black_name: String,
     /// The detected opening, if any was recognized.
     pub opening: Option<DetectedOpening>,
+    /// How the game ended.
+    pub termination: Termination,
+}
+
+/// How a game came to an end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Termination {
+    /// The game was played out (checkmate, draw by rule, or move limit).
+    #[default]
+    Normal,
+    /// The game was ended early by resign/draw adjudication.
+    Adjudication,
+    /// An engine ran out of clock time or failed to return a move in time.
+    TimeForfeit,
+    /// An engine process exited or stopped communicating.
+    Crash,
+}
+
+impl Termination {
+    /// Returns the identifier used in JSON output (e.g. `"time_forfeit"`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Termination::Normal => "normal",
+            Termination::Adjudication => "adjudication",
+            Termination::TimeForfeit => "time_forfeit",
+            Termination::Crash => "crash",
+        }
+    }
+
+    /// Returns the value of the PGN `Termination` tag.
+    pub fn as_pgn_str(self) -> &'static str {
+        match self {
+            Termination::Normal => "normal",
+            Termination::Adjudication => "adjudication",
+            Termination::TimeForfeit => "time forfeit",
+            Termination::Crash => "abandoned",
+        }
+    }
 }
 
 /// The outcome of a chess game.
@@ -92,6 +140,52 @@ pub enum MatchResult {
     Draw,
 }
 
+impl MatchResult {
+    /// Returns the result with the colors swapped.
+    ///
+    /// Useful for reading a result from the point of view of an engine that
+    /// played black.
+    pub fn flipped(self) -> Self {
+        match self {
+            MatchResult::WhiteWins => MatchResult::BlackWins,
+            MatchResult::BlackWins => MatchResult::WhiteWins,
+            MatchResult::Draw => MatchResult::Draw,
+        }
+    }
+}
+
+/// Extra time an engine is given beyond its allotted thinking time before
+/// it forfeits the game.
+pub const MOVE_TIMEOUT_MARGIN: Duration = Duration::from_secs(2);
+
+/// How long an engine may take to finish the UCI handshake.
+pub const INIT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long an engine may take to acknowledge a new position.
+pub const SYNC_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Returns how long to wait for a `bestmove` from `side` given the `go` arguments.
+///
+/// `movetime N` and `wtime`/`btime` limits get [`MOVE_TIMEOUT_MARGIN`] added
+/// on top. Depth, node, and infinite searches have no natural bound and
+/// return `None`.
+pub fn move_timeout(go_args: &str, side: Color) -> Option<Duration> {
+    let clock_key = match side {
+        Color::White => "wtime",
+        Color::Black => "btime",
+    };
+
+    let mut tokens = go_args.split_whitespace();
+    let mut millis = None;
+    while let Some(token) = tokens.next() {
+        if token == "movetime" || token == clock_key {
+            millis = tokens.next().and_then(|v| v.parse::<u64>().ok());
+        }
+    }
+
+    millis.map(|ms| Duration::from_millis(ms) + MOVE_TIMEOUT_MARGIN)
+}
+
 /// Executes games between two UCI chess engines.
 ///
 /// `GameRunner` manages two UCI clients and coordinates game play between them,
@@ -102,7 +196,7 @@ pub enum MatchResult {
 /// ```ignore
 /// let white = UciClient::spawn("./white_engine")?;
 /// let black = UciClient::spawn("./black_engine")?;
-/// let mut runner = GameRunner::new(white, black, "movetime 500".to_string(), vec![])?;
+/// let mut runner = GameRunner::new(white, black, "5+3".parse()?, vec![])?;
 /// let result = runner.play_game()?;
 /// println!("Game result: {:?}", result.result);
 /// ```
@@ -111,10 +205,14 @@ pub struct GameRunner {
     white: UciClient,
     /// The UCI client for the black player.
     black: UciClient,
-    /// The time control string to use for move requests.
-    time_control: String,
+    /// The time control used to build move requests and run the clocks.
+    time_control: TimeControl,
     /// Opening moves to play before the game starts (in UCI notation).
     opening_moves: Vec<String>,
+    /// Optional resign/draw adjudication thresholds.
+    adjudication: Option<AdjudicationConfig>,
+    /// Flag that stops the game after the current move once set.
+    shutdown: Option<Arc<AtomicBool>>,
 }
 
 impl GameRunner {
@@ -126,43 +224,95 @@ impl GameRunner {
     ///
     /// * `white` - The UCI client for the white player
     /// * `black` - The UCI client for the black player
-    /// * `time_control` - The time control string (e.g., "movetime 500")
+    /// * `time_control` - The time control (e.g., parsed from "movetime 500" or "5+3")
     /// * `opening_moves` - Optional opening moves to play at start (in UCI notation)
     ///
     /// # Errors
     ///
-    /// Returns an error if either engine fails to initialize.
+    /// Returns an error if either engine fails to initialize, including
+    /// [`UciError::Timeout`] if it does not finish the handshake within
+    /// [`INIT_TIMEOUT`].
     pub fn new(
         mut white: UciClient,
         mut black: UciClient,
-        time_control: String,
+        time_control: TimeControl,
         opening_moves: Vec<String>,
     ) -> Result<Self, GameError> {
-        white.init()?;
-        black.init()?;
+        white.init_with_timeout(Some(INIT_TIMEOUT))?;
+        black.init_with_timeout(Some(INIT_TIMEOUT))?;
         Ok(Self {
             white,
             black,
             time_control,
             opening_moves,
+            adjudication: None,
+            shutdown: None,
         })
     }
 
+    /// Sends `setoption name <name> value <value>` to both engines.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either engine cannot be reached.
+    pub fn set_option(&mut self, name: &str, value: &str) -> Result<(), GameError> {
+        self.white.set_option(name, value)?;
+        self.black.set_option(name, value)?;
+        Ok(())
+    }
+
+    /// Enables resign/draw adjudication using the given thresholds.
+    ///
+    /// After each engine move the reported scores are checked with
+    /// [`adjudicate`], and the game ends early once a rule triggers.
+    #[must_use]
+    pub fn with_adjudication(mut self, config: AdjudicationConfig) -> Self {
+        self.adjudication = Some(config);
+        self
+    }
+
+    /// Stops the game after the current move once `shutdown` is set.
+    ///
+    /// An interrupted game sends `quit` to both engines and
+    /// [`play_game`](Self::play_game) returns [`GameError::Interrupted`].
+    #[must_use]
+    pub fn with_shutdown(mut self, shutdown: Arc<AtomicBool>) -> Self {
+        self.shutdown = Some(shutdown);
+        self
+    }
+
+    /// Returns true if a shutdown was requested through [`with_shutdown`](Self::with_shutdown).
+    fn shutdown_requested(&self) -> bool {
+        self.shutdown
+            .as_ref()
+            .is_some_and(|flag| flag.load(Ordering::SeqCst))
+    }
+
     /// Plays a complete game between the two engines.
     ///
     /// Executes the game loop, alternating moves between white and black
-    /// until the game ends (checkmate, stalemate, draw, or error).
+    /// until the game ends (checkmate, stalemate, draw, adjudication, or error).
     /// If opening moves were specified, they are played first before
     /// engines start making their own moves.
     ///
+    /// With a clocked time control each side's remaining time is charged for
+    /// every move and sent to the engines as `wtime`/`btime`. A side whose
+    /// clock runs out loses on time.
+    ///
+    /// An engine that crashes, or does not acknowledge a position within
+    /// [`SYNC_TIMEOUT`] or answer `go` within [`move_timeout`],
+    /// loses the game, and the result records a [`Termination::Crash`] or
+    /// [`Termination::TimeForfeit`].
+    ///
     /// # Returns
     ///
     /// Returns a [`GameResult`] containing the move history and outcome.
     ///
     /// # Errors
     ///
-    /// Returns an error if an engine produces an invalid move or if
-    /// UCI communication fails.
+    /// Returns an error if an engine produces an invalid move or if an opening
+    /// move is illegal, or [`GameError::Interrupted`] if a shutdown was
+    /// requested before the game finished.
     ///
     /// # Testing
     ///
@@ -188,12 +338,17 @@ impl GameRunner {
             });
         }
 
+        let mut adjudicated: Option<MatchResult> = None;
+        let mut forfeit: Option<(MatchResult, Termination)> = None;
+        let mut clock = self.time_control.start_clock();
+
         loop {
             if game.is_game_over() {
                 break;
             }
 
-            let current = if game.position().side_to_move == Color::White {
+            let side = game.position().side_to_move;
+            let current = if side == Color::White {
                 &mut self.white
             } else {
                 &mut self.black
@@ -201,8 +356,35 @@ impl GameRunner {
 
             // Extract UCI moves for position command
             let uci_moves: Vec<String> = moves.iter().map(|m| m.uci.clone()).collect();
-            current.set_position(&uci_moves)?;
-            let (bestmove, search_info) = current.go(&self.time_control)?;
+            let go_args = self.time_control.go_args(&clock, side);
+            let timeout = move_timeout(&go_args, side);
+            let opponent_wins = match side {
+                Color::White => MatchResult::BlackWins,
+                Color::Black => MatchResult::WhiteWins,
+            };
+
+            // The clock starts once the engine has taken the position
+            let synced = current.set_position_with_timeout(&uci_moves, Some(SYNC_TIMEOUT));
+            let started = Instant::now();
+            let response = synced.and_then(|()| current.go_with_timeout(&go_args, timeout));
+
+            // A crashed or unresponsive engine loses the game
+            let (bestmove, search_info) = match response {
+                Ok(response) => response,
+                Err(e) => {
+                    let termination = match e {
+                        UciError::Timeout => Termination::TimeForfeit,
+                        _ => Termination::Crash,
+                    };
+                    forfeit = Some((opponent_wins, termination));
+                    break;
+                }
+            };
+
+            if !clock.record_move(&self.time_control, side, started.elapsed()) {
+                forfeit = Some((opponent_wins, Termination::TimeForfeit));
+                break;
+            }
 
             if bestmove.is_empty() || bestmove == "(none)" || bestmove == "0000" {
                 break;
@@ -212,21 +394,44 @@ impl GameRunner {
                 return Err(GameError::InvalidMove(bestmove));
             }
 
-            moves.push(MoveRecord {
-                uci: bestmove,
-                search_info,
-            });
+            // Record the move as standard UCI, so that castling written as
+            // king-takes-rook reaches the opponent as e.g. `e1g1`
+            let uci = game
+                .move_history()
+                .last()
+                .map_or(bestmove, |m| m.mov.to_uci());
+            moves.push(MoveRecord { uci, search_info });
+
+            if let Some(config) = &self.adjudication {
+                adjudicated = adjudicate(&moves, config);
+                if adjudicated.is_some() {
+                    break;
+                }
+            }
 
             // Safety limit to prevent infinite games
             if moves.len() > 500 {
                 break;
             }
+
+            if self.shutdown_requested() {
+                let _ = self.white.quit();
+                let _ = self.black.quit();
+                return Err(GameError::Interrupted);
+            }
         }
 
-        let result = match game.result() {
-            Some(EngineResult::WhiteWins) => MatchResult::WhiteWins,
-            Some(EngineResult::BlackWins) => MatchResult::BlackWins,
-            Some(EngineResult::Draw(_)) | None => MatchResult::Draw,
+        let (result, termination) = if let Some(forfeit) = forfeit {
+            forfeit
+        } else if let Some(result) = adjudicated {
+            (result, Termination::Adjudication)
+        } else {
+            let result = match game.result() {
+                Some(EngineResult::WhiteWins) => MatchResult::WhiteWins,
+                Some(EngineResult::BlackWins) => MatchResult::BlackWins,
+                Some(EngineResult::Draw(_)) | None => MatchResult::Draw,
+            };
+            (result, Termination::Normal)
         };
 
         Ok(GameResult {
@@ -235,6 +440,7 @@ impl GameRunner {
             white_name,
             black_name,
             opening: None, // Opening detection is done separately after game creation
+            termination,
         })
     }
 }
@@ -275,6 +481,13 @@ mod tests {
         assert_eq!(format!("{:?}", MatchResult::Draw), "Draw");
     }
 
+    #[test]
+    fn test_match_result_flipped() {
+        assert_eq!(MatchResult::WhiteWins.flipped(), MatchResult::BlackWins);
+        assert_eq!(MatchResult::BlackWins.flipped(), MatchResult::WhiteWins);
+        assert_eq!(MatchResult::Draw.flipped(), MatchResult::Draw);
+    }
+
     #[test]
     fn test_match_result_equality() {
         assert_eq!(MatchResult::WhiteWins, MatchResult::WhiteWins);
@@ -298,6 +511,7 @@ mod tests {
                 },
             ],
             result: MatchResult::Draw,
+            termination: Termination::Normal,
             white_name: "Engine A".to_string(),
             black_name: "Engine B".to_string(),
             opening: None,
@@ -321,6 +535,7 @@ mod tests {
                 nodes: Some(1234567),
                 time_ms: Some(1500),
                 pv: vec!["e2e4".to_string(), "e7e5".to_string()],
+                ..Default::default()
             }),
         };
 
@@ -342,6 +557,7 @@ mod tests {
                 nodes: None,
                 time_ms: None,
                 pv: vec![],
+                ..Default::default()
             }),
         };
 
@@ -374,6 +590,7 @@ mod tests {
                 nodes: Some(100000),
                 time_ms: Some(200),
                 pv: vec!["d2d4".to_string(), "d7d5".to_string()],
+                ..Default::default()
             }),
         };
         let cloned = record.clone();
@@ -403,6 +620,7 @@ mod tests {
                 search_info: None,
             }],
             result: MatchResult::WhiteWins,
+            termination: Termination::Normal,
             white_name: "Stockfish".to_string(),
             black_name: "Komodo".to_string(),
             opening: None,
@@ -417,6 +635,7 @@ mod tests {
         let result = GameResult {
             moves: vec![],
             result: MatchResult::BlackWins,
+            termination: Termination::Normal,
             white_name: "Engine1".to_string(),
             black_name: "Engine2".to_string(),
             opening: None,
@@ -429,6 +648,7 @@ mod tests {
         let result = GameResult {
             moves: vec![],
             result: MatchResult::Draw,
+            termination: Termination::Normal,
             white_name: "A".to_string(),
             black_name: "B".to_string(),
             opening: None,
@@ -445,6 +665,7 @@ mod tests {
                 search_info: None,
             }],
             result: MatchResult::Draw,
+            termination: Termination::Normal,
             white_name: "W".to_string(),
             black_name: "B".to_string(),
             opening: None,
@@ -664,6 +885,7 @@ mod tests {
                 search_info: None,
             }],
             result: MatchResult::Draw,
+            termination: Termination::Normal,
             white_name: "White".to_string(),
             black_name: "Black".to_string(),
             opening: Some(DetectedOpening {
@@ -677,4 +899,110 @@ mod tests {
         let opening = result.opening.as_ref().unwrap();
         assert_eq!(opening.id, "french-defense");
     }
+
+    #[test]
+    fn test_termination_strings() {
+        assert_eq!(Termination::Normal.as_str(), "normal");
+        assert_eq!(Termination::TimeForfeit.as_str(), "time_forfeit");
+        assert_eq!(Termination::Crash.as_str(), "crash");
+        assert_eq!(Termination::TimeForfeit.as_pgn_str(), "time forfeit");
+        assert_eq!(
+            serde_json::to_string(&Termination::TimeForfeit).unwrap(),
+            "\"time_forfeit\""
+        );
+    }
+
+    #[test]
+    fn test_move_timeout() {
+        assert_eq!(
+            move_timeout("movetime 500", Color::White),
+            Some(Duration::from_millis(500) + MOVE_TIMEOUT_MARGIN)
+        );
+        assert_eq!(
+            move_timeout("wtime 60000 btime 30000 winc 1000", Color::Black),
+            Some(Duration::from_millis(30000) + MOVE_TIMEOUT_MARGIN)
+        );
+        assert_eq!(move_timeout("depth 10", Color::White), None);
+        assert_eq!(move_timeout("infinite", Color::White), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_crashed_engine_loses() {
+        use test_support::fake_engine;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mover = fake_engine(dir.path(), "mover", "echo 'bestmove e2e4'");
+        let crasher = fake_engine(dir.path(), "crasher", "exit 1");
+
+        let white = UciClient::spawn(&mover).unwrap();
+        let black = UciClient::spawn(&crasher).unwrap();
+        let mut runner = GameRunner::new(white, black, TimeControl::MoveTime(10), vec![]).unwrap();
+
+        let result = runner.play_game().unwrap();
+        assert_eq!(result.result, MatchResult::WhiteWins);
+        assert_eq!(result.termination, Termination::Crash);
+        assert_eq!(result.moves.len(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_unresponsive_engine_forfeits_on_time() {
+        use test_support::fake_engine;
+
+        let dir = tempfile::tempdir().unwrap();
+        let sleeper = fake_engine(dir.path(), "sleeper", ":");
+        let mover = fake_engine(dir.path(), "mover", "echo 'bestmove e7e5'");
+
+        let white = UciClient::spawn(&sleeper).unwrap();
+        let black = UciClient::spawn(&mover).unwrap();
+        let mut runner = GameRunner::new(white, black, TimeControl::MoveTime(10), vec![]).unwrap();
+
+        let result = runner.play_game().unwrap();
+        assert_eq!(result.result, MatchResult::BlackWins);
+        assert_eq!(result.termination, Termination::TimeForfeit);
+        assert!(result.moves.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_slow_engine_flags() {
+        use test_support::fake_engine;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mover = fake_engine(dir.path(), "mover", "echo 'bestmove e2e4'");
+        let slow = fake_engine(dir.path(), "slow", "sleep 0.3; echo 'bestmove e7e5'");
+
+        let white = UciClient::spawn(&mover).unwrap();
+        let black = UciClient::spawn(&slow).unwrap();
+        let time_control = TimeControl::Increment {
+            base_ms: 100,
+            increment_ms: 0,
+        };
+        let mut runner = GameRunner::new(white, black, time_control, vec![]).unwrap();
+
+        let result = runner.play_game().unwrap();
+        assert_eq!(result.result, MatchResult::WhiteWins);
+        assert_eq!(result.termination, Termination::TimeForfeit);
+        assert_eq!(result.moves.len(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_shutdown_interrupts_game_after_current_move() {
+        use test_support::fake_engine;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mover = fake_engine(dir.path(), "mover", "echo 'bestmove e2e4'");
+        let other = fake_engine(dir.path(), "other", "echo 'bestmove e7e5'");
+
+        let white = UciClient::spawn(&mover).unwrap();
+        let black = UciClient::spawn(&other).unwrap();
+        let shutdown = Arc::new(AtomicBool::new(true));
+        let mut runner = GameRunner::new(white, black, TimeControl::MoveTime(10), vec![])
+            .unwrap()
+            .with_shutdown(shutdown);
+
+        assert!(matches!(runner.play_game(), Err(GameError::Interrupted)));
+    }
 }
@@ -5,6 +5,8 @@
 //! This is useful for analysis, machine learning, and detailed game review.
 
 use crate::game_runner::{DetectedOpening, GameResult, MatchResult, MoveRecord};
+use crate::uci_client::SearchInfo;
+use chess_engine::Game;
 use chrono::Utc;
 use serde::Serialize;
 use std::path::Path;
@@ -23,20 +25,61 @@ struct GameJson<'a> {
     black: &'a str,
     /// Game result: "white", "black", or "draw".
     result: &'a str,
+    /// How the game ended: "normal", "adjudication", "time_forfeit", or "crash".
+    termination: &'a str,
     /// Detected opening information, if recognized.
     #[serde(skip_serializing_if = "Option::is_none")]
     opening: Option<&'a DetectedOpening>,
-    /// Complete move list with search information.
-    moves: &'a [MoveRecord],
+    /// Complete move list with notation and search information.
+    moves: Vec<MoveJson<'a>>,
     /// ISO 8601 timestamp when the file was created.
     created_at: String,
 }
 
+/// JSON representation of a single move.
+#[derive(Serialize)]
+struct MoveJson<'a> {
+    /// The move in UCI notation.
+    uci: &'a str,
+    /// The move in Standard Algebraic Notation, or null if it could not be
+    /// replayed.
+    san: Option<String>,
+    /// FEN of the position after the move, or null if it could not be
+    /// replayed.
+    fen_after: Option<String>,
+    /// Search information from the engine that played the move.
+    search_info: Option<&'a SearchInfo>,
+}
+
+/// Replays `moves` from the starting position to attach SAN and FEN.
+///
+/// Replay stops at the first move that is not legal, leaving `san` and
+/// `fen_after` empty for it and all later moves.
+fn move_entries(moves: &[MoveRecord]) -> Vec<MoveJson<'_>> {
+    let mut game = Game::new();
+    let mut replaying = true;
+    moves
+        .iter()
+        .map(|record| {
+            replaying = replaying && game.make_move_uci(&record.uci).is_ok();
+            let san = replaying
+                .then(|| game.move_history().last().map(|m| m.san.clone()))
+                .flatten();
+            MoveJson {
+                uci: &record.uci,
+                san,
+                fen_after: replaying.then(|| game.to_fen()),
+                search_info: record.search_info.as_ref(),
+            }
+        })
+        .collect()
+}
+
 /// Writes a completed game result to a JSON file with full search information.
 ///
 /// Creates a JSON file containing the game metadata (id, players, result) and
-/// the complete move list with UCI search information (depth, score, nodes, PV)
-/// for each move.
+/// the complete move list with SAN, the FEN after each move, and UCI search
+/// information (depth, score, nodes, PV).
 ///
 /// # Arguments
 ///
@@ -58,9 +101,12 @@ struct GameJson<'a> {
 ///   "white": "Engine A",
 ///   "black": "Engine B",
 ///   "result": "white",
+///   "termination": "normal",
 ///   "moves": [
 ///     {
 ///       "uci": "e2e4",
+///       "san": "e4",
+///       "fen_after": "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
 ///       "search_info": {
 ///         "depth": 20,
 ///         "score_cp": 35,
@@ -102,8 +148,9 @@ pub fn write_json<P: AsRef<Path>>(path: P, id: &str, result: &GameResult) -> std
         white: &result.white_name,
         black: &result.black_name,
         result: result_str,
+        termination: result.termination.as_str(),
         opening: result.opening.as_ref(),
-        moves: &result.moves,
+        moves: move_entries(&result.moves),
         created_at: Utc::now().to_rfc3339(),
     };
 
@@ -115,7 +162,8 @@ pub fn write_json<P: AsRef<Path>>(path: P, id: &str, result: &GameResult) -> std
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::uci_client::SearchInfo;
+    use crate::game_runner::Termination;
+    use chess_engine::Position;
     use std::fs;
     use std::io::Read;
 
@@ -135,6 +183,7 @@ mod tests {
                         nodes: Some(1234567),
                         time_ms: Some(1000),
                         pv: vec!["e2e4".to_string(), "e7e5".to_string()],
+                        ..Default::default()
                     }),
                 },
                 MoveRecord {
@@ -146,10 +195,12 @@ mod tests {
                         nodes: Some(987654),
                         time_ms: Some(950),
                         pv: vec!["e7e5".to_string(), "g1f3".to_string()],
+                        ..Default::default()
                     }),
                 },
             ],
             result: MatchResult::WhiteWins,
+            termination: Termination::Normal,
             white_name: "TestWhite".to_string(),
             black_name: "TestBlack".to_string(),
             opening: None,
@@ -248,6 +299,7 @@ mod tests {
                 search_info: None,
             }],
             result: MatchResult::BlackWins,
+            termination: Termination::Normal,
             white_name: "White".to_string(),
             black_name: "Black".to_string(),
             opening: None,
@@ -276,6 +328,7 @@ mod tests {
         let result = GameResult {
             moves: vec![],
             result: MatchResult::Draw,
+            termination: Termination::Normal,
             white_name: "White".to_string(),
             black_name: "Black".to_string(),
             opening: None,
@@ -308,6 +361,7 @@ mod tests {
                 search_info: None,
             }],
             result: MatchResult::WhiteWins,
+            termination: Termination::Normal,
             white_name: "White".to_string(),
             black_name: "Black".to_string(),
             opening: None,
@@ -343,9 +397,11 @@ mod tests {
                     nodes: Some(500000),
                     time_ms: Some(2000),
                     pv: vec!["d1h5".to_string(), "g7g6".to_string(), "h5f7".to_string()],
+                    ..Default::default()
                 }),
             }],
             result: MatchResult::WhiteWins,
+            termination: Termination::Normal,
             white_name: "White".to_string(),
             black_name: "Black".to_string(),
             opening: None,
@@ -396,6 +452,7 @@ mod tests {
                 },
             ],
             result: MatchResult::WhiteWins,
+            termination: Termination::Normal,
             white_name: "Minimax".to_string(),
             black_name: "Random".to_string(),
             opening: Some(DetectedOpening {
@@ -436,6 +493,7 @@ mod tests {
                 search_info: None,
             }],
             result: MatchResult::Draw,
+            termination: Termination::Normal,
             white_name: "White".to_string(),
             black_name: "Black".to_string(),
             opening: None,
@@ -457,4 +515,67 @@ mod tests {
 
         fs::remove_file(&json_path).ok();
     }
+
+    #[test]
+    fn test_write_json_termination() {
+        let temp_dir = std::env::temp_dir();
+        let path = temp_dir.join("test_game_termination.json");
+
+        let result = GameResult {
+            moves: vec![MoveRecord {
+                uci: "e2e4".to_string(),
+                search_info: None,
+            }],
+            result: MatchResult::WhiteWins,
+            white_name: "Engine A".to_string(),
+            black_name: "Engine B".to_string(),
+            opening: None,
+            termination: Termination::Crash,
+        };
+
+        write_json(&path, "termination-id", &result).expect("Failed to write JSON");
+
+        let content = fs::read_to_string(&path).expect("Failed to read file");
+        let parsed: serde_json::Value = serde_json::from_str(&content).expect("Invalid JSON");
+        assert_eq!(parsed["termination"], "crash");
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_write_json_moves_have_san_and_fen() {
+        let json_path = std::env::temp_dir().join("test_game_san_fen.json");
+        let moves = ["e2e4", "e7e5", "g1f3", "b8c6", "f1b5"];
+        let result = GameResult {
+            moves: moves
+                .iter()
+                .map(|uci| MoveRecord {
+                    uci: uci.to_string(),
+                    search_info: None,
+                })
+                .collect(),
+            result: MatchResult::Draw,
+            termination: Termination::Normal,
+            white_name: "W".to_string(),
+            black_name: "B".to_string(),
+            opening: None,
+        };
+
+        write_json(&json_path, "san-fen", &result).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&json_path).unwrap()).unwrap();
+        fs::remove_file(&json_path).ok();
+
+        let entries = json["moves"].as_array().unwrap();
+        assert_eq!(entries.len(), moves.len());
+        for entry in entries {
+            assert!(!entry["san"].as_str().unwrap().is_empty());
+            Position::from_fen(entry["fen_after"].as_str().unwrap()).unwrap();
+        }
+        assert_eq!(entries[4]["san"], "Bb5");
+        assert_eq!(
+            entries[0]["fen_after"],
+            "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"
+        );
+    }
 }
@@ -7,13 +7,23 @@
 //!
 //! - [`uci_client`] - UCI protocol client for communicating with chess engines
 //! - [`game_runner`] - Game execution logic for running matches
+//! - [`match_runner`] - Running the games of a match in parallel
+//! - [`adjudication`] - Early resign/draw adjudication based on engine scores
+//! - [`time_control`] - Time control parsing and game clocks
+//! - [`sprt`] - Sequential probability ratio test for stopping matches early
+//! - [`pentanomial`] - Pentanomial statistics for color-reversed game pairs
 //! - [`storage`] - SQLite storage for game results and statistics
 //! - [`pgn`] - PGN file generation
 //! - [`json_output`] - JSON file generation with search information
 
+pub mod adjudication;
 pub mod config;
 pub mod game_runner;
 pub mod json_output;
+pub mod match_runner;
+pub mod pentanomial;
 pub mod pgn;
+pub mod sprt;
 pub mod storage;
+pub mod time_control;
 pub mod uci_client;
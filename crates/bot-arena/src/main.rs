@@ -1,17 +1,22 @@
-mod config;
-mod game_runner;
-mod json_output;
-mod pgn;
-mod storage;
-mod uci_client;
-
-use chess_analysis::{AnalysisConfig, GameAnalysis, GameAnalyzer, MoveInput};
-use chess_openings::{builtin::builtin_openings, OpeningDatabase};
+use bot_arena::{
+    config, game_runner, json_output, match_runner, pentanomial, pgn, sprt, storage, time_control,
+    uci_client,
+};
+
+use chess_analysis::{AnalysisConfig, AnalysisLimit, GameAnalysis, GameAnalyzer, MoveInput};
+use chess_openings::{builtin::builtin_openings, Opening, OpeningDatabase};
 use clap::{Parser, Subcommand};
 use config::ArenaConfig;
-use game_runner::{detect_opening, GameRunner, MatchResult};
+use game_runner::{detect_opening, GameRunner, MatchResult, Termination};
+use pentanomial::Pentanomial;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use serde::Deserialize;
+use sprt::{Sprt, SprtDecision};
+use std::collections::HashMap;
+use std::ops::ControlFlow;
 use storage::Storage;
+use time_control::TimeControl;
 use uci_client::UciClient;
 
 #[derive(Parser)]
@@ -39,6 +44,22 @@ enum Commands {
         /// Opening ID to use (e.g., "italian-game", "sicilian-najdorf")
         #[arg(short, long)]
         opening: Option<String>,
+        /// Number of games to play in parallel
+        #[arg(short = 'j', long, default_value = "1")]
+        concurrency: usize,
+        /// Stop early once an SPRT decides between two Elo hypotheses, given as
+        /// "elo0,elo1" or "elo0,elo1,alpha,beta" (e.g. "0,5")
+        #[arg(long, value_parser = parse_sprt)]
+        sprt: Option<Sprt>,
+        /// Play games as color-reversed pairs and report pentanomial statistics;
+        /// without --opening, each pair starts from its own builtin opening
+        #[arg(long)]
+        pairs: bool,
+        /// Seed for reproducible runs: without --opening, each game (or pair)
+        /// starts from a builtin opening drawn with this seed, and the seed is
+        /// passed to the bots through `setoption name Seed`
+        #[arg(long)]
+        seed: Option<u64>,
     },
     /// Analyze a game with Stockfish
     Analyze {
@@ -51,9 +72,18 @@ enum Commands {
         /// Analysis depth
         #[arg(long, default_value = "15")]
         depth: u32,
+        /// Search a fixed number of nodes per position instead of a depth
+        #[arg(long, conflicts_with = "movetime")]
+        nodes: Option<u64>,
+        /// Search each position for this many milliseconds instead of a depth
+        #[arg(long)]
+        movetime: Option<u64>,
         /// Number of opening book moves to skip
         #[arg(long, default_value = "0")]
         book_moves: usize,
+        /// Directory with Syzygy tablebase files for exact endgame evaluation
+        #[arg(long)]
+        tb_path: Option<std::path::PathBuf>,
     },
     /// List and search chess openings
     Openings {
@@ -69,6 +99,28 @@ enum Commands {
     },
 }
 
+/// Parses `elo0,elo1[,alpha,beta]` for the `--sprt` option.
+fn parse_sprt(s: &str) -> Result<Sprt, String> {
+    let values = s
+        .split(',')
+        .map(|v| v.trim().parse::<f64>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| format!("invalid number in '{}'", s))?;
+
+    let (elo0, elo1, alpha, beta) = match values[..] {
+        [elo0, elo1] => (elo0, elo1, 0.05, 0.05),
+        [elo0, elo1, alpha, beta] => (elo0, elo1, alpha, beta),
+        _ => return Err(format!("expected elo0,elo1[,alpha,beta] but got '{}'", s)),
+    };
+    if elo1 <= elo0 {
+        return Err("elo1 must be greater than elo0".to_string());
+    }
+    if !(0.0..0.5).contains(&alpha) || !(0.0..0.5).contains(&beta) || alpha == 0.0 || beta == 0.0 {
+        return Err("alpha and beta must be between 0 and 0.5".to_string());
+    }
+    Ok(Sprt::new(elo0, elo1).with_error_rates(alpha, beta))
+}
+
 fn main() {
     let cli = Cli::parse();
     let config = ArenaConfig::load().unwrap_or_default();
@@ -84,6 +136,10 @@ fn main() {
             games,
             preset,
             opening,
+            concurrency,
+            sprt,
+            pairs,
+            seed,
         } => {
             let white_path = config
                 .get_bot(&white)
@@ -113,6 +169,14 @@ fn main() {
                 )
             };
 
+            let time_control: TimeControl = match time_control.parse() {
+                Ok(tc) => tc,
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            };
+
             // Look up opening if specified
             let opening_moves: Vec<String> = if let Some(ref opening_id) = opening {
                 let db = OpeningDatabase::with_openings(builtin_openings());
@@ -143,82 +207,232 @@ fn main() {
                 .ensure_bot(&black, Some(black_path.to_str().unwrap_or("")))
                 .ok();
 
-            println!("Running {} games: {} vs {}", games, white, black);
+            // Each pair needs both colors
+            let games = if pairs && !games.is_multiple_of(2) {
+                println!("Playing {} games to complete the last pair", games + 1);
+                games + 1
+            } else {
+                games
+            };
 
-            let mut white_wins = 0;
-            let mut black_wins = 0;
-            let mut draws = 0;
+            // In pair mode the second game of each pair swaps colors
+            let swapped = |game: u32| pairs && game.is_multiple_of(2);
 
-            for i in 1..=games {
-                let white_client =
-                    UciClient::spawn(&white_path).expect("Failed to spawn white engine");
-                let black_client =
-                    UciClient::spawn(&black_path).expect("Failed to spawn black engine");
+            if concurrency > 1 {
+                println!(
+                    "Running {} games: {} vs {} ({} at a time)",
+                    games, white, black, concurrency
+                );
+            } else {
+                println!("Running {} games: {} vs {}", games, white, black);
+            }
+
+            let db = OpeningDatabase::with_openings(builtin_openings());
+
+            // Without a fixed opening, every pair gets its own opening, and so
+            // does every game given a seed. They are drawn up front so the
+            // sequence does not depend on the order in which concurrent games
+            // finish
+            let seeded_openings: Vec<Vec<String>> =
+                match opening_seed(opening.is_some(), seed, pairs) {
+                    Some(seed) => {
+                        let draws = if pairs { games / 2 } else { games };
+                        println!("Selecting openings with seed {}", seed);
+                        select_openings(&db, draws as usize, seed)
+                            .into_iter()
+                            .map(|o| o.moves.clone())
+                            .collect()
+                    }
+                    None => Vec::new(),
+                };
+            let opening_for = |game: u32| {
+                let index = if pairs { (game - 1) / 2 } else { game - 1 };
+                seeded_openings
+                    .get(index as usize)
+                    .unwrap_or(&opening_moves)
+                    .clone()
+            };
+
+            let play = |game: u32| {
+                let (white_name, black_name, w_path, b_path) = if swapped(game) {
+                    (&black, &white, &black_path, &white_path)
+                } else {
+                    (&white, &black, &white_path, &black_path)
+                };
+                let white_client = UciClient::spawn(w_path)?;
+                let black_client = UciClient::spawn(b_path)?;
 
                 let mut runner = GameRunner::new(
                     white_client,
                     black_client,
                     time_control.clone(),
-                    opening_moves.clone(),
-                )
-                .expect("Failed to initialize game");
-
-                match runner.play_game() {
-                    Ok(mut result) => {
-                        // Set bot names from config
-                        result.white_name = white.clone();
-                        result.black_name = black.clone();
-
-                        // Detect opening from game moves
-                        let db = OpeningDatabase::with_openings(builtin_openings());
-                        result.opening = detect_opening(&result.moves, &db);
-
-                        match result.result {
-                            MatchResult::WhiteWins => white_wins += 1,
-                            MatchResult::BlackWins => black_wins += 1,
-                            MatchResult::Draw => draws += 1,
-                        }
-
-                        // Save game to database
-                        let game_id = storage
-                            .save_game(&result)
-                            .unwrap_or_else(|_| uuid::Uuid::new_v4().to_string());
-
-                        // Save PGN file
-                        let date = chrono::Utc::now().format("%Y-%m-%d").to_string();
-                        let pgn_dir = format!("data/games/{}", date);
-                        if let Err(e) = std::fs::create_dir_all(&pgn_dir) {
-                            eprintln!("Warning: Failed to create PGN directory {}: {}", pgn_dir, e);
-                        }
-                        let pgn_path = format!("{}/{}.pgn", pgn_dir, game_id);
-                        if let Err(e) = pgn::write_pgn(&pgn_path, &result) {
-                            eprintln!("Warning: Failed to save PGN file: {}", e);
-                        }
-
-                        // Save JSON file with search info
-                        let json_path = format!("{}/{}.json", pgn_dir, game_id);
-                        if let Err(e) = json_output::write_json(&json_path, &game_id, &result) {
-                            eprintln!("Warning: Failed to write JSON: {}", e);
-                        }
+                    opening_for(game),
+                )?;
+                if let Some(adjudication) = config.adjudication {
+                    runner = runner.with_adjudication(adjudication);
+                }
+                if let Some(seed) = seed {
+                    // A different seed per game keeps random bots from
+                    // repeating the same game
+                    runner.set_option("Seed", &bot_seed(seed, game).to_string())?;
+                }
 
-                        println!(
-                            "Game {}: {:?} ({} moves)",
-                            i,
-                            result.result,
-                            result.moves.len()
-                        );
-                    }
+                let mut result = runner.play_game()?;
+
+                // Set bot names from config
+                result.white_name = white_name.clone();
+                result.black_name = black_name.clone();
+
+                // Detect opening from game moves
+                result.opening = detect_opening(&result.moves, &db);
+                Ok(result)
+            };
+
+            let mut sprt = sprt;
+            let mut failed = 0;
+            let mut unpaired: HashMap<u32, MatchResult> = HashMap::new();
+            let mut completed_pairs = Vec::new();
+            // Wins, draws and losses of the first engine, whichever color it played
+            let (mut wins, mut draws, mut losses) = (0u32, 0u32, 0u32);
+
+            // Games are saved on this thread as they finish
+            let tally = match_runner::run_match(games, concurrency, play, |i, outcome| {
+                let result = match outcome {
+                    Ok(result) => result,
                     Err(e) => {
                         eprintln!("Game {} error: {}", i, e);
+                        failed += 1;
+                        return ControlFlow::Continue(());
                     }
+                };
+
+                // Save game to database
+                let game_id = storage
+                    .save_game(result)
+                    .unwrap_or_else(|_| uuid::Uuid::new_v4().to_string());
+
+                // Save PGN file
+                let date = chrono::Utc::now().format("%Y-%m-%d").to_string();
+                let pgn_dir = format!("data/games/{}", date);
+                if let Err(e) = std::fs::create_dir_all(&pgn_dir) {
+                    eprintln!("Warning: Failed to create PGN directory {}: {}", pgn_dir, e);
                 }
-            }
+                let pgn_path = format!("{}/{}.pgn", pgn_dir, game_id);
+                if let Err(e) = pgn::write_pgn(&pgn_path, result) {
+                    eprintln!("Warning: Failed to save PGN file: {}", e);
+                }
+
+                // Save JSON file with search info
+                let json_path = format!("{}/{}.json", pgn_dir, game_id);
+                if let Err(e) = json_output::write_json(&json_path, &game_id, result) {
+                    eprintln!("Warning: Failed to write JSON: {}", e);
+                }
+
+                if result.termination == Termination::Normal {
+                    println!(
+                        "Game {}: {:?} ({} moves)",
+                        i,
+                        result.result,
+                        result.moves.len()
+                    );
+                } else {
+                    println!(
+                        "Game {}: {:?} by {} ({} moves)",
+                        i,
+                        result.result,
+                        result.termination.as_str(),
+                        result.moves.len()
+                    );
+                }
+
+                let first_engine_result = if swapped(i) {
+                    result.result.flipped()
+                } else {
+                    result.result
+                };
+                match first_engine_result {
+                    MatchResult::WhiteWins => wins += 1,
+                    MatchResult::Draw => draws += 1,
+                    MatchResult::BlackWins => losses += 1,
+                }
+
+                if pairs {
+                    // Games 2k-1 and 2k form pair k, with the first engine white in game 2k-1
+                    if let Some(other) = unpaired.remove(&i.div_ceil(2)) {
+                        let pair = if swapped(i) {
+                            (other, result.result)
+                        } else {
+                            (result.result, other)
+                        };
+                        completed_pairs.push(pair);
+                    } else {
+                        unpaired.insert(i.div_ceil(2), result.result);
+                    }
+                }
+
+                // Stop the session once the SPRT reaches a decision
+                if let Some(sprt) = sprt.as_mut() {
+                    let decision = sprt.update(first_engine_result);
+                    if decision != SprtDecision::Continue {
+                        let (lower, upper) = sprt.bounds();
+                        println!(
+                            "SPRT: {:?} after {} games (LLR {:.2}, bounds [{:.2}, {:.2}])",
+                            decision,
+                            sprt.games(),
+                            sprt.llr(),
+                            lower,
+                            upper
+                        );
+                        return ControlFlow::Break(());
+                    }
+                }
+                ControlFlow::Continue(())
+            });
 
-            // Print session results
+            // Print session results for the first engine, as the SPRT counts them
             println!(
-                "\nSession Results: W:{} D:{} L:{}",
-                white_wins, draws, black_wins
+                "\nSession Results ({} games, {} W:{} D:{} L:{})",
+                tally.total(),
+                white,
+                wins,
+                draws,
+                losses
             );
+            if failed > 0 {
+                println!("{} games failed to complete", failed);
+            }
+            if pairs {
+                let pentanomial = Pentanomial::from_pairs(&completed_pairs);
+                let [p0, p1, p2, p3, p4] = pentanomial.counts;
+                println!(
+                    "Pentanomial ({} pairs, 0/0.5/1/1.5/2 points for {}): [{}, {}, {}, {}, {}]",
+                    pentanomial.pairs(),
+                    white,
+                    p0,
+                    p1,
+                    p2,
+                    p3,
+                    p4
+                );
+                if let Some(estimate) = pentanomial.elo_estimate() {
+                    println!(
+                        "Elo difference: {:+.1} +/- {:.1} (95%)",
+                        estimate.elo, estimate.error
+                    );
+                }
+            }
+            if let Some(sprt) = &sprt {
+                if sprt.decision() == SprtDecision::Continue {
+                    let (wins, draws, losses) = sprt.counts();
+                    println!(
+                        "SPRT: no decision after W:{} D:{} L:{} (LLR {:.2})",
+                        wins,
+                        draws,
+                        losses,
+                        sprt.llr()
+                    );
+                }
+            }
 
             // Print cumulative stats from database
             if let Ok((total_games, wins, db_draws, losses)) = storage.get_stats(&white) {
@@ -238,9 +452,17 @@ fn main() {
             game_id,
             engine,
             depth,
+            nodes,
+            movetime,
             book_moves,
+            tb_path,
         } => {
-            run_analyze(&config, &game_id, engine, depth, book_moves);
+            let limit = match (nodes, movetime) {
+                (Some(nodes), _) => AnalysisLimit::Nodes(nodes),
+                (None, Some(ms)) => AnalysisLimit::MoveTime(ms),
+                (None, None) => AnalysisLimit::Depth(depth),
+            };
+            run_analyze(&config, &game_id, engine, limit, book_moves, tb_path);
         }
         Commands::Openings { search, eco, tag } => {
             run_openings(search, eco, tag);
@@ -248,6 +470,43 @@ fn main() {
     }
 }
 
+/// Returns the seed to draw per-game or per-pair openings with, or `None`
+/// if every game starts from the same opening.
+///
+/// A fixed `--opening` always wins. Otherwise openings are drawn with
+/// `seed`, and pairs draw them with a random seed even without one: two
+/// pairs from the same opening would only repeat each other.
+fn opening_seed(fixed_opening: bool, seed: Option<u64>, pairs: bool) -> Option<u64> {
+    if fixed_opening {
+        None
+    } else if pairs {
+        Some(seed.unwrap_or_else(rand::random))
+    } else {
+        seed
+    }
+}
+
+/// Returns the `Seed` option value for game `game` of a match run with
+/// `seed`.
+///
+/// Bots declare `Seed` as a spin from 0 to `i64::MAX`, so the per-game seed
+/// is folded into that range instead of overflowing it.
+fn bot_seed(seed: u64, game: u32) -> u64 {
+    seed.wrapping_add(u64::from(game)) & i64::MAX as u64
+}
+
+/// Draws `count` openings from `db`, weighted by popularity, using an RNG
+/// seeded with `seed`.
+///
+/// The same seed and database always give the same sequence. Openings are
+/// drawn independently, so one may appear more than once.
+fn select_openings(db: &OpeningDatabase, count: usize, seed: u64) -> Vec<&Opening> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..count)
+        .filter_map(|_| db.weighted_random(1, &mut rng).into_iter().next())
+        .collect()
+}
+
 /// Runs the openings command to list and search chess openings.
 fn run_openings(search: Option<String>, eco: Option<String>, tag: Option<String>) {
     let db = OpeningDatabase::with_openings(builtin_openings());
@@ -333,28 +592,15 @@ fn convert_moves(moves: &[MoveRecordJson]) -> Vec<MoveInput> {
     moves
         .iter()
         .map(|m| {
-            let (bot_eval_cp, bot_eval_mate, bot_depth, bot_nodes, bot_time_ms, bot_pv) =
-                if let Some(ref info) = m.search_info {
-                    (
-                        info.score_cp,
-                        info.score_mate,
-                        info.depth,
-                        info.nodes,
-                        info.time_ms,
-                        info.pv.clone().unwrap_or_default(),
-                    )
-                } else {
-                    (None, None, None, None, None, vec![])
-                };
-
+            let info = m.search_info.as_ref();
             MoveInput {
                 uci: m.uci.clone(),
-                bot_eval_cp,
-                bot_eval_mate,
-                bot_depth,
-                bot_nodes,
-                bot_time_ms,
-                bot_pv,
+                bot_eval_cp: info.and_then(|i| i.score_cp),
+                bot_eval_mate: info.and_then(|i| i.score_mate),
+                bot_depth: info.and_then(|i| i.depth),
+                bot_nodes: info.and_then(|i| i.nodes),
+                bot_time_ms: info.and_then(|i| i.time_ms),
+                bot_pv: info.and_then(|i| i.pv.clone()).unwrap_or_default(),
             }
         })
         .collect()
@@ -391,6 +637,19 @@ fn print_analysis_results(analysis: &GameAnalysis) {
     println!("  Blunders: {}", analysis.black_stats.blunders);
     println!("  Mistakes: {}", analysis.black_stats.mistakes);
     println!("  Inaccuracies: {}", analysis.black_stats.inaccuracies);
+    println!();
+
+    let meta = &analysis.meta;
+    print!(
+        "Engine: {} positions, {} nodes in {:.1}s",
+        meta.positions_searched,
+        meta.nodes,
+        meta.time_ms as f64 / 1000.0
+    );
+    match meta.nps() {
+        Some(nps) => println!(" ({} nps)", nps),
+        None => println!(),
+    }
 }
 
 /// Saves analysis results to JSON file.
@@ -414,8 +673,9 @@ fn run_analyze(
     config: &ArenaConfig,
     game_id: &str,
     engine_override: Option<String>,
-    depth: u32,
+    limit: AnalysisLimit,
     book_moves: usize,
+    tb_path: Option<std::path::PathBuf>,
 ) {
     // Determine engine path
     let engine_path = engine_override.unwrap_or_else(|| config.analysis.stockfish_path.clone());
@@ -447,12 +707,17 @@ fn run_analyze(
         game.moves.len()
     );
     println!("Using engine: {}", engine_path);
-    println!("Depth: {}, Book moves: {}", depth, book_moves);
+    println!("Limit: {}, Book moves: {}", limit, book_moves);
+    if let Some(path) = &tb_path {
+        println!("Using tablebase: {}", path.display());
+    }
 
     // Create analyzer
     let analysis_config = AnalysisConfig {
-        depth,
+        limit: Some(limit),
         opening_book_moves: book_moves,
+        tb_path,
+        ..AnalysisConfig::default()
     };
 
     let mut analyzer = match GameAnalyzer::new(&engine_path, analysis_config) {
@@ -472,14 +737,20 @@ fn run_analyze(
 
     // Run analysis
     println!("\nAnalyzing {} moves...", moves.len());
-    let analysis =
-        match analyzer.analyze_game(&game.id, &game.white, &game.black, &moves, &game.result) {
-            Ok(a) => a,
-            Err(e) => {
-                eprintln!("Error: Analysis failed: {}", e);
-                std::process::exit(1);
-            }
-        };
+    let analysis = match analyzer.analyze_game(
+        &game.id,
+        &game.white,
+        &game.black,
+        &moves,
+        &game.result,
+        None,
+    ) {
+        Ok(a) => a,
+        Err(e) => {
+            eprintln!("Error: Analysis failed: {}", e);
+            std::process::exit(1);
+        }
+    };
 
     // Print results
     print_analysis_results(&analysis);
@@ -509,6 +780,7 @@ mod tests {
                 games,
                 preset,
                 opening,
+                ..
             } => {
                 assert_eq!(white, "bot1");
                 assert_eq!(black, "bot2");
@@ -533,17 +805,105 @@ mod tests {
                 games,
                 preset,
                 opening,
+                concurrency,
+                sprt,
+                pairs,
+                seed,
             } => {
                 assert_eq!(white, "bot1");
                 assert_eq!(black, "bot2");
                 assert_eq!(games, 10);
                 assert!(preset.is_none());
                 assert!(opening.is_none());
+                assert_eq!(concurrency, 1);
+                assert!(sprt.is_none());
+                assert!(!pairs);
+                assert!(seed.is_none());
             }
             _ => panic!("Expected Match command"),
         }
     }
 
+    #[test]
+    fn test_cli_parses_match_command_with_seed() {
+        let cli =
+            Cli::try_parse_from(["bot-arena", "match", "bot1", "bot2", "--seed", "42"]).unwrap();
+        match cli.command {
+            Commands::Match { seed, .. } => assert_eq!(seed, Some(42)),
+            _ => panic!("Expected Match command"),
+        }
+    }
+
+    #[test]
+    fn test_pairs_draw_openings_without_a_seed() {
+        assert_eq!(opening_seed(true, Some(7), true), None);
+        assert_eq!(opening_seed(true, None, true), None);
+        assert_eq!(opening_seed(false, Some(7), true), Some(7));
+        assert!(opening_seed(false, None, true).is_some());
+        assert_eq!(opening_seed(false, Some(7), false), Some(7));
+        assert_eq!(opening_seed(false, None, false), None);
+    }
+
+    #[test]
+    fn test_bot_seed_stays_in_spin_range() {
+        assert_eq!(bot_seed(42, 1), 43);
+        assert_eq!(bot_seed(u64::MAX, 3), 2);
+        assert_eq!(bot_seed(i64::MAX as u64 - 1, 1), i64::MAX as u64);
+        assert_eq!(bot_seed(i64::MAX as u64, 5), 4);
+    }
+
+    #[test]
+    fn test_same_seed_selects_same_openings() {
+        let db = OpeningDatabase::with_openings(builtin_openings());
+        let ids = |seed| -> Vec<String> {
+            select_openings(&db, 20, seed)
+                .iter()
+                .map(|o| o.id.clone())
+                .collect()
+        };
+
+        let first = ids(7);
+        assert_eq!(first.len(), 20);
+        assert_eq!(first, ids(7));
+        assert_ne!(first, ids(8));
+    }
+
+    #[test]
+    fn test_cli_parses_match_command_with_concurrency() {
+        let cli = Cli::try_parse_from(["bot-arena", "match", "bot1", "bot2", "--concurrency", "4"])
+            .unwrap();
+        match cli.command {
+            Commands::Match { concurrency, .. } => assert_eq!(concurrency, 4),
+            _ => panic!("Expected Match command"),
+        }
+
+        let cli = Cli::try_parse_from(["bot-arena", "match", "bot1", "bot2", "-j", "2"]).unwrap();
+        match cli.command {
+            Commands::Match { concurrency, .. } => assert_eq!(concurrency, 2),
+            _ => panic!("Expected Match command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parses_match_command_with_sprt() {
+        let cli =
+            Cli::try_parse_from(["bot-arena", "match", "bot1", "bot2", "--sprt", "0,5"]).unwrap();
+        match cli.command {
+            Commands::Match { sprt, .. } => {
+                let (lower, upper) = sprt.unwrap().bounds();
+                assert!((upper - 2.944).abs() < 1e-3);
+                assert!((lower + 2.944).abs() < 1e-3);
+            }
+            _ => panic!("Expected Match command"),
+        }
+
+        let sprt = parse_sprt("-2, 3, 0.01, 0.1").unwrap();
+        assert!((sprt.bounds().1 - (0.9f64 / 0.01).ln()).abs() < 1e-9);
+
+        assert!(Cli::try_parse_from(["bot-arena", "match", "a", "b", "--sprt", "5"]).is_err());
+        assert!(Cli::try_parse_from(["bot-arena", "match", "a", "b", "--sprt", "5,0"]).is_err());
+    }
+
     #[test]
     fn test_cli_parses_match_command_with_games_override() {
         let cli = Cli::try_parse_from(["bot-arena", "match", "bot1", "bot2", "-g", "50"]);
@@ -594,6 +954,7 @@ mod tests {
             bots: HashMap::new(),
             presets,
             analysis: Default::default(),
+            adjudication: None,
         };
 
         // Simulate the preset lookup logic from main
@@ -647,12 +1008,18 @@ mod tests {
                 game_id,
                 engine,
                 depth,
+                nodes,
+                movetime,
                 book_moves,
+                tb_path,
             } => {
                 assert_eq!(game_id, "test-game-123");
                 assert!(engine.is_none());
                 assert_eq!(depth, 15); // default
+                assert!(nodes.is_none());
+                assert!(movetime.is_none());
                 assert_eq!(book_moves, 0); // default
+                assert!(tb_path.is_none());
             }
             _ => panic!("Expected Analyze command"),
         }
@@ -681,6 +1048,7 @@ mod tests {
                 engine,
                 depth,
                 book_moves,
+                ..
             } => {
                 assert_eq!(game_id, "game-456");
                 assert_eq!(engine, Some("/usr/bin/stockfish".to_string()));
@@ -691,6 +1059,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parses_analyze_node_limit() {
+        let cli = Cli::try_parse_from([
+            "bot-arena",
+            "analyze",
+            "--game-id",
+            "g",
+            "--nodes",
+            "100000",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Analyze { nodes, .. } => assert_eq!(nodes, Some(100000)),
+            _ => panic!("Expected Analyze command"),
+        }
+
+        let both = Cli::try_parse_from([
+            "bot-arena",
+            "analyze",
+            "--game-id",
+            "g",
+            "--nodes",
+            "100000",
+            "--movetime",
+            "100",
+        ]);
+        assert!(both.is_err());
+    }
+
     #[test]
     fn test_cli_analyze_help_includes_options() {
         let cmd = Cli::command();
@@ -0,0 +1,202 @@
+//! Running the games of a match, optionally in parallel.
+//!
+//! [`run_match`] plays a fixed number of games on a pool of worker threads.
+//! Finished games are handed back to the calling thread one at a time, so
+//! callbacks that write to [`Storage`](crate::storage::Storage) (which wraps a
+//! single SQLite connection) never run concurrently.
+
+use std::ops::ControlFlow;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+use crate::game_runner::{GameError, GameResult, MatchResult};
+
+/// Win/draw/loss counts for a match, from white's point of view.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MatchTally {
+    /// Games won by white.
+    pub white_wins: u32,
+    /// Games drawn.
+    pub draws: u32,
+    /// Games won by black.
+    pub black_wins: u32,
+}
+
+impl MatchTally {
+    /// Counts a finished game.
+    pub fn record(&mut self, result: MatchResult) {
+        match result {
+            MatchResult::WhiteWins => self.white_wins += 1,
+            MatchResult::BlackWins => self.black_wins += 1,
+            MatchResult::Draw => self.draws += 1,
+        }
+    }
+
+    /// Returns the number of games counted.
+    pub fn total(&self) -> u32 {
+        self.white_wins + self.draws + self.black_wins
+    }
+}
+
+/// Plays `games` games with at most `concurrency` running at once.
+///
+/// `play` is called with the 1-based game number on a worker thread and
+/// should spawn its own engines. `on_finish` is called on the calling thread
+/// as each game completes, in completion order, so it may hold non-thread-safe
+/// resources such as a database connection. Games that fail are passed to
+/// `on_finish` but not counted in the returned tally.
+///
+/// If `on_finish` returns [`ControlFlow::Break`], no new games are started.
+/// Games already in progress are allowed to finish but are discarded.
+///
+/// A `concurrency` of 0 is treated as 1.
+pub fn run_match<P, F>(games: u32, concurrency: usize, play: P, mut on_finish: F) -> MatchTally
+where
+    P: Fn(u32) -> Result<GameResult, GameError> + Sync,
+    F: FnMut(u32, &Result<GameResult, GameError>) -> ControlFlow<()>,
+{
+    let workers = concurrency.clamp(1, games.max(1) as usize);
+    let next_game = AtomicU32::new(1);
+    let stopped = AtomicBool::new(false);
+    let mut tally = MatchTally::default();
+
+    thread::scope(|scope| {
+        let (tx, rx) = mpsc::channel();
+
+        for _ in 0..workers {
+            let tx = tx.clone();
+            let play = &play;
+            let next_game = &next_game;
+            let stopped = &stopped;
+            scope.spawn(move || loop {
+                let game = next_game.fetch_add(1, Ordering::Relaxed);
+                if game > games || stopped.load(Ordering::Relaxed) {
+                    break;
+                }
+                if tx.send((game, play(game))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+
+        for (game, outcome) in rx {
+            if let Ok(result) = &outcome {
+                tally.record(result.result);
+            }
+            if on_finish(game, &outcome).is_break() {
+                stopped.store(true, Ordering::Relaxed);
+                break;
+            }
+        }
+    });
+
+    tally
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_runner::{MoveRecord, Termination};
+    use crate::storage::Storage;
+    use std::time::Duration;
+
+    /// A stand-in for a real game: the outcome depends only on the game number.
+    fn mock_game(game: u32) -> Result<GameResult, GameError> {
+        // Vary the duration so games finish out of order
+        thread::sleep(Duration::from_millis(u64::from(game % 4) * 5));
+
+        if game == 7 {
+            return Err(GameError::InvalidMove("a1a1".to_string()));
+        }
+        let result = match game % 3 {
+            0 => MatchResult::WhiteWins,
+            1 => MatchResult::Draw,
+            _ => MatchResult::BlackWins,
+        };
+        Ok(GameResult {
+            moves: vec![MoveRecord {
+                uci: "e2e4".to_string(),
+                search_info: None,
+            }],
+            result,
+            white_name: "white".to_string(),
+            black_name: "black".to_string(),
+            opening: None,
+            termination: Termination::Normal,
+        })
+    }
+
+    fn run_and_save(concurrency: usize) -> (MatchTally, Storage, Vec<u32>) {
+        let storage = Storage::open(":memory:").unwrap();
+        let mut finished = Vec::new();
+        let tally = run_match(20, concurrency, mock_game, |game, outcome| {
+            finished.push(game);
+            if let Ok(result) = outcome {
+                storage.save_game(result).unwrap();
+            }
+            ControlFlow::Continue(())
+        });
+        (tally, storage, finished)
+    }
+
+    #[test]
+    fn concurrent_games_are_all_saved() {
+        let (tally, storage, mut finished) = run_and_save(4);
+
+        finished.sort_unstable();
+        assert_eq!(finished, (1..=20).collect::<Vec<_>>());
+
+        // Game 7 failed and is neither saved nor counted
+        assert_eq!(tally.total(), 19);
+        let (games, wins, draws, losses) = storage.get_stats("white").unwrap();
+        assert_eq!(games, 19);
+        assert_eq!(
+            (wins, draws, losses),
+            (
+                tally.white_wins as i32,
+                tally.draws as i32,
+                tally.black_wins as i32
+            )
+        );
+    }
+
+    #[test]
+    fn concurrent_tally_matches_serial() {
+        let (serial, _, serial_order) = run_and_save(1);
+        let (parallel, _, _) = run_and_save(8);
+
+        assert_eq!(serial_order, (1..=20).collect::<Vec<_>>());
+        assert_eq!(parallel, serial);
+        assert_eq!(
+            serial,
+            MatchTally {
+                white_wins: 6,
+                draws: 6,
+                black_wins: 7,
+            }
+        );
+    }
+
+    #[test]
+    fn zero_concurrency_runs_serially() {
+        let tally = run_match(3, 0, mock_game, |_, _| ControlFlow::Continue(()));
+        assert_eq!(tally.total(), 3);
+    }
+
+    #[test]
+    fn break_stops_starting_games() {
+        let mut seen = 0;
+        let tally = run_match(20, 2, mock_game, |_, _| {
+            seen += 1;
+            if seen == 5 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert_eq!(seen, 5);
+        assert!(tally.total() <= 5);
+    }
+}
@@ -0,0 +1,170 @@
+//! Pentanomial scoring of color-reversed game pairs.
+//!
+//! When each opening is played twice with colors swapped, the two games of a
+//! pair are strongly correlated: a lopsided opening tends to win for whoever
+//! has the better side. Scoring the pair as a unit, rather than the games
+//! individually, removes that correlation from the variance and gives
+//! tighter Elo error bars for the same number of games.
+//!
+//! A pair is worth 0, 0.5, 1, 1.5, or 2 points to the engine under test,
+//! and [`Pentanomial`] counts how many pairs fell in each bucket.
+
+use crate::game_runner::MatchResult;
+
+/// z-score for a two-sided 95% confidence interval.
+const Z_95: f64 = 1.959964;
+
+/// An Elo difference with its 95% confidence half-width.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EloEstimate {
+    /// Estimated Elo difference of the engine under test.
+    pub elo: f64,
+    /// Half-width of the 95% confidence interval, in Elo.
+    pub error: f64,
+}
+
+/// Converts an expected score in `(0, 1)` to an Elo difference.
+fn score_to_elo(score: f64) -> f64 {
+    let score = score.clamp(1e-6, 1.0 - 1e-6);
+    -400.0 * (1.0 / score - 1.0).log10()
+}
+
+/// Counts of game pairs by the points the engine under test scored.
+///
+/// `counts[i]` is the number of pairs worth `i / 2` points, so index 0 is a
+/// pair of losses and index 4 a pair of wins.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Pentanomial {
+    /// Pairs per bucket, from 0 points to 2 points.
+    pub counts: [u32; 5],
+}
+
+impl Pentanomial {
+    /// Builds counts from a list of `(as_white, as_black)` game results.
+    pub fn from_pairs(pairs: &[(MatchResult, MatchResult)]) -> Self {
+        let mut pentanomial = Self::default();
+        for &(as_white, as_black) in pairs {
+            pentanomial.record_pair(as_white, as_black);
+        }
+        pentanomial
+    }
+
+    /// Records a pair of games.
+    ///
+    /// `as_white` is the result of the game in which the engine under test
+    /// played white, and `as_black` the game in which it played black. Both
+    /// are board results, so [`MatchResult::BlackWins`] in `as_black` is a
+    /// win for the engine.
+    pub fn record_pair(&mut self, as_white: MatchResult, as_black: MatchResult) {
+        let half_points = |result: MatchResult, color_won: MatchResult| match result {
+            MatchResult::Draw => 1,
+            r if r == color_won => 2,
+            _ => 0,
+        };
+        let bucket = half_points(as_white, MatchResult::WhiteWins)
+            + half_points(as_black, MatchResult::BlackWins);
+        self.counts[bucket] += 1;
+    }
+
+    /// Returns the number of pairs recorded.
+    pub fn pairs(&self) -> u32 {
+        self.counts.iter().sum()
+    }
+
+    /// Returns the variance-reduced Elo estimate, or `None` with no pairs.
+    ///
+    /// The mean and variance are taken over pair scores (as a fraction of
+    /// the 2 available points), and the confidence interval on the score is
+    /// mapped through the logistic Elo curve.
+    pub fn elo_estimate(&self) -> Option<EloEstimate> {
+        let pairs = self.pairs();
+        if pairs == 0 {
+            return None;
+        }
+
+        let n = f64::from(pairs);
+        let frequency = |i: usize| f64::from(self.counts[i]) / n;
+        let score: f64 = (0..5).map(|i| frequency(i) * i as f64 / 4.0).sum();
+        let variance: f64 = (0..5)
+            .map(|i| frequency(i) * (i as f64 / 4.0 - score).powi(2))
+            .sum();
+
+        let margin = Z_95 * (variance / n).sqrt();
+        let upper = score_to_elo(score + margin);
+        let lower = score_to_elo(score - margin);
+
+        Some(EloEstimate {
+            elo: score_to_elo(score),
+            error: (upper - lower) / 2.0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use MatchResult::*;
+
+    #[test]
+    fn pairs_map_to_buckets() {
+        let pentanomial = Pentanomial::from_pairs(&[
+            // Lost both
+            (BlackWins, WhiteWins),
+            // Lost one, drew one
+            (Draw, WhiteWins),
+            (BlackWins, Draw),
+            // Both drawn, or won one and lost one
+            (Draw, Draw),
+            (WhiteWins, WhiteWins),
+            (BlackWins, BlackWins),
+            // Won one, drew one
+            (WhiteWins, Draw),
+            // Won both
+            (WhiteWins, BlackWins),
+            (WhiteWins, BlackWins),
+        ]);
+        assert_eq!(pentanomial.counts, [1, 2, 3, 1, 2]);
+        assert_eq!(pentanomial.pairs(), 9);
+    }
+
+    #[test]
+    fn no_pairs_has_no_estimate() {
+        assert!(Pentanomial::default().elo_estimate().is_none());
+    }
+
+    #[test]
+    fn balanced_pairs_estimate_zero_elo() {
+        let pentanomial = Pentanomial {
+            counts: [2, 5, 10, 5, 2],
+        };
+        let estimate = pentanomial.elo_estimate().unwrap();
+        assert!(estimate.elo.abs() < 1e-9);
+        assert!(estimate.error > 0.0);
+    }
+
+    #[test]
+    fn winning_pairs_estimate_positive_elo() {
+        let pentanomial = Pentanomial {
+            counts: [1, 3, 10, 10, 6],
+        };
+        let estimate = pentanomial.elo_estimate().unwrap();
+        // Mean pair score is 0.642, about +101 Elo
+        assert!((estimate.elo - 101.2).abs() < 0.1);
+        assert!(estimate.elo - estimate.error > 0.0);
+    }
+
+    #[test]
+    fn correlated_pairs_tighten_error_bars() {
+        // Every pair splits 1-1: the engines are equal and the pair score
+        // never varies, even though individual games are all decisive
+        let split = Pentanomial {
+            counts: [0, 0, 20, 0, 0],
+        };
+        assert_eq!(split.elo_estimate().unwrap().error, 0.0);
+
+        let noisy = Pentanomial {
+            counts: [5, 0, 10, 0, 5],
+        };
+        assert!(noisy.elo_estimate().unwrap().error > 0.0);
+    }
+}
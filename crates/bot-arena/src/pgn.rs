@@ -5,7 +5,8 @@
 
 #[cfg(test)]
 use crate::game_runner::MoveRecord;
-use crate::game_runner::{GameResult, MatchResult};
+use crate::game_runner::{GameResult, MatchResult, Termination};
+use chess_engine::Game;
 use chrono::Utc;
 use std::io::Write;
 use std::path::Path;
@@ -33,8 +34,8 @@ use std::path::Path;
 /// - Move text with move numbers (e.g., "1. e2e4 e7e5 2. g1f3 ...")
 /// - Result terminator
 ///
-/// Note: Moves are currently written in UCI notation. SAN (Standard Algebraic
-/// Notation) conversion may be added in a future version.
+/// Note: Moves are written in UCI notation. Use [`uci_to_san`] with
+/// [`format_pgn`] for SAN (Standard Algebraic Notation) movetext.
 ///
 /// # Example
 ///
@@ -52,48 +53,102 @@ use std::path::Path;
 /// write_pgn("game.pgn", &result)?;
 /// ```
 pub fn write_pgn<P: AsRef<Path>>(path: P, result: &GameResult) -> std::io::Result<()> {
-    let mut file = std::fs::File::create(path)?;
-
     let result_str = match result.result {
         MatchResult::WhiteWins => "1-0",
         MatchResult::BlackWins => "0-1",
         MatchResult::Draw => "1/2-1/2",
     };
 
-    writeln!(file, "[Event \"Bot Arena Match\"]")?;
-    writeln!(file, "[Site \"local\"]")?;
-    writeln!(file, "[Date \"{}\"]", Utc::now().format("%Y.%m.%d"))?;
-    writeln!(file, "[Round \"-\"]")?;
-    writeln!(file, "[White \"{}\"]", result.white_name)?;
-    writeln!(file, "[Black \"{}\"]", result.black_name)?;
-    writeln!(file, "[Result \"{}\"]", result_str)?;
+    let mut tags = vec![
+        ("Event", "Bot Arena Match".to_string()),
+        ("Site", "local".to_string()),
+        ("Date", Utc::now().format("%Y.%m.%d").to_string()),
+        ("Round", "-".to_string()),
+        ("White", result.white_name.clone()),
+        ("Black", result.black_name.clone()),
+        ("Result", result_str.to_string()),
+    ];
+    if result.termination != Termination::Normal {
+        tags.push(("Termination", result.termination.as_pgn_str().to_string()));
+    }
 
     // Add optional opening headers if detected
     if let Some(opening) = &result.opening {
-        writeln!(file, "[Opening \"{}\"]", opening.name)?;
+        tags.push(("Opening", opening.name.clone()));
         if let Some(eco) = &opening.eco {
-            writeln!(file, "[ECO \"{}\"]", eco)?;
+            tags.push(("ECO", eco.clone()));
         }
     }
 
-    writeln!(file)?;
+    // Write moves in UCI notation
+    let moves: Vec<String> = result.moves.iter().map(|m| m.uci.clone()).collect();
+
+    let mut file = std::fs::File::create(path)?;
+    file.write_all(format_pgn(&tags, &moves, result_str).as_bytes())
+}
+
+/// Converts UCI moves to SAN by replaying them from the starting position.
+///
+/// If a move cannot be played (it is malformed or illegal), it and every
+/// later move are returned unchanged, since their SAN cannot be known.
+///
+/// # Example
+///
+/// ```
+/// use bot_arena::pgn::uci_to_san;
+///
+/// let moves = vec!["e2e4".to_string(), "e7e5".to_string(), "g1f3".to_string()];
+/// assert_eq!(uci_to_san(&moves), vec!["e4", "e5", "Nf3"]);
+/// ```
+pub fn uci_to_san(moves: &[String]) -> Vec<String> {
+    let mut game = Game::new();
+    let mut san = Vec::with_capacity(moves.len());
+    for uci in moves {
+        if game.make_move_uci(uci).is_err() {
+            break;
+        }
+        if let Some(last) = game.move_history().last() {
+            san.push(last.san.clone());
+        }
+    }
+    san.extend(moves[san.len()..].iter().cloned());
+    san
+}
+
+/// Formats a game as PGN text.
+///
+/// Writes each `(name, value)` tag pair in order, a blank line, and then the
+/// numbered movetext followed by the `result` token (`1-0`, `0-1`,
+/// `1/2-1/2`, or `*`). Moves are written as given, so pass them through
+/// [`uci_to_san`] first for standard SAN movetext. Movetext lines are wrapped
+/// at 80 characters without splitting tokens.
+pub fn format_pgn(tags: &[(&str, String)], moves: &[String], result: &str) -> String {
+    let mut pgn = String::new();
+    for (name, value) in tags {
+        pgn.push_str(&format!(
+            "[{} \"{}\"]\n",
+            name,
+            value.replace('\\', "\\\\").replace('"', "\\\"")
+        ));
+    }
+    pgn.push('\n');
 
-    // Write moves in PGN format (UCI for now, SAN conversion later)
     let mut move_text = String::new();
-    for (i, record) in result.moves.iter().enumerate() {
+    for (i, mv) in moves.iter().enumerate() {
         if i % 2 == 0 {
             move_text.push_str(&format!("{}. ", i / 2 + 1));
         }
-        move_text.push_str(&record.uci);
+        move_text.push_str(mv);
         move_text.push(' ');
     }
-    move_text.push_str(result_str);
+    move_text.push_str(result);
 
     // Wrap at 80 chars at word boundaries
     let mut line = String::new();
     for word in move_text.split_whitespace() {
         if !line.is_empty() && line.len() + 1 + word.len() > 80 {
-            writeln!(file, "{}", line)?;
+            pgn.push_str(&line);
+            pgn.push('\n');
             line.clear();
         }
         if !line.is_empty() {
@@ -102,10 +157,11 @@ pub fn write_pgn<P: AsRef<Path>>(path: P, result: &GameResult) -> std::io::Resul
         line.push_str(word);
     }
     if !line.is_empty() {
-        writeln!(file, "{}", line)?;
+        pgn.push_str(&line);
+        pgn.push('\n');
     }
 
-    Ok(())
+    pgn
 }
 
 #[cfg(test)]
@@ -140,6 +196,7 @@ mod tests {
                 },
             ],
             result: MatchResult::WhiteWins,
+            termination: Termination::Normal,
             white_name: "TestEngineWhite".to_string(),
             black_name: "TestEngineBlack".to_string(),
             opening: None,
@@ -272,6 +329,7 @@ mod tests {
         let result = GameResult {
             moves,
             result: MatchResult::Draw,
+            termination: Termination::Normal,
             white_name: "LongGameWhite".to_string(),
             black_name: "LongGameBlack".to_string(),
             opening: None,
@@ -338,6 +396,7 @@ mod tests {
                 },
             ],
             result: MatchResult::BlackWins,
+            termination: Termination::Normal,
             white_name: "White".to_string(),
             black_name: "Black".to_string(),
             opening: None,
@@ -373,6 +432,7 @@ mod tests {
                 search_info: None,
             }],
             result: MatchResult::Draw,
+            termination: Termination::Normal,
             white_name: "White".to_string(),
             black_name: "Black".to_string(),
             opening: None,
@@ -405,6 +465,7 @@ mod tests {
         let result = GameResult {
             moves: vec![],
             result: MatchResult::Draw,
+            termination: Termination::Normal,
             white_name: "White".to_string(),
             black_name: "Black".to_string(),
             opening: None,
@@ -457,6 +518,7 @@ mod tests {
                 },
             ],
             result: MatchResult::WhiteWins,
+            termination: Termination::Normal,
             white_name: "Minimax".to_string(),
             black_name: "Random".to_string(),
             opening: Some(DetectedOpening {
@@ -499,6 +561,7 @@ mod tests {
                 search_info: None,
             }],
             result: MatchResult::Draw,
+            termination: Termination::Normal,
             white_name: "Engine1".to_string(),
             black_name: "Engine2".to_string(),
             opening: Some(DetectedOpening {
@@ -527,4 +590,59 @@ mod tests {
 
         fs::remove_file(&pgn_path).ok();
     }
+
+    #[test]
+    fn test_write_pgn_termination_tag() {
+        let temp_dir = std::env::temp_dir();
+        let path = temp_dir.join("test_game_termination.pgn");
+
+        let mut result = create_test_result();
+        result.result = MatchResult::BlackWins;
+        result.termination = Termination::TimeForfeit;
+        write_pgn(&path, &result).expect("Failed to write PGN");
+        let content = fs::read_to_string(&path).expect("Failed to read file");
+        assert!(content.contains("[Termination \"time forfeit\"]"));
+
+        // Normal games omit the tag
+        write_pgn(&path, &create_test_result()).expect("Failed to write PGN");
+        let content = fs::read_to_string(&path).expect("Failed to read file");
+        assert!(!content.contains("[Termination"));
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_uci_to_san() {
+        let moves: Vec<String> = ["e2e4", "e7e5", "f1c4", "b8c6", "d1h5", "g8f6", "h5f7"]
+            .iter()
+            .map(|m| m.to_string())
+            .collect();
+        assert_eq!(
+            uci_to_san(&moves),
+            vec!["e4", "e5", "Bc4", "Nc6", "Qh5", "Nf6", "Qxf7#"]
+        );
+    }
+
+    #[test]
+    fn test_uci_to_san_keeps_unplayable_moves() {
+        let moves: Vec<String> = ["e2e4", "e2e4", "g1f3"]
+            .iter()
+            .map(|m| m.to_string())
+            .collect();
+        assert_eq!(uci_to_san(&moves), vec!["e4", "e2e4", "g1f3"]);
+    }
+
+    #[test]
+    fn test_format_pgn() {
+        let tags = [
+            ("White", "A \"quoted\" bot".to_string()),
+            ("Result", "*".to_string()),
+        ];
+        let moves = vec!["e4".to_string(), "e5".to_string(), "Nf3".to_string()];
+        assert_eq!(
+            format_pgn(&tags, &moves, "*"),
+            "[White \"A \\\"quoted\\\" bot\"]\n[Result \"*\"]\n\n1. e4 e5 2. Nf3 *\n"
+        );
+        assert_eq!(format_pgn(&[], &[], "*"), "\n*\n");
+    }
 }
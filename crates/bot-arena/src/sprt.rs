@@ -0,0 +1,220 @@
+//! Sequential Probability Ratio Test for engine A/B testing.
+//!
+//! An SPRT decides between two hypotheses about the Elo difference between
+//! two engines, `H0: elo = elo0` and `H1: elo = elo1`, after every game. The
+//! match can stop as soon as the log-likelihood ratio (LLR) leaves the bounds
+//! given by the error rates `alpha` and `beta`.
+//!
+//! The LLR uses the normal approximation over the trinomial (win/draw/loss)
+//! score distribution, as popularised by Fishtest:
+//!
+//! ```text
+//! LLR ≈ N · (s1 − s0) · (2s − s0 − s1) / (2σ²)
+//! ```
+//!
+//! where `s` is the observed mean score, `σ²` its per-game variance, and
+//! `s0`/`s1` the expected scores under each hypothesis.
+
+use crate::game_runner::MatchResult;
+
+/// Pseudo-games added to each outcome when estimating the score and its
+/// variance, which keeps the estimate sensible for short or one-sided result
+/// streams (e.g. a single win).
+const PRIOR_COUNT: f64 = 0.5;
+
+/// The state of the test after a game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SprtDecision {
+    /// Not enough evidence yet; keep playing.
+    Continue,
+    /// The LLR fell below the lower bound: the engine is not `elo1` better.
+    AcceptH0,
+    /// The LLR rose above the upper bound: the engine is `elo1` better.
+    AcceptH1,
+}
+
+/// Returns the expected score for an Elo difference under the logistic model.
+fn expected_score(elo: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-elo / 400.0))
+}
+
+/// A running SPRT over game results.
+///
+/// Results are from the point of view of the engine being tested, which
+/// plays white in the `match` command: [`MatchResult::WhiteWins`] counts as
+/// a win.
+///
+/// # Example
+///
+/// ```
+/// use bot_arena::game_runner::MatchResult;
+/// use bot_arena::sprt::{Sprt, SprtDecision};
+///
+/// let mut sprt = Sprt::new(0.0, 10.0);
+/// assert_eq!(sprt.update(MatchResult::Draw), SprtDecision::Continue);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Sprt {
+    elo0: f64,
+    elo1: f64,
+    alpha: f64,
+    beta: f64,
+    wins: u32,
+    draws: u32,
+    losses: u32,
+}
+
+impl Sprt {
+    /// Creates a test of `H0: elo = elo0` against `H1: elo = elo1` with
+    /// 5% false positive and false negative rates.
+    pub fn new(elo0: f64, elo1: f64) -> Self {
+        Self {
+            elo0,
+            elo1,
+            alpha: 0.05,
+            beta: 0.05,
+            wins: 0,
+            draws: 0,
+            losses: 0,
+        }
+    }
+
+    /// Sets the false positive (`alpha`) and false negative (`beta`) rates.
+    #[must_use]
+    pub fn with_error_rates(mut self, alpha: f64, beta: f64) -> Self {
+        self.alpha = alpha;
+        self.beta = beta;
+        self
+    }
+
+    /// Records a game result and returns the current decision.
+    pub fn update(&mut self, result: MatchResult) -> SprtDecision {
+        match result {
+            MatchResult::WhiteWins => self.wins += 1,
+            MatchResult::Draw => self.draws += 1,
+            MatchResult::BlackWins => self.losses += 1,
+        }
+        self.decision()
+    }
+
+    /// Returns the decision for the results recorded so far.
+    pub fn decision(&self) -> SprtDecision {
+        let (lower, upper) = self.bounds();
+        let llr = self.llr();
+        if llr >= upper {
+            SprtDecision::AcceptH1
+        } else if llr <= lower {
+            SprtDecision::AcceptH0
+        } else {
+            SprtDecision::Continue
+        }
+    }
+
+    /// Returns the `(lower, upper)` LLR bounds.
+    pub fn bounds(&self) -> (f64, f64) {
+        let lower = (self.beta / (1.0 - self.alpha)).ln();
+        let upper = ((1.0 - self.beta) / self.alpha).ln();
+        (lower, upper)
+    }
+
+    /// Returns the log-likelihood ratio of H1 over H0.
+    pub fn llr(&self) -> f64 {
+        if self.games() == 0 {
+            return 0.0;
+        }
+
+        let count = |n: u32| f64::from(n) + PRIOR_COUNT;
+        let (w, d, l) = (count(self.wins), count(self.draws), count(self.losses));
+        let total = w + d + l;
+
+        let score = (w + 0.5 * d) / total;
+        let variance =
+            (w * (1.0 - score).powi(2) + d * (0.5 - score).powi(2) + l * score.powi(2)) / total;
+
+        let s0 = expected_score(self.elo0);
+        let s1 = expected_score(self.elo1);
+        let n = f64::from(self.games());
+        n * (s1 - s0) * (2.0 * score - s0 - s1) / (2.0 * variance)
+    }
+
+    /// Returns the `(wins, draws, losses)` recorded so far.
+    pub fn counts(&self) -> (u32, u32, u32) {
+        (self.wins, self.draws, self.losses)
+    }
+
+    /// Returns the number of games recorded.
+    pub fn games(&self) -> u32 {
+        self.wins + self.draws + self.losses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feeds `pattern` repeatedly until a decision is reached or `max` games.
+    fn run(sprt: &mut Sprt, pattern: &[MatchResult], max: usize) -> SprtDecision {
+        for result in pattern.iter().cycle().take(max) {
+            let decision = sprt.update(*result);
+            if decision != SprtDecision::Continue {
+                return decision;
+            }
+        }
+        SprtDecision::Continue
+    }
+
+    #[test]
+    fn bounds_for_default_error_rates() {
+        let (lower, upper) = Sprt::new(0.0, 5.0).bounds();
+        assert!((lower + 2.944).abs() < 1e-3);
+        assert!((upper - 2.944).abs() < 1e-3);
+    }
+
+    #[test]
+    fn lopsided_results_accept_h1() {
+        use MatchResult::*;
+        let mut sprt = Sprt::new(0.0, 10.0);
+        let decision = run(&mut sprt, &[WhiteWins, WhiteWins, Draw, BlackWins], 10_000);
+        assert_eq!(decision, SprtDecision::AcceptH1);
+        assert!(sprt.llr() >= sprt.bounds().1);
+        assert!(sprt.games() < 1000);
+    }
+
+    #[test]
+    fn losing_results_accept_h0() {
+        use MatchResult::*;
+        let mut sprt = Sprt::new(0.0, 10.0);
+        let decision = run(&mut sprt, &[BlackWins, Draw, WhiteWins, BlackWins], 10_000);
+        assert_eq!(decision, SprtDecision::AcceptH0);
+    }
+
+    #[test]
+    fn even_results_continue() {
+        use MatchResult::*;
+        let mut sprt = Sprt::new(0.0, 10.0);
+        assert_eq!(sprt.llr(), 0.0);
+        let decision = run(&mut sprt, &[WhiteWins, BlackWins, Draw, Draw], 20);
+        assert_eq!(decision, SprtDecision::Continue);
+        assert_eq!(sprt.counts(), (5, 10, 5));
+    }
+
+    #[test]
+    fn all_wins_accept_h1() {
+        let mut sprt = Sprt::new(0.0, 10.0);
+        let decision = run(&mut sprt, &[MatchResult::WhiteWins], 1000);
+        assert_eq!(decision, SprtDecision::AcceptH1);
+    }
+
+    #[test]
+    fn stricter_error_rates_need_more_games() {
+        use MatchResult::*;
+        let pattern = [WhiteWins, WhiteWins, Draw, BlackWins];
+
+        let mut loose = Sprt::new(0.0, 10.0);
+        run(&mut loose, &pattern, 10_000);
+        let mut strict = Sprt::new(0.0, 10.0).with_error_rates(0.01, 0.01);
+        run(&mut strict, &pattern, 10_000);
+
+        assert!(strict.games() > loose.games());
+    }
+}
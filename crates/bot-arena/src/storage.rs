@@ -4,13 +4,63 @@
 //! and performance statistics using SQLite as the backing database.
 
 #[cfg(test)]
-use crate::game_runner::MoveRecord;
+use crate::game_runner::{DetectedOpening, MoveRecord};
 use crate::game_runner::{GameResult, MatchResult};
 use chrono::Utc;
 use rusqlite::{Connection, Result as SqliteResult};
 use std::path::Path;
 use uuid::Uuid;
 
+/// A stored game, as returned by the game queries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameSummary {
+    /// Unique ID assigned when the game was saved.
+    pub id: String,
+    /// Name of the bot playing white.
+    pub white_bot: String,
+    /// Name of the bot playing black.
+    pub black_bot: String,
+    /// The game outcome.
+    pub result: MatchResult,
+    /// Number of moves (plies) played.
+    pub move_count: i32,
+    /// ID of the detected opening, if any.
+    pub opening_id: Option<String>,
+    /// RFC 3339 timestamp of when the game was saved.
+    pub created_at: String,
+}
+
+/// Returns the stored form of a game result.
+fn result_to_str(result: MatchResult) -> &'static str {
+    match result {
+        MatchResult::WhiteWins => "white",
+        MatchResult::BlackWins => "black",
+        MatchResult::Draw => "draw",
+    }
+}
+
+/// Maps a row of `SELECT {GAME_COLUMNS}` to a [`GameSummary`].
+fn game_from_row(row: &rusqlite::Row) -> SqliteResult<GameSummary> {
+    let result: String = row.get(3)?;
+    let result = match result.as_str() {
+        "white" => MatchResult::WhiteWins,
+        "black" => MatchResult::BlackWins,
+        _ => MatchResult::Draw,
+    };
+    Ok(GameSummary {
+        id: row.get(0)?,
+        white_bot: row.get(1)?,
+        black_bot: row.get(2)?,
+        result,
+        move_count: row.get(4)?,
+        opening_id: row.get(5)?,
+        created_at: row.get(6)?,
+    })
+}
+
+/// Columns read by [`game_from_row`].
+const GAME_COLUMNS: &str = "id, white_bot, black_bot, result, move_count, opening_id, created_at";
+
 /// SQLite-backed storage for game results and bot statistics.
 ///
 /// Provides methods to persist game outcomes, track bot performance,
@@ -66,7 +116,8 @@ impl Storage {
                 result TEXT NOT NULL,
                 move_count INTEGER NOT NULL,
                 moves TEXT NOT NULL,
-                created_at TEXT NOT NULL
+                created_at TEXT NOT NULL,
+                opening_id TEXT
             );
 
             CREATE TABLE IF NOT EXISTS bot_stats (
@@ -79,6 +130,24 @@ impl Storage {
                 PRIMARY KEY (bot_id, opponent_id)
             );
             ",
+        )?;
+
+        // Databases created before openings were stored lack the column
+        let has_opening: bool = self.conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM pragma_table_info('games') WHERE name = 'opening_id')",
+            [],
+            |row| row.get(0),
+        )?;
+        if !has_opening {
+            self.conn
+                .execute_batch("ALTER TABLE games ADD COLUMN opening_id TEXT;")?;
+        }
+
+        self.conn.execute_batch(
+            "
+            CREATE INDEX IF NOT EXISTS idx_games_opening ON games(opening_id);
+            CREATE INDEX IF NOT EXISTS idx_games_result ON games(result);
+            ",
         )
     }
 
@@ -121,11 +190,6 @@ impl Storage {
     /// Returns an error if the database operations fail.
     pub fn save_game(&self, result: &GameResult) -> SqliteResult<String> {
         let id = Uuid::new_v4().to_string();
-        let result_str = match result.result {
-            MatchResult::WhiteWins => "white",
-            MatchResult::BlackWins => "black",
-            MatchResult::Draw => "draw",
-        };
 
         // Extract UCI moves for storage
         let moves_str = result
@@ -136,16 +200,17 @@ impl Storage {
             .join(" ");
 
         self.conn.execute(
-            "INSERT INTO games (id, white_bot, black_bot, result, move_count, moves, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            "INSERT INTO games (id, white_bot, black_bot, result, move_count, moves, created_at, opening_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
             (
                 &id,
                 &result.white_name,
                 &result.black_name,
-                result_str,
+                result_to_str(result.result),
                 result.moves.len() as i32,
                 moves_str,
                 Utc::now().to_rfc3339(),
+                result.opening.as_ref().map(|o| &o.id),
             ),
         )?;
 
@@ -211,6 +276,34 @@ impl Storage {
         Ok(())
     }
 
+    /// Returns the games in which `opening_id` was detected, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub fn games_by_opening(&self, opening_id: &str) -> SqliteResult<Vec<GameSummary>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM games WHERE opening_id = ?1 ORDER BY created_at, rowid",
+            GAME_COLUMNS
+        ))?;
+        let games = stmt.query_map([opening_id], game_from_row)?;
+        games.collect()
+    }
+
+    /// Returns the games that ended with `result`, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub fn games_by_result(&self, result: MatchResult) -> SqliteResult<Vec<GameSummary>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM games WHERE result = ?1 ORDER BY created_at, rowid",
+            GAME_COLUMNS
+        ))?;
+        let games = stmt.query_map([result_to_str(result)], game_from_row)?;
+        games.collect()
+    }
+
     /// Retrieves aggregate statistics for a bot.
     ///
     /// Returns the total games, wins, draws, and losses for a bot
@@ -248,6 +341,7 @@ impl Storage {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::game_runner::Termination;
 
     /// Helper function to create an in-memory database for testing.
     fn create_test_storage() -> Storage {
@@ -347,6 +441,7 @@ mod tests {
                 },
             ],
             result: MatchResult::WhiteWins,
+            termination: Termination::Normal,
             white_name: "engine_a".to_string(),
             black_name: "engine_b".to_string(),
             opening: None,
@@ -378,6 +473,7 @@ mod tests {
                 search_info: None,
             }],
             result: MatchResult::Draw,
+            termination: Termination::Normal,
             white_name: "engine_a".to_string(),
             black_name: "engine_b".to_string(),
             opening: None,
@@ -408,4 +504,99 @@ mod tests {
         assert_eq!(draws, 0);
         assert_eq!(losses, 0);
     }
+
+    fn tagged_game(opening: Option<&str>, result: MatchResult) -> GameResult {
+        GameResult {
+            moves: vec![MoveRecord {
+                uci: "e2e4".to_string(),
+                search_info: None,
+            }],
+            result,
+            termination: Termination::Normal,
+            white_name: "engine_a".to_string(),
+            black_name: "engine_b".to_string(),
+            opening: opening.map(|id| DetectedOpening {
+                id: id.to_string(),
+                name: id.to_string(),
+                eco: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_games_by_opening_and_result() {
+        let storage = create_test_storage();
+        let italian_win = storage
+            .save_game(&tagged_game(Some("italian-game"), MatchResult::WhiteWins))
+            .unwrap();
+        let italian_draw = storage
+            .save_game(&tagged_game(Some("italian-game"), MatchResult::Draw))
+            .unwrap();
+        let sicilian_loss = storage
+            .save_game(&tagged_game(
+                Some("sicilian-defense"),
+                MatchResult::BlackWins,
+            ))
+            .unwrap();
+        let unknown_draw = storage
+            .save_game(&tagged_game(None, MatchResult::Draw))
+            .unwrap();
+
+        let ids = |games: Vec<GameSummary>| games.into_iter().map(|g| g.id).collect::<Vec<_>>();
+
+        let italian = storage.games_by_opening("italian-game").unwrap();
+        assert_eq!(italian[0].opening_id.as_deref(), Some("italian-game"));
+        assert_eq!(italian[0].result, MatchResult::WhiteWins);
+        assert_eq!(italian[0].move_count, 1);
+        assert_eq!(ids(italian), [italian_win.clone(), italian_draw.clone()]);
+        assert_eq!(
+            ids(storage.games_by_opening("sicilian-defense").unwrap()),
+            [sicilian_loss.as_str()]
+        );
+        assert!(storage
+            .games_by_opening("french-defense")
+            .unwrap()
+            .is_empty());
+
+        assert_eq!(
+            ids(storage.games_by_result(MatchResult::Draw).unwrap()),
+            [italian_draw, unknown_draw]
+        );
+        assert_eq!(
+            ids(storage.games_by_result(MatchResult::BlackWins).unwrap()),
+            [sicilian_loss]
+        );
+        assert_eq!(
+            ids(storage.games_by_result(MatchResult::WhiteWins).unwrap()),
+            [italian_win]
+        );
+    }
+
+    #[test]
+    fn test_open_adds_opening_column_to_old_database() {
+        let path = std::env::temp_dir().join(format!("arena-storage-{}.db", Uuid::new_v4()));
+        Connection::open(&path)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE games (
+                    id TEXT PRIMARY KEY,
+                    white_bot TEXT NOT NULL,
+                    black_bot TEXT NOT NULL,
+                    result TEXT NOT NULL,
+                    move_count INTEGER NOT NULL,
+                    moves TEXT NOT NULL,
+                    created_at TEXT NOT NULL
+                );",
+            )
+            .unwrap();
+
+        let storage = Storage::open(&path).unwrap();
+        storage
+            .save_game(&tagged_game(Some("italian-game"), MatchResult::Draw))
+            .unwrap();
+        assert_eq!(storage.games_by_opening("italian-game").unwrap().len(), 1);
+
+        drop(storage);
+        std::fs::remove_file(&path).ok();
+    }
 }
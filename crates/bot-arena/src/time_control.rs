@@ -0,0 +1,357 @@
+//! Time controls and game clocks.
+//!
+//! A [`TimeControl`] describes how much thinking time each engine gets, and a
+//! [`Clock`] tracks the time each side has left during a game so that the
+//! runner can send real `go wtime ... btime ...` commands.
+//!
+//! Supported formats:
+//!
+//! | Format        | Meaning                                            |
+//! |---------------|----------------------------------------------------|
+//! | `movetime 500`| 500 ms per move, no clock                          |
+//! | `40/300`      | 40 moves in 300 seconds, repeating                 |
+//! | `5+3`         | 5 minutes plus a 3 second increment per move       |
+//!
+//! Anything else (e.g. `depth 10` or `nodes 100000`) is passed to the engine
+//! unchanged.
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use chess_core::Color;
+use thiserror::Error;
+
+/// Errors that can occur when parsing a time control string.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum TimeControlError {
+    /// The string looked like a known format but could not be parsed.
+    #[error("Invalid time control: {0}")]
+    Invalid(String),
+}
+
+/// How much thinking time each engine is given.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeControl {
+    /// A fixed time per move in milliseconds (`movetime 500`).
+    MoveTime(u64),
+    /// A number of moves in a fixed budget (`40/300`). The budget is added
+    /// to the clock again after every `moves` moves.
+    Repeating {
+        /// Moves per time period.
+        moves: u32,
+        /// Time per period in milliseconds.
+        period_ms: u64,
+    },
+    /// A base time plus an increment after every move (`5+3`).
+    Increment {
+        /// Starting time in milliseconds.
+        base_ms: u64,
+        /// Time added after each move in milliseconds.
+        increment_ms: u64,
+    },
+    /// Any other `go` arguments, passed through verbatim (e.g. `depth 10`).
+    Raw(String),
+}
+
+impl TimeControl {
+    /// Returns true if this time control runs a clock for each side.
+    pub fn is_clocked(&self) -> bool {
+        matches!(
+            self,
+            TimeControl::Repeating { .. } | TimeControl::Increment { .. }
+        )
+    }
+
+    /// Returns a fresh clock for a game played with this time control.
+    pub fn start_clock(&self) -> Clock {
+        let initial_ms = match self {
+            TimeControl::Repeating { period_ms, .. } => *period_ms as i64,
+            TimeControl::Increment { base_ms, .. } => *base_ms as i64,
+            TimeControl::MoveTime(_) | TimeControl::Raw(_) => 0,
+        };
+        Clock {
+            white_ms: initial_ms,
+            black_ms: initial_ms,
+            white_moves: 0,
+            black_moves: 0,
+        }
+    }
+
+    /// Returns the arguments for the next `go` command with `side` to move.
+    pub fn go_args(&self, clock: &Clock, side: Color) -> String {
+        match self {
+            TimeControl::MoveTime(ms) => format!("movetime {}", ms),
+            TimeControl::Repeating { moves, .. } => {
+                let played = clock.moves(side);
+                let moves_to_go = *moves - played % *moves;
+                format!(
+                    "wtime {} btime {} movestogo {}",
+                    clock.white_ms.max(0),
+                    clock.black_ms.max(0),
+                    moves_to_go
+                )
+            }
+            TimeControl::Increment { increment_ms, .. } => format!(
+                "wtime {} btime {} winc {} binc {}",
+                clock.white_ms.max(0),
+                clock.black_ms.max(0),
+                increment_ms,
+                increment_ms
+            ),
+            TimeControl::Raw(args) => args.clone(),
+        }
+    }
+}
+
+/// Parses a number of seconds or minutes, allowing a fractional part.
+fn parse_millis(value: &str, unit_ms: f64, input: &str) -> Result<u64, TimeControlError> {
+    value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite() && *v >= 0.0)
+        .map(|v| (v * unit_ms).round() as u64)
+        .ok_or_else(|| TimeControlError::Invalid(input.to_string()))
+}
+
+impl FromStr for TimeControl {
+    type Err = TimeControlError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let invalid = || TimeControlError::Invalid(s.to_string());
+
+        if let Some(ms) = s.strip_prefix("movetime ") {
+            let ms = ms.trim().parse().map_err(|_| invalid())?;
+            return Ok(TimeControl::MoveTime(ms));
+        }
+
+        if let Some((moves, seconds)) = s.split_once('/') {
+            let moves: u32 = moves.trim().parse().map_err(|_| invalid())?;
+            if moves == 0 {
+                return Err(invalid());
+            }
+            let period_ms = parse_millis(seconds, 1000.0, s)?;
+            return Ok(TimeControl::Repeating { moves, period_ms });
+        }
+
+        if let Some((minutes, seconds)) = s.split_once('+') {
+            let base_ms = parse_millis(minutes, 60_000.0, s)?;
+            let increment_ms = parse_millis(seconds, 1000.0, s)?;
+            return Ok(TimeControl::Increment {
+                base_ms,
+                increment_ms,
+            });
+        }
+
+        if s.is_empty() {
+            return Err(invalid());
+        }
+        Ok(TimeControl::Raw(s.to_string()))
+    }
+}
+
+impl fmt::Display for TimeControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeControl::MoveTime(ms) => write!(f, "movetime {}", ms),
+            TimeControl::Repeating { moves, period_ms } => {
+                write!(f, "{}/{}", moves, *period_ms as f64 / 1000.0)
+            }
+            TimeControl::Increment {
+                base_ms,
+                increment_ms,
+            } => write!(
+                f,
+                "{}+{}",
+                *base_ms as f64 / 60_000.0,
+                *increment_ms as f64 / 1000.0
+            ),
+            TimeControl::Raw(args) => f.write_str(args),
+        }
+    }
+}
+
+/// The remaining time for each side during a game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Clock {
+    /// White's remaining time in milliseconds.
+    pub white_ms: i64,
+    /// Black's remaining time in milliseconds.
+    pub black_ms: i64,
+    /// Number of moves white has completed.
+    white_moves: u32,
+    /// Number of moves black has completed.
+    black_moves: u32,
+}
+
+impl Clock {
+    /// Returns the number of moves `side` has completed.
+    pub fn moves(&self, side: Color) -> u32 {
+        match side {
+            Color::White => self.white_moves,
+            Color::Black => self.black_moves,
+        }
+    }
+
+    /// Charges `elapsed` to `side` and applies any increment or new period.
+    ///
+    /// Returns `false` if the side flagged, i.e. its clock reached zero
+    /// before the move was completed. Unclocked time controls never flag.
+    pub fn record_move(
+        &mut self,
+        time_control: &TimeControl,
+        side: Color,
+        elapsed: Duration,
+    ) -> bool {
+        if !time_control.is_clocked() {
+            return true;
+        }
+
+        let (remaining, moves) = match side {
+            Color::White => (&mut self.white_ms, &mut self.white_moves),
+            Color::Black => (&mut self.black_ms, &mut self.black_moves),
+        };
+
+        *remaining -= elapsed.as_millis() as i64;
+        if *remaining <= 0 {
+            return false;
+        }
+
+        *moves += 1;
+        match time_control {
+            TimeControl::Increment { increment_ms, .. } => *remaining += *increment_ms as i64,
+            TimeControl::Repeating {
+                moves: per_period,
+                period_ms,
+            } => {
+                if moves.is_multiple_of(*per_period) {
+                    *remaining += *period_ms as i64;
+                }
+            }
+            TimeControl::MoveTime(_) | TimeControl::Raw(_) => {}
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_movetime() {
+        let tc: TimeControl = "movetime 500".parse().unwrap();
+        assert_eq!(tc, TimeControl::MoveTime(500));
+        assert!(!tc.is_clocked());
+        assert_eq!(tc.go_args(&tc.start_clock(), Color::White), "movetime 500");
+        assert!("movetime fast".parse::<TimeControl>().is_err());
+    }
+
+    #[test]
+    fn parse_repeating() {
+        let tc: TimeControl = "40/300".parse().unwrap();
+        assert_eq!(
+            tc,
+            TimeControl::Repeating {
+                moves: 40,
+                period_ms: 300_000
+            }
+        );
+        assert_eq!(
+            tc.go_args(&tc.start_clock(), Color::White),
+            "wtime 300000 btime 300000 movestogo 40"
+        );
+        assert!("0/300".parse::<TimeControl>().is_err());
+        assert!("x/300".parse::<TimeControl>().is_err());
+    }
+
+    #[test]
+    fn parse_increment() {
+        let tc: TimeControl = "5+3".parse().unwrap();
+        assert_eq!(
+            tc,
+            TimeControl::Increment {
+                base_ms: 300_000,
+                increment_ms: 3000
+            }
+        );
+        assert_eq!(
+            tc.go_args(&tc.start_clock(), Color::Black),
+            "wtime 300000 btime 300000 winc 3000 binc 3000"
+        );
+
+        let tc: TimeControl = "0.5+0.1".parse().unwrap();
+        assert_eq!(
+            tc,
+            TimeControl::Increment {
+                base_ms: 30_000,
+                increment_ms: 100
+            }
+        );
+        assert!("5+".parse::<TimeControl>().is_err());
+    }
+
+    #[test]
+    fn parse_raw_passthrough() {
+        let tc: TimeControl = "depth 10".parse().unwrap();
+        assert_eq!(tc, TimeControl::Raw("depth 10".to_string()));
+        assert_eq!(tc.go_args(&tc.start_clock(), Color::White), "depth 10");
+        assert!("".parse::<TimeControl>().is_err());
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for s in ["movetime 500", "40/300", "5+3", "depth 10"] {
+            let tc: TimeControl = s.parse().unwrap();
+            assert_eq!(tc.to_string(), s);
+        }
+    }
+
+    #[test]
+    fn increment_added_after_move() {
+        let tc: TimeControl = "1+2".parse().unwrap();
+        let mut clock = tc.start_clock();
+        assert!(clock.record_move(&tc, Color::White, Duration::from_millis(500)));
+        assert_eq!(clock.white_ms, 60_000 - 500 + 2000);
+        assert_eq!(clock.black_ms, 60_000);
+    }
+
+    #[test]
+    fn repeating_period_added_after_last_move() {
+        let tc = TimeControl::Repeating {
+            moves: 2,
+            period_ms: 1000,
+        };
+        let mut clock = tc.start_clock();
+        assert!(clock.record_move(&tc, Color::Black, Duration::from_millis(300)));
+        assert_eq!(clock.black_ms, 700);
+        assert_eq!(
+            tc.go_args(&clock, Color::Black),
+            "wtime 1000 btime 700 movestogo 1"
+        );
+
+        assert!(clock.record_move(&tc, Color::Black, Duration::from_millis(300)));
+        assert_eq!(clock.black_ms, 1400);
+        assert_eq!(
+            tc.go_args(&clock, Color::Black),
+            "wtime 1000 btime 1400 movestogo 2"
+        );
+    }
+
+    #[test]
+    fn flag_when_clock_runs_out() {
+        let tc: TimeControl = "0.01+1".parse().unwrap();
+        let mut clock = tc.start_clock();
+        assert!(!clock.record_move(&tc, Color::White, Duration::from_millis(600)));
+        assert!(clock.white_ms <= 0);
+    }
+
+    #[test]
+    fn unclocked_never_flags() {
+        let tc = TimeControl::MoveTime(10);
+        let mut clock = tc.start_clock();
+        assert!(clock.record_move(&tc, Color::White, Duration::from_secs(60)));
+    }
+}
@@ -22,7 +22,10 @@
 
 use std::io::{BufRead, BufReader, Write};
 use std::path::Path;
-use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
 /// Information extracted from UCI `info` lines during engine search.
@@ -56,6 +59,14 @@ pub struct SearchInfo {
     pub time_ms: Option<u64>,
     /// Principal variation - the expected best line of play.
     pub pv: Vec<String>,
+    /// The selective search depth reached (in plies).
+    pub seldepth: Option<u32>,
+    /// Search speed in nodes per second.
+    pub nps: Option<u64>,
+    /// Hash table usage in permille.
+    pub hashfull: Option<u32>,
+    /// Which of several principal variations this line reports (1 = best).
+    pub multipv: Option<u32>,
 }
 
 impl SearchInfo {
@@ -120,10 +131,26 @@ impl SearchInfo {
                         _ => {}
                     }
                 }
+                "seldepth" => {
+                    i += 1;
+                    info.seldepth = parts.get(i).and_then(|s| s.parse().ok());
+                }
+                "multipv" => {
+                    i += 1;
+                    info.multipv = parts.get(i).and_then(|s| s.parse().ok());
+                }
                 "nodes" => {
                     i += 1;
                     info.nodes = parts.get(i).and_then(|s| s.parse().ok());
                 }
+                "nps" => {
+                    i += 1;
+                    info.nps = parts.get(i).and_then(|s| s.parse().ok());
+                }
+                "hashfull" => {
+                    i += 1;
+                    info.hashfull = parts.get(i).and_then(|s| s.parse().ok());
+                }
                 "time" => {
                     i += 1;
                     info.time_ms = parts.get(i).and_then(|s| s.parse().ok());
@@ -143,6 +170,36 @@ impl SearchInfo {
             None
         }
     }
+
+    /// Folds a later `info` line into this one.
+    ///
+    /// A deeper line replaces this one. A line at the same depth fills in
+    /// or overrides the fields it reports, so status lines without a score
+    /// or PV do not erase them. Shallower lines and lines for a secondary
+    /// principal variation (`multipv` other than 1) are ignored.
+    pub fn merge(&mut self, newer: SearchInfo) {
+        if newer.multipv.is_some_and(|n| n != 1) || newer.depth < self.depth {
+            return;
+        }
+        if newer.depth > self.depth {
+            *self = newer;
+            return;
+        }
+        if newer.score_cp.is_some() || newer.score_mate.is_some() {
+            // A new score replaces the old one, whichever kind it is
+            self.score_cp = newer.score_cp;
+            self.score_mate = newer.score_mate;
+        }
+        self.nodes = newer.nodes.or(self.nodes);
+        self.time_ms = newer.time_ms.or(self.time_ms);
+        self.seldepth = newer.seldepth.or(self.seldepth);
+        self.nps = newer.nps.or(self.nps);
+        self.hashfull = newer.hashfull.or(self.hashfull);
+        self.multipv = newer.multipv.or(self.multipv);
+        if !newer.pv.is_empty() {
+            self.pv = newer.pv;
+        }
+    }
 }
 
 /// Errors that can occur when communicating with a UCI engine.
@@ -162,13 +219,20 @@ pub enum UciError {
     #[allow(dead_code)]
     #[error("Invalid response: {0}")]
     InvalidResponse(String),
+    /// The engine did not respond within the allowed time.
+    #[error("Engine timed out")]
+    Timeout,
+    /// The engine process exited or closed its output.
+    #[error("Engine process exited")]
+    EngineExited,
 }
 
 /// A client for communicating with a UCI-compatible chess engine.
 ///
 /// `UciClient` manages a subprocess running a chess engine and provides
 /// methods to send UCI commands and receive responses. The engine is
-/// communicated with via stdin/stdout pipes.
+/// communicated with via stdin/stdout pipes. Output is read on a background
+/// thread so that reads can time out if the engine stops responding.
 ///
 /// # Lifecycle
 ///
@@ -181,8 +245,8 @@ pub struct UciClient {
     process: Child,
     /// Handle to write commands to the engine's stdin.
     stdin: ChildStdin,
-    /// Buffered reader for the engine's stdout.
-    stdout: BufReader<ChildStdout>,
+    /// Lines read from the engine's stdout by the reader thread.
+    lines: Receiver<String>,
     /// The engine's name as reported during UCI initialization.
     pub name: String,
 }
@@ -221,10 +285,21 @@ impl UciClient {
         let stdin = process.stdin.take().unwrap();
         let stdout = BufReader::new(process.stdout.take().unwrap());
 
+        // Forward output lines until the engine closes stdout
+        let (tx, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in stdout.lines() {
+                let Ok(line) = line else { break };
+                if tx.send(line.trim().to_string()).is_err() {
+                    break;
+                }
+            }
+        });
+
         Ok(Self {
             process,
             stdin,
-            stdout,
+            lines,
             name: String::new(),
         })
     }
@@ -254,11 +329,42 @@ impl UciClient {
     ///
     /// # Errors
     ///
-    /// Returns [`UciError::SpawnError`] if reading from stdout fails.
+    /// Returns [`UciError::EngineExited`] if the engine has closed its output.
     pub fn read_line(&mut self) -> Result<String, UciError> {
-        let mut line = String::new();
-        self.stdout.read_line(&mut line)?;
-        Ok(line.trim().to_string())
+        self.lines.recv().map_err(|_| UciError::EngineExited)
+    }
+
+    /// Reads a single line from the engine's stdout, waiting at most `timeout`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UciError::Timeout`] if no line arrives in time, or
+    /// [`UciError::EngineExited`] if the engine has closed its output.
+    pub fn read_line_timeout(&mut self, timeout: Duration) -> Result<String, UciError> {
+        self.lines.recv_timeout(timeout).map_err(|e| match e {
+            RecvTimeoutError::Timeout => UciError::Timeout,
+            RecvTimeoutError::Disconnected => UciError::EngineExited,
+        })
+    }
+
+    /// Reads a single line, giving up once `deadline` has passed.
+    ///
+    /// A `deadline` of `None` waits indefinitely.
+    fn read_line_until(&mut self, deadline: Option<Instant>) -> Result<String, UciError> {
+        match deadline {
+            Some(deadline) => {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                self.read_line_timeout(remaining)
+            }
+            None => self.read_line(),
+        }
+    }
+
+    /// Sends `isready` and waits for `readyok`, giving up once `deadline` has passed.
+    fn sync_until(&mut self, deadline: Option<Instant>) -> Result<(), UciError> {
+        self.send("isready")?;
+        while self.read_line_until(deadline)? != "readyok" {}
+        Ok(())
     }
 
     /// Initializes the UCI protocol with the engine.
@@ -285,27 +391,47 @@ impl UciClient {
     /// # Ok::<(), bot_arena::uci_client::UciError>(())
     /// ```
     pub fn init(&mut self) -> Result<(), UciError> {
+        self.init_with_timeout(None)
+    }
+
+    /// Like [`init`](Self::init), but gives up if the handshake does not finish
+    /// within `timeout`.
+    ///
+    /// The timeout covers both the `uciok` and the `readyok` replies. A
+    /// `timeout` of `None` waits indefinitely.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UciError::Timeout`] if the engine does not answer in time,
+    /// [`UciError::EngineExited`] if it exits during the handshake, or
+    /// [`UciError::SpawnError`] if sending a command fails.
+    pub fn init_with_timeout(&mut self, timeout: Option<Duration>) -> Result<(), UciError> {
+        let deadline = timeout.map(|t| Instant::now() + t);
         self.send("uci")?;
 
         loop {
-            let line = self.read_line()?;
-            if line.starts_with("id name ") {
-                self.name = line.strip_prefix("id name ").unwrap().to_string();
+            let line = self.read_line_until(deadline)?;
+            if let Some(name) = line.strip_prefix("id name ") {
+                self.name = name.to_string();
             }
             if line == "uciok" {
                 break;
             }
         }
 
-        self.send("isready")?;
-        loop {
-            let line = self.read_line()?;
-            if line == "readyok" {
-                break;
-            }
-        }
+        self.sync_until(deadline)
+    }
 
-        Ok(())
+    /// Sets an engine option with `setoption name <name> value <value>`.
+    ///
+    /// Engines ignore options they do not declare, so this is safe to send
+    /// to any engine after [`init`](Self::init).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UciError::SpawnError`] if sending the command fails.
+    pub fn set_option(&mut self, name: &str, value: &str) -> Result<(), UciError> {
+        self.send(&format!("setoption name {} value {}", name, value))
     }
 
     /// Sets the current position for the engine.
@@ -344,11 +470,32 @@ impl UciClient {
         }
     }
 
+    /// Like [`set_position`](Self::set_position), but then waits for the
+    /// engine to confirm with `readyok` within `timeout`.
+    ///
+    /// A `timeout` of `None` waits indefinitely.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UciError::Timeout`] if the engine does not answer in time,
+    /// [`UciError::EngineExited`] if it exits before answering, or
+    /// [`UciError::SpawnError`] if sending a command fails.
+    pub fn set_position_with_timeout(
+        &mut self,
+        moves: &[String],
+        timeout: Option<Duration>,
+    ) -> Result<(), UciError> {
+        let deadline = timeout.map(|t| Instant::now() + t);
+        self.set_position(moves)?;
+        self.sync_until(deadline)
+    }
+
     /// Requests the engine to calculate the best move.
     ///
     /// Sends a `go` command with the specified time control and waits for
     /// the engine to respond with `bestmove`. Returns the best move in
-    /// UCI notation along with the last search info received.
+    /// UCI notation along with the search info of the deepest principal
+    /// line, accumulated with [`SearchInfo::merge`] over all `info` lines.
     ///
     /// # Arguments
     ///
@@ -358,12 +505,13 @@ impl UciClient {
     /// # Returns
     ///
     /// A tuple of `(bestmove, Option<SearchInfo>)` where `bestmove` is the
-    /// UCI move string and `SearchInfo` contains the last search metrics
+    /// UCI move string and `SearchInfo` contains the deepest search metrics
     /// reported by the engine before returning the best move.
     ///
     /// # Errors
     ///
-    /// Returns [`UciError::SpawnError`] if communication fails.
+    /// Returns [`UciError::SpawnError`] if sending the command fails, or
+    /// [`UciError::EngineExited`] if the engine exits before answering.
     ///
     /// # Example
     ///
@@ -383,19 +531,41 @@ impl UciClient {
     /// # Ok::<(), bot_arena::uci_client::UciError>(())
     /// ```
     pub fn go(&mut self, time_control: &str) -> Result<(String, Option<SearchInfo>), UciError> {
+        self.go_with_timeout(time_control, None)
+    }
+
+    /// Like [`go`](Self::go), but gives up if no `bestmove` arrives within `timeout`.
+    ///
+    /// A `timeout` of `None` waits indefinitely.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UciError::Timeout`] if the engine does not answer in time,
+    /// [`UciError::EngineExited`] if it exits before answering, or
+    /// [`UciError::SpawnError`] if sending the command fails.
+    pub fn go_with_timeout(
+        &mut self,
+        time_control: &str,
+        timeout: Option<Duration>,
+    ) -> Result<(String, Option<SearchInfo>), UciError> {
         self.send(&format!("go {}", time_control))?;
 
+        let deadline = timeout.map(|t| Instant::now() + t);
         let mut last_info: Option<SearchInfo> = None;
 
         loop {
-            let line = self.read_line()?;
+            let line = self.read_line_until(deadline)?;
             if line.starts_with("bestmove ") {
                 let bestmove = line.split_whitespace().nth(1).unwrap_or("").to_string();
                 return Ok((bestmove, last_info));
             }
-            // Capture search info lines
+            // Accumulate search info lines
             if let Some(info) = SearchInfo::parse(&line) {
-                last_info = Some(info);
+                match &mut last_info {
+                    Some(last) => last.merge(info),
+                    None if info.multipv.is_none_or(|n| n == 1) => last_info = Some(info),
+                    None => {}
+                }
             }
         }
     }
@@ -434,6 +604,8 @@ impl Drop for UciClient {
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(unix)]
+    use test_support::{fake_engine, FakeEngine};
 
     #[test]
     fn test_uci_error_display() {
@@ -446,6 +618,9 @@ mod tests {
 
         let invalid = UciError::InvalidResponse("bad data".to_string());
         assert_eq!(invalid.to_string(), "Invalid response: bad data");
+
+        assert_eq!(UciError::Timeout.to_string(), "Engine timed out");
+        assert_eq!(UciError::EngineExited.to_string(), "Engine process exited");
     }
 
     #[test]
@@ -559,6 +734,7 @@ mod tests {
             nodes: Some(50000),
             time_ms: Some(500),
             pv: vec!["e2e4".to_string(), "e7e5".to_string()],
+            ..Default::default()
         };
 
         let json = serde_json::to_string(&info).expect("Failed to serialize");
@@ -589,6 +765,7 @@ mod tests {
             nodes: Some(50000),
             time_ms: Some(500),
             pv: vec!["e2e4".to_string()],
+            ..Default::default()
         };
 
         let cloned = info.clone();
@@ -706,4 +883,147 @@ mod tests {
         assert!(not_ready.to_string().contains("not ready"));
         assert!(invalid.to_string().contains("Invalid response"));
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_go_reports_exited_engine() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = fake_engine(dir.path(), "crasher", "exit 1");
+
+        let mut client = UciClient::spawn(&path).unwrap();
+        client.init().unwrap();
+        assert_eq!(client.name, "crasher");
+
+        client.set_position(&[]).unwrap();
+        let err = client.go("movetime 100").unwrap_err();
+        assert!(matches!(err, UciError::EngineExited));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_go_with_timeout_reports_timeout() {
+        let dir = tempfile::tempdir().unwrap();
+        // Never answers the go command
+        let path = fake_engine(dir.path(), "sleeper", ":");
+
+        let mut client = UciClient::spawn(&path).unwrap();
+        client.init().unwrap();
+        client.set_position(&[]).unwrap();
+
+        let err = client
+            .go_with_timeout("movetime 10", Some(Duration::from_millis(50)))
+            .unwrap_err();
+        assert!(matches!(err, UciError::Timeout));
+    }
+
+    #[test]
+    fn test_search_info_parse_seldepth_nps_hashfull() {
+        let line = "info depth 12 seldepth 18 multipv 1 score cp 15 nodes 80000 nps 400000 hashfull 37 time 200 pv d2d4";
+        let info = SearchInfo::parse(line).unwrap();
+        assert_eq!(info.seldepth, Some(18));
+        assert_eq!(info.multipv, Some(1));
+        assert_eq!(info.nps, Some(400000));
+        assert_eq!(info.hashfull, Some(37));
+    }
+
+    #[test]
+    fn test_search_info_merge_keeps_deepest_principal_line() {
+        let mut info = SearchInfo::parse("info depth 8 score cp 10 pv e2e4").unwrap();
+        info.merge(SearchInfo::parse("info depth 9 multipv 2 score cp -40 pv a2a3").unwrap());
+        info.merge(SearchInfo::parse("info depth 7 score cp 99 pv h2h4").unwrap());
+        assert_eq!(info.score_cp, Some(10));
+
+        // A status line at the same depth keeps the score and PV
+        info.merge(SearchInfo::parse("info depth 8 nodes 5000 nps 250000").unwrap());
+        assert_eq!(info.score_cp, Some(10));
+        assert_eq!(info.pv, vec!["e2e4"]);
+        assert_eq!(info.nps, Some(250000));
+
+        info.merge(SearchInfo::parse("info depth 8 score mate 4 pv d1h5").unwrap());
+        assert_eq!(info.score_cp, None);
+        assert_eq!(info.score_mate, Some(4));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_go_returns_deepest_info() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = fake_engine(
+            dir.path(),
+            "thinker",
+            "echo 'info depth 1 seldepth 1 score cp 5 nodes 20 pv a2a3'; \
+             echo 'info depth 2 seldepth 4 multipv 1 score cp 30 nodes 400 nps 90000 hashfull 3 time 4 pv e2e4 e7e5'; \
+             echo 'info depth 2 multipv 2 score cp 10 pv d2d4'; \
+             echo 'info depth 2 currmove g1f3 currmovenumber 3'; \
+             echo 'bestmove e2e4'",
+        );
+
+        let mut client = UciClient::spawn(&path).unwrap();
+        client.init().unwrap();
+        client.set_position(&[]).unwrap();
+
+        let (bestmove, info) = client.go("movetime 10").unwrap();
+        assert_eq!(bestmove, "e2e4");
+        let info = info.unwrap();
+        assert_eq!(info.depth, Some(2));
+        assert_eq!(info.seldepth, Some(4));
+        assert_eq!(info.score_cp, Some(30));
+        assert_eq!(info.nodes, Some(400));
+        assert_eq!(info.nps, Some(90000));
+        assert_eq!(info.hashfull, Some(3));
+        assert_eq!(info.time_ms, Some(4));
+        assert_eq!(info.pv, vec!["e2e4", "e7e5"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_init_with_timeout_reports_silent_engine() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = FakeEngine::new("silent").silent().write(dir.path());
+
+        let mut client = UciClient::spawn(&path).unwrap();
+        let started = Instant::now();
+        let err = client
+            .init_with_timeout(Some(Duration::from_millis(100)))
+            .unwrap_err();
+        assert!(matches!(err, UciError::Timeout));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_set_position_with_timeout_waits_for_readyok() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = fake_engine(dir.path(), "ready", ":");
+
+        let mut client = UciClient::spawn(&path).unwrap();
+        client
+            .init_with_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        assert_eq!(client.name, "ready");
+        client
+            .set_position_with_timeout(&["e2e4".to_string()], Some(Duration::from_secs(5)))
+            .unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_go_with_timeout_returns_bestmove() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = fake_engine(
+            dir.path(),
+            "mover",
+            "echo 'info depth 1 score cp 20'; echo 'bestmove e2e4'",
+        );
+
+        let mut client = UciClient::spawn(&path).unwrap();
+        client.init().unwrap();
+        client.set_position(&[]).unwrap();
+
+        let (bestmove, info) = client
+            .go_with_timeout("movetime 10", Some(Duration::from_secs(5)))
+            .unwrap();
+        assert_eq!(bestmove, "e2e4");
+        assert_eq!(info.unwrap().score_cp, Some(20));
+    }
 }
@@ -0,0 +1,135 @@
+//! Per-connection access control.
+//!
+//! When an auth token is configured, a connection's first message must be
+//! `{"type": "auth", "token": "..."}`; any other first message, or a wrong
+//! token, closes the connection. Authenticated connections may run a
+//! limited number of bot sessions.
+
+use serde_json::Value;
+
+/// What to do with an incoming message.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// Handle the message normally.
+    Proceed,
+    /// The message was a successful `auth` and needs no further handling.
+    Authenticated,
+    /// Refuse the message and close the connection.
+    Reject(String),
+}
+
+/// Access state for one connection.
+#[derive(Debug)]
+pub struct Access {
+    token: Option<String>,
+    authenticated: bool,
+    max_sessions: usize,
+}
+
+impl Access {
+    /// Creates the access state for a new connection. Empty tokens are
+    /// treated as unset.
+    pub fn new(token: Option<&str>, max_sessions: usize) -> Self {
+        let token = token.filter(|t| !t.is_empty()).map(str::to_string);
+        Self {
+            authenticated: token.is_none(),
+            token,
+            max_sessions,
+        }
+    }
+
+    /// Checks an incoming message against the authentication requirement.
+    pub fn check_message(&mut self, json: &Value) -> Verdict {
+        if self.authenticated {
+            return Verdict::Proceed;
+        }
+
+        let expected = self.token.as_deref().unwrap_or_default();
+        let presented = json["token"].as_str().unwrap_or_default();
+        if json["type"] != "auth" {
+            Verdict::Reject("Authentication required".to_string())
+        } else if constant_time_eq(presented.as_bytes(), expected.as_bytes()) {
+            self.authenticated = true;
+            Verdict::Authenticated
+        } else {
+            Verdict::Reject("Invalid auth token".to_string())
+        }
+    }
+
+    /// Checks whether another bot session may be started while
+    /// `active_sessions` are running.
+    pub fn check_connect(&self, active_sessions: usize) -> Result<(), String> {
+        if active_sessions >= self.max_sessions {
+            Err(format!(
+                "Session limit reached ({} per connection)",
+                self.max_sessions
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Compares two byte strings without short-circuiting on the first mismatch.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn connect_before_auth_is_refused() {
+        let mut access = Access::new(Some("secret"), 4);
+        let verdict = access.check_message(&json!({ "type": "connect", "bot": "random" }));
+        assert_eq!(
+            verdict,
+            Verdict::Reject("Authentication required".to_string())
+        );
+    }
+
+    #[test]
+    fn wrong_token_is_refused() {
+        let mut access = Access::new(Some("secret"), 4);
+        let verdict = access.check_message(&json!({ "type": "auth", "token": "guess" }));
+        assert!(matches!(verdict, Verdict::Reject(_)));
+    }
+
+    #[test]
+    fn auth_then_messages_proceed() {
+        let mut access = Access::new(Some("secret"), 4);
+        let auth = json!({ "type": "auth", "token": "secret" });
+        assert_eq!(access.check_message(&auth), Verdict::Authenticated);
+
+        let connect = json!({ "type": "connect", "bot": "random" });
+        assert_eq!(access.check_message(&connect), Verdict::Proceed);
+    }
+
+    #[test]
+    fn no_token_needs_no_auth() {
+        let mut access = Access::new(None, 4);
+        assert_eq!(
+            access.check_message(&json!({ "type": "list" })),
+            Verdict::Proceed
+        );
+
+        let mut access = Access::new(Some(""), 4);
+        assert_eq!(
+            access.check_message(&json!({ "type": "list" })),
+            Verdict::Proceed
+        );
+    }
+
+    #[test]
+    fn session_cap_rejects_extra_connects() {
+        let access = Access::new(None, 2);
+        assert!(access.check_connect(0).is_ok());
+        assert!(access.check_connect(1).is_ok());
+        assert_eq!(
+            access.check_connect(2),
+            Err("Session limit reached (2 per connection)".to_string())
+        );
+    }
+}
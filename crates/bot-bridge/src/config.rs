@@ -3,11 +3,27 @@
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::path::Path;
+use std::time::Duration;
 
 #[derive(Debug, Deserialize)]
 pub struct Config {
     #[serde(default = "default_port")]
     pub port: u16,
+    /// Seconds between WebSocket ping frames.
+    #[serde(default = "default_ping_interval_secs")]
+    pub ping_interval_secs: u64,
+    /// Seconds without client traffic after which a bot session is stopped.
+    #[serde(default = "default_session_timeout_secs")]
+    pub session_timeout_secs: u64,
+    /// Seconds a bot may take to finish the UCI startup handshake.
+    #[serde(default = "default_startup_timeout_secs")]
+    pub startup_timeout_secs: u64,
+    /// Maximum number of bot sessions a single connection may run.
+    #[serde(default = "default_max_sessions_per_connection")]
+    pub max_sessions_per_connection: usize,
+    /// Shared secret clients must send in an `auth` message before anything else.
+    #[serde(default)]
+    pub auth_token: Option<String>,
     #[serde(default)]
     pub bots: HashMap<String, BotConfig>,
 }
@@ -16,12 +32,43 @@ fn default_port() -> u16 {
     9999
 }
 
+fn default_ping_interval_secs() -> u64 {
+    15
+}
+
+fn default_session_timeout_secs() -> u64 {
+    60
+}
+
+fn default_startup_timeout_secs() -> u64 {
+    10
+}
+
+fn default_max_sessions_per_connection() -> usize {
+    4
+}
+
 #[derive(Debug, Deserialize)]
 pub struct BotConfig {
     pub command: String,
 }
 
 impl Config {
+    /// Interval between WebSocket ping frames.
+    pub fn ping_interval(&self) -> Duration {
+        Duration::from_secs(self.ping_interval_secs.max(1))
+    }
+
+    /// Time without client traffic after which a session is considered dead.
+    pub fn session_timeout(&self) -> Duration {
+        Duration::from_secs(self.session_timeout_secs)
+    }
+
+    /// Time a bot may take to answer `uciok` and `readyok` after spawning.
+    pub fn startup_timeout(&self) -> Duration {
+        Duration::from_secs(self.startup_timeout_secs)
+    }
+
     pub async fn load() -> Result<Self, Box<dyn std::error::Error>> {
         // Look for bots.toml in current directory or parent directories
         let paths = ["bots.toml", "../bots.toml", "../../bots.toml"];
@@ -39,6 +86,11 @@ impl Config {
         println!("No bots.toml found, using defaults");
         Ok(Config {
             port: default_port(),
+            ping_interval_secs: default_ping_interval_secs(),
+            session_timeout_secs: default_session_timeout_secs(),
+            startup_timeout_secs: default_startup_timeout_secs(),
+            max_sessions_per_connection: default_max_sessions_per_connection(),
+            auth_token: None,
             bots: HashMap::new(),
         })
     }
@@ -0,0 +1,120 @@
+//! Structured messages derived from bot output.
+//!
+//! Besides the raw `uci` lines, the bridge reports a bot's moves as
+//! `{"type": "bestmove", "uci", "san"}` and a bot that has no move left as
+//! `{"type": "gameover", "result"}`. The position a `bestmove` refers to is
+//! tracked from the `position` commands the client sends to the bot.
+
+use chess_engine::rules::GameResult;
+use chess_engine::Game;
+use serde_json::{json, Value};
+use uci::{GuiCommand, StartPosition};
+
+/// Returns the game set up by a `position` command, or `None` for any other
+/// command.
+///
+/// Moves are replayed up to the first illegal one. An invalid FEN falls back
+/// to the starting position, as the bots themselves do.
+pub fn position_from_command(cmd: &str) -> Option<Game> {
+    let Ok(GuiCommand::Position { start, moves }) = GuiCommand::parse(cmd) else {
+        return None;
+    };
+    let mut game = match start {
+        StartPosition::StartPos => Game::new(),
+        StartPosition::Fen(fen) => Game::from_fen(&fen).unwrap_or_default(),
+    };
+    for uci in &moves {
+        if game.make_move_uci(uci).is_err() {
+            break;
+        }
+    }
+    Some(game)
+}
+
+/// Builds the structured message for a `bestmove` line played in `game`.
+///
+/// Returns `None` if `line` is not a `bestmove` line. A null move
+/// (`(none)` or `0000`) becomes a `gameover` message with the game's result
+/// in PGN notation, or `*` if the rules do not end the game. A move that is
+/// not legal in `game` keeps its UCI with a null `san`.
+pub fn bestmove_message(line: &str, game: &Game) -> Option<Value> {
+    let mut parts = line.split_whitespace();
+    if parts.next() != Some("bestmove") {
+        return None;
+    }
+    let uci = parts.next().unwrap_or("");
+
+    if uci.is_empty() || uci == "(none)" || uci == "0000" {
+        let result = match game.result() {
+            Some(GameResult::WhiteWins) => "1-0",
+            Some(GameResult::BlackWins) => "0-1",
+            Some(GameResult::Draw(_)) => "1/2-1/2",
+            None => "*",
+        };
+        return Some(json!({ "type": "gameover", "result": result }));
+    }
+
+    let mut after = game.clone();
+    let played = after
+        .make_move_uci(uci)
+        .ok()
+        .and_then(|()| after.move_history().last());
+    // Castling written as king-takes-rook is reported as standard UCI
+    let uci = played.map_or_else(|| uci.to_string(), |m| m.mov.to_uci());
+    let san = played.map(|m| m.san.clone());
+    Some(json!({ "type": "bestmove", "uci": uci, "san": san }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bestmove_is_enriched_with_san() {
+        let msg = bestmove_message("bestmove e2e4 ponder e7e5", &Game::new()).unwrap();
+        assert_eq!(msg["type"], "bestmove");
+        assert_eq!(msg["uci"], "e2e4");
+        assert_eq!(msg["san"], "e4");
+
+        assert!(bestmove_message("info depth 1 pv e2e4", &Game::new()).is_none());
+    }
+
+    #[test]
+    fn illegal_bestmove_keeps_uci() {
+        let msg = bestmove_message("bestmove e2e5", &Game::new()).unwrap();
+        assert_eq!(msg["uci"], "e2e5");
+        assert!(msg["san"].is_null());
+    }
+
+    #[test]
+    fn king_takes_rook_castling_is_reported_as_standard_uci() {
+        let game =
+            position_from_command("position fen r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+        let msg = bestmove_message("bestmove e1h1", &game).unwrap();
+        assert_eq!(msg["uci"], "e1g1");
+        assert_eq!(msg["san"], "O-O");
+    }
+
+    #[test]
+    fn position_commands_are_tracked() {
+        let game = position_from_command("position startpos moves e2e4 e7e5 g1f3").unwrap();
+        let msg = bestmove_message("bestmove b8c6", &game).unwrap();
+        assert_eq!(msg["san"], "Nc6");
+
+        let game = position_from_command("position fen 8/8/8/8/8/8/8/K6k w - - 0 1").unwrap();
+        assert_eq!(game.to_fen(), "8/8/8/8/8/8/8/K6k w - - 0 1");
+
+        assert!(position_from_command("go movetime 100").is_none());
+    }
+
+    #[test]
+    fn null_move_reports_game_over() {
+        // Fool's mate: white is checkmated
+        let game = position_from_command("position startpos moves f2f3 e7e5 g2g4 d8h4").unwrap();
+        let msg = bestmove_message("bestmove (none)", &game).unwrap();
+        assert_eq!(msg, json!({ "type": "gameover", "result": "0-1" }));
+
+        let msg = bestmove_message("bestmove 0000", &Game::new()).unwrap();
+        assert_eq!(msg["result"], "*");
+    }
+}
@@ -3,20 +3,31 @@
 //! This server accepts WebSocket connections from the browser and routes
 //! UCI commands to/from bot processes via stdin/stdout.
 //! Supports multiple concurrent bot sessions per connection.
+//!
+//! Each connection is pinged periodically. Bot sessions whose client sends
+//! no traffic (including pongs) within the configured timeout are stopped,
+//! so a closed browser tab does not leave bot processes behind.
+//!
+//! Access rules are described in [`access`]. Bot moves are also reported as
+//! structured messages, described in [`enrich`].
 
+mod access;
 mod config;
+mod enrich;
 mod session;
 
 use futures_util::{SinkExt, StreamExt};
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::net::TcpListener;
 use tokio::sync::RwLock;
 use tokio_tungstenite::tungstenite::Message;
 
+use access::{Access, Verdict};
 use config::Config;
-use session::BotSession;
+use session::{reap_stale, BotSession};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -61,26 +72,42 @@ async fn handle_connection(
     // Channel for bot output -> websocket
     let (bot_tx, mut bot_rx) = tokio::sync::mpsc::channel::<String>(100);
 
-    // Task to forward bot output to websocket
+    // Task to forward bot output to websocket, ping the client, and reap
+    // sessions whose client has gone quiet
     let sessions_clone = Arc::clone(&sessions);
+    let ping_interval = config.ping_interval();
+    let session_timeout = config.session_timeout();
     let forward_task = tokio::spawn(async move {
-        while let Some(line) = bot_rx.recv().await {
-            // Check if this is already a control message (JSON with "type" field)
-            let msg_str = if let Ok(json) = serde_json::from_str::<serde_json::Value>(&line) {
-                if json.get("type").is_some() {
-                    // Already a control message, pass through directly
-                    line
-                } else {
-                    // Wrap as UCI output
-                    serde_json::json!({ "type": "uci", "line": line }).to_string()
+        let mut heartbeat = tokio::time::interval(ping_interval);
+        loop {
+            tokio::select! {
+                line = bot_rx.recv() => {
+                    let Some(line) = line else {
+                        break;
+                    };
+                    if ws_sender
+                        .send(Message::Text(wrap_output(line).into()))
+                        .await
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+                _ = heartbeat.tick() => {
+                    if ws_sender.send(Message::Ping(Vec::new().into())).await.is_err() {
+                        break;
+                    }
+                    let mut sessions = sessions_clone.write().await;
+                    for name in reap_stale(&mut sessions, Instant::now(), session_timeout).await {
+                        println!("Stopped idle bot session {} for {}", name, peer);
+                        let response = serde_json::json!({
+                            "type": "disconnected",
+                            "bot": name,
+                            "reason": "timeout"
+                        });
+                        let _ = ws_sender.send(Message::Text(response.to_string().into())).await;
+                    }
                 }
-            } else {
-                // Not JSON, wrap as UCI output
-                serde_json::json!({ "type": "uci", "line": line }).to_string()
-            };
-
-            if ws_sender.send(Message::Text(msg_str.into())).await.is_err() {
-                break;
             }
         }
         // Clean up all sessions on disconnect
@@ -90,8 +117,20 @@ async fn handle_connection(
         }
     });
 
+    let mut access = Access::new(
+        config.auth_token.as_deref(),
+        config.max_sessions_per_connection,
+    );
+
     // Handle incoming websocket messages
     while let Some(msg) = ws_receiver.next().await {
+        // Any traffic, including pongs, shows the client is still alive
+        if msg.is_ok() {
+            for sess in sessions.write().await.values_mut() {
+                sess.touch();
+            }
+        }
+
         let msg = match msg {
             Ok(Message::Text(text)) => text,
             Ok(Message::Close(_)) => break,
@@ -108,9 +147,34 @@ async fn handle_connection(
             Err(_) => continue,
         };
 
+        match access.check_message(&json) {
+            Verdict::Proceed => {}
+            Verdict::Authenticated => {
+                let response = serde_json::json!({ "type": "authenticated" });
+                bot_tx.send(response.to_string()).await.ok();
+                continue;
+            }
+            Verdict::Reject(reason) => {
+                println!("Rejected connection from {}: {}", peer, reason);
+                let response = serde_json::json!({ "type": "error", "message": reason });
+                bot_tx.send(response.to_string()).await.ok();
+                // No sessions exist yet, so dropping the sender lets the
+                // forward task flush the error and finish
+                drop(bot_tx);
+                let _ = forward_task.await;
+                return Ok(());
+            }
+        }
+
         let msg_type = json["type"].as_str().unwrap_or("");
 
         match msg_type {
+            "ping" => {
+                // App-level liveness check
+                let response = serde_json::json!({ "type": "pong" });
+                bot_tx.send(response.to_string()).await.ok();
+            }
+
             "list" => {
                 // List available bots
                 let bots: Vec<&String> = config.bots.keys().collect();
@@ -135,9 +199,21 @@ async fn handle_connection(
                     continue;
                 }
 
+                if let Err(message) = access.check_connect(sessions.read().await.len()) {
+                    let response = serde_json::json!({ "type": "error", "message": message });
+                    bot_tx.send(response.to_string()).await.ok();
+                    continue;
+                }
+
                 // Look up bot config
                 if let Some(bot_config) = config.bots.get(bot_name) {
-                    match BotSession::spawn(&bot_config.command, bot_tx.clone()).await {
+                    match BotSession::spawn(
+                        &bot_config.command,
+                        bot_tx.clone(),
+                        config.startup_timeout(),
+                    )
+                    .await
+                    {
                         Ok(sess) => {
                             let session_id = sess.id.clone();
                             sessions.write().await.insert(bot_name.to_string(), sess);
@@ -230,3 +306,12 @@ async fn handle_connection(
     println!("Connection closed from {}", peer);
     Ok(())
 }
+
+/// Wraps a line of bot output as a `uci` message unless it already is a
+/// control message (JSON with a `type` field).
+fn wrap_output(line: String) -> String {
+    match serde_json::from_str::<serde_json::Value>(&line) {
+        Ok(json) if json.get("type").is_some() => line,
+        _ => serde_json::json!({ "type": "uci", "line": line }).to_string(),
+    }
+}
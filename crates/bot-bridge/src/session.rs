@@ -1,22 +1,63 @@
 //! Bot session management.
 
+use std::collections::HashMap;
 use std::process::Stdio;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader, Lines};
 use tokio::process::{Child, Command};
 use tokio::sync::mpsc;
 
+use crate::enrich::{bestmove_message, position_from_command};
+use chess_engine::Game;
+
+/// Errors from the UCI startup handshake.
+#[derive(Debug, Error)]
+pub enum HandshakeError {
+    /// The bot did not send the expected reply in time.
+    #[error("bot did not send {expected} within {timeout:?}")]
+    Timeout {
+        expected: &'static str,
+        timeout: Duration,
+    },
+    /// The bot closed its output before finishing the handshake.
+    #[error("bot exited during the UCI handshake")]
+    Exited,
+    /// Reading from or writing to the bot failed.
+    #[error("I/O error during the UCI handshake: {0}")]
+    Io(#[from] std::io::Error),
+}
+
 /// A running bot session.
 pub struct BotSession {
     pub id: String,
     child: Child,
     stdin_tx: mpsc::Sender<String>,
+    last_activity: Instant,
+    /// The position most recently sent to the bot.
+    game: Arc<Mutex<Game>>,
+}
+
+/// A session that can be reaped once its client goes quiet.
+pub trait Session {
+    /// When the client last showed signs of life.
+    fn last_activity(&self) -> Instant;
+
+    /// Stop the session and release its resources.
+    async fn stop(self);
 }
 
 impl BotSession {
     /// Spawn a new bot process.
+    ///
+    /// The bot must complete the `uci`/`uciok` and `isready`/`readyok`
+    /// handshake within `startup_timeout`, otherwise it is killed and a
+    /// [`HandshakeError`] is returned.
     pub async fn spawn(
         command: &str,
         output_tx: mpsc::Sender<String>,
+        startup_timeout: Duration,
     ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
         // Parse command and args
         let parts: Vec<&str> = command.split_whitespace().collect();
@@ -29,8 +70,14 @@ impl BotSession {
             .stderr(Stdio::piped())
             .spawn()?;
 
-        let stdin = child.stdin.take().ok_or("Failed to open stdin")?;
+        let mut stdin = child.stdin.take().ok_or("Failed to open stdin")?;
         let stdout = child.stdout.take().ok_or("Failed to open stdout")?;
+        let mut lines = BufReader::new(stdout).lines();
+
+        if let Err(e) = handshake(&mut stdin, &mut lines, startup_timeout).await {
+            let _ = child.kill().await;
+            return Err(e.into());
+        }
 
         // Generate session ID
         let id = format!("{:x}", rand_id());
@@ -54,15 +101,22 @@ impl BotSession {
             }
         });
 
-        // Task to read from stdout
+        // Task to read from stdout, following each `bestmove` with its
+        // structured message
+        let game = Arc::new(Mutex::new(Game::new()));
         let output_tx_clone = output_tx.clone();
+        let game_clone = Arc::clone(&game);
         tokio::spawn(async move {
-            let reader = BufReader::new(stdout);
-            let mut lines = reader.lines();
             while let Ok(Some(line)) = lines.next_line().await {
+                let enriched = bestmove_message(&line, &game_clone.lock().unwrap());
                 if output_tx_clone.send(line).await.is_err() {
                     break;
                 }
+                if let Some(msg) = enriched {
+                    if output_tx_clone.send(msg.to_string()).await.is_err() {
+                        break;
+                    }
+                }
             }
         });
 
@@ -70,14 +124,27 @@ impl BotSession {
             id,
             child,
             stdin_tx,
+            last_activity: Instant::now(),
+            game,
         })
     }
 
     /// Send a UCI command to the bot.
+    ///
+    /// `position` commands also update the position that later `bestmove`
+    /// replies are read against.
     pub async fn send(&self, cmd: &str) -> Result<(), mpsc::error::SendError<String>> {
+        if let Some(game) = position_from_command(cmd) {
+            *self.game.lock().unwrap() = game;
+        }
         self.stdin_tx.send(cmd.to_string()).await
     }
 
+    /// Record client activity for this session.
+    pub fn touch(&mut self) {
+        self.last_activity = Instant::now();
+    }
+
     /// Stop the bot process.
     pub async fn stop(mut self) {
         // Try graceful shutdown first
@@ -91,6 +158,67 @@ impl BotSession {
     }
 }
 
+/// Runs the UCI startup handshake, consuming the bot's replies.
+async fn handshake<W, R>(
+    stdin: &mut W,
+    lines: &mut Lines<R>,
+    timeout: Duration,
+) -> Result<(), HandshakeError>
+where
+    W: AsyncWrite + Unpin,
+    R: AsyncBufRead + Unpin,
+{
+    for (command, expected) in [("uci", "uciok"), ("isready", "readyok")] {
+        stdin.write_all(format!("{command}\n").as_bytes()).await?;
+        stdin.flush().await?;
+
+        let reply = async {
+            while let Some(line) = lines.next_line().await? {
+                if line.trim() == expected {
+                    return Ok(());
+                }
+            }
+            Err(HandshakeError::Exited)
+        };
+        tokio::time::timeout(timeout, reply)
+            .await
+            .map_err(|_| HandshakeError::Timeout { expected, timeout })??;
+    }
+    Ok(())
+}
+
+impl Session for BotSession {
+    fn last_activity(&self) -> Instant {
+        self.last_activity
+    }
+
+    async fn stop(self) {
+        BotSession::stop(self).await;
+    }
+}
+
+/// Stop and remove every session idle for longer than `timeout` at `now`.
+///
+/// Returns the names of the removed sessions.
+pub async fn reap_stale<S: Session>(
+    sessions: &mut HashMap<String, S>,
+    now: Instant,
+    timeout: Duration,
+) -> Vec<String> {
+    let stale: Vec<String> = sessions
+        .iter()
+        .filter(|(_, sess)| now.saturating_duration_since(sess.last_activity()) > timeout)
+        .map(|(name, _)| name.clone())
+        .collect();
+
+    for name in &stale {
+        if let Some(sess) = sessions.remove(name) {
+            sess.stop().await;
+        }
+    }
+    stale
+}
+
 fn rand_id() -> u64 {
     use std::time::{SystemTime, UNIX_EPOCH};
     let duration = SystemTime::now()
@@ -98,3 +226,88 @@ fn rand_id() -> u64 {
         .unwrap_or_default();
     duration.as_nanos() as u64
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    struct MockSession {
+        last_activity: Instant,
+        stopped: Arc<AtomicBool>,
+    }
+
+    impl Session for MockSession {
+        fn last_activity(&self) -> Instant {
+            self.last_activity
+        }
+
+        async fn stop(self) {
+            self.stopped.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn spawn_fails_when_bot_never_sends_uciok() {
+        // `cat` echoes commands back but never answers them
+        let (tx, _rx) = mpsc::channel(10);
+        let err = BotSession::spawn("cat", tx, Duration::from_millis(200))
+            .await
+            .err()
+            .unwrap();
+
+        match err.downcast_ref::<HandshakeError>() {
+            Some(HandshakeError::Timeout { expected, .. }) => assert_eq!(*expected, "uciok"),
+            other => panic!("expected a timeout, got {other:?}"),
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn spawn_fails_when_bot_exits_during_handshake() {
+        let (tx, _rx) = mpsc::channel(10);
+        let err = BotSession::spawn("true", tx, Duration::from_secs(5))
+            .await
+            .err()
+            .unwrap();
+
+        assert!(matches!(
+            err.downcast_ref::<HandshakeError>(),
+            Some(HandshakeError::Exited | HandshakeError::Io(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn reaper_stops_sessions_past_timeout() {
+        let start = Instant::now();
+        let idle_stopped = Arc::new(AtomicBool::new(false));
+        let active_stopped = Arc::new(AtomicBool::new(false));
+
+        let mut sessions = HashMap::new();
+        sessions.insert(
+            "idle".to_string(),
+            MockSession {
+                last_activity: start,
+                stopped: Arc::clone(&idle_stopped),
+            },
+        );
+        sessions.insert(
+            "active".to_string(),
+            MockSession {
+                last_activity: start + Duration::from_secs(50),
+                stopped: Arc::clone(&active_stopped),
+            },
+        );
+
+        let now = start + Duration::from_secs(61);
+        let reaped = reap_stale(&mut sessions, now, Duration::from_secs(60)).await;
+
+        assert_eq!(reaped, vec!["idle".to_string()]);
+        assert!(idle_stopped.load(Ordering::SeqCst));
+        assert!(!active_stopped.load(Ordering::SeqCst));
+        assert!(sessions.contains_key("active"));
+        assert!(!sessions.contains_key("idle"));
+    }
+}
@@ -0,0 +1,128 @@
+//! Opening book support.
+//!
+//! The bot looks up the game's move history in the built-in
+//! [`MoveDatabase`] and plays a weighted-random book move while one exists,
+//! saving search time and varying its openings between games.
+
+use chess_core::Move;
+use chess_engine::rules::RuleSet;
+use chess_engine::{Position, StandardChess};
+use chess_openings::builtin::builtin_database;
+use chess_openings::MoveDatabase;
+use rand::Rng;
+
+/// Default number of plies for which the book is consulted.
+pub const DEFAULT_BOOK_DEPTH: usize = 8;
+
+/// An opening book with a depth limit that can be switched off.
+pub struct OpeningBook {
+    database: MoveDatabase,
+    /// Whether book moves are played at all (UCI `OwnBook`).
+    pub enabled: bool,
+    /// Number of plies from the start for which the book is consulted.
+    pub depth: usize,
+}
+
+impl OpeningBook {
+    /// Creates a book backed by the built-in opening database.
+    pub fn builtin() -> Self {
+        Self::new(builtin_database())
+    }
+
+    /// Creates an enabled book over `database` with the default depth.
+    pub fn new(database: MoveDatabase) -> Self {
+        Self {
+            database,
+            enabled: true,
+            depth: DEFAULT_BOOK_DEPTH,
+        }
+    }
+
+    /// Picks a book move for `position`, reached by playing `history` (UCI
+    /// moves) from the starting position.
+    ///
+    /// Returns `None` when the book is disabled, the game is past the book
+    /// depth, the position is not in the book, or the chosen book move is
+    /// not legal in `position`.
+    pub fn pick<R: Rng>(
+        &self,
+        position: &Position,
+        history: &[String],
+        rng: &mut R,
+    ) -> Option<Move> {
+        if !self.enabled || history.len() >= self.depth {
+            return None;
+        }
+
+        let key = history.join(" ");
+        let uci = &self.database.select_move(&key, rng)?.uci;
+
+        StandardChess.generate_moves(position).find_uci(uci)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uci_moves(moves: &[&str]) -> Vec<String> {
+        moves.iter().map(|m| m.to_string()).collect()
+    }
+
+    #[test]
+    fn start_position_plays_a_book_first_move() {
+        let book = OpeningBook::builtin();
+        let first_moves: Vec<String> = builtin_database()
+            .lookup("")
+            .unwrap()
+            .iter()
+            .map(|m| m.uci.clone())
+            .collect();
+
+        let position = StandardChess.initial_position();
+        let mut rng = rand::rng();
+        for _ in 0..20 {
+            let mv = book.pick(&position, &[], &mut rng).unwrap();
+            assert!(first_moves.contains(&mv.to_uci()), "{}", mv.to_uci());
+        }
+    }
+
+    #[test]
+    fn follows_book_after_moves() {
+        let book = OpeningBook::builtin();
+        let history = uci_moves(&["e2e4", "e7e5"]);
+        let mut position = StandardChess.initial_position();
+        for uci in &history {
+            let legal = StandardChess
+                .generate_moves(&position)
+                .find_uci(uci)
+                .unwrap();
+            position = StandardChess.make_move(&position, legal);
+        }
+
+        let mv = book.pick(&position, &history, &mut rand::rng()).unwrap();
+        assert!(["g1f3", "f1c4", "b1c3"].contains(&mv.to_uci().as_str()));
+    }
+
+    #[test]
+    fn disabled_or_too_deep_book_is_skipped() {
+        let position = StandardChess.initial_position();
+        let mut rng = rand::rng();
+
+        let mut book = OpeningBook::builtin();
+        book.enabled = false;
+        assert!(book.pick(&position, &[], &mut rng).is_none());
+
+        let mut book = OpeningBook::builtin();
+        book.depth = 0;
+        assert!(book.pick(&position, &[], &mut rng).is_none());
+    }
+
+    #[test]
+    fn unknown_line_is_not_in_book() {
+        let book = OpeningBook::builtin();
+        let history = uci_moves(&["a2a3"]);
+        let position = StandardChess.initial_position();
+        assert!(book.pick(&position, &history, &mut rand::rng()).is_none());
+    }
+}
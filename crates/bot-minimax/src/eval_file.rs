@@ -0,0 +1,157 @@
+//! Evaluation parameters loaded from a file.
+//!
+//! Piece values and piece-square tables can be tuned without recompiling by
+//! pointing the bot at a TOML file, either with `setoption name EvalFile
+//! value <path>` or the [`EVAL_FILE_ENV`] environment variable:
+//!
+//! ```toml
+//! [values]
+//! knight = 300
+//!
+//! [tables]
+//! pawn = [0, 0, 0, 0, 0, 0, 0, 0, ...] # 64 entries, a1 first
+//! ```
+//!
+//! Tables are indexed by square index, a1 = 0 through h8 = 63, from white's
+//! point of view. Pieces missing from the file keep their built-in value and
+//! table. The king's value is ignored.
+
+use chess_core::Piece;
+use chess_engine::eval::EvalParams;
+use serde::Deserialize;
+use std::path::Path;
+use thiserror::Error;
+
+/// Environment variable naming an evaluation file to load at startup.
+pub const EVAL_FILE_ENV: &str = "MINIMAX_EVAL_FILE";
+
+/// Errors from loading an evaluation file.
+#[derive(Debug, Error)]
+pub enum EvalFileError {
+    /// The file could not be read.
+    #[error("failed to read eval file: {0}")]
+    Read(#[from] std::io::Error),
+    /// The file is not valid TOML or has unknown keys.
+    #[error("failed to parse eval file: {0}")]
+    Parse(#[from] toml::de::Error),
+    /// A piece-square table does not have 64 entries.
+    #[error("table for {piece} has {len} entries, expected 64")]
+    TableLength { piece: &'static str, len: usize },
+}
+
+/// One entry per piece, all optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PerPiece<T> {
+    pawn: Option<T>,
+    knight: Option<T>,
+    bishop: Option<T>,
+    rook: Option<T>,
+    queen: Option<T>,
+    king: Option<T>,
+}
+
+impl<T> PerPiece<T> {
+    /// Returns the entries that are set, with their piece and its key.
+    fn into_entries(self) -> impl Iterator<Item = (Piece, &'static str, T)> {
+        [
+            (Piece::Pawn, "pawn", self.pawn),
+            (Piece::Knight, "knight", self.knight),
+            (Piece::Bishop, "bishop", self.bishop),
+            (Piece::Rook, "rook", self.rook),
+            (Piece::Queen, "queen", self.queen),
+            (Piece::King, "king", self.king),
+        ]
+        .into_iter()
+        .filter_map(|(piece, key, entry)| entry.map(|e| (piece, key, e)))
+    }
+}
+
+/// The layout of an evaluation file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EvalFile {
+    #[serde(default)]
+    values: PerPiece<i32>,
+    #[serde(default)]
+    tables: PerPiece<Vec<i32>>,
+}
+
+/// Parses evaluation parameters from TOML, starting from the built-in ones.
+pub fn parse_eval_params(text: &str) -> Result<EvalParams, EvalFileError> {
+    let file: EvalFile = toml::from_str(text)?;
+    let mut params = EvalParams::BUILTIN;
+
+    for (piece, _, value) in file.values.into_entries() {
+        params.values[piece.index()] = value;
+    }
+    for (piece, key, table) in file.tables.into_entries() {
+        params.tables[piece.index()] =
+            table
+                .try_into()
+                .map_err(|t: Vec<i32>| EvalFileError::TableLength {
+                    piece: key,
+                    len: t.len(),
+                })?;
+    }
+
+    Ok(params)
+}
+
+/// Loads evaluation parameters from the TOML file at `path`.
+pub fn load_eval_params(path: impl AsRef<Path>) -> Result<EvalParams, EvalFileError> {
+    parse_eval_params(&std::fs::read_to_string(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chess_engine::Position;
+
+    #[test]
+    fn tweaked_values_and_tables_change_evaluate() {
+        // Centralising knights is worth nothing, but knights are worth more
+        let text = format!(
+            "[values]\nknight = 400\n\n[tables]\nknight = [{}]\n",
+            vec!["0"; 64].join(", ")
+        );
+        let params = parse_eval_params(&text).unwrap();
+        assert_eq!(params.values[Piece::Knight.index()], 400);
+        assert_eq!(
+            params.values[Piece::Pawn.index()],
+            EvalParams::BUILTIN.values[Piece::Pawn.index()]
+        );
+
+        // White is a knight up, black to move
+        let position = Position::from_fen("4k3/8/8/8/3N4/8/8/4K3 b - - 0 1").unwrap();
+        let kings = EvalParams::BUILTIN
+            .evaluate_white(&Position::from_fen("4k3/8/8/8/8/8/8/4K3 b - - 0 1").unwrap());
+        assert_eq!(params.evaluate_white(&position), kings + 400);
+        assert_eq!(params.evaluate(&position), -(kings + 400));
+    }
+
+    #[test]
+    fn empty_file_keeps_builtin_params() {
+        assert_eq!(parse_eval_params("").unwrap(), EvalParams::BUILTIN);
+    }
+
+    #[test]
+    fn short_table_is_rejected() {
+        let err = parse_eval_params("[tables]\nrook = [1, 2, 3]").unwrap_err();
+        assert!(matches!(
+            err,
+            EvalFileError::TableLength {
+                piece: "rook",
+                len: 3
+            }
+        ));
+    }
+
+    #[test]
+    fn unknown_piece_is_rejected() {
+        assert!(matches!(
+            parse_eval_params("[values]\nelephant = 250"),
+            Err(EvalFileError::Parse(_))
+        ));
+    }
+}
@@ -1,173 +1,429 @@
 //! Minimax bot with alpha-beta pruning.
 //!
 //! A basic chess bot that uses minimax search with alpha-beta pruning
-//! and a simple material + position evaluation function.
+//! and a simple material + position evaluation function. Opening moves come
+//! from the built-in opening book while the game is still in it. Piece
+//! values and tables can be replaced from an evaluation file.
 
-use chess_core::{Color, Move, Piece};
+mod book;
+mod eval_file;
+
+use book::OpeningBook;
+use chess_core::{Color, Move, MoveFlag, Piece};
+use chess_engine::eval::EvalParams;
 use chess_engine::rules::RuleSet;
 use chess_engine::{is_king_attacked, Position, StandardChess};
-use std::io::{BufReader, Stdin, Stdout};
+use std::io::{BufRead, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
 use std::time::{Duration, Instant};
-use uci::{stdio_engine, GuiCommand, InfoBuilder, UciEngine};
-
-type StdioEngine = UciEngine<BufReader<Stdin>, Stdout>;
-
-/// Piece values in centipawns
-const PAWN_VALUE: i32 = 100;
-const KNIGHT_VALUE: i32 = 320;
-const BISHOP_VALUE: i32 = 330;
-const ROOK_VALUE: i32 = 500;
-const QUEEN_VALUE: i32 = 900;
-
-/// Piece-square tables for positional evaluation (from white's perspective).
-/// Values are in centipawns, added to piece base value.
-const PAWN_PST: [i32; 64] = [
-    0, 0, 0, 0, 0, 0, 0, 0, 50, 50, 50, 50, 50, 50, 50, 50, 10, 10, 20, 30, 30, 20, 10, 10, 5, 5,
-    10, 25, 25, 10, 5, 5, 0, 0, 0, 20, 20, 0, 0, 0, 5, -5, -10, 0, 0, -10, -5, 5, 5, 10, 10, -20,
-    -20, 10, 10, 5, 0, 0, 0, 0, 0, 0, 0, 0,
-];
-
-const KNIGHT_PST: [i32; 64] = [
-    -50, -40, -30, -30, -30, -30, -40, -50, -40, -20, 0, 0, 0, 0, -20, -40, -30, 0, 10, 15, 15, 10,
-    0, -30, -30, 5, 15, 20, 20, 15, 5, -30, -30, 0, 15, 20, 20, 15, 0, -30, -30, 5, 10, 15, 15, 10,
-    5, -30, -40, -20, 0, 5, 5, 0, -20, -40, -50, -40, -30, -30, -30, -30, -40, -50,
-];
-
-const BISHOP_PST: [i32; 64] = [
-    -20, -10, -10, -10, -10, -10, -10, -20, -10, 0, 0, 0, 0, 0, 0, -10, -10, 0, 5, 10, 10, 5, 0,
-    -10, -10, 5, 5, 10, 10, 5, 5, -10, -10, 0, 10, 10, 10, 10, 0, -10, -10, 10, 10, 10, 10, 10, 10,
-    -10, -10, 5, 0, 0, 0, 0, 5, -10, -20, -10, -10, -10, -10, -10, -10, -20,
-];
-
-const ROOK_PST: [i32; 64] = [
-    0, 0, 0, 0, 0, 0, 0, 0, 5, 10, 10, 10, 10, 10, 10, 5, -5, 0, 0, 0, 0, 0, 0, -5, -5, 0, 0, 0, 0,
-    0, 0, -5, -5, 0, 0, 0, 0, 0, 0, -5, -5, 0, 0, 0, 0, 0, 0, -5, -5, 0, 0, 0, 0, 0, 0, -5, 0, 0,
-    0, 5, 5, 0, 0, 0,
-];
-
-const QUEEN_PST: [i32; 64] = [
-    -20, -10, -10, -5, -5, -10, -10, -20, -10, 0, 0, 0, 0, 0, 0, -10, -10, 0, 5, 5, 5, 5, 0, -10,
-    -5, 0, 5, 5, 5, 5, 0, -5, 0, 0, 5, 5, 5, 5, 0, -5, -10, 5, 5, 5, 5, 5, 0, -10, -10, 0, 5, 0, 0,
-    0, 0, -10, -20, -10, -10, -5, -5, -10, -10, -20,
-];
-
-const KING_MIDDLEGAME_PST: [i32; 64] = [
-    -30, -40, -40, -50, -50, -40, -40, -30, -30, -40, -40, -50, -50, -40, -40, -30, -30, -40, -40,
-    -50, -50, -40, -40, -30, -30, -40, -40, -50, -50, -40, -40, -30, -20, -30, -30, -40, -40, -30,
-    -30, -20, -10, -20, -20, -20, -20, -20, -20, -10, 20, 20, 0, 0, 0, 0, 20, 20, 20, 30, 10, 0, 0,
-    10, 30, 20,
-];
+use uci::{
+    stdio_engine, EngineMessage, GoOptions, GuiCommand, InfoBuilder, OptionType, StartPosition,
+    UciEngine,
+};
+
+/// Score for delivering checkmate, reduced by the plies needed to reach it
+const MATE_SCORE: i32 = 100_000;
+
+/// Scores within this many plies of [`MATE_SCORE`] are forced mates
+const MAX_MATE_PLY: i32 = 1_000;
+
+/// Depth reduction for the null-move search
+const NULL_MOVE_REDUCTION: u8 = 2;
+
+/// Bound above any reachable score
+const INFINITY: i32 = MATE_SCORE + 1;
+
+/// Initial half-width of the aspiration window around the previous score.
+/// Without a quiescence search scores swing between odd and even depths, so
+/// the window is wide enough to absorb a typical swing.
+const ASPIRATION_WINDOW: i32 = 100;
+
+/// Deepest ply for which killer moves are kept
+const MAX_PLY: usize = 128;
+
+/// Ordering score of captures and promotions, searched first
+const TACTICAL_ORDER: u32 = 3_000_000;
+
+/// Ordering score of the first killer move; the second scores one less
+const KILLER_ORDER: u32 = 2_000_001;
+
+/// Default time reserved per move for communication delays
+const DEFAULT_MOVE_OVERHEAD_MS: u64 = 30;
+
+/// Shortest search the bot will run, however little time is left
+const MIN_SEARCH_TIME: Duration = Duration::from_millis(1);
+
+/// Largest `Contempt` accepted, in centipawns
+const MAX_CONTEMPT: i32 = 500;
+
+/// Options declared in reply to `uci`.
+///
+/// `Hash` and `Threads` are accepted for GUI compatibility; the search has
+/// no hash table and runs on one thread.
+fn uci_options() -> Vec<EngineMessage> {
+    let spin = |name: &str, default: i64, min: i64, max: i64| EngineMessage::Opt {
+        name: name.to_string(),
+        opt_type: OptionType::Spin,
+        default: Some(default.to_string()),
+        min: Some(min),
+        max: Some(max),
+        var: Vec::new(),
+    };
+    vec![
+        spin("Hash", 16, 1, 1024),
+        spin("Threads", 1, 1, 1),
+        spin("MoveOverhead", DEFAULT_MOVE_OVERHEAD_MS as i64, 0, 5000),
+        EngineMessage::Opt {
+            name: "Ponder".to_string(),
+            opt_type: OptionType::Check,
+            default: Some("false".to_string()),
+            min: None,
+            max: None,
+            var: Vec::new(),
+        },
+        EngineMessage::Opt {
+            name: "OwnBook".to_string(),
+            opt_type: OptionType::Check,
+            default: Some("true".to_string()),
+            min: None,
+            max: None,
+            var: Vec::new(),
+        },
+        spin("BookDepth", book::DEFAULT_BOOK_DEPTH as i64, 0, 100),
+        spin(
+            "Contempt",
+            0,
+            -i64::from(MAX_CONTEMPT),
+            i64::from(MAX_CONTEMPT),
+        ),
+        EngineMessage::Opt {
+            name: "EvalFile".to_string(),
+            opt_type: OptionType::String,
+            default: Some("<empty>".to_string()),
+            min: None,
+            max: None,
+            var: Vec::new(),
+        },
+    ]
+}
+
+/// Returns how long to search for a `go` command with `side` to move.
+///
+/// Uses `movetime` when given, otherwise about 2.5% of the remaining clock,
+/// otherwise one second. `move_overhead` is then subtracted so the move
+/// reaches the GUI before the clock runs out. `go infinite` has no limit.
+fn search_time(opts: &GoOptions, side: Color, move_overhead: Duration) -> Duration {
+    if opts.infinite {
+        return Duration::MAX;
+    }
+    let budget = if let Some(mt) = opts.movetime {
+        Duration::from_millis(mt)
+    } else {
+        let our_time = match side {
+            Color::White => opts.wtime,
+            Color::Black => opts.btime,
+        };
+        match our_time {
+            Some(time_ms) => Duration::from_millis(time_ms / 40),
+            None => Duration::from_secs(1),
+        }
+    };
+    budget.saturating_sub(move_overhead).max(MIN_SEARCH_TIME)
+}
+
+/// Settings from UCI options that shape how positions are scored
+#[derive(Debug, Clone, Default)]
+struct SearchSettings {
+    /// Piece values and tables used to score leaf positions
+    eval: Arc<EvalParams>,
+    /// How much worse than level a draw is for the side searching, in
+    /// centipawns. Negative values make the bot seek draws.
+    contempt: i32,
+}
+
+/// Signals shared between the UCI loop and a background search
+#[derive(Debug, Default)]
+struct SearchControl {
+    /// Set to end the search as soon as possible
+    stop: AtomicBool,
+    /// Set while pondering: the search has no time limit and holds its
+    /// bestmove back until `ponderhit` or `stop`
+    pondering: AtomicBool,
+    /// Set when the search was superseded and its result must not be sent
+    discard: AtomicBool,
+}
+
+/// A search running on its own thread, so that commands are still read
+/// while it thinks.
+struct BackgroundSearch {
+    control: Arc<SearchControl>,
+    handle: JoinHandle<()>,
+}
+
+impl BackgroundSearch {
+    /// Starts searching `position` for up to `max_time`, writing `info` lines
+    /// and the final `bestmove` to `output`.
+    ///
+    /// Positions are scored with `settings`. A `ponder` search runs
+    /// without a time limit until [`ponderhit`],
+    /// after which it gets `max_time` from that moment. An `infinite` search
+    /// only sends its move after [`stop`].
+    ///
+    /// [`ponderhit`]: BackgroundSearch::ponderhit
+    /// [`stop`]: BackgroundSearch::stop
+    fn start<W: Write + Send + 'static>(
+        position: Position,
+        history: Vec<u64>,
+        settings: SearchSettings,
+        max_time: Duration,
+        ponder: bool,
+        infinite: bool,
+        output: W,
+    ) -> Self {
+        let control = Arc::new(SearchControl::default());
+        control.pondering.store(ponder, Ordering::Relaxed);
+
+        let thread_control = Arc::clone(&control);
+        let handle = thread::spawn(move || {
+            let mut engine = UciEngine::new(std::io::empty(), output);
+            let best = search(
+                &position,
+                &history,
+                settings,
+                max_time,
+                &thread_control,
+                &mut engine,
+            );
+
+            // A bestmove while pondering would be played as our move, and
+            // an infinite search waits for `stop` even when it runs out of depth
+            while (infinite || thread_control.pondering.load(Ordering::Relaxed))
+                && !thread_control.stop.load(Ordering::Relaxed)
+            {
+                thread::sleep(Duration::from_millis(1));
+            }
+
+            if !thread_control.discard.load(Ordering::Relaxed) {
+                // No legal moves - game over
+                let mv = best.map_or_else(|| "0000".to_string(), |mv| mv.to_uci());
+                engine.send_bestmove(&mv).ok();
+            }
+        });
+
+        Self { control, handle }
+    }
+
+    /// Switches a ponder search to a normal timed search.
+    fn ponderhit(&self) {
+        self.control.pondering.store(false, Ordering::Relaxed);
+    }
+
+    /// Stops the search and waits for it to send its best move so far.
+    fn stop(self) {
+        self.control.stop.store(true, Ordering::Relaxed);
+        self.handle.join().ok();
+    }
+
+    /// Stops the search without sending its result.
+    fn discard(self) {
+        self.control.discard.store(true, Ordering::Relaxed);
+        self.stop();
+    }
+}
 
 /// Search state
 struct Searcher {
     nodes: u64,
     start_time: Instant,
+    /// When the time budget started counting: the search start, or the
+    /// latest check while pondering
+    clock: Instant,
     max_time: Duration,
     stopped: bool,
+    control: Arc<SearchControl>,
+    /// Zobrist hashes of the positions before the current node, from the
+    /// game so far followed by the current search path
+    history: Vec<u64>,
+    /// Whether null-move pruning is enabled
+    null_move_pruning: bool,
+    /// Ply of the node reached by the latest null move on the search path
+    null_move_ply: Option<i32>,
+    /// Two quiet moves per ply that recently caused a beta cutoff, most
+    /// recent first
+    killers: Vec<[Option<Move>; 2]>,
+    /// Beta cutoffs of quiet moves, weighted by depth, by from and to square
+    history_table: Vec<[u32; 64]>,
+    /// Whether killer and history scores order quiet moves; without them
+    /// only captures and promotions are ordered
+    quiet_move_ordering: bool,
+    /// Piece values and tables used to score leaf positions
+    eval: Arc<EvalParams>,
+    /// Draw score offset, see [`SearchSettings::contempt`]
+    contempt: i32,
 }
 
 impl Searcher {
-    fn new(max_time: Duration) -> Self {
+    fn new(max_time: Duration, history: &[u64]) -> Self {
         Searcher {
             nodes: 0,
             start_time: Instant::now(),
+            clock: Instant::now(),
             max_time,
             stopped: false,
+            control: Arc::default(),
+            history: history.to_vec(),
+            null_move_pruning: true,
+            null_move_ply: None,
+            killers: vec![[None; 2]; MAX_PLY],
+            history_table: vec![[0; 64]; 64],
+            quiet_move_ordering: true,
+            eval: Arc::new(EvalParams::BUILTIN),
+            contempt: 0,
         }
     }
 
-    fn check_time(&mut self) {
-        if self.nodes.is_multiple_of(4096) && self.start_time.elapsed() > self.max_time {
-            self.stopped = true;
+    /// Returns the score of a draw at `ply`, for the side to move there.
+    ///
+    /// The side the search runs for moves at even plies and values a draw
+    /// at `-contempt`; its opponent gains what it loses.
+    fn draw_score(&self, ply: i32) -> i32 {
+        if ply % 2 == 0 {
+            -self.contempt
+        } else {
+            self.contempt
         }
     }
-}
 
-/// Evaluate the position from the side to move's perspective
-fn evaluate(position: &Position) -> i32 {
-    let mut score = 0i32;
-
-    // Material and positional evaluation
-    for color in [Color::White, Color::Black] {
-        let sign = if color == Color::White { 1 } else { -1 };
-
-        // Pawns
-        for sq in position.pieces_of(Piece::Pawn, color) {
-            let idx = if color == Color::White {
-                sq.index() as usize
-            } else {
-                63 - sq.index() as usize
-            };
-            score += sign * (PAWN_VALUE + PAWN_PST[idx]);
-        }
+    /// Returns the moves at `position` in search order: captures and
+    /// promotions by most valuable victim and least valuable attacker, then
+    /// killer moves for `ply`, then quiet moves by history score.
+    fn order_moves(&self, position: &Position, moves: &[Move], ply: i32) -> Vec<Move> {
+        let killers = self.killers.get(ply as usize).copied().unwrap_or_default();
+        let mut scored: Vec<(u32, Move)> = moves
+            .iter()
+            .map(|&mv| {
+                let score = if let Some(victim) = captured_piece(position, mv) {
+                    let attacker = position.piece_at(mv.from()).map_or(0, |(p, _)| p.index());
+                    let promotion = mv.flag().promotion_piece().map_or(0, |p| p.index());
+                    TACTICAL_ORDER + 100 * victim.index() as u32 + 10 * promotion as u32
+                        - attacker as u32
+                } else if let Some(promotion) = mv.flag().promotion_piece() {
+                    TACTICAL_ORDER + 10 * promotion.index() as u32
+                } else if !self.quiet_move_ordering {
+                    0
+                } else if killers[0] == Some(mv) {
+                    KILLER_ORDER
+                } else if killers[1] == Some(mv) {
+                    KILLER_ORDER - 1
+                } else {
+                    self.history_table[mv.from().index() as usize][mv.to().index() as usize]
+                        .min(KILLER_ORDER - 2)
+                };
+                (score, mv)
+            })
+            .collect();
+        // Stable, so equal scores keep the generation order
+        scored.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+        scored.into_iter().map(|(_, mv)| mv).collect()
+    }
 
-        // Knights
-        for sq in position.pieces_of(Piece::Knight, color) {
-            let idx = if color == Color::White {
-                sq.index() as usize
-            } else {
-                63 - sq.index() as usize
-            };
-            score += sign * (KNIGHT_VALUE + KNIGHT_PST[idx]);
+    /// Records a quiet move that caused a beta cutoff at `ply`.
+    fn record_cutoff(&mut self, position: &Position, mv: Move, ply: i32, depth: u8) {
+        if captured_piece(position, mv).is_some() || mv.flag().is_promotion() {
+            return;
         }
-
-        // Bishops
-        for sq in position.pieces_of(Piece::Bishop, color) {
-            let idx = if color == Color::White {
-                sq.index() as usize
-            } else {
-                63 - sq.index() as usize
-            };
-            score += sign * (BISHOP_VALUE + BISHOP_PST[idx]);
+        if let Some(killers) = self.killers.get_mut(ply as usize) {
+            if killers[0] != Some(mv) {
+                killers[1] = killers[0];
+                killers[0] = Some(mv);
+            }
         }
+        let entry = &mut self.history_table[mv.from().index() as usize][mv.to().index() as usize];
+        *entry = entry.saturating_add(u32::from(depth) * u32::from(depth));
+    }
 
-        // Rooks
-        for sq in position.pieces_of(Piece::Rook, color) {
-            let idx = if color == Color::White {
-                sq.index() as usize
-            } else {
-                63 - sq.index() as usize
-            };
-            score += sign * (ROOK_VALUE + ROOK_PST[idx]);
-        }
+    /// Returns true if `position` already occurred since the last capture or
+    /// pawn move, in the game or on the current search path.
+    fn is_repetition(&self, position: &Position) -> bool {
+        let hash = position.zobrist_hash();
+        self.history
+            .iter()
+            .rev()
+            .take(position.halfmove_clock as usize)
+            .any(|&h| h == hash)
+    }
 
-        // Queens
-        for sq in position.pieces_of(Piece::Queen, color) {
-            let idx = if color == Color::White {
-                sq.index() as usize
-            } else {
-                63 - sq.index() as usize
-            };
-            score += sign * (QUEEN_VALUE + QUEEN_PST[idx]);
-        }
+    /// Returns true if a null move may be tried at `position`.
+    ///
+    /// Passing is never allowed twice in a row or while in check, and not
+    /// when the side to move has only king and pawns, where zugzwang makes
+    /// passing look better than any real move.
+    fn can_try_null_move(&self, position: &Position, ply: i32, in_check: bool) -> bool {
+        let side = position.side_to_move;
+        let pieces = [Piece::Knight, Piece::Bishop, Piece::Rook, Piece::Queen]
+            .iter()
+            .any(|&piece| position.pieces_of(piece, side).is_not_empty());
+        self.null_move_pruning && self.null_move_ply != Some(ply) && !in_check && pieces
+    }
 
-        // King (middlegame table)
-        for sq in position.pieces_of(Piece::King, color) {
-            let idx = if color == Color::White {
-                sq.index() as usize
-            } else {
-                63 - sq.index() as usize
-            };
-            score += sign * KING_MIDDLEGAME_PST[idx];
+    fn check_time(&mut self) {
+        if !self.nodes.is_multiple_of(4096) {
+            return;
+        }
+        if self.control.stop.load(Ordering::Relaxed) {
+            self.stopped = true;
+        } else if self.control.pondering.load(Ordering::Relaxed) {
+            // The clock starts running at ponderhit
+            self.clock = Instant::now();
+        } else if self.clock.elapsed() > self.max_time {
+            self.stopped = true;
         }
     }
+}
 
-    // Return score from side to move's perspective
-    if position.side_to_move == Color::White {
-        score
-    } else {
-        -score
+/// Returns the piece `mv` captures at `position`, if any.
+fn captured_piece(position: &Position, mv: Move) -> Option<Piece> {
+    if mv.flag() == MoveFlag::EnPassant {
+        return Some(Piece::Pawn);
     }
+    if mv.flag().is_castling() {
+        return None;
+    }
+    position.piece_at(mv.to()).map(|(piece, _)| piece)
+}
+
+/// Converts a mate score into moves to mate for UCI `score mate`.
+///
+/// Positive values mean the side to move mates, negative values that it is
+/// mated. Returns `None` for ordinary scores.
+fn mate_in(score: i32) -> Option<i32> {
+    let plies = MATE_SCORE - score.abs();
+    if plies > MAX_MATE_PLY {
+        return None;
+    }
+    Some(if score > 0 {
+        (plies + 1) / 2
+    } else {
+        -(plies / 2)
+    })
 }
 
-/// Alpha-beta search
+/// Alpha-beta search. `ply` is the distance from the root.
+///
+/// On return `pv` holds the principal variation from `position`, empty if
+/// no move raised alpha.
 fn alpha_beta(
     searcher: &mut Searcher,
     position: &Position,
     depth: u8,
+    ply: i32,
     mut alpha: i32,
     beta: i32,
+    pv: &mut Vec<Move>,
 ) -> i32 {
+    pv.clear();
     searcher.nodes += 1;
     searcher.check_time();
 
@@ -175,112 +431,286 @@ fn alpha_beta(
         return 0;
     }
 
+    // A repeated position is scored as a draw
+    if searcher.is_repetition(position) {
+        return searcher.draw_score(ply);
+    }
+
     // Terminal node
     if depth == 0 {
-        return evaluate(position);
+        return searcher.eval.evaluate(position);
     }
 
     let moves = StandardChess.generate_moves(position);
+    let in_check = is_king_attacked(position, position.side_to_move);
 
     // Check for checkmate or stalemate
     if moves.is_empty() {
-        if is_king_attacked(position, position.side_to_move) {
-            // Checkmate - return large negative score (we lost)
-            return -100_000 + (100 - depth as i32); // Prefer faster mates
+        if in_check {
+            // Checkmate - we lost, later mates are less bad
+            return -MATE_SCORE + ply;
         } else {
             // Stalemate
-            return 0;
+            return searcher.draw_score(ply);
         }
     }
 
-    for mv in moves.as_slice() {
-        let new_pos = StandardChess.make_move(position, *mv);
-        let score = -alpha_beta(searcher, &new_pos, depth - 1, -beta, -alpha);
+    let mut child_pv = Vec::new();
+    searcher.history.push(position.zobrist_hash());
+
+    // Null-move pruning: if passing still fails high at reduced depth, the
+    // position is good enough to cut off without searching real moves
+    if depth > NULL_MOVE_REDUCTION && searcher.can_try_null_move(position, ply, in_check) {
+        let outer_null_ply = searcher.null_move_ply.replace(ply + 1);
+        let score = -alpha_beta(
+            searcher,
+            &position.null_move(),
+            depth - 1 - NULL_MOVE_REDUCTION,
+            ply + 1,
+            -beta,
+            -beta + 1,
+            &mut child_pv,
+        );
+        searcher.null_move_ply = outer_null_ply;
+
+        if !searcher.stopped && score >= beta {
+            searcher.history.pop();
+            return beta;
+        }
+    }
+
+    for mv in searcher.order_moves(position, moves.as_slice(), ply) {
+        let new_pos = StandardChess.make_move(position, mv);
+        let score = -alpha_beta(
+            searcher,
+            &new_pos,
+            depth - 1,
+            ply + 1,
+            -beta,
+            -alpha,
+            &mut child_pv,
+        );
 
         if searcher.stopped {
-            return 0;
+            alpha = 0;
+            break;
         }
 
         if score >= beta {
-            return beta; // Beta cutoff
+            searcher.record_cutoff(position, mv, ply, depth);
+            alpha = beta; // Beta cutoff
+            break;
         }
         if score > alpha {
             alpha = score;
+            pv.clear();
+            pv.push(mv);
+            pv.extend_from_slice(&child_pv);
         }
     }
+    searcher.history.pop();
 
     alpha
 }
 
-/// Find the best move using iterative deepening
-fn search(position: &Position, max_time: Duration, engine: &mut StdioEngine) -> Option<Move> {
-    let mut searcher = Searcher::new(max_time);
-    let mut best_move: Option<Move> = None;
+/// Converts a principal variation to UCI strings, stopping at the first move
+/// that is not legal in the position it would be played from.
+fn legal_pv(position: &Position, pv: &[Move]) -> Vec<String> {
+    let mut position = position.clone();
+    let mut line = Vec::new();
+    for mv in pv {
+        if !StandardChess
+            .generate_moves(&position)
+            .as_slice()
+            .contains(mv)
+        {
+            break;
+        }
+        line.push(mv.to_uci());
+        position = StandardChess.make_move(&position, *mv);
+    }
+    line
+}
+
+/// Find the best move using iterative deepening.
+///
+/// `history` holds the hashes of the game positions before `position`, so
+/// that moves repeating one of them are scored as draws. Positions are
+/// scored with `settings`. The search ends early when `control` asks it to
+/// stop.
+fn search<R: BufRead, W: Write>(
+    position: &Position,
+    history: &[u64],
+    settings: SearchSettings,
+    max_time: Duration,
+    control: &Arc<SearchControl>,
+    engine: &mut UciEngine<R, W>,
+) -> Option<Move> {
+    let mut searcher = Searcher::new(max_time, history);
+    searcher.control = Arc::clone(control);
+    searcher.eval = settings.eval;
+    searcher.contempt = settings.contempt;
+    searcher.history.push(position.zobrist_hash());
+    let mut previous_score = None;
 
     let moves = StandardChess.generate_moves(position);
-    if moves.is_empty() {
-        return None;
-    }
+    // Stopped before the first iteration completes, any legal move will do
+    let mut best_move = Some(*moves.as_slice().first()?);
 
     // Iterative deepening
     for depth in 1..=64u8 {
         let iter_start = Instant::now();
-        let mut current_best: Option<Move> = None;
-        let mut current_score = i32::MIN;
-        let mut alpha = i32::MIN + 1;
-        let beta = i32::MAX;
 
-        for mv in moves.as_slice() {
-            let new_pos = StandardChess.make_move(position, *mv);
-            let score = -alpha_beta(&mut searcher, &new_pos, depth - 1, -beta, -alpha);
+        // Update best move if we completed this depth
+        let Some((best_score, pv)) = search_depth(
+            &mut searcher,
+            position,
+            moves.as_slice(),
+            depth,
+            previous_score,
+        ) else {
+            break;
+        };
+        best_move = Some(pv[0]);
+        previous_score = Some(best_score);
 
-            if searcher.stopped {
-                break;
-            }
+        // Send search info
+        let info = InfoBuilder::new().depth(depth as u32);
+        let info = match mate_in(best_score) {
+            Some(moves) => info.score_mate(moves),
+            None => info.score_cp(best_score),
+        };
+        let info = info
+            .nodes(searcher.nodes)
+            .time(searcher.start_time.elapsed().as_millis() as u64)
+            .pv(legal_pv(position, &pv))
+            .build();
 
-            if score > current_score {
-                current_score = score;
-                current_best = Some(*mv);
-                if score > alpha {
-                    alpha = score;
-                }
-            }
+        engine.send_info(info).ok();
+
+        // Check if we should stop
+        let elapsed = iter_start.elapsed();
+        if elapsed.as_millis() > 0
+            && !control.pondering.load(Ordering::Relaxed)
+            && searcher.clock.elapsed() > max_time / 2
+        {
+            break; // Unlikely to complete next depth in time
         }
+    }
 
-        if searcher.stopped {
-            break;
+    best_move
+}
+
+/// Searches the root `moves` to `depth`, returning the best score and its
+/// principal variation, or `None` if the search was stopped.
+///
+/// With the previous iteration's score the search starts in an aspiration
+/// window around it, widening and re-searching whenever the result falls
+/// outside the window.
+fn search_depth(
+    searcher: &mut Searcher,
+    position: &Position,
+    moves: &[Move],
+    depth: u8,
+    previous_score: Option<i32>,
+) -> Option<(i32, Vec<Move>)> {
+    let mut delta = ASPIRATION_WINDOW;
+    let (mut alpha, mut beta) = match previous_score {
+        Some(score) => (
+            (score - delta).max(-INFINITY),
+            (score + delta).min(INFINITY),
+        ),
+        None => (-INFINITY, INFINITY),
+    };
+
+    loop {
+        let (score, pv) = search_root(searcher, position, moves, depth, alpha, beta)?;
+        if score <= alpha && alpha > -INFINITY {
+            delta *= 2;
+            alpha = (score - delta).max(-INFINITY);
+        } else if score >= beta && beta < INFINITY {
+            delta *= 2;
+            beta = (score + delta).min(INFINITY);
+        } else {
+            return Some((score, pv));
         }
+    }
+}
 
-        // Update best move if we completed this depth
-        if let Some(mv) = current_best {
-            best_move = Some(mv);
-            let best_score = current_score;
-
-            // Send search info
-            let info = InfoBuilder::new()
-                .depth(depth as u32)
-                .score_cp(best_score)
-                .nodes(searcher.nodes)
-                .time(searcher.start_time.elapsed().as_millis() as u64)
-                .pv(vec![mv.to_uci()])
-                .build();
-
-            engine.send_info(info).ok();
+/// Searches the root `moves` to `depth` within the `(alpha, beta)` window.
+fn search_root(
+    searcher: &mut Searcher,
+    position: &Position,
+    moves: &[Move],
+    depth: u8,
+    mut alpha: i32,
+    beta: i32,
+) -> Option<(i32, Vec<Move>)> {
+    let mut best_score = i32::MIN;
+    let mut best_pv = Vec::new();
+    let mut child_pv = Vec::new();
+
+    for mv in moves {
+        let new_pos = StandardChess.make_move(position, *mv);
+        let score = -alpha_beta(
+            searcher,
+            &new_pos,
+            depth - 1,
+            1,
+            -beta,
+            -alpha,
+            &mut child_pv,
+        );
+
+        if searcher.stopped {
+            return None;
         }
 
-        // Check if we should stop
-        let elapsed = iter_start.elapsed();
-        if elapsed.as_millis() > 0 && searcher.start_time.elapsed() > max_time / 2 {
-            break; // Unlikely to complete next depth in time
+        if score > best_score {
+            best_score = score;
+            best_pv.clear();
+            best_pv.push(*mv);
+            best_pv.extend_from_slice(&child_pv);
+            if score > alpha {
+                alpha = score;
+            }
+        }
+        if score >= beta {
+            break; // Fail high, the window will be widened
         }
     }
 
-    best_move
+    Some((best_score, best_pv))
 }
 
 fn main() {
     let mut engine = stdio_engine();
     let mut position = StandardChess.initial_position();
+    let mut book = OpeningBook::builtin();
+    // UCI moves played from the starting position, or `None` after a FEN
+    // setup, since book keys are move histories from the start
+    let mut history: Option<Vec<String>> = Some(Vec::new());
+    // Hashes of the positions played before the current one
+    let mut game_hashes: Vec<u64> = Vec::new();
+    let mut move_overhead = Duration::from_millis(DEFAULT_MOVE_OVERHEAD_MS);
+    // A bad eval file from the environment is reported once the GUI sends
+    // `uci`, the same way as a bad `setoption name EvalFile`
+    let mut eval_file_error: Option<String> = None;
+    let mut settings = SearchSettings {
+        eval: Arc::new(match std::env::var(eval_file::EVAL_FILE_ENV) {
+            Ok(path) if !path.is_empty() => {
+                eval_file::load_eval_params(&path).unwrap_or_else(|e| {
+                    eval_file_error = Some(format!("EvalFile {}: {}", path, e));
+                    EvalParams::BUILTIN
+                })
+            }
+            _ => EvalParams::BUILTIN,
+        }),
+        contempt: 0,
+    };
+    let mut running: Option<BackgroundSearch> = None;
+    // Set by `debug on`; enables extra `info string` output
+    let mut debug = false;
 
     loop {
         let cmd = match engine.read_command() {
@@ -294,6 +724,14 @@ fn main() {
         match cmd {
             GuiCommand::Uci => {
                 engine.send_id("MinimaxBot", "Chess Devtools").unwrap();
+                for option in uci_options() {
+                    engine.send(&option).unwrap();
+                }
+                if let Some(message) = eval_file_error.take() {
+                    engine
+                        .send_info(InfoBuilder::new().string(&message).build())
+                        .unwrap();
+                }
                 engine.send_uciok().unwrap();
             }
 
@@ -306,70 +744,647 @@ fn main() {
                 engine.send_readyok().unwrap();
             }
 
-            GuiCommand::Position { fen, moves } => {
+            GuiCommand::Position { start, moves } => {
+                // A search still running belongs to the old position
+                if let Some(search) = running.take() {
+                    search.discard();
+                }
+
                 // Set up position from FEN or starting position
-                position = match fen {
-                    Some(f) => {
+                history = (start == StartPosition::StartPos).then(Vec::new);
+                game_hashes.clear();
+                position = match start {
+                    StartPosition::Fen(f) => {
                         Position::from_fen(&f).unwrap_or_else(|_| StandardChess.initial_position())
                     }
-                    None => StandardChess.initial_position(),
+                    StartPosition::StartPos => StandardChess.initial_position(),
                 };
 
                 // Apply moves
                 for mv_str in moves {
-                    if let Some(mv) = Move::from_uci(&mv_str) {
-                        let legal_moves = StandardChess.generate_moves(&position);
-                        if let Some(&legal_mv) = legal_moves.as_slice().iter().find(|m| {
-                            m.from() == mv.from()
-                                && m.to() == mv.to()
-                                && m.flag().promotion_piece() == mv.flag().promotion_piece()
-                        }) {
-                            position = StandardChess.make_move(&position, legal_mv);
+                    if let Some(legal_mv) =
+                        StandardChess.generate_moves(&position).find_uci(&mv_str)
+                    {
+                        game_hashes.push(position.zobrist_hash());
+                        position = StandardChess.make_move(&position, legal_mv);
+                        if let Some(history) = &mut history {
+                            history.push(legal_mv.to_uci());
                         }
                     }
                 }
             }
 
             GuiCommand::Go(opts) => {
-                // Determine search time
-                let max_time = if let Some(mt) = opts.movetime {
-                    Duration::from_millis(mt)
-                } else {
-                    // Use time controls if available
-                    let our_time = match position.side_to_move {
-                        Color::White => opts.wtime,
-                        Color::Black => opts.btime,
-                    };
-
-                    if let Some(time_ms) = our_time {
-                        // Use about 2.5% of remaining time
-                        Duration::from_millis(time_ms / 40)
-                    } else {
-                        // Default to 1 second
-                        Duration::from_secs(1)
-                    }
-                };
+                if let Some(search) = running.take() {
+                    search.discard();
+                }
+                let max_time = search_time(&opts, position.side_to_move, move_overhead);
 
-                // Search for best move
-                if let Some(mv) = search(&position, max_time, &mut engine) {
+                // Play from the book while in it, otherwise search. Book
+                // moves are instant, so ponder and infinite searches just
+                // search.
+                let book_move = history
+                    .as_deref()
+                    .filter(|_| !opts.ponder && !opts.infinite)
+                    .and_then(|h| book.pick(&position, h, &mut rand::rng()));
+                if debug {
+                    let message = match book_move {
+                        Some(mv) => format!("book move {}", mv.to_uci()),
+                        None => format!("searching for {} ms", max_time.as_millis()),
+                    };
+                    engine
+                        .send_info(InfoBuilder::new().string(&message).build())
+                        .unwrap();
+                }
+                if let Some(mv) = book_move {
                     engine.send_bestmove(&mv.to_uci()).unwrap();
                 } else {
-                    // No legal moves - game over
-                    engine.send_bestmove("0000").unwrap();
+                    running = Some(BackgroundSearch::start(
+                        position.clone(),
+                        game_hashes.clone(),
+                        settings.clone(),
+                        max_time,
+                        opts.ponder,
+                        opts.infinite,
+                        std::io::stdout(),
+                    ));
+                }
+            }
+
+            GuiCommand::PonderHit => {
+                if let Some(search) = &running {
+                    search.ponderhit();
                 }
             }
 
             GuiCommand::Stop => {
-                // Nothing to stop (we don't support pondering)
+                if let Some(search) = running.take() {
+                    search.stop();
+                }
             }
 
             GuiCommand::Quit => {
+                if let Some(search) = running.take() {
+                    search.discard();
+                }
                 break;
             }
 
+            GuiCommand::SetOption { name, value } => {
+                let value = value.unwrap_or_default();
+                if name.eq_ignore_ascii_case("OwnBook") {
+                    book.enabled = value.eq_ignore_ascii_case("true");
+                } else if name.eq_ignore_ascii_case("BookDepth") {
+                    if let Ok(depth) = value.parse() {
+                        book.depth = depth;
+                    }
+                } else if name.eq_ignore_ascii_case("MoveOverhead") {
+                    if let Ok(ms) = value.parse() {
+                        move_overhead = Duration::from_millis(ms);
+                    }
+                } else if name.eq_ignore_ascii_case("Contempt") {
+                    if let Ok(cp) = value.parse::<i32>() {
+                        settings.contempt = cp.clamp(-MAX_CONTEMPT, MAX_CONTEMPT);
+                    }
+                } else if name.eq_ignore_ascii_case("EvalFile") {
+                    // An empty value goes back to the built-in evaluation
+                    if value.is_empty() || value == "<empty>" {
+                        settings.eval = Arc::new(EvalParams::BUILTIN);
+                    } else {
+                        match eval_file::load_eval_params(&value) {
+                            Ok(params) => settings.eval = Arc::new(params),
+                            Err(e) => {
+                                let message = format!("EvalFile {}: {}", value, e);
+                                engine
+                                    .send_info(InfoBuilder::new().string(&message).build())
+                                    .unwrap();
+                            }
+                        }
+                    }
+                }
+            }
+
+            GuiCommand::Debug(on) => {
+                debug = on;
+            }
+
+            GuiCommand::Register(_) => {
+                // No registration required
+            }
+
             GuiCommand::Unknown(_) => {
                 // Ignore unknown commands
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chess_engine::eval::QUEEN_VALUE;
+    use std::io::Cursor;
+    use std::sync::Mutex;
+
+    /// Output that tests can read while a background search writes to it
+    #[derive(Clone, Default)]
+    struct SharedOutput(Arc<Mutex<Vec<u8>>>);
+
+    impl SharedOutput {
+        fn bestmoves(&self) -> Vec<String> {
+            String::from_utf8(self.0.lock().unwrap().clone())
+                .unwrap()
+                .lines()
+                .filter_map(|l| l.strip_prefix("bestmove "))
+                .map(str::to_string)
+                .collect()
+        }
+
+        /// Waits up to `timeout` for a bestmove line.
+        fn wait_for_bestmove(&self, timeout: Duration) -> Option<String> {
+            let start = Instant::now();
+            while start.elapsed() < timeout {
+                if let Some(mv) = self.bestmoves().pop() {
+                    return Some(mv);
+                }
+                thread::sleep(Duration::from_millis(5));
+            }
+            None
+        }
+    }
+
+    impl Write for SharedOutput {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn assert_legal(position: &Position, uci: &str) {
+        assert!(
+            StandardChess
+                .generate_moves(position)
+                .as_slice()
+                .iter()
+                .any(|m| m.to_uci() == uci),
+            "illegal bestmove {}",
+            uci
+        );
+    }
+
+    fn best_move(position: &Position, history: &[u64]) -> Move {
+        let mut engine = UciEngine::new(Cursor::new(Vec::new()), Vec::new());
+        search(
+            position,
+            history,
+            SearchSettings::default(),
+            Duration::from_millis(200),
+            &Arc::default(),
+            &mut engine,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn repeated_position_scores_as_draw() {
+        let position = Position::from_fen("7k/8/8/8/8/8/8/Q6K w - - 4 3").unwrap();
+        let mut searcher = Searcher::new(Duration::from_secs(10), &[position.zobrist_hash()]);
+        assert_eq!(
+            alpha_beta(
+                &mut searcher,
+                &position,
+                2,
+                0,
+                -200_000,
+                200_000,
+                &mut Vec::new()
+            ),
+            0
+        );
+
+        // Repetitions before the last irreversible move do not count
+        let reset = Position::from_fen("7k/8/8/8/8/8/8/Q6K w - - 0 3").unwrap();
+        let mut searcher = Searcher::new(Duration::from_secs(10), &[reset.zobrist_hash()]);
+        assert!(
+            alpha_beta(
+                &mut searcher,
+                &reset,
+                2,
+                0,
+                -200_000,
+                200_000,
+                &mut Vec::new()
+            ) > QUEEN_VALUE / 2
+        );
+    }
+
+    #[test]
+    fn avoids_repeating_when_winning() {
+        // White is a queen up and has just shuffled, so the bot's preferred
+        // move now leads back to a position from earlier in the game
+        let position = Position::from_fen("7k/8/8/8/8/8/8/Q6K w - - 4 3").unwrap();
+        let preferred = best_move(&position, &[]);
+        let repeated = StandardChess.make_move(&position, preferred);
+
+        let mv = best_move(&position, &[repeated.zobrist_hash()]);
+        assert_ne!(mv, preferred);
+
+        // The alternative keeps the decisive advantage
+        let after = StandardChess.make_move(&position, mv);
+        let mut searcher = Searcher::new(Duration::from_secs(10), &[]);
+        let score = -alpha_beta(
+            &mut searcher,
+            &after,
+            2,
+            1,
+            -200_000,
+            200_000,
+            &mut Vec::new(),
+        );
+        assert!(score > QUEEN_VALUE / 2, "score {}", score);
+    }
+
+    #[test]
+    fn contempt_avoids_an_immediate_repetition() {
+        // Level, symmetrical position where the shallow search rates the
+        // king moves best; Kh1 goes back to a position from earlier in the
+        // game
+        let position = Position::from_fen("6k1/5ppp/8/8/8/8/5PPP/6K1 w - - 10 30").unwrap();
+        let repeating = StandardChess
+            .generate_moves(&position)
+            .find_uci("g1h1")
+            .unwrap();
+        let repeated = StandardChess.make_move(&position, repeating);
+        let moves = StandardChess.generate_moves(&position);
+
+        let best_with_contempt = |contempt: i32| {
+            let mut searcher = Searcher::new(Duration::from_secs(10), &[repeated.zobrist_hash()]);
+            searcher.contempt = contempt;
+            searcher.history.push(position.zobrist_hash());
+            let (score, pv) =
+                search_depth(&mut searcher, &position, moves.as_slice(), 3, None).unwrap();
+            (score, pv[0])
+        };
+
+        // Without contempt the draw beats the slightly worse alternatives
+        assert_eq!(best_with_contempt(0), (0, repeating));
+
+        let (score, mv) = best_with_contempt(50);
+        assert_ne!(mv, repeating);
+        assert!(score > -50, "score {}", score);
+    }
+
+    #[test]
+    fn move_overhead_reduces_search_time() {
+        let clock = GoOptions {
+            wtime: Some(60_000),
+            btime: Some(20_000),
+            ..Default::default()
+        };
+        assert_eq!(
+            search_time(&clock, Color::White, Duration::ZERO),
+            Duration::from_millis(1500)
+        );
+        assert_eq!(
+            search_time(&clock, Color::White, Duration::from_millis(200)),
+            Duration::from_millis(1300)
+        );
+        assert_eq!(
+            search_time(&clock, Color::Black, Duration::from_millis(200)),
+            Duration::from_millis(300)
+        );
+
+        let movetime = GoOptions {
+            movetime: Some(100),
+            ..Default::default()
+        };
+        assert_eq!(
+            search_time(&movetime, Color::White, Duration::from_millis(30)),
+            Duration::from_millis(70)
+        );
+
+        // The overhead never leaves the search without any time
+        assert_eq!(
+            search_time(&movetime, Color::White, Duration::from_secs(1)),
+            MIN_SEARCH_TIME
+        );
+
+        let infinite = GoOptions {
+            infinite: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            search_time(&infinite, Color::White, Duration::from_millis(30)),
+            Duration::MAX
+        );
+    }
+
+    #[test]
+    fn declares_uci_options() {
+        let lines: Vec<String> = uci_options().iter().map(EngineMessage::to_uci).collect();
+        assert!(lines.contains(&"option name Hash type spin default 16 min 1 max 1024".to_string()));
+        assert!(lines
+            .contains(&"option name MoveOverhead type spin default 30 min 0 max 5000".to_string()));
+        assert!(lines.contains(&"option name OwnBook type check default true".to_string()));
+        assert!(lines.contains(&"option name EvalFile type string default <empty>".to_string()));
+        assert!(lines
+            .contains(&"option name Contempt type spin default 0 min -500 max 500".to_string()));
+    }
+
+    #[test]
+    fn mate_scores_convert_to_moves() {
+        assert_eq!(mate_in(MATE_SCORE - 1), Some(1));
+        assert_eq!(mate_in(MATE_SCORE - 3), Some(2));
+        assert_eq!(mate_in(-MATE_SCORE + 2), Some(-1));
+        assert_eq!(mate_in(-MATE_SCORE + 4), Some(-2));
+        assert_eq!(mate_in(QUEEN_VALUE), None);
+        assert_eq!(mate_in(-QUEEN_VALUE), None);
+    }
+
+    #[test]
+    fn reports_mate_in_two() {
+        // 1. Kc7 Ka7 2. Ra1#
+        let position = Position::from_fen("k7/8/2K5/8/8/8/8/1R6 w - - 0 1").unwrap();
+        let mut output = Vec::new();
+        let mut engine = UciEngine::new(Cursor::new(Vec::new()), &mut output);
+        search(
+            &position,
+            &[],
+            SearchSettings::default(),
+            Duration::from_millis(300),
+            &Arc::default(),
+            &mut engine,
+        )
+        .unwrap();
+        drop(engine);
+
+        let output = String::from_utf8(output).unwrap();
+        let last_info = output.lines().rfind(|l| l.starts_with("info")).unwrap();
+        assert!(last_info.contains("score mate 2"), "{}", last_info);
+    }
+
+    #[test]
+    fn reports_legal_principal_variation() {
+        // 1. Nc7+ forks king and rook
+        let position = Position::from_fen("r3k3/8/8/3N4/8/8/8/4K3 w - - 0 1").unwrap();
+        let mut output = Vec::new();
+        let mut engine = UciEngine::new(Cursor::new(Vec::new()), &mut output);
+        let best = search(
+            &position,
+            &[],
+            SearchSettings::default(),
+            Duration::from_millis(300),
+            &Arc::default(),
+            &mut engine,
+        )
+        .unwrap();
+        drop(engine);
+
+        let output = String::from_utf8(output).unwrap();
+        let last_info = output.lines().rfind(|l| l.starts_with("info")).unwrap();
+        let pv: Vec<&str> = last_info
+            .split(" pv ")
+            .nth(1)
+            .unwrap()
+            .split_whitespace()
+            .collect();
+        assert_eq!(best.to_uci(), "d5c7");
+        assert_eq!(pv[0], best.to_uci());
+        assert!(pv.len() > 1, "{}", last_info);
+
+        let mut replay = position.clone();
+        for uci in pv {
+            let legal = StandardChess
+                .generate_moves(&replay)
+                .find_uci(uci)
+                .unwrap_or_else(|| panic!("illegal pv move {}", uci));
+            replay = StandardChess.make_move(&replay, legal);
+        }
+    }
+
+    #[test]
+    fn aspiration_windows_do_not_search_more_nodes() {
+        let position =
+            Position::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+        let moves = StandardChess.generate_moves(&position);
+        let run = |aspiration: bool| {
+            let mut searcher = Searcher::new(Duration::from_secs(600), &[]);
+            searcher.history.push(position.zobrist_hash());
+            let mut previous = None;
+            let mut best = None;
+            for depth in 1..=4 {
+                let (score, pv) =
+                    search_depth(&mut searcher, &position, moves.as_slice(), depth, previous)
+                        .unwrap();
+                previous = aspiration.then_some(score);
+                best = Some(pv[0]);
+            }
+            (searcher.nodes, best.unwrap())
+        };
+
+        let (full_nodes, full_best) = run(false);
+        let (aspiration_nodes, aspiration_best) = run(true);
+        assert_eq!(aspiration_best, full_best);
+        assert!(
+            aspiration_nodes <= full_nodes,
+            "{} > {}",
+            aspiration_nodes,
+            full_nodes
+        );
+    }
+
+    #[test]
+    fn null_move_pruning_reduces_nodes() {
+        let position =
+            Position::from_fen("r4rk1/ppp2ppp/2n5/3p4/3P4/2N5/PPP2PPP/R4RK1 w - - 0 1").unwrap();
+        let moves = StandardChess.generate_moves(&position);
+        let run = |null_move_pruning: bool| {
+            let mut searcher = Searcher::new(Duration::from_secs(600), &[]);
+            searcher.null_move_pruning = null_move_pruning;
+            searcher.history.push(position.zobrist_hash());
+            let (_, pv) = search_root(
+                &mut searcher,
+                &position,
+                moves.as_slice(),
+                4,
+                -INFINITY,
+                INFINITY,
+            )
+            .unwrap();
+            (searcher.nodes, pv[0])
+        };
+
+        let (full_nodes, full_best) = run(false);
+        let (pruned_nodes, pruned_best) = run(true);
+        assert_eq!(pruned_best, full_best);
+        assert!(
+            pruned_nodes < full_nodes,
+            "{} >= {}",
+            pruned_nodes,
+            full_nodes
+        );
+    }
+
+    #[test]
+    fn killer_and_history_ordering_reduces_nodes() {
+        // Position 1 of the Win at Chess test suite
+        let position =
+            Position::from_fen("2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - 0 1")
+                .unwrap();
+        let moves = StandardChess.generate_moves(&position);
+        let run = |quiet_move_ordering: bool| {
+            let mut searcher = Searcher::new(Duration::from_secs(600), &[]);
+            searcher.quiet_move_ordering = quiet_move_ordering;
+            searcher.history.push(position.zobrist_hash());
+            let mut best = None;
+            for depth in 1..=5 {
+                let (_, pv) = search_root(
+                    &mut searcher,
+                    &position,
+                    moves.as_slice(),
+                    depth,
+                    -INFINITY,
+                    INFINITY,
+                )
+                .unwrap();
+                best = Some(pv[0]);
+            }
+            (searcher.nodes, best.unwrap())
+        };
+
+        let (plain_nodes, plain_best) = run(false);
+        let (ordered_nodes, ordered_best) = run(true);
+        assert_eq!(ordered_best, plain_best);
+        assert!(
+            ordered_nodes < plain_nodes,
+            "{} >= {}",
+            ordered_nodes,
+            plain_nodes
+        );
+    }
+
+    #[test]
+    fn captures_are_ordered_by_victim_then_attacker() {
+        // The pawn and the queen can both take the rook, only the pawn can
+        // take the knight
+        let position = Position::from_fen("4k3/8/8/2n1r3/3P4/8/8/4QK2 w - - 0 1").unwrap();
+        let moves = StandardChess.generate_moves(&position);
+        let searcher = Searcher::new(Duration::from_secs(1), &[]);
+        let ordered: Vec<String> = searcher
+            .order_moves(&position, moves.as_slice(), 0)
+            .iter()
+            .take(3)
+            .map(|m| m.to_uci())
+            .collect();
+        assert_eq!(ordered, ["d4e5", "e1e5", "d4c5"]);
+    }
+
+    #[test]
+    fn null_move_is_not_tried_in_pawn_endings_or_check() {
+        let searcher = Searcher::new(Duration::from_secs(1), &[]);
+        let pawn_ending = Position::from_fen("4k3/4p3/8/8/8/8/4P3/4K3 w - - 0 1").unwrap();
+        assert!(!searcher.can_try_null_move(&pawn_ending, 1, false));
+
+        let middlegame = Position::from_fen("4k3/4p3/8/8/8/8/4P3/3QK3 w - - 0 1").unwrap();
+        assert!(searcher.can_try_null_move(&middlegame, 1, false));
+        assert!(!searcher.can_try_null_move(&middlegame, 1, true));
+    }
+
+    #[test]
+    fn ponderhit_turns_ponder_search_into_timed_search() {
+        let position = StandardChess.initial_position();
+        let output = SharedOutput::default();
+        let search = BackgroundSearch::start(
+            position.clone(),
+            Vec::new(),
+            SearchSettings::default(),
+            Duration::from_millis(50),
+            true,
+            false,
+            output.clone(),
+        );
+
+        // Pondering outlasts the time budget without sending a move
+        thread::sleep(Duration::from_millis(150));
+        assert!(output.bestmoves().is_empty());
+
+        search.ponderhit();
+        let mv = output.wait_for_bestmove(Duration::from_secs(5)).unwrap();
+        assert_legal(&position, &mv);
+        search.stop();
+        assert_eq!(output.bestmoves().len(), 1);
+    }
+
+    #[test]
+    fn stop_ends_ponder_search_with_bestmove() {
+        let position = StandardChess.initial_position();
+        let output = SharedOutput::default();
+        let search = BackgroundSearch::start(
+            position.clone(),
+            Vec::new(),
+            SearchSettings::default(),
+            Duration::from_secs(60),
+            true,
+            false,
+            output.clone(),
+        );
+        thread::sleep(Duration::from_millis(50));
+
+        let start = Instant::now();
+        search.stop();
+        assert!(start.elapsed() < Duration::from_secs(1));
+        let moves = output.bestmoves();
+        assert_eq!(moves.len(), 1);
+        assert_legal(&position, &moves[0]);
+    }
+
+    #[test]
+    fn discarded_search_sends_nothing() {
+        let output = SharedOutput::default();
+        let search = BackgroundSearch::start(
+            StandardChess.initial_position(),
+            Vec::new(),
+            SearchSettings::default(),
+            Duration::from_secs(60),
+            true,
+            false,
+            output.clone(),
+        );
+        search.discard();
+        assert!(output.bestmoves().is_empty());
+    }
+
+    #[test]
+    fn stop_ends_infinite_search_promptly() {
+        let position =
+            Position::from_fen("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3")
+                .unwrap();
+        let output = SharedOutput::default();
+        let infinite = GoOptions {
+            infinite: true,
+            ..Default::default()
+        };
+        let search = BackgroundSearch::start(
+            position.clone(),
+            Vec::new(),
+            SearchSettings::default(),
+            search_time(&infinite, position.side_to_move, Duration::ZERO),
+            false,
+            true,
+            output.clone(),
+        );
+
+        thread::sleep(Duration::from_millis(200));
+        assert!(output.bestmoves().is_empty());
+
+        let start = Instant::now();
+        search.stop();
+        assert!(start.elapsed() < Duration::from_millis(500));
+        let moves = output.bestmoves();
+        assert_eq!(moves.len(), 1);
+        assert_legal(&position, &moves[0]);
+    }
+}
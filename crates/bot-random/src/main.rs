@@ -5,12 +5,27 @@
 
 use chess_engine::rules::RuleSet;
 use chess_engine::{Position, StandardChess};
+use rand::rngs::StdRng;
 use rand::seq::IndexedRandom;
-use uci::{stdio_engine, GuiCommand};
+use rand::SeedableRng;
+use uci::{stdio_engine, GuiCommand, InfoBuilder, OptionType, StartPosition};
+
+/// Returns the RNG for a `Seed` option value; 0 seeds from system entropy.
+fn seeded_rng(seed: u64) -> StdRng {
+    if seed == 0 {
+        rand::make_rng()
+    } else {
+        StdRng::seed_from_u64(seed)
+    }
+}
 
 fn main() {
     let mut engine = stdio_engine();
     let mut position = StandardChess.initial_position();
+    // Set by `debug on`; enables extra `info string` output
+    let mut debug = false;
+    // Reseeded by `setoption name Seed` for reproducible games
+    let mut rng = seeded_rng(0);
 
     loop {
         let cmd = match engine.read_command() {
@@ -24,6 +39,16 @@ fn main() {
         match cmd {
             GuiCommand::Uci => {
                 engine.send_id("RandomBot", "Chess Devtools").unwrap();
+                engine
+                    .send_option(
+                        "Seed",
+                        OptionType::Spin,
+                        Some("0"),
+                        Some(0),
+                        Some(i64::MAX),
+                        &[],
+                    )
+                    .unwrap();
                 engine.send_uciok().unwrap();
             }
 
@@ -36,27 +61,22 @@ fn main() {
                 engine.send_readyok().unwrap();
             }
 
-            GuiCommand::Position { fen, moves } => {
+            GuiCommand::Position { start, moves } => {
                 // Set up position from FEN or starting position
-                position = match fen {
-                    Some(f) => {
+                position = match start {
+                    StartPosition::Fen(f) => {
                         Position::from_fen(&f).unwrap_or_else(|_| StandardChess.initial_position())
                     }
-                    None => StandardChess.initial_position(),
+                    StartPosition::StartPos => StandardChess.initial_position(),
                 };
 
                 // Apply moves
                 for mv_str in moves {
-                    if let Some(mv) = chess_core::Move::from_uci(&mv_str) {
-                        // Find matching legal move with correct flags
-                        let legal_moves = StandardChess.generate_moves(&position);
-                        if let Some(&legal_mv) = legal_moves.as_slice().iter().find(|m| {
-                            m.from() == mv.from()
-                                && m.to() == mv.to()
-                                && m.flag().promotion_piece() == mv.flag().promotion_piece()
-                        }) {
-                            position = StandardChess.make_move(&position, legal_mv);
-                        }
+                    // Find matching legal move with correct flags
+                    if let Some(legal_mv) =
+                        StandardChess.generate_moves(&position).find_uci(&mv_str)
+                    {
+                        position = StandardChess.make_move(&position, legal_mv);
                     }
                 }
             }
@@ -65,12 +85,18 @@ fn main() {
                 // Pick a random legal move
                 let legal_moves = StandardChess.generate_moves(&position);
                 let moves = legal_moves.as_slice();
+                if debug {
+                    let message = format!("choosing from {} legal moves", moves.len());
+                    engine
+                        .send_info(InfoBuilder::new().string(&message).build())
+                        .unwrap();
+                }
 
                 if moves.is_empty() {
                     // No legal moves - game over
                     engine.send_bestmove("0000").unwrap();
                 } else {
-                    let mv = moves.choose(&mut rand::rng()).unwrap();
+                    let mv = moves.choose(&mut rng).unwrap();
                     engine.send_bestmove(&mv.to_uci()).unwrap();
                 }
             }
@@ -79,10 +105,30 @@ fn main() {
                 // Nothing to stop for instant moves
             }
 
+            GuiCommand::PonderHit => {
+                // Nothing to continue, pondering is not supported
+            }
+
             GuiCommand::Quit => {
                 break;
             }
 
+            GuiCommand::SetOption { name, value } => {
+                if name.eq_ignore_ascii_case("Seed") {
+                    if let Some(seed) = value.and_then(|v| v.parse().ok()) {
+                        rng = seeded_rng(seed);
+                    }
+                }
+            }
+
+            GuiCommand::Debug(on) => {
+                debug = on;
+            }
+
+            GuiCommand::Register(_) => {
+                // No registration required
+            }
+
             GuiCommand::Unknown(_) => {
                 // Ignore unknown commands
             }
@@ -3,11 +3,20 @@
 //! This module provides the [`GameAnalyzer`] for analyzing complete chess games
 //! and classifying each move's quality.
 
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use chess_core::{Color, Move};
+use chess_engine::{generate_moves, Game, Position};
 use thiserror::Error;
 
-use crate::engine::{AnalysisEngine, EngineError};
+use crate::cache::{cache_key, CacheStats, EvalCache};
+use crate::engine::{AnalysisEngine, AnalysisLimit, EngineError, PositionAnalysis};
 use crate::evaluation::Evaluation;
-use crate::quality::{GameAnalysis, MoveAnalysis, MoveQuality, PlayerStats};
+use crate::quality::{
+    is_brilliant, AnalysisMeta, GameAnalysis, MoveAnalysis, MoveQuality, Outcome, PlayerStats,
+};
+use crate::tablebase::{Tablebase, TablebaseError};
 
 /// Errors that can occur during game analysis.
 #[derive(Error, Debug)]
@@ -18,6 +27,9 @@ pub enum AnalyzerError {
     /// Invalid game data was provided.
     #[error("Invalid game data: {0}")]
     InvalidGame(String),
+    /// Error opening the endgame tablebase.
+    #[error("Tablebase error: {0}")]
+    Tablebase(#[from] TablebaseError),
 }
 
 /// Input data for a single move to be analyzed.
@@ -25,9 +37,10 @@ pub enum AnalyzerError {
 pub struct MoveInput {
     /// The move in UCI notation (e.g., "e2e4").
     pub uci: String,
-    /// Bot's evaluation in centipawns (positive = white advantage).
+    /// Bot's evaluation in centipawns, for the side to move as reported
+    /// over UCI.
     pub bot_eval_cp: Option<i32>,
-    /// Bot's evaluation as mate-in-N moves.
+    /// Bot's evaluation as mate-in-N moves, for the side to move.
     pub bot_eval_mate: Option<i32>,
     /// Search depth used by the bot.
     pub bot_depth: Option<u32>,
@@ -42,30 +55,61 @@ pub struct MoveInput {
 /// Configuration for game analysis.
 #[derive(Debug, Clone)]
 pub struct AnalysisConfig {
-    /// Maximum search depth for position analysis.
+    /// Maximum search depth for position analysis, used when `limit` is
+    /// not set.
     pub depth: u32,
+    /// Search limit for position analysis. Overrides `depth` when set.
+    pub limit: Option<AnalysisLimit>,
     /// Number of opening book moves to mark as forced.
     pub opening_book_moves: usize,
+    /// Directory containing Syzygy tablebase files.
+    ///
+    /// When set, positions covered by the tablebase are evaluated with the
+    /// exact tablebase result instead of the engine evaluation.
+    pub tb_path: Option<PathBuf>,
+    /// Margin in centipawns by which the engine's best move must beat its
+    /// second-best for the player to have had only one reasonable move.
+    ///
+    /// When set, engines search two lines (UCI `MultiPV 2`) so that
+    /// [`MoveAnalysis::forced`] can be set from the gap between them.
+    /// Moves that were the only legal move are always forced.
+    pub forced_margin: Option<i32>,
 }
 
 impl Default for AnalysisConfig {
     fn default() -> Self {
         Self {
             depth: 15,
+            limit: None,
             opening_book_moves: 0,
+            tb_path: None,
+            forced_margin: None,
         }
     }
 }
 
+impl AnalysisConfig {
+    /// Returns the search limit for each position: `limit` if set,
+    /// otherwise a search to `depth`.
+    pub fn search_limit(&self) -> AnalysisLimit {
+        self.limit.unwrap_or(AnalysisLimit::Depth(self.depth))
+    }
+}
+
 /// Analyzes chess games to classify move quality.
 ///
 /// Uses a UCI-compatible engine (like Stockfish) to evaluate positions
 /// and compare bot moves against optimal play.
 pub struct GameAnalyzer {
-    /// The analysis engine instance.
-    engine: AnalysisEngine,
+    /// The analysis engine processes; the first is used for sequential
+    /// analysis.
+    engines: Vec<AnalysisEngine>,
     /// Configuration for analysis.
     config: AnalysisConfig,
+    /// Endgame tablebase, if configured.
+    tablebase: Option<Tablebase>,
+    /// Engine results for positions already analyzed.
+    cache: EvalCache,
 }
 
 impl GameAnalyzer {
@@ -78,7 +122,8 @@ impl GameAnalyzer {
     ///
     /// # Errors
     ///
-    /// Returns an error if the engine cannot be initialized.
+    /// Returns an error if the engine cannot be initialized, or if
+    /// `config.tb_path` is set and contains no usable tables.
     ///
     /// # Examples
     ///
@@ -89,19 +134,80 @@ impl GameAnalyzer {
     /// let analyzer = GameAnalyzer::new("stockfish", config)?;
     /// ```
     pub fn new(stockfish_path: &str, config: AnalysisConfig) -> Result<Self, AnalyzerError> {
-        let engine = AnalysisEngine::new(stockfish_path)?;
-        Ok(Self { engine, config })
+        Self::with_engines(stockfish_path, config, 1)
+    }
+
+    /// Creates a game analyzer backed by `engines` engine processes.
+    ///
+    /// [`analyze_game_parallel`](Self::analyze_game_parallel) spreads the
+    /// positions of a game across all of them; [`analyze_game`](Self::analyze_game)
+    /// only uses the first. At least one engine is always started.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any engine cannot be initialized, or if
+    /// `config.tb_path` is set and contains no usable tables.
+    pub fn with_engines(
+        stockfish_path: &str,
+        config: AnalysisConfig,
+        engines: usize,
+    ) -> Result<Self, AnalyzerError> {
+        let mut engines = (0..engines.max(1))
+            .map(|_| AnalysisEngine::new(stockfish_path))
+            .collect::<Result<Vec<_>, _>>()?;
+        if config.forced_margin.is_some() {
+            for engine in &mut engines {
+                engine.set_multipv(2)?;
+            }
+        }
+        let tablebase = config.tb_path.as_ref().map(Tablebase::open).transpose()?;
+        Ok(Self {
+            engines,
+            config,
+            tablebase,
+            cache: EvalCache::new(),
+        })
+    }
+
+    /// Returns the number of engine processes in the pool.
+    pub fn engine_count(&self) -> usize {
+        self.engines.len()
+    }
+
+    /// Returns the evaluation cache hit and miss counts.
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
+
+    /// Replaces the engine evaluation with the tablebase result when the
+    /// position is covered by the tablebase.
+    ///
+    /// The engine's second-best line is dropped with it, since it cannot be
+    /// compared with a tablebase result.
+    fn apply_tablebase(&self, position: &Position, analysis: &mut PositionAnalysis) {
+        let Some(tablebase) = &self.tablebase else {
+            return;
+        };
+        if let Some(wdl) = tablebase.probe_wdl(position) {
+            analysis.evaluation = wdl.to_evaluation();
+            analysis.second_best = None;
+        }
     }
 
     /// Analyzes a complete chess game.
     ///
     /// For each move in the game:
-    /// 1. Analyzes the position before the move at the configured depth.
+    /// 1. Analyzes the position before the move at the configured search
+    ///    limit.
     /// 2. Calculates centipawn loss by comparing the best move's evaluation
     ///    to the actual move's evaluation.
     /// 3. Classifies move quality based on centipawn loss.
     ///
     /// Opening book moves (if configured) are marked as [`MoveQuality::Forced`].
+    /// If a tablebase is configured, positions it covers use the exact
+    /// tablebase result in place of the engine evaluation. Positions already
+    /// analyzed with the same limit, in this game or an earlier one, are taken
+    /// from the evaluation cache instead of asking the engine again.
     ///
     /// # Arguments
     ///
@@ -109,11 +215,17 @@ impl GameAnalyzer {
     /// * `white_name` - Name of the white player/bot.
     /// * `black_name` - Name of the black player/bot.
     /// * `moves` - List of moves with optional bot metadata.
-    /// * `result` - Game result (e.g., "1-0", "0-1", "1/2-1/2").
+    /// * `result` - Recorded game result (e.g., "1-0", "0-1", "1/2-1/2").
+    ///   It is taken as given, since games often end by resignation or on
+    ///   time in a position that is not over on the board, and sets each
+    ///   player's [`outcome`](crate::PlayerStats::outcome).
+    /// * `start_fen` - Position the game started from, if not the standard
+    ///   starting position (e.g. for Chess960 or set-up positions).
     ///
     /// # Errors
     ///
-    /// Returns an error if engine analysis fails or game data is invalid.
+    /// Returns an error if engine analysis fails or game data, including the
+    /// start FEN, is invalid.
     ///
     /// # Examples
     ///
@@ -134,7 +246,7 @@ impl GameAnalyzer {
     ///
     /// let config = AnalysisConfig::default();
     /// let mut analyzer = GameAnalyzer::new("stockfish", config)?;
-    /// let analysis = analyzer.analyze_game("game1", "bot1", "bot2", &moves, "1-0")?;
+    /// let analysis = analyzer.analyze_game("game1", "bot1", "bot2", &moves, "1-0", None)?;
     /// ```
     pub fn analyze_game(
         &mut self,
@@ -143,44 +255,217 @@ impl GameAnalyzer {
         black_name: &str,
         moves: &[MoveInput],
         result: &str,
+        start_fen: Option<&str>,
     ) -> Result<GameAnalysis, AnalyzerError> {
-        // Validate input
-        if moves.is_empty() {
-            return Err(AnalyzerError::InvalidGame(
-                "no moves to analyze".to_string(),
-            ));
-        }
+        let game = GameInfo {
+            game_id,
+            white_name,
+            black_name,
+            result,
+        };
+        self.analyze_with(game, moves, start_fen, Self::search_in_order)
+    }
+
+    /// Analyzes a complete chess game, spreading the positions across every
+    /// engine in the pool.
+    ///
+    /// Positions that are not already cached are dealt out to the engines
+    /// round-robin in move order and searched concurrently, one thread per
+    /// engine. Each search is independent of which engine runs it, so the
+    /// result is the same [`GameAnalysis`] that [`analyze_game`](Self::analyze_game)
+    /// would produce.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any engine fails or game data is invalid.
+    pub fn analyze_game_parallel(
+        &mut self,
+        game_id: &str,
+        white_name: &str,
+        black_name: &str,
+        moves: &[MoveInput],
+        result: &str,
+        start_fen: Option<&str>,
+    ) -> Result<GameAnalysis, AnalyzerError> {
+        let game = GameInfo {
+            game_id,
+            white_name,
+            black_name,
+            result,
+        };
+        self.analyze_with(game, moves, start_fen, Self::search_in_parallel)
+    }
+
+    /// Replays `moves`, gets the engine result for every position of the
+    /// game from `search` and classifies the moves.
+    fn analyze_with(
+        &mut self,
+        game: GameInfo<'_>,
+        moves: &[MoveInput],
+        start_fen: Option<&str>,
+        search: SearchFn,
+    ) -> Result<GameAnalysis, AnalyzerError> {
+        let (history, positions) = replay(start_fen, moves)?;
+        let (mut evaluations, meta) = search(self, start_fen, &history, &positions)?;
+
+        let analyzed_moves = self.classify_moves(moves, &positions, &mut evaluations);
+        Ok(GameAnalysis {
+            meta,
+            start_fen: start_fen.map(str::to_string),
+            ..game_analysis(
+                game.game_id,
+                game.white_name,
+                game.black_name,
+                game.result,
+                analyzed_moves,
+                positions[0].side_to_move,
+                eval_timeline(&evaluations, &positions),
+            )
+        })
+    }
 
+    /// Searches every position of the game in order with the first engine,
+    /// taking positions already analyzed from the cache.
+    fn search_in_order(
+        &mut self,
+        start_fen: Option<&str>,
+        history: &[String],
+        positions: &[Position],
+    ) -> Result<(Vec<PositionAnalysis>, AnalysisMeta), AnalyzerError> {
         // Clear engine hash tables for fresh analysis
-        self.engine.clear_hash()?;
+        self.engines[0].clear_hash()?;
 
-        let mut analyzed_moves: Vec<MoveAnalysis> = Vec::with_capacity(moves.len());
-        let mut move_history: Vec<String> = Vec::new();
+        let limit = self.config.search_limit();
+        let mut evaluations = Vec::with_capacity(positions.len());
+        let mut meta = AnalysisMeta::default();
+        for (ply, position) in positions.iter().enumerate() {
+            let analysis = match self.cache.get(position, limit) {
+                Some(analysis) => analysis,
+                None => {
+                    // Send the move history so the engine sees repetitions
+                    let analysis =
+                        self.engines[0].analyze_limited(start_fen, &history[..ply], limit)?;
+                    meta.record(analysis.nodes, analysis.time_ms);
+                    self.cache.insert(position, limit, analysis.clone());
+                    analysis
+                }
+            };
+            evaluations.push(analysis);
+        }
+        Ok((evaluations, meta))
+    }
 
-        for (move_idx, move_input) in moves.iter().enumerate() {
-            let is_opening_book = move_idx < self.config.opening_book_moves;
+    /// Searches the uncached positions of the game concurrently, dealt out
+    /// to the engines round-robin in move order.
+    fn search_in_parallel(
+        &mut self,
+        start_fen: Option<&str>,
+        history: &[String],
+        positions: &[Position],
+    ) -> Result<(Vec<PositionAnalysis>, AnalysisMeta), AnalyzerError> {
+        for engine in &mut self.engines {
+            engine.clear_hash()?;
+        }
 
-            // Analyze position before the move
-            let analysis_before = self
-                .engine
-                .analyze_moves(&move_history, self.config.depth)?;
+        // Look up each unique position once; later repeats are served from
+        // the cache after the searches finish, as in the sequential path
+        let limit = self.config.search_limit();
+        let mut evaluations: Vec<Option<PositionAnalysis>> = vec![None; positions.len()];
+        let mut seen = HashSet::new();
+        let mut pending = Vec::new();
+        for (ply, position) in positions.iter().enumerate() {
+            if !seen.insert(cache_key(position)) {
+                continue;
+            }
+            match self.cache.get(position, limit) {
+                Some(analysis) => evaluations[ply] = Some(analysis),
+                None => pending.push(ply),
+            }
+        }
 
-            // Add the move to history for next iteration
-            move_history.push(move_input.uci.clone());
+        let engine_count = self.engines.len();
+        let searched = std::thread::scope(|scope| {
+            let workers: Vec<_> = self
+                .engines
+                .iter_mut()
+                .enumerate()
+                .map(|(worker, engine)| {
+                    let plies: Vec<usize> = pending
+                        .iter()
+                        .copied()
+                        .skip(worker)
+                        .step_by(engine_count)
+                        .collect();
+                    scope.spawn(move || {
+                        plies
+                            .into_iter()
+                            .map(|ply| {
+                                let analysis =
+                                    engine.analyze_limited(start_fen, &history[..ply], limit)?;
+                                Ok((ply, analysis))
+                            })
+                            .collect::<Result<Vec<_>, EngineError>>()
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .map(|worker| worker.join().expect("analysis thread panicked"))
+                .collect::<Result<Vec<_>, _>>()
+        })?;
 
-            // Analyze position after the move
-            let analysis_after = self
-                .engine
-                .analyze_moves(&move_history, self.config.depth)?;
+        let mut meta = AnalysisMeta::default();
+        for (ply, analysis) in searched.into_iter().flatten() {
+            meta.record(analysis.nodes, analysis.time_ms);
+            self.cache.insert(&positions[ply], limit, analysis.clone());
+            evaluations[ply] = Some(analysis);
+        }
 
-            // Determine if this is white's move (even index = white, odd = black)
-            let is_white_move = move_idx % 2 == 0;
+        let evaluations = evaluations
+            .into_iter()
+            .zip(positions)
+            .map(|(analysis, position)| {
+                analysis
+                    .or_else(|| self.cache.get(position, limit))
+                    .expect("every position was searched or cached")
+            })
+            .collect();
+        Ok((evaluations, meta))
+    }
+
+    /// Classifies each move from the engine results for every position of
+    /// the game, `evaluations[ply]` being the position before move `ply`.
+    ///
+    /// Evaluations of positions covered by the tablebase are replaced with
+    /// the tablebase result first.
+    fn classify_moves(
+        &self,
+        moves: &[MoveInput],
+        positions: &[Position],
+        evaluations: &mut [PositionAnalysis],
+    ) -> Vec<MoveAnalysis> {
+        for (analysis, position) in evaluations.iter_mut().zip(positions) {
+            self.apply_tablebase(position, analysis);
+        }
+
+        let mut analyzed_moves: Vec<MoveAnalysis> = Vec::with_capacity(moves.len());
+        for (move_idx, move_input) in moves.iter().enumerate() {
+            let is_opening_book = move_idx < self.config.opening_book_moves;
+            let analysis_before = &evaluations[move_idx];
+            let analysis_after = &evaluations[move_idx + 1];
+
+            let mover = positions[move_idx].side_to_move;
+            let is_white_move = mover == Color::White;
+
+            // Engine scores are for the side to move; the stored analysis
+            // reports them from white's point of view
+            let eval_before = white_relative(analysis_before.evaluation, mover);
+            let eval_after = white_relative(analysis_after.evaluation, mover.opposite());
 
             // Calculate centipawn loss
-            // The evaluation before is from the side to move's perspective
-            // If best move was played, eval_after (negated for opponent) should equal eval_before
-            let best_eval_cp = analysis_before.evaluation.to_centipawns();
-            let actual_eval_cp = analysis_after.evaluation.flip().to_centipawns();
+            // If the best move was played, eval_after should equal eval_before
+            let best_eval_cp = eval_before.to_centipawns();
+            let actual_eval_cp = eval_after.to_centipawns();
 
             // CP loss: how much worse was the actual move compared to best
             // For white: higher is better, so loss = best - actual
@@ -191,12 +476,25 @@ impl GameAnalyzer {
                 (actual_eval_cp - best_eval_cp).max(0)
             };
 
-            // Classify move quality
-            let quality = MoveQuality::from_cp_loss(cp_loss, is_opening_book);
+            // Classify move quality, upgrading sound sacrifices
+            let mut quality = MoveQuality::from_cp_loss(cp_loss, is_opening_book);
+            if quality == MoveQuality::Best
+                && is_brilliant_move(moves, positions, move_idx, analysis_before, analysis_after)
+            {
+                quality = MoveQuality::Brilliant;
+            }
+
+            let forced = is_forced_move(
+                &positions[move_idx],
+                analysis_before,
+                self.config.forced_margin,
+            );
 
-            // Build bot evaluation from input
-            let bot_eval =
-                Evaluation::from_uci_score(move_input.bot_eval_cp, move_input.bot_eval_mate);
+            // Bots report their score for the side to move, like the engine;
+            // a move without a score has no bot evaluation
+            let (cp, mate) = (move_input.bot_eval_cp, move_input.bot_eval_mate);
+            let bot_eval = (cp.is_some() || mate.is_some())
+                .then(|| white_relative(Evaluation::from_uci(cp, mate), mover));
 
             // Create MoveAnalysis
             let move_analysis = MoveAnalysis {
@@ -208,49 +506,216 @@ impl GameAnalyzer {
                 bot_nodes: move_input.bot_nodes,
                 bot_time_ms: move_input.bot_time_ms,
                 bot_pv: move_input.bot_pv.clone(),
-                engine_eval_before: Some(analysis_before.evaluation),
-                engine_eval_after: Some(analysis_after.evaluation),
+                engine_eval_before: Some(eval_before),
+                engine_eval_after: Some(eval_after),
                 engine_best_move: Some(analysis_before.best_move.clone()),
                 engine_pv: analysis_before.pv.clone(),
                 centipawn_loss: Some(cp_loss),
+                forced,
             };
 
             analyzed_moves.push(move_analysis);
         }
 
-        // Separate moves for white and black
-        let white_moves: Vec<&MoveAnalysis> = analyzed_moves.iter().step_by(2).collect();
-        let black_moves: Vec<&MoveAnalysis> = analyzed_moves.iter().skip(1).step_by(2).collect();
+        analyzed_moves
+    }
+}
 
-        // Convert references to owned values for PlayerStats::from_moves
-        let white_moves_owned: Vec<MoveAnalysis> = white_moves.into_iter().cloned().collect();
-        let black_moves_owned: Vec<MoveAnalysis> = black_moves.into_iter().cloned().collect();
+/// Checks whether the player in `position` had only one reasonable move:
+/// it was the only legal move, or the engine's best line beat its
+/// second-best by at least `margin` centipawns. Positions whose evaluation
+/// came from the tablebase have no second-best line and only count when
+/// they have a single legal move.
+fn is_forced_move(position: &Position, analysis: &PositionAnalysis, margin: Option<i32>) -> bool {
+    if generate_moves(position).len() == 1 {
+        return true;
+    }
+    match (margin, analysis.second_best) {
+        (Some(margin), Some(second_best)) => {
+            analysis.evaluation.to_centipawns() - second_best.to_centipawns() >= margin
+        }
+        _ => false,
+    }
+}
 
-        let white_stats = PlayerStats::from_moves(&white_moves_owned);
-        let black_stats = PlayerStats::from_moves(&black_moves_owned);
+/// Checks whether move `move_idx` of the game is a brilliant sacrifice,
+/// given the engine results before and after it.
+fn is_brilliant_move(
+    moves: &[MoveInput],
+    positions: &[Position],
+    move_idx: usize,
+    analysis_before: &PositionAnalysis,
+    analysis_after: &PositionAnalysis,
+) -> bool {
+    let position = &positions[move_idx];
+    let uci = &moves[move_idx].uci;
+    let Some(m) = generate_moves(position)
+        .as_slice()
+        .iter()
+        .copied()
+        .find(|m| m.to_uci() == *uci)
+    else {
+        return false;
+    };
 
-        Ok(GameAnalysis {
-            game_id: game_id.to_string(),
-            white_bot: white_name.to_string(),
-            black_bot: black_name.to_string(),
-            opening: None, // Opening detection not implemented
-            result: result.to_string(),
-            moves: analyzed_moves,
-            white_stats,
-            black_stats,
+    // The square the opponent just captured on, if any
+    let recapture_square = move_idx
+        .checked_sub(1)
+        .and_then(|prev| Move::from_uci(&moves[prev].uci))
+        .map(|prev| prev.to())
+        .filter(|&to| positions[move_idx - 1].piece_at(to).is_some());
+
+    is_brilliant(
+        position,
+        m,
+        recapture_square,
+        analysis_before.evaluation,
+        analysis_after.evaluation.negate(),
+        &analysis_before.best_move,
+    )
+}
+
+/// Names and recorded result of a game being analyzed.
+struct GameInfo<'a> {
+    game_id: &'a str,
+    white_name: &'a str,
+    black_name: &'a str,
+    result: &'a str,
+}
+
+/// Finds the engine result for every position of a game, given its start
+/// FEN, move history and positions, along with the totals of the searches
+/// that were run.
+type SearchFn = fn(
+    &mut GameAnalyzer,
+    Option<&str>,
+    &[String],
+    &[Position],
+) -> Result<(Vec<PositionAnalysis>, AnalysisMeta), AnalyzerError>;
+
+/// Assembles the per-player statistics and the final [`GameAnalysis`].
+///
+/// `first_mover` is the side that played the first of `analyzed_moves`.
+fn game_analysis(
+    game_id: &str,
+    white_name: &str,
+    black_name: &str,
+    result: &str,
+    analyzed_moves: Vec<MoveAnalysis>,
+    first_mover: Color,
+    eval_timeline: Vec<(usize, Evaluation)>,
+) -> GameAnalysis {
+    // Separate moves for white and black
+    let (white_skip, black_skip) = match first_mover {
+        Color::White => (0, 1),
+        Color::Black => (1, 0),
+    };
+    let white_moves: Vec<&MoveAnalysis> =
+        analyzed_moves.iter().skip(white_skip).step_by(2).collect();
+    let black_moves: Vec<&MoveAnalysis> =
+        analyzed_moves.iter().skip(black_skip).step_by(2).collect();
+
+    // Convert references to owned values for PlayerStats::from_moves
+    let white_moves_owned: Vec<MoveAnalysis> = white_moves.into_iter().cloned().collect();
+    let black_moves_owned: Vec<MoveAnalysis> = black_moves.into_iter().cloned().collect();
+
+    // The recorded result decides who won, not the final position
+    let white_outcome = Outcome::for_white(result);
+    let white_stats = PlayerStats {
+        outcome: white_outcome,
+        ..PlayerStats::from_moves(&white_moves_owned)
+    };
+    let black_stats = PlayerStats {
+        outcome: white_outcome.map(Outcome::flipped),
+        ..PlayerStats::from_moves(&black_moves_owned)
+    };
+
+    GameAnalysis {
+        game_id: game_id.to_string(),
+        white_bot: white_name.to_string(),
+        black_bot: black_name.to_string(),
+        opening: None, // Opening detection not implemented
+        result: result.to_string(),
+        moves: analyzed_moves,
+        white_stats,
+        black_stats,
+        eval_timeline,
+        meta: AnalysisMeta::default(),
+        start_fen: None,
+    }
+}
+
+/// Converts the side-to-move evaluation of every position into a timeline
+/// from white's point of view.
+fn eval_timeline(
+    evaluations: &[PositionAnalysis],
+    positions: &[Position],
+) -> Vec<(usize, Evaluation)> {
+    evaluations
+        .iter()
+        .zip(positions)
+        .enumerate()
+        .map(|(ply, (analysis, position))| {
+            (
+                ply,
+                white_relative(analysis.evaluation, position.side_to_move),
+            )
         })
+        .collect()
+}
+
+/// Converts an evaluation for `side_to_move` to white's point of view.
+fn white_relative(evaluation: Evaluation, side_to_move: Color) -> Evaluation {
+    match side_to_move {
+        Color::White => evaluation,
+        Color::Black => evaluation.negate(),
+    }
+}
+
+/// Plays `moves` from `start_fen` (or the standard starting position),
+/// returning the UCI move history and every position of the game, starting
+/// with the initial one.
+fn replay(
+    start_fen: Option<&str>,
+    moves: &[MoveInput],
+) -> Result<(Vec<String>, Vec<Position>), AnalyzerError> {
+    if moves.is_empty() {
+        return Err(AnalyzerError::InvalidGame(
+            "no moves to analyze".to_string(),
+        ));
+    }
+
+    let mut game = match start_fen {
+        Some(fen) => Game::from_fen(fen)
+            .map_err(|e| AnalyzerError::InvalidGame(format!("invalid start FEN: {}", e)))?,
+        None => Game::new(),
+    };
+    let mut history = Vec::with_capacity(moves.len());
+    let mut positions = Vec::with_capacity(moves.len() + 1);
+    positions.push(game.position().clone());
+    for (move_idx, move_input) in moves.iter().enumerate() {
+        game.make_move_uci(&move_input.uci)
+            .map_err(|e| AnalyzerError::InvalidGame(format!("move {}: {}", move_idx + 1, e)))?;
+        history.push(move_input.uci.clone());
+        positions.push(game.position().clone());
     }
+    Ok((history, positions))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tablebase::TB_WIN_CP;
+    #[cfg(unix)]
+    use test_support::FakeEngine;
 
     #[test]
     fn test_analysis_config_default() {
         let config = AnalysisConfig::default();
         assert_eq!(config.depth, 15);
+        assert_eq!(config.search_limit(), AnalysisLimit::Depth(15));
         assert_eq!(config.opening_book_moves, 0);
+        assert!(config.tb_path.is_none());
     }
 
     #[test]
@@ -289,6 +754,12 @@ mod tests {
         let display = format!("{}", invalid_err);
         assert!(display.contains("Invalid game data"));
         assert!(display.contains("no moves provided"));
+
+        // Test Tablebase error variant
+        let tb_err = AnalyzerError::Tablebase(TablebaseError::NoTables("/tmp/tb".to_string()));
+        let display = format!("{}", tb_err);
+        assert!(display.contains("Tablebase error"));
+        assert!(display.contains("/tmp/tb"));
     }
 
     #[test]
@@ -312,11 +783,550 @@ mod tests {
     fn test_analysis_config_clone() {
         let config = AnalysisConfig {
             depth: 20,
+            limit: None,
             opening_book_moves: 10,
+            tb_path: Some(PathBuf::from("/opt/syzygy")),
+            forced_margin: Some(150),
         };
 
         let cloned = config.clone();
         assert_eq!(cloned.depth, 20);
         assert_eq!(cloned.opening_book_moves, 10);
+        assert_eq!(cloned.tb_path, Some(PathBuf::from("/opt/syzygy")));
+        assert_eq!(cloned.forced_margin, Some(150));
+    }
+
+    /// A [`FakeEngine`] that runs the shell snippet `on_go` for every `go`,
+    /// with the last `position` command in `$position`, before replying
+    /// `bestmove 0000`. Every `setoption` command is appended to
+    /// `options.log` in `dir`.
+    #[cfg(unix)]
+    fn scripted_engine(dir: &std::path::Path, on_go: &str) -> PathBuf {
+        FakeEngine::new("engine.sh")
+            .on_go(on_go)
+            .bestmove("0000")
+            .log_options(dir.join("options.log"))
+            .write(dir)
+    }
+
+    /// Writes a fake UCI engine script that logs each search it is asked
+    /// for, prefixed with its process id. It scores a position by the number
+    /// of words in its `position` command, so results differ per ply, and
+    /// reports 1000 nodes in 4 ms for every search.
+    #[cfg(unix)]
+    fn fake_engine(dir: &std::path::Path) -> (PathBuf, PathBuf) {
+        let log = dir.join("searches.log");
+        let on_go = format!(
+            r#"echo "$$ | $position | $line" >> "{}"
+         set -- $position
+         echo "info depth 1 score cp $# nodes 1000 time 4 pv 0000""#,
+            log.display()
+        );
+        (scripted_engine(dir, &on_go), log)
+    }
+
+    fn move_inputs(moves: &[&str]) -> Vec<MoveInput> {
+        moves
+            .iter()
+            .map(|uci| MoveInput {
+                uci: uci.to_string(),
+                bot_eval_cp: None,
+                bot_eval_mate: None,
+                bot_depth: None,
+                bot_nodes: None,
+                bot_time_ms: None,
+                bot_pv: vec![],
+            })
+            .collect()
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_repeated_positions_use_the_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let (engine, log) = fake_engine(dir.path());
+        let config = AnalysisConfig {
+            depth: 12,
+            ..AnalysisConfig::default()
+        };
+        let mut analyzer = GameAnalyzer::new(engine.to_str().unwrap(), config).unwrap();
+
+        // The knights return home, so the last two positions repeat the
+        // start position and the position after 1. Nf3
+        let moves = move_inputs(&["g1f3", "g8f6", "f3g1", "f6g8", "g1f3"]);
+        let analysis = analyzer
+            .analyze_game("g1", "a", "b", &moves, "*", None)
+            .unwrap();
+        assert_eq!(analysis.moves.len(), 5);
+
+        // Six positions, four of them unique, each searched once
+        let searches = std::fs::read_to_string(&log).unwrap();
+        assert_eq!(searches.lines().count(), 4, "{}", searches);
+        assert!(searches.lines().all(|l| l.ends_with("go depth 12")));
+        assert_eq!(analyzer.cache_stats(), CacheStats { hits: 2, misses: 4 });
+
+        // A second game reuses the entries from the first
+        analyzer
+            .analyze_game("g2", "a", "b", &move_inputs(&["g1f3"]), "*", None)
+            .unwrap();
+        let searches = std::fs::read_to_string(&log).unwrap();
+        assert_eq!(searches.lines().count(), 4);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_recorded_result_decides_outcome_for_non_terminal_games() {
+        let dir = tempfile::tempdir().unwrap();
+        let (engine, _) = fake_engine(dir.path());
+        let mut analyzer =
+            GameAnalyzer::new(engine.to_str().unwrap(), AnalysisConfig::default()).unwrap();
+
+        // White resigned after 2. Nf3, far from mate on the board
+        let moves = move_inputs(&["e2e4", "e7e5", "g1f3"]);
+        let analysis = analyzer
+            .analyze_game("g1", "a", "b", &moves, "0-1", None)
+            .unwrap();
+        assert_eq!(analysis.result, "0-1");
+        assert_eq!(analysis.white_stats.outcome, Some(Outcome::Loss));
+        assert_eq!(analysis.black_stats.outcome, Some(Outcome::Win));
+
+        // Every move, including the last, still gets an engine-based loss
+        assert_eq!(analysis.moves.len(), 3);
+        assert!(analysis.moves.iter().all(|m| m.centipawn_loss.is_some()));
+        assert!(analysis.moves[2].engine_eval_after.is_some());
+
+        let unfinished = analyzer
+            .analyze_game("g2", "a", "b", &moves, "*", None)
+            .unwrap();
+        assert_eq!(unfinished.white_stats.outcome, None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_cache_entries_are_per_depth() {
+        let dir = tempfile::tempdir().unwrap();
+        let (engine, log) = fake_engine(dir.path());
+        let mut analyzer =
+            GameAnalyzer::new(engine.to_str().unwrap(), AnalysisConfig::default()).unwrap();
+        let moves = move_inputs(&["e2e4"]);
+        analyzer
+            .analyze_game("g1", "a", "b", &moves, "*", None)
+            .unwrap();
+
+        // The same positions at a deeper depth must be searched again
+        analyzer.config.depth = 20;
+        analyzer
+            .analyze_game("g2", "a", "b", &moves, "*", None)
+            .unwrap();
+
+        let searches = std::fs::read_to_string(&log).unwrap();
+        let depths: Vec<&str> = searches
+            .lines()
+            .map(|l| l.rsplit(' ').next().unwrap())
+            .collect();
+        assert_eq!(depths, ["15", "15", "20", "20"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_node_limit_issues_go_nodes() {
+        let dir = tempfile::tempdir().unwrap();
+        let (engine, log) = fake_engine(dir.path());
+
+        let mut direct = AnalysisEngine::new(engine.to_str().unwrap()).unwrap();
+        let analysis = direct
+            .analyze_limited(None, &[], AnalysisLimit::Nodes(100000))
+            .unwrap();
+        assert_eq!(analysis.best_move, "0000");
+        assert_eq!(analysis.nodes, 1000);
+
+        let config = AnalysisConfig {
+            limit: Some(AnalysisLimit::Nodes(100000)),
+            ..AnalysisConfig::default()
+        };
+        let mut analyzer = GameAnalyzer::new(engine.to_str().unwrap(), config).unwrap();
+        analyzer
+            .analyze_game("g1", "a", "b", &move_inputs(&["e2e4"]), "*", None)
+            .unwrap();
+
+        let searches = std::fs::read_to_string(&log).unwrap();
+        assert_eq!(searches.lines().count(), 3, "{}", searches);
+        assert!(searches.lines().all(|l| l.ends_with("go nodes 100000")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_parallel_analysis_matches_sequential() {
+        let dir = tempfile::tempdir().unwrap();
+        let (engine, log) = fake_engine(dir.path());
+        let engine = engine.to_str().unwrap();
+        let moves = move_inputs(&["e2e4", "e7e5", "g1f3", "b8c6", "f1b5", "a7a6"]);
+
+        let mut pooled = GameAnalyzer::with_engines(engine, AnalysisConfig::default(), 2).unwrap();
+        assert_eq!(pooled.engine_count(), 2);
+        let parallel = pooled
+            .analyze_game_parallel("g1", "a", "b", &moves, "*", None)
+            .unwrap();
+
+        // Every ply was searched exactly once, shared between both engines
+        let searches = std::fs::read_to_string(&log).unwrap();
+        let mut searched: Vec<&str> = searches
+            .lines()
+            .map(|l| l.split(" | ").nth(1).unwrap())
+            .collect();
+        assert_eq!(searched.len(), 7, "{}", searches);
+        searched.sort();
+        searched.dedup();
+        assert_eq!(searched.len(), 7);
+        let pids: HashSet<&str> = searches
+            .lines()
+            .map(|l| l.split(" | ").next().unwrap())
+            .collect();
+        assert_eq!(pids.len(), 2);
+
+        // Results come back in move order: the fake engine scores the
+        // position after n moves as n + 3 ("position startpos moves ...")
+        // for the side to move, stored from white's point of view
+        for (idx, analyzed) in parallel.moves.iter().enumerate() {
+            let before = if idx == 0 { 2 } else { idx as i32 + 3 };
+            let sign = if idx % 2 == 0 { 1 } else { -1 };
+            assert_eq!(analyzed.uci, moves[idx].uci);
+            assert_eq!(
+                analyzed.engine_eval_before,
+                Some(Evaluation::Centipawn(sign * before))
+            );
+            assert_eq!(
+                analyzed.engine_eval_after,
+                Some(Evaluation::Centipawn(-sign * (idx as i32 + 4)))
+            );
+        }
+
+        let mut single = GameAnalyzer::new(engine, AnalysisConfig::default()).unwrap();
+        let sequential = single
+            .analyze_game("g1", "a", "b", &moves, "*", None)
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&parallel).unwrap(),
+            serde_json::to_value(&sequential).unwrap()
+        );
+        assert_eq!(pooled.cache_stats(), single.cache_stats());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_only_legal_move_is_forced() {
+        let dir = tempfile::tempdir().unwrap();
+        let (engine, _) = fake_engine(dir.path());
+        let mut analyzer = GameAnalyzer::new(engine.to_str().unwrap(), AnalysisConfig::default())
+            .expect("fake engine starts");
+
+        // Kxb2 is white's only legal move; black's king then has three
+        let analysis = analyzer
+            .analyze_game(
+                "g1",
+                "a",
+                "b",
+                &move_inputs(&["a1b2", "a8b8"]),
+                "1/2-1/2",
+                Some("k7/7p/8/8/8/8/1r6/K7 w - - 0 1"),
+            )
+            .unwrap();
+
+        assert!(analysis.moves[0].forced);
+        assert!(!analysis.moves[1].forced);
+        assert_eq!(analysis.forced_moves(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_forced_margin_compares_the_top_two_lines() {
+        // An engine with two close candidates at the start and a single
+        // good reply to 1. e4
+        let dir = tempfile::tempdir().unwrap();
+        let script = scripted_engine(
+            dir.path(),
+            r#"case "$position" in
+           *e2e4) echo "info depth 1 multipv 1 score cp 300 nodes 10 pv d7d5"
+                  echo "info depth 1 multipv 2 score cp -200 nodes 10 pv a7a6" ;;
+           *) echo "info depth 1 multipv 1 score cp 30 nodes 10 pv e2e4"
+              echo "info depth 1 multipv 2 score cp 20 nodes 10 pv d2d4" ;;
+         esac"#,
+        );
+        let log = dir.path().join("options.log");
+
+        let config = AnalysisConfig {
+            forced_margin: Some(150),
+            ..AnalysisConfig::default()
+        };
+        let mut analyzer = GameAnalyzer::new(script.to_str().unwrap(), config).unwrap();
+        let moves = move_inputs(&["e2e4", "d7d5"]);
+        let analysis = analyzer
+            .analyze_game("g1", "a", "b", &moves, "*", None)
+            .unwrap();
+
+        let options = std::fs::read_to_string(&log).unwrap();
+        assert_eq!(options.trim(), "setoption name MultiPV value 2");
+        // 30 against 20 leaves a choice; 300 against -200 does not
+        assert!(!analysis.moves[0].forced);
+        assert!(analysis.moves[1].forced);
+        assert_eq!(analysis.forced_moves(), 1);
+
+        // Without a margin only the legal move count matters
+        let mut analyzer =
+            GameAnalyzer::new(script.to_str().unwrap(), AnalysisConfig::default()).unwrap();
+        let analysis = analyzer
+            .analyze_game("g1", "a", "b", &moves, "*", None)
+            .unwrap();
+        assert_eq!(analysis.forced_moves(), 0);
+    }
+
+    /// Directory with the KQvK tables checked in as test fixtures.
+    const SYZYGY_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/syzygy");
+
+    #[cfg(unix)]
+    #[test]
+    fn test_tablebase_replaces_engine_eval_within_piece_limit() {
+        // 1. Qxd2 leaves KQvK, which the tables cover; the engine calls
+        // every position +0.30 for the side to move
+        let dir = tempfile::tempdir().unwrap();
+        let script = scripted_engine(
+            dir.path(),
+            r#"echo "info depth 1 score cp 30 nodes 10 pv 0000""#,
+        );
+        let config = AnalysisConfig {
+            tb_path: Some(PathBuf::from(SYZYGY_PATH)),
+            ..AnalysisConfig::default()
+        };
+        let mut analyzer = GameAnalyzer::new(script.to_str().unwrap(), config).unwrap();
+        let analysis = analyzer
+            .analyze_game(
+                "g1",
+                "a",
+                "b",
+                &move_inputs(&["d1d2"]),
+                "*",
+                Some("4k3/8/8/8/8/8/3p4/3QK3 w - - 0 1"),
+            )
+            .unwrap();
+
+        // Four pieces are over the limit, so the engine's score stands
+        let capture = &analysis.moves[0];
+        assert_eq!(capture.engine_eval_before, Some(Evaluation::Centipawn(30)));
+        // Black to move in KQvK is lost: the tablebase win replaces the
+        // engine's -0.30 from white's point of view
+        assert_eq!(
+            capture.engine_eval_after,
+            Some(Evaluation::Centipawn(TB_WIN_CP))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_forced_margin_ignores_tablebase_positions() {
+        // The tablebase scores KQvK as won, far above the engine's second
+        // line, but the engine's two lines are close
+        let dir = tempfile::tempdir().unwrap();
+        let script = scripted_engine(
+            dir.path(),
+            r#"echo "info depth 1 multipv 1 score cp 30 nodes 10 pv d1d7"
+         echo "info depth 1 multipv 2 score cp 20 nodes 10 pv d1d8""#,
+        );
+        let config = AnalysisConfig {
+            forced_margin: Some(150),
+            tb_path: Some(PathBuf::from(SYZYGY_PATH)),
+            ..AnalysisConfig::default()
+        };
+        let mut analyzer = GameAnalyzer::new(script.to_str().unwrap(), config).unwrap();
+        let analysis = analyzer
+            .analyze_game(
+                "g1",
+                "a",
+                "b",
+                &move_inputs(&["d1d7"]),
+                "*",
+                Some("4k3/8/8/8/8/8/8/3QK3 w - - 0 1"),
+            )
+            .unwrap();
+        assert!(!analysis.moves[0].forced);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_move_evaluations_are_white_relative() {
+        // An engine that finds mate for white once black plays 2...g5, and
+        // otherwise scores every position 30cp in white's favour, reported
+        // for the side to move as UCI engines do
+        let dir = tempfile::tempdir().unwrap();
+        let script = scripted_engine(
+            dir.path(),
+            r#"case "$position" in
+           *g7g5) echo "info depth 1 score mate 1 nodes 10 pv d1h5" ;;
+           *e2e4|*d2d4) echo "info depth 1 score cp -30 nodes 10 pv 0000" ;;
+           *) echo "info depth 1 score cp 30 nodes 10 pv 0000" ;;
+         esac"#,
+        );
+
+        let mut analyzer =
+            GameAnalyzer::new(script.to_str().unwrap(), AnalysisConfig::default()).unwrap();
+        let mut moves = move_inputs(&["e2e4", "f7f6", "d2d4", "g7g5"]);
+        // Black's bot thought it was fine, from its own side of the board
+        moves[3].bot_eval_cp = Some(20);
+        let analysis = analyzer
+            .analyze_game("g1", "a", "b", &moves, "*", None)
+            .unwrap();
+
+        for analyzed in &analysis.moves[..3] {
+            assert_eq!(analyzed.engine_eval_before, Some(Evaluation::Centipawn(30)));
+        }
+        let blunder = &analysis.moves[3];
+        assert_eq!(blunder.engine_eval_before, Some(Evaluation::Centipawn(30)));
+        assert_eq!(blunder.engine_eval_after, Some(Evaluation::Mate(1)));
+        assert_eq!(blunder.bot_eval, Some(Evaluation::Centipawn(-20)));
+        assert_eq!(blunder.quality, MoveQuality::Blunder);
+        assert_eq!(analysis.black_stats.blunders, 1);
+        assert_eq!(analysis.white_stats.blunders, 0);
+
+        let json = serde_json::to_value(blunder).unwrap();
+        assert_eq!(json["engine_eval_after"], serde_json::json!({ "Mate": 1 }));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_parallel_analysis_rejects_invalid_moves() {
+        let dir = tempfile::tempdir().unwrap();
+        let (engine, log) = fake_engine(dir.path());
+        let mut analyzer =
+            GameAnalyzer::with_engines(engine.to_str().unwrap(), AnalysisConfig::default(), 2)
+                .unwrap();
+
+        let err = analyzer
+            .analyze_game_parallel("g1", "a", "b", &move_inputs(&["e2e4", "e2e4"]), "*", None)
+            .unwrap_err();
+        assert!(matches!(err, AnalyzerError::InvalidGame(_)));
+        assert!(!log.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_eval_timeline_is_from_whites_point_of_view() {
+        let dir = tempfile::tempdir().unwrap();
+        let (engine, _) = fake_engine(dir.path());
+        let mut analyzer =
+            GameAnalyzer::new(engine.to_str().unwrap(), AnalysisConfig::default()).unwrap();
+        let moves = move_inputs(&["e2e4", "e7e5", "g1f3"]);
+        let analysis = analyzer
+            .analyze_game("g1", "a", "b", &moves, "*", None)
+            .unwrap();
+
+        // One entry per position, including the start. The fake engine
+        // scores every position in favour of the side to move, so white is
+        // ahead when white is to move and behind when black is
+        assert_eq!(
+            analysis.eval_timeline,
+            vec![
+                (0, Evaluation::Centipawn(2)),
+                (1, Evaluation::Centipawn(-4)),
+                (2, Evaluation::Centipawn(5)),
+                (3, Evaluation::Centipawn(-6)),
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_analysis_meta_counts_engine_searches() {
+        let dir = tempfile::tempdir().unwrap();
+        let (engine, _) = fake_engine(dir.path());
+        let mut analyzer =
+            GameAnalyzer::new(engine.to_str().unwrap(), AnalysisConfig::default()).unwrap();
+        let moves = move_inputs(&["e2e4", "e7e5"]);
+        let analysis = analyzer
+            .analyze_game("g1", "a", "b", &moves, "*", None)
+            .unwrap();
+        assert_eq!(
+            analysis.meta,
+            AnalysisMeta {
+                positions_searched: 3,
+                nodes: 3000,
+                time_ms: 12,
+            }
+        );
+        assert_eq!(analysis.meta.nps(), Some(250_000));
+
+        // Cached positions take no engine time
+        let again = analyzer
+            .analyze_game("g2", "a", "b", &moves, "*", None)
+            .unwrap();
+        assert_eq!(again.meta, AnalysisMeta::default());
+        assert_eq!(again.meta.nps(), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_analysis_from_start_fen() {
+        let dir = tempfile::tempdir().unwrap();
+        let (engine, log) = fake_engine(dir.path());
+        let mut analyzer =
+            GameAnalyzer::new(engine.to_str().unwrap(), AnalysisConfig::default()).unwrap();
+
+        // Giuoco Piano after 4. c3, black to move
+        let fen = "r1bqk1nr/pppp1ppp/2n5/2b1p3/2B1P3/2P2N2/PP1P1PPP/RNBQK2R b KQkq - 0 4";
+        let moves = move_inputs(&["g8f6", "d2d4", "e5d4"]);
+        let analysis = analyzer
+            .analyze_game("g1", "a", "b", &moves, "*", Some(fen))
+            .unwrap();
+
+        assert_eq!(analysis.moves.len(), 3);
+        assert!(analysis
+            .moves
+            .iter()
+            .all(|m| m.engine_eval_before.is_some() && m.engine_eval_after.is_some()));
+
+        // The engine is given the FEN followed by the moves played so far
+        let searches = std::fs::read_to_string(&log).unwrap();
+        let positions: Vec<&str> = searches
+            .lines()
+            .map(|l| l.split(" | ").nth(1).unwrap())
+            .collect();
+        assert_eq!(positions[0], format!("position fen {}", fen));
+        assert_eq!(
+            positions[3],
+            format!("position fen {} moves g8f6 d2d4 e5d4", fen)
+        );
+
+        // Black moves first, so the even plies are black's
+        assert_eq!(analysis.start_fen.as_deref(), Some(fen));
+        assert_eq!(analysis.first_mover(), Color::Black);
+        assert_eq!(
+            analysis.eval_timeline,
+            vec![
+                (0, Evaluation::Centipawn(-8)),
+                (1, Evaluation::Centipawn(10)),
+                (2, Evaluation::Centipawn(-11)),
+                (3, Evaluation::Centipawn(12)),
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_invalid_start_fen_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let (engine, log) = fake_engine(dir.path());
+        let mut analyzer =
+            GameAnalyzer::new(engine.to_str().unwrap(), AnalysisConfig::default()).unwrap();
+
+        let err = analyzer
+            .analyze_game(
+                "g1",
+                "a",
+                "b",
+                &move_inputs(&["e2e4"]),
+                "*",
+                Some("not a fen"),
+            )
+            .unwrap_err();
+        assert!(matches!(err, AnalyzerError::InvalidGame(_)));
+        assert!(!log.exists());
     }
 }
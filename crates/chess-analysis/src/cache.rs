@@ -0,0 +1,162 @@
+//! Position evaluation cache.
+//!
+//! Analyzing many games evaluates the same positions over and over, openings
+//! especially. [`EvalCache`] remembers engine results keyed by position and
+//! search limit so each unique position is only sent to the engine once.
+
+use std::collections::HashMap;
+
+use chess_engine::Position;
+
+use crate::engine::{AnalysisLimit, PositionAnalysis};
+
+/// Hit and miss counts for an [`EvalCache`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheStats {
+    /// Lookups answered from the cache.
+    pub hits: u64,
+    /// Lookups that needed an engine call.
+    pub misses: u64,
+}
+
+/// In-memory cache of engine analyses keyed by (position, search limit).
+///
+/// Positions are keyed by their FEN without the move counters, so the same
+/// position reached by transposition or repetition shares an entry. Entries
+/// are only returned for the exact limit they were computed with; a plain
+/// depth stands for [`AnalysisLimit::Depth`].
+#[derive(Debug, Default)]
+pub struct EvalCache {
+    entries: HashMap<(String, AnalysisLimit), PositionAnalysis>,
+    stats: CacheStats,
+}
+
+impl EvalCache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached analysis of `position` at `limit`, counting a hit
+    /// or a miss.
+    pub fn get(
+        &mut self,
+        position: &Position,
+        limit: impl Into<AnalysisLimit>,
+    ) -> Option<PositionAnalysis> {
+        let found = self
+            .entries
+            .get(&(cache_key(position), limit.into()))
+            .cloned();
+        if found.is_some() {
+            self.stats.hits += 1;
+        } else {
+            self.stats.misses += 1;
+        }
+        found
+    }
+
+    /// Stores the analysis of `position` at `limit`.
+    pub fn insert(
+        &mut self,
+        position: &Position,
+        limit: impl Into<AnalysisLimit>,
+        analysis: PositionAnalysis,
+    ) {
+        self.entries
+            .insert((cache_key(position), limit.into()), analysis);
+    }
+
+    /// Returns the number of cached analyses.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if nothing is cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the hit and miss counts since the cache was created.
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Removes all entries and resets the counters.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.stats = CacheStats::default();
+    }
+}
+
+/// Returns the FEN of `position` without the halfmove and fullmove counters.
+pub(crate) fn cache_key(position: &Position) -> String {
+    let fen = position.to_fen();
+    fen.split(' ').take(4).collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Evaluation;
+
+    fn analysis(cp: i32, depth: u32) -> PositionAnalysis {
+        PositionAnalysis {
+            best_move: "e2e4".to_string(),
+            evaluation: Evaluation::Centipawn(cp),
+            depth,
+            nodes: 1000,
+            time_ms: 10,
+            pv: vec!["e2e4".to_string()],
+            second_best: None,
+        }
+    }
+
+    #[test]
+    fn test_cache_hit_and_miss() {
+        let mut cache = EvalCache::new();
+        let position = Position::startpos();
+
+        assert!(cache.get(&position, 15).is_none());
+        cache.insert(&position, 15, analysis(20, 15));
+        let cached = cache.get(&position, 15).unwrap();
+        assert_eq!(cached.evaluation, Evaluation::Centipawn(20));
+
+        assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 1 });
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_cache_is_depth_aware() {
+        let mut cache = EvalCache::new();
+        let position = Position::startpos();
+        cache.insert(&position, 15, analysis(20, 15));
+
+        assert!(cache.get(&position, 20).is_none());
+        cache.insert(&position, 20, analysis(30, 20));
+        assert_eq!(
+            cache.get(&position, 15).unwrap().evaluation,
+            Evaluation::Centipawn(20)
+        );
+        assert_eq!(
+            cache.get(&position, 20).unwrap().evaluation,
+            Evaluation::Centipawn(30)
+        );
+        // A node limit never matches a depth
+        assert!(cache.get(&position, AnalysisLimit::Nodes(15)).is_none());
+    }
+
+    #[test]
+    fn test_cache_ignores_move_counters() {
+        let mut cache = EvalCache::new();
+        let early = Position::startpos();
+        let later =
+            Position::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 4 3").unwrap();
+        cache.insert(&early, 10, analysis(20, 10));
+        assert!(cache.get(&later, 10).is_some());
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.stats(), CacheStats::default());
+    }
+}
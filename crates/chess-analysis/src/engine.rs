@@ -36,8 +36,48 @@ pub struct PositionAnalysis {
     pub depth: u32,
     /// The number of nodes searched.
     pub nodes: u64,
+    /// Search time reported by the engine, in milliseconds.
+    pub time_ms: u64,
     /// The principal variation (sequence of best moves).
     pub pv: Vec<String>,
+    /// The evaluation of the second-best move, when the engine was asked
+    /// for more than one line (see [`AnalysisEngine::set_multipv`]).
+    pub second_best: Option<Evaluation>,
+}
+
+/// How long the engine searches each position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnalysisLimit {
+    /// Search to a fixed depth (`go depth N`).
+    Depth(u32),
+    /// Search a fixed number of nodes (`go nodes N`). Unlike depth or time,
+    /// this gives the same result on any machine.
+    Nodes(u64),
+    /// Search for a fixed time in milliseconds (`go movetime N`).
+    MoveTime(u64),
+}
+
+impl AnalysisLimit {
+    /// Returns the UCI `go` command for this limit.
+    pub fn go_command(&self) -> String {
+        format!("go {}", self)
+    }
+}
+
+impl std::fmt::Display for AnalysisLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnalysisLimit::Depth(depth) => write!(f, "depth {}", depth),
+            AnalysisLimit::Nodes(nodes) => write!(f, "nodes {}", nodes),
+            AnalysisLimit::MoveTime(ms) => write!(f, "movetime {}", ms),
+        }
+    }
+}
+
+impl From<u32> for AnalysisLimit {
+    fn from(depth: u32) -> Self {
+        AnalysisLimit::Depth(depth)
+    }
 }
 
 /// Wrapper for UCI-compatible analysis engines like Stockfish.
@@ -163,7 +203,7 @@ impl AnalysisEngine {
     /// Position analysis including best move, evaluation, and principal variation.
     pub fn analyze_fen(&mut self, fen: &str, depth: u32) -> Result<PositionAnalysis, EngineError> {
         self.send_command(&format!("position fen {}", fen))?;
-        self.run_analysis(depth)
+        self.run_analysis(AnalysisLimit::Depth(depth))
     }
 
     /// Analyze a position given as a sequence of moves from the starting position.
@@ -181,24 +221,61 @@ impl AnalysisEngine {
         moves: &[String],
         depth: u32,
     ) -> Result<PositionAnalysis, EngineError> {
-        if moves.is_empty() {
-            self.send_command("position startpos")?;
-        } else {
-            let moves_str = moves.join(" ");
-            self.send_command(&format!("position startpos moves {}", moves_str))?;
+        self.analyze_moves_from(None, moves, depth)
+    }
+
+    /// Analyze a position given as a sequence of moves from `start_fen`, or
+    /// from the standard starting position if it is `None`.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_fen` - Starting position in FEN notation
+    /// * `moves` - Sequence of moves in UCI notation played from the start
+    /// * `depth` - Maximum search depth
+    pub fn analyze_moves_from(
+        &mut self,
+        start_fen: Option<&str>,
+        moves: &[String],
+        depth: u32,
+    ) -> Result<PositionAnalysis, EngineError> {
+        self.analyze_limited(start_fen, moves, AnalysisLimit::Depth(depth))
+    }
+
+    /// Analyze a position given as a sequence of moves from `start_fen`, or
+    /// from the standard starting position if it is `None`, searching until
+    /// `limit` is reached.
+    ///
+    /// The engine stops on its own once the limit is reached; the result is
+    /// taken from the last info line before its `bestmove`.
+    pub fn analyze_limited(
+        &mut self,
+        start_fen: Option<&str>,
+        moves: &[String],
+        limit: AnalysisLimit,
+    ) -> Result<PositionAnalysis, EngineError> {
+        let mut command = match start_fen {
+            Some(fen) => format!("position fen {}", fen),
+            None => "position startpos".to_string(),
+        };
+        if !moves.is_empty() {
+            command.push_str(" moves ");
+            command.push_str(&moves.join(" "));
         }
-        self.run_analysis(depth)
+        self.send_command(&command)?;
+        self.run_analysis(limit)
     }
 
     /// Run the analysis for the current position.
-    fn run_analysis(&mut self, depth: u32) -> Result<PositionAnalysis, EngineError> {
-        self.send_command(&format!("go depth {}", depth))?;
+    fn run_analysis(&mut self, limit: AnalysisLimit) -> Result<PositionAnalysis, EngineError> {
+        self.send_command(&limit.go_command())?;
 
         let mut best_move = String::new();
         let mut evaluation = Evaluation::Centipawn(0);
         let mut best_depth: u32 = 0;
         let mut nodes: u64 = 0;
+        let mut time_ms: u64 = 0;
         let mut pv: Vec<String> = Vec::new();
+        let mut second_best: Option<Evaluation> = None;
 
         let mut lines_read = 0;
         loop {
@@ -211,6 +288,18 @@ impl AnalysisEngine {
             let line = self.read_line()?;
 
             if line.starts_with("info depth ") {
+                // Lines after the first only tell us how good the
+                // alternatives are
+                match Self::parse_info_multipv(&line) {
+                    Some(1) | None => {}
+                    Some(2) => {
+                        if let Some(parsed) = Self::parse_info_line(&line) {
+                            second_best = Some(parsed.1);
+                        }
+                        continue;
+                    }
+                    Some(_) => continue,
+                }
                 // Parse info line: "info depth X score cp Y nodes Z pv ..."
                 if let Some(parsed) = Self::parse_info_line(&line) {
                     best_depth = parsed.0;
@@ -218,6 +307,9 @@ impl AnalysisEngine {
                     nodes = parsed.2;
                     pv = parsed.3;
                 }
+                if let Some(time) = Self::parse_info_time(&line) {
+                    time_ms = time;
+                }
             } else if line.starts_with("bestmove ") {
                 // Parse bestmove: "bestmove e2e4 ponder e7e5"
                 let parts: Vec<&str> = line.split_whitespace().collect();
@@ -239,10 +331,26 @@ impl AnalysisEngine {
             evaluation,
             depth: best_depth,
             nodes,
+            time_ms,
             pv,
+            second_best,
         })
     }
 
+    /// Parse the search time (`time <ms>`) from a UCI info line.
+    fn parse_info_time(line: &str) -> Option<u64> {
+        let mut parts = line.split_whitespace();
+        parts.find(|&part| part == "time")?;
+        parts.next()?.parse().ok()
+    }
+
+    /// Parse the line number (`multipv <n>`) from a UCI info line.
+    fn parse_info_multipv(line: &str) -> Option<u32> {
+        let mut parts = line.split_whitespace();
+        parts.find(|&part| part == "multipv")?;
+        parts.next()?.parse().ok()
+    }
+
     /// Parse a UCI info line to extract depth, score, nodes, and PV.
     ///
     /// Format: "info depth X score cp Y nodes Z pv move1 move2 ..."
@@ -310,10 +418,25 @@ impl AnalysisEngine {
         self.send_command("stop")
     }
 
+    /// Ask the engine to search `lines` best moves instead of one (UCI
+    /// `MultiPV`).
+    ///
+    /// With two or more lines, each [`PositionAnalysis`] also reports the
+    /// evaluation of the second-best move.
+    pub fn set_multipv(&mut self, lines: u32) -> Result<(), EngineError> {
+        self.send_command(&format!("setoption name MultiPV value {}", lines.max(1)))?;
+        self.wait_ready()
+    }
+
     /// Clear the engine's hash tables and prepare for a new game.
     pub fn clear_hash(&mut self) -> Result<(), EngineError> {
         self.send_command("ucinewgame")?;
         // Wait for engine to be ready after clearing
+        self.wait_ready()
+    }
+
+    /// Send `isready` and wait for the engine's `readyok`.
+    fn wait_ready(&mut self) -> Result<(), EngineError> {
         self.send_command("isready")?;
         let mut lines_read = 0;
         loop {
@@ -374,6 +497,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_info_time() {
+        let line = "info depth 15 seldepth 20 score cp 35 nodes 50000 nps 250000 time 200 pv e2e4";
+        assert_eq!(AnalysisEngine::parse_info_time(line), Some(200));
+        assert_eq!(
+            AnalysisEngine::parse_info_time("info depth 5 score cp 0 nodes 1000"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_info_multipv() {
+        let line = "info depth 12 multipv 2 score cp -40 nodes 9000 pv d2d4";
+        assert_eq!(AnalysisEngine::parse_info_multipv(line), Some(2));
+        assert_eq!(
+            AnalysisEngine::parse_info_multipv("info depth 12 score cp 10 pv e2e4"),
+            None
+        );
+    }
+
     #[test]
     fn test_position_analysis_clone() {
         let analysis = PositionAnalysis {
@@ -381,7 +524,9 @@ mod tests {
             evaluation: Evaluation::Centipawn(35),
             depth: 20,
             nodes: 1_000_000,
+            time_ms: 500,
             pv: vec!["e2e4".to_string(), "e7e5".to_string()],
+            second_best: None,
         };
 
         let cloned = analysis.clone();
@@ -391,6 +536,14 @@ mod tests {
         assert_eq!(cloned.pv.len(), 2);
     }
 
+    #[test]
+    fn test_analysis_limit_go_command() {
+        assert_eq!(AnalysisLimit::Depth(18).go_command(), "go depth 18");
+        assert_eq!(AnalysisLimit::Nodes(100000).go_command(), "go nodes 100000");
+        assert_eq!(AnalysisLimit::MoveTime(250).go_command(), "go movetime 250");
+        assert_eq!(AnalysisLimit::from(12), AnalysisLimit::Depth(12));
+    }
+
     #[test]
     fn test_engine_error_display() {
         let spawn_err = EngineError::SpawnError(std::io::Error::new(
@@ -52,6 +52,24 @@ impl Evaluation {
         }
     }
 
+    /// Builds an evaluation from UCI `score cp` / `score mate` values.
+    ///
+    /// Like [`from_uci_score`](Self::from_uci_score), mate takes precedence,
+    /// but a missing score is treated as a level position instead of `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chess_analysis::Evaluation;
+    ///
+    /// assert_eq!(Evaluation::from_uci(Some(35), None), Evaluation::Centipawn(35));
+    /// assert_eq!(Evaluation::from_uci(Some(35), Some(-2)), Evaluation::Mate(-2));
+    /// assert_eq!(Evaluation::from_uci(None, None), Evaluation::Centipawn(0));
+    /// ```
+    pub fn from_uci(score_cp: Option<i32>, score_mate: Option<i32>) -> Self {
+        Self::from_uci_score(score_cp, score_mate).unwrap_or(Evaluation::Centipawn(0))
+    }
+
     /// Returns the centipawn value, converting mate to a large value.
     ///
     /// Mate scores are converted to approximately ±10000 centipawns,
@@ -80,6 +98,30 @@ impl Evaluation {
         }
     }
 
+    /// Returns the expected score (0.0 to 1.0) for the side this evaluation
+    /// favours when positive.
+    ///
+    /// Uses a logistic curve fitted to engine games, so +0.00 is 0.5 and
+    /// large advantages approach 1.0. Mates count as certain results.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chess_analysis::Evaluation;
+    ///
+    /// assert_eq!(Evaluation::Centipawn(0).win_probability(), 0.5);
+    /// assert!(Evaluation::Centipawn(300).win_probability() > 0.7);
+    /// assert_eq!(Evaluation::Mate(2).win_probability(), 1.0);
+    /// assert_eq!(Evaluation::Mate(-2).win_probability(), 0.0);
+    /// ```
+    pub fn win_probability(&self) -> f64 {
+        match self {
+            Evaluation::Centipawn(cp) => 1.0 / (1.0 + (-0.00368208 * f64::from(*cp)).exp()),
+            Evaluation::Mate(n) if *n > 0 => 1.0,
+            Evaluation::Mate(_) => 0.0,
+        }
+    }
+
     /// Returns true if this evaluation is better for white than the other.
     ///
     /// # Examples
@@ -101,17 +143,20 @@ impl Evaluation {
         self.to_centipawns() < other.to_centipawns()
     }
 
-    /// Returns the evaluation from black's perspective (negated).
+    /// Returns the evaluation from the other side's perspective.
+    ///
+    /// Engines report scores for the side to move, so negating converts a
+    /// score after a move back to the perspective of the player who made it.
     ///
     /// # Examples
     ///
     /// ```
     /// use chess_analysis::Evaluation;
     ///
-    /// assert_eq!(Evaluation::Centipawn(50).flip(), Evaluation::Centipawn(-50));
-    /// assert_eq!(Evaluation::Mate(3).flip(), Evaluation::Mate(-3));
+    /// assert_eq!(Evaluation::Centipawn(-120).negate(), Evaluation::Centipawn(120));
+    /// assert_eq!(Evaluation::Mate(-3).negate(), Evaluation::Mate(3));
     /// ```
-    pub fn flip(&self) -> Self {
+    pub fn negate(self) -> Self {
         match self {
             Evaluation::Centipawn(cp) => Evaluation::Centipawn(-cp),
             Evaluation::Mate(n) => Evaluation::Mate(-n),
@@ -123,14 +168,16 @@ impl std::fmt::Display for Evaluation {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Evaluation::Centipawn(cp) => {
-                let sign = if *cp >= 0 { "+" } else { "" };
-                write!(f, "{}{:.2}", sign, *cp as f32 / 100.0)
+                // Integer arithmetic keeps the hundredths exact
+                let sign = if *cp >= 0 { '+' } else { '-' };
+                let abs = cp.unsigned_abs();
+                write!(f, "{}{}.{:02}", sign, abs / 100, abs % 100)
             }
             Evaluation::Mate(n) => {
-                if *n > 0 {
-                    write!(f, "#{}", n)
+                if *n < 0 {
+                    write!(f, "#-{}", n.unsigned_abs())
                 } else {
-                    write!(f, "#-{}", -n)
+                    write!(f, "#{}", n)
                 }
             }
         }
@@ -141,6 +188,19 @@ impl std::fmt::Display for Evaluation {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_win_probability_is_symmetric() {
+        for cp in [0, 50, 150, 400, 1000] {
+            let ahead = Evaluation::Centipawn(cp).win_probability();
+            let behind = Evaluation::Centipawn(-cp).win_probability();
+            assert!((ahead + behind - 1.0).abs() < 1e-12);
+        }
+        assert!(
+            Evaluation::Centipawn(200).win_probability()
+                > Evaluation::Centipawn(100).win_probability()
+        );
+    }
+
     #[test]
     fn test_from_uci_score_centipawn() {
         let eval = Evaluation::from_uci_score(Some(35), None);
@@ -210,6 +270,42 @@ mod tests {
         assert_eq!(format!("{}", Evaluation::Mate(-10)), "#-10");
     }
 
+    #[test]
+    fn test_display_pawn_rounding() {
+        assert_eq!(Evaluation::Centipawn(5).to_string(), "+0.05");
+        assert_eq!(Evaluation::Centipawn(-5).to_string(), "-0.05");
+        assert_eq!(Evaluation::Centipawn(-120).to_string(), "-1.20");
+        assert_eq!(Evaluation::Centipawn(1234).to_string(), "+12.34");
+        assert_eq!(Evaluation::Centipawn(i32::MIN).to_string(), "-21474836.48");
+    }
+
+    #[test]
+    fn test_from_uci() {
+        assert_eq!(
+            Evaluation::from_uci(Some(35), None),
+            Evaluation::Centipawn(35)
+        );
+        assert_eq!(Evaluation::from_uci(None, Some(5)), Evaluation::Mate(5));
+        assert_eq!(
+            Evaluation::from_uci(Some(900), Some(-3)),
+            Evaluation::Mate(-3)
+        );
+        assert_eq!(Evaluation::from_uci(None, None), Evaluation::Centipawn(0));
+    }
+
+    #[test]
+    fn test_negate_mate_sign() {
+        assert_eq!(Evaluation::Mate(5).negate(), Evaluation::Mate(-5));
+        assert_eq!(Evaluation::Mate(5).negate().to_string(), "#-5");
+        assert_eq!(Evaluation::Mate(-3).negate().to_string(), "#3");
+        // `score mate 0` is sent for the side that is already mated
+        assert_eq!(Evaluation::Mate(0).to_string(), "#0");
+        assert_eq!(Evaluation::Mate(0).negate().to_string(), "#0");
+        assert_eq!(Evaluation::Mate(i32::MIN).to_string(), "#-2147483648");
+        assert_eq!(Evaluation::Centipawn(35).negate().to_string(), "-0.35");
+        assert_eq!(Evaluation::Centipawn(0).negate().to_string(), "+0.00");
+    }
+
     #[test]
     fn test_is_better_for_white() {
         let good = Evaluation::Centipawn(100);
@@ -231,14 +327,17 @@ mod tests {
     }
 
     #[test]
-    fn test_flip() {
-        assert_eq!(Evaluation::Centipawn(50).flip(), Evaluation::Centipawn(-50));
+    fn test_negate() {
+        assert_eq!(
+            Evaluation::Centipawn(50).negate(),
+            Evaluation::Centipawn(-50)
+        );
         assert_eq!(
-            Evaluation::Centipawn(-100).flip(),
+            Evaluation::Centipawn(-100).negate(),
             Evaluation::Centipawn(100)
         );
-        assert_eq!(Evaluation::Mate(3).flip(), Evaluation::Mate(-3));
-        assert_eq!(Evaluation::Mate(-5).flip(), Evaluation::Mate(5));
+        assert_eq!(Evaluation::Mate(3).negate(), Evaluation::Mate(-3));
+        assert_eq!(Evaluation::Mate(-5).negate(), Evaluation::Mate(5));
     }
 
     #[test]
@@ -9,6 +9,9 @@
 //! - [`MoveQuality`] - Classification of move quality (Best, Excellent, Good, etc.)
 //! - [`AnalysisEngine`] - Wrapper for UCI analysis engines like Stockfish
 //! - [`GameAnalyzer`] - Analyzes complete games with move quality classification
+//! - [`EvalCache`] - Cache of engine evaluations shared across analyzed games
+//! - [`Tablebase`] - Syzygy endgame tablebase probing for exact endgame results
+//! - [`find_puzzles`] - Tactical puzzles from the blunders of an analyzed game
 //!
 //! # Example
 //!
@@ -17,16 +20,22 @@
 //!
 //! let config = AnalysisConfig::default();
 //! let mut analyzer = GameAnalyzer::new("stockfish", config)?;
-//! let analysis = analyzer.analyze_game("game1", "white", "black", &moves, "draw")?;
+//! let analysis = analyzer.analyze_game("game1", "white", "black", &moves, "draw", None)?;
 //! println!("White accuracy: {:.1}%", analysis.white_stats.accuracy_percent);
 //! ```
 
 pub mod analyzer;
+pub mod cache;
 pub mod engine;
 pub mod evaluation;
+pub mod puzzles;
 pub mod quality;
+pub mod tablebase;
 
 pub use analyzer::{AnalysisConfig, AnalyzerError, GameAnalyzer, MoveInput};
-pub use engine::{AnalysisEngine, EngineError, PositionAnalysis};
+pub use cache::{CacheStats, EvalCache};
+pub use engine::{AnalysisEngine, AnalysisLimit, EngineError, PositionAnalysis};
 pub use evaluation::Evaluation;
-pub use quality::{GameAnalysis, MoveAnalysis, MoveQuality, PlayerStats};
+pub use puzzles::{find_puzzles, Puzzle, PuzzleTheme};
+pub use quality::{AnalysisMeta, GameAnalysis, MoveAnalysis, MoveQuality, Outcome, PlayerStats};
+pub use tablebase::{Tablebase, TablebaseError, Wdl, TB_WIN_CP};
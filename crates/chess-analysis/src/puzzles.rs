@@ -0,0 +1,270 @@
+//! Tactical puzzles taken from analyzed games.
+//!
+//! A puzzle is a position where the opponent has just blundered and the
+//! player to move can punish it with a forcing move: a mate, or a check or
+//! capture that wins decisive material. [`find_puzzles`] scans a
+//! [`GameAnalysis`] for such positions.
+
+use chess_core::{Color, MoveFlag};
+use chess_engine::{generate_moves, is_king_attacked, make_move, Game};
+use serde::{Deserialize, Serialize};
+
+use crate::analyzer::AnalyzerError;
+use crate::evaluation::Evaluation;
+use crate::quality::{GameAnalysis, MoveQuality};
+
+/// Advantage in centipawns the player to move must have after the
+/// blunder for the position to make a puzzle.
+pub const PUZZLE_MIN_ADVANTAGE: i32 = 200;
+
+/// What the solver of a puzzle has to find.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PuzzleTheme {
+    /// A forced checkmate
+    Mate,
+    /// A forcing move that wins decisive material
+    WinMaterial,
+}
+
+/// A tactical puzzle from a game.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Puzzle {
+    /// Position to solve, in FEN notation
+    pub fen: String,
+    /// The solution in UCI notation, starting with the solver's move. Mates
+    /// give the engine's whole line up to the final move; other puzzles
+    /// only the first move, since the engine's follow-up is rarely unique.
+    pub solution_moves: Vec<String>,
+    /// What the solution achieves
+    pub theme: PuzzleTheme,
+    /// Index into [`GameAnalysis::moves`] of the move the solver faces
+    pub ply: usize,
+}
+
+/// Finds the puzzles in an analyzed game, replayed from its
+/// [`start_fen`](GameAnalysis::start_fen).
+///
+/// A position becomes a puzzle when the previous move was a
+/// [`MoveQuality::Blunder`], the engine rates the position at least
+/// [`PUZZLE_MIN_ADVANTAGE`] for the player to move, and the engine's best
+/// move is forcing: it mates, gives check or captures. Puzzles come back
+/// in move order.
+///
+/// # Errors
+///
+/// Returns [`AnalyzerError::InvalidGame`] if the start FEN or a move of the
+/// analysis is invalid.
+pub fn find_puzzles(analysis: &GameAnalysis) -> Result<Vec<Puzzle>, AnalyzerError> {
+    let mut game = match analysis.start_fen.as_deref() {
+        Some(fen) => Game::from_fen(fen)
+            .map_err(|e| AnalyzerError::InvalidGame(format!("invalid start FEN: {}", e)))?,
+        None => Game::new(),
+    };
+
+    let mut puzzles = Vec::new();
+    for (ply, analyzed) in analysis.moves.iter().enumerate() {
+        let after_blunder = ply > 0 && analysis.moves[ply - 1].quality == MoveQuality::Blunder;
+        if after_blunder {
+            if let Some(puzzle) = puzzle_at(&game, ply, analysis) {
+                puzzles.push(puzzle);
+            }
+        }
+        game.make_move_uci(&analyzed.uci)
+            .map_err(|e| AnalyzerError::InvalidGame(format!("move {}: {}", ply + 1, e)))?;
+    }
+    Ok(puzzles)
+}
+
+/// Builds the puzzle for the position of `game`, before move `ply`, if the
+/// engine's best move there is a decisive forcing move.
+fn puzzle_at(game: &Game, ply: usize, analysis: &GameAnalysis) -> Option<Puzzle> {
+    let analyzed = &analysis.moves[ply];
+    let position = game.position();
+    let solver = position.side_to_move;
+
+    // Engine evaluations are stored from white's point of view
+    let eval = analyzed.engine_eval_before?;
+    let eval = match solver {
+        Color::White => eval,
+        Color::Black => eval.negate(),
+    };
+
+    let mut line = analyzed.engine_pv.clone();
+    if line.is_empty() {
+        line.push(analyzed.engine_best_move.clone()?);
+    }
+    let best = generate_moves(position).find_uci(&line[0])?;
+
+    let theme = if matches!(eval, Evaluation::Mate(n) if n > 0) {
+        PuzzleTheme::Mate
+    } else {
+        let gives_check = is_king_attacked(&make_move(position, best), solver.opposite());
+        let captures = position.piece_at(best.to()).is_some() || best.flag() == MoveFlag::EnPassant;
+        if eval.to_centipawns() < PUZZLE_MIN_ADVANTAGE || !(gives_check || captures) {
+            return None;
+        }
+        PuzzleTheme::WinMaterial
+    };
+
+    // Mating lines end with the solver's move; other puzzles only need the
+    // first one
+    match theme {
+        PuzzleTheme::Mate => line.truncate(line.len() - (line.len() + 1) % 2),
+        PuzzleTheme::WinMaterial => line.truncate(1),
+    }
+
+    Some(Puzzle {
+        fen: game.to_fen(),
+        solution_moves: line,
+        theme,
+        ply,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quality::{AnalysisMeta, MoveAnalysis, PlayerStats};
+
+    /// A move analysis with the engine's view of the position before it.
+    fn analyzed(uci: &str, quality: MoveQuality, before: Evaluation, pv: &[&str]) -> MoveAnalysis {
+        MoveAnalysis {
+            uci: uci.to_string(),
+            san: None,
+            quality,
+            bot_eval: None,
+            bot_depth: None,
+            bot_nodes: None,
+            bot_time_ms: None,
+            bot_pv: Vec::new(),
+            engine_eval_before: Some(before),
+            engine_eval_after: None,
+            engine_best_move: pv.first().map(|m| m.to_string()),
+            engine_pv: pv.iter().map(|m| m.to_string()).collect(),
+            centipawn_loss: None,
+            forced: false,
+        }
+    }
+
+    fn game(moves: Vec<MoveAnalysis>) -> GameAnalysis {
+        GameAnalysis {
+            game_id: "g1".to_string(),
+            white_bot: "a".to_string(),
+            black_bot: "b".to_string(),
+            opening: None,
+            result: "1-0".to_string(),
+            moves,
+            white_stats: PlayerStats::default(),
+            black_stats: PlayerStats::default(),
+            eval_timeline: Vec::new(),
+            meta: AnalysisMeta::default(),
+            start_fen: None,
+        }
+    }
+
+    #[test]
+    fn test_blunder_into_mate_makes_one_puzzle() {
+        use MoveQuality::*;
+
+        // 1. e4 f6 2. d4 g5?? 3. Qh5#
+        let level = Evaluation::Centipawn(30);
+        let analysis = game(vec![
+            analyzed("e2e4", Best, level, &["e2e4", "e7e5"]),
+            analyzed("f7f6", Inaccuracy, level, &["e7e5"]),
+            analyzed("d2d4", Best, level, &["d2d4"]),
+            analyzed("g7g5", Blunder, level, &["e7e6"]),
+            analyzed("d1h5", Best, Evaluation::Mate(1), &["d1h5"]),
+        ]);
+
+        let puzzles = find_puzzles(&analysis).unwrap();
+        assert_eq!(
+            puzzles,
+            vec![Puzzle {
+                fen: "rnbqkbnr/ppppp2p/5p2/6p1/3PP3/8/PPP2PPP/RNBQKBNR w KQkq g6 0 3".to_string(),
+                solution_moves: vec!["d1h5".to_string()],
+                theme: PuzzleTheme::Mate,
+                ply: 4,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_win_material_needs_a_forcing_move() {
+        use MoveQuality::*;
+
+        // 1. e4 d5?? 2. Nc3?? Nf6, scored as if each blunder lost the game:
+        // white's best reply takes on d5, but black's best reply is quiet
+        let analysis = game(vec![
+            analyzed("e2e4", Best, Evaluation::Centipawn(30), &["e2e4"]),
+            analyzed("d7d5", Blunder, Evaluation::Centipawn(30), &["e7e5"]),
+            analyzed("b1c3", Blunder, Evaluation::Centipawn(400), &["e4d5"]),
+            analyzed("g8f6", Best, Evaluation::Centipawn(-450), &["g8f6", "e4e5"]),
+        ]);
+        let puzzles = find_puzzles(&analysis).unwrap();
+        assert_eq!(puzzles.len(), 1);
+        assert_eq!(puzzles[0].ply, 2);
+        assert_eq!(puzzles[0].theme, PuzzleTheme::WinMaterial);
+        assert_eq!(puzzles[0].solution_moves, vec!["e4d5".to_string()]);
+    }
+
+    #[test]
+    fn test_en_passant_capture_is_forcing() {
+        use MoveQuality::*;
+
+        // 1. e4 a6 2. e5 d5?? 3. exd6 e.p., scored as if it won a piece
+        let level = Evaluation::Centipawn(30);
+        let analysis = game(vec![
+            analyzed("e2e4", Best, level, &["e2e4"]),
+            analyzed("a7a6", Inaccuracy, level, &["e7e5"]),
+            analyzed("e4e5", Best, level, &["e4e5"]),
+            analyzed("d7d5", Blunder, level, &["d7d6"]),
+            analyzed("e5d6", Best, Evaluation::Centipawn(400), &["e5d6"]),
+        ]);
+        let puzzles = find_puzzles(&analysis).unwrap();
+        assert_eq!(puzzles.len(), 1);
+        assert_eq!(puzzles[0].theme, PuzzleTheme::WinMaterial);
+        assert_eq!(puzzles[0].solution_moves, vec!["e5d6".to_string()]);
+    }
+
+    #[test]
+    fn test_puzzles_replay_from_the_analysis_start_fen() {
+        use MoveQuality::*;
+
+        // 1. Ra7?? Rxa7 from a rook ending
+        let mut analysis = game(vec![
+            analyzed("a1a7", Blunder, Evaluation::Centipawn(0), &["e1e2"]),
+            analyzed("d7a7", Best, Evaluation::Centipawn(-500), &["d7a7"]),
+        ]);
+        analysis.start_fen = Some("4k3/3r4/8/8/8/8/8/R3K3 w - - 0 1".to_string());
+
+        let puzzles = find_puzzles(&analysis).unwrap();
+        assert_eq!(puzzles.len(), 1);
+        assert_eq!(puzzles[0].fen, "4k3/R2r4/8/8/8/8/8/4K3 b - - 1 1");
+        assert_eq!(puzzles[0].solution_moves, vec!["d7a7".to_string()]);
+    }
+
+    #[test]
+    fn test_small_advantage_is_no_puzzle() {
+        use MoveQuality::*;
+
+        let analysis = game(vec![
+            analyzed("e2e4", Blunder, Evaluation::Centipawn(30), &["d2d4"]),
+            analyzed("d7d5", Best, Evaluation::Centipawn(-100), &["d7d5"]),
+        ]);
+        assert!(find_puzzles(&analysis).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_move_is_an_error() {
+        let analysis = game(vec![analyzed(
+            "e2e5",
+            MoveQuality::Best,
+            Evaluation::Centipawn(0),
+            &[],
+        )]);
+        assert!(matches!(
+            find_puzzles(&analysis),
+            Err(AnalyzerError::InvalidGame(_))
+        ));
+    }
+}
@@ -1,12 +1,29 @@
 //! Move quality classification and game analysis.
 
+use chess_core::{Color, Move, Square};
+use chess_engine::movegen::see;
+use chess_engine::Position;
 use serde::{Deserialize, Serialize};
 
 use crate::Evaluation;
 
+/// Minimum material, in centipawns of static exchange loss, that a move must
+/// give up to count as a sacrifice.
+pub const BRILLIANT_MIN_SACRIFICE: i32 = 200;
+
+/// Largest evaluation drop, in centipawns, that a sacrifice may cost and
+/// still be brilliant.
+pub const BRILLIANT_EVAL_TOLERANCE: i32 = 10;
+
+/// Smallest drop in the mover's win probability that counts as a turning
+/// point (see [`GameAnalysis::turning_point`]).
+pub const TURNING_POINT_MIN_SWING: f64 = 0.2;
+
 /// Classification of move quality based on evaluation loss.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MoveQuality {
+    /// Top engine choice that sacrifices material (see [`is_brilliant`])
+    Brilliant,
     /// Matches top engine choice (0 cp loss)
     Best,
     /// Within 10cp of best
@@ -66,6 +83,7 @@ impl MoveQuality {
     /// ```
     /// use chess_analysis::MoveQuality;
     ///
+    /// assert!(!MoveQuality::Brilliant.is_negative());
     /// assert!(!MoveQuality::Best.is_negative());
     /// assert!(!MoveQuality::Excellent.is_negative());
     /// assert!(!MoveQuality::Good.is_negative());
@@ -80,9 +98,88 @@ impl MoveQuality {
             MoveQuality::Inaccuracy | MoveQuality::Mistake | MoveQuality::Blunder
         )
     }
+
+    /// Returns the annotation glyph written after the move in game scores,
+    /// or `None` for moves that get no annotation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chess_analysis::MoveQuality;
+    ///
+    /// assert_eq!(MoveQuality::Brilliant.glyph(), Some("!!"));
+    /// assert_eq!(MoveQuality::Inaccuracy.glyph(), Some("?!"));
+    /// assert_eq!(MoveQuality::Mistake.glyph(), Some("?"));
+    /// assert_eq!(MoveQuality::Blunder.glyph(), Some("??"));
+    /// assert_eq!(MoveQuality::Best.glyph(), None);
+    /// assert_eq!(MoveQuality::Forced.glyph(), None);
+    /// ```
+    pub fn glyph(&self) -> Option<&'static str> {
+        match self {
+            MoveQuality::Brilliant => Some("!!"),
+            MoveQuality::Inaccuracy => Some("?!"),
+            MoveQuality::Mistake => Some("?"),
+            MoveQuality::Blunder => Some("??"),
+            MoveQuality::Best
+            | MoveQuality::Excellent
+            | MoveQuality::Good
+            | MoveQuality::Forced => None,
+        }
+    }
+}
+
+/// Returns true if playing `m` in `position` is a brilliant sacrifice.
+///
+/// A move is brilliant when all of the following hold:
+/// - it is the engine's top choice (`engine_best_move`, in UCI notation);
+/// - its static exchange evaluation loses at least
+///   [`BRILLIANT_MIN_SACRIFICE`] centipawns;
+/// - it does not capture on `recapture_square`, the square where the
+///   opponent's previous move captured, since taking back is not a
+///   sacrifice even when the exchange looks bad in isolation;
+/// - the evaluation after the move is no worse for the mover than
+///   [`BRILLIANT_EVAL_TOLERANCE`] below the evaluation before it, and the
+///   mover is not worse off than level.
+///
+/// Both evaluations are from the point of view of the side making the move.
+///
+/// # Examples
+///
+/// ```
+/// use chess_analysis::{quality::is_brilliant, Evaluation};
+/// use chess_core::Move;
+/// use chess_engine::Position;
+///
+/// // Qg8+!! Rxg8 Nf7# gives up the queen to force mate
+/// let position = Position::from_fen("5r1k/6pp/7N/8/2Q5/8/6PP/6K1 w - - 0 1").unwrap();
+/// let m = Move::from_uci("c4g8").unwrap();
+/// assert!(is_brilliant(&position, m, None, Evaluation::Mate(2), Evaluation::Mate(1), "c4g8"));
+/// ```
+pub fn is_brilliant(
+    position: &Position,
+    m: Move,
+    recapture_square: Option<Square>,
+    eval_before: Evaluation,
+    eval_after: Evaluation,
+    engine_best_move: &str,
+) -> bool {
+    if m.to_uci() != engine_best_move || recapture_square == Some(m.to()) {
+        return false;
+    }
+
+    let before = eval_before.to_centipawns();
+    let after = eval_after.to_centipawns();
+    if after < 0 || after < before - BRILLIANT_EVAL_TOLERANCE {
+        return false;
+    }
+
+    see(position, m) <= -BRILLIANT_MIN_SACRIFICE
 }
 
 /// Analysis result for a single move.
+///
+/// Every [`Evaluation`] is from white's point of view (positive = white
+/// advantage), whichever side made the move.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MoveAnalysis {
     /// The move that was played (in UCI notation)
@@ -91,7 +188,7 @@ pub struct MoveAnalysis {
     pub san: Option<String>,
     /// Quality classification of the move
     pub quality: MoveQuality,
-    /// Bot's own evaluation of the position
+    /// Bot's own evaluation of the position, white-relative
     pub bot_eval: Option<Evaluation>,
     /// Search depth used by the bot
     pub bot_depth: Option<u32>,
@@ -101,9 +198,9 @@ pub struct MoveAnalysis {
     pub bot_time_ms: Option<u64>,
     /// Principal variation from the bot's search
     pub bot_pv: Vec<String>,
-    /// Engine evaluation before the move
+    /// Engine evaluation before the move, white-relative
     pub engine_eval_before: Option<Evaluation>,
-    /// Engine evaluation after the move
+    /// Engine evaluation after the move, white-relative
     pub engine_eval_after: Option<Evaluation>,
     /// Best move according to the engine
     pub engine_best_move: Option<String>,
@@ -111,6 +208,48 @@ pub struct MoveAnalysis {
     pub engine_pv: Vec<String>,
     /// Centipawn loss from playing this move
     pub centipawn_loss: Option<i32>,
+    /// Whether the player had only one reasonable move: the only legal
+    /// move, or one the engine rated far above its second choice (see
+    /// [`AnalysisConfig::forced_margin`](crate::AnalysisConfig::forced_margin))
+    #[serde(default)]
+    pub forced: bool,
+}
+
+/// How a game ended for one player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    /// The player won
+    Win,
+    /// The game was drawn
+    Draw,
+    /// The player lost
+    Loss,
+}
+
+impl Outcome {
+    /// Returns white's outcome for a recorded game result.
+    ///
+    /// Accepts PGN results (`1-0`, `0-1`, `1/2-1/2`) and the arena's
+    /// `white`, `black` and `draw`. Returns `None` for unfinished games
+    /// (`*`) and anything else.
+    pub fn for_white(result: &str) -> Option<Outcome> {
+        match result.trim() {
+            "1-0" | "white" => Some(Outcome::Win),
+            "0-1" | "black" => Some(Outcome::Loss),
+            "1/2-1/2" | "draw" => Some(Outcome::Draw),
+            _ => None,
+        }
+    }
+
+    /// Returns the opponent's outcome.
+    pub fn flipped(self) -> Outcome {
+        match self {
+            Outcome::Win => Outcome::Loss,
+            Outcome::Draw => Outcome::Draw,
+            Outcome::Loss => Outcome::Win,
+        }
+    }
 }
 
 /// Statistics for a player's performance in a game.
@@ -132,6 +271,10 @@ pub struct PlayerStats {
     pub avg_time_ms: u64,
     /// Accuracy percentage (0-100)
     pub accuracy_percent: f32,
+    /// The player's result, taken from the recorded game result; `None`
+    /// for unfinished games
+    #[serde(default)]
+    pub outcome: Option<Outcome>,
 }
 
 impl PlayerStats {
@@ -242,10 +385,40 @@ impl PlayerStats {
             avg_nodes,
             avg_time_ms,
             accuracy_percent,
+            outcome: None,
         }
     }
 }
 
+/// Engine effort spent analyzing a game.
+///
+/// Only positions the engine actually searched are counted; positions
+/// served from the evaluation cache cost nothing. With several engines the
+/// times are summed, so [`nps`](Self::nps) is the speed of one engine.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnalysisMeta {
+    /// Number of positions searched by the engine
+    pub positions_searched: usize,
+    /// Total nodes searched
+    pub nodes: u64,
+    /// Total search time reported by the engine, in milliseconds
+    pub time_ms: u64,
+}
+
+impl AnalysisMeta {
+    /// Adds the effort of one engine search.
+    pub fn record(&mut self, nodes: u64, time_ms: u64) {
+        self.positions_searched += 1;
+        self.nodes += nodes;
+        self.time_ms += time_ms;
+    }
+
+    /// Effective nodes per second, or `None` if no search time was reported.
+    pub fn nps(&self) -> Option<u64> {
+        (self.time_ms > 0).then(|| self.nodes.saturating_mul(1000) / self.time_ms)
+    }
+}
+
 /// Complete analysis of a chess game.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameAnalysis {
@@ -265,6 +438,74 @@ pub struct GameAnalysis {
     pub white_stats: PlayerStats,
     /// Statistics for black
     pub black_stats: PlayerStats,
+    /// Evaluation of every position by ply, from white's point of view
+    /// (positive = white advantage), starting with the initial position
+    #[serde(default)]
+    pub eval_timeline: Vec<(usize, Evaluation)>,
+    /// Nodes and time spent by the engine on this game
+    #[serde(default)]
+    pub meta: AnalysisMeta,
+    /// Position the game started from in FEN notation, if not the standard
+    /// starting position
+    #[serde(default)]
+    pub start_fen: Option<String>,
+}
+
+impl GameAnalysis {
+    /// Returns the side that played the first of [`moves`](Self::moves):
+    /// the side to move in [`start_fen`](Self::start_fen), or white.
+    pub fn first_mover(&self) -> Color {
+        match self
+            .start_fen
+            .as_deref()
+            .and_then(|fen| fen.split_whitespace().nth(1))
+        {
+            Some("b") => Color::Black,
+            _ => Color::White,
+        }
+    }
+
+    /// Returns the side that played move `ply` of [`moves`](Self::moves).
+    pub fn mover(&self, ply: usize) -> Color {
+        if ply.is_multiple_of(2) {
+            self.first_mover()
+        } else {
+            self.first_mover().opposite()
+        }
+    }
+
+    /// Finds the move that most changed the expected outcome of the game.
+    ///
+    /// Compares consecutive entries of [`eval_timeline`](Self::eval_timeline)
+    /// and returns the move whose player lost the most win probability by
+    /// playing it, as `(ply, color, swing)`. `ply` indexes [`moves`](Self::moves)
+    /// (the move from timeline entry `ply` to `ply + 1`), `color` is the
+    /// player who made it and `swing` is the win probability they gave
+    /// away. Returns `None` if no move costs at least
+    /// [`TURNING_POINT_MIN_SWING`]; the earliest move wins ties.
+    pub fn turning_point(&self) -> Option<(usize, Color, f64)> {
+        let mut best: Option<(usize, Color, f64)> = None;
+        for pair in self.eval_timeline.windows(2) {
+            let (ply, before) = pair[0];
+            let (_, after) = pair[1];
+            let white_swing = before.win_probability() - after.win_probability();
+            let color = self.mover(ply);
+            let swing = match color {
+                Color::White => white_swing,
+                Color::Black => -white_swing,
+            };
+            if swing >= TURNING_POINT_MIN_SWING && best.is_none_or(|(_, _, s)| swing > s) {
+                best = Some((ply, color, swing));
+            }
+        }
+        best
+    }
+
+    /// Returns the number of moves where the player had only one reasonable
+    /// move (see [`MoveAnalysis::forced`]).
+    pub fn forced_moves(&self) -> usize {
+        self.moves.iter().filter(|m| m.forced).count()
+    }
 }
 
 #[cfg(test)]
@@ -322,6 +563,7 @@ mod tests {
     #[test]
     fn test_move_quality_is_negative() {
         // Positive qualities
+        assert!(!MoveQuality::Brilliant.is_negative());
         assert!(!MoveQuality::Best.is_negative());
         assert!(!MoveQuality::Excellent.is_negative());
         assert!(!MoveQuality::Good.is_negative());
@@ -333,6 +575,96 @@ mod tests {
         assert!(MoveQuality::Blunder.is_negative());
     }
 
+    /// Philidor's legacy: Qg8+ Rxg8 Nf7# sacrifices the queen.
+    const QUEEN_SAC_FEN: &str = "5r1k/6pp/7N/8/2Q5/8/6PP/6K1 w - - 0 1";
+
+    fn uci(m: &str) -> Move {
+        Move::from_uci(m).unwrap()
+    }
+
+    #[test]
+    fn test_queen_sacrifice_is_brilliant() {
+        let position = Position::from_fen(QUEEN_SAC_FEN).unwrap();
+        assert!(is_brilliant(
+            &position,
+            uci("c4g8"),
+            None,
+            Evaluation::Mate(2),
+            Evaluation::Mate(1),
+            "c4g8"
+        ));
+
+        // From a winning but not mating position, keeping the eval is enough
+        assert!(is_brilliant(
+            &position,
+            uci("c4g8"),
+            None,
+            Evaluation::Centipawn(600),
+            Evaluation::Centipawn(605),
+            "c4g8"
+        ));
+    }
+
+    #[test]
+    fn test_sacrifice_needs_engine_approval_and_eval() {
+        let position = Position::from_fen(QUEEN_SAC_FEN).unwrap();
+
+        // Not the engine's choice
+        assert!(!is_brilliant(
+            &position,
+            uci("c4g8"),
+            None,
+            Evaluation::Mate(2),
+            Evaluation::Mate(1),
+            "h6f7"
+        ));
+        // Gives away the advantage
+        assert!(!is_brilliant(
+            &position,
+            uci("c4g8"),
+            None,
+            Evaluation::Centipawn(600),
+            Evaluation::Centipawn(100),
+            "c4g8"
+        ));
+        // Keeps a losing position losing
+        assert!(!is_brilliant(
+            &position,
+            uci("c4g8"),
+            None,
+            Evaluation::Centipawn(-300),
+            Evaluation::Centipawn(-300),
+            "c4g8"
+        ));
+    }
+
+    #[test]
+    fn test_recapture_is_not_brilliant() {
+        // After ...Bxd5 the queen takes back on d5 although the pawn on e6
+        // defends it, so the exchange on its own loses material
+        let position = Position::from_fen("4k3/8/4p3/3b4/8/8/8/3QK3 w - - 0 1").unwrap();
+        assert!(see(&position, uci("d1d5")) < -BRILLIANT_MIN_SACRIFICE);
+        assert!(!is_brilliant(
+            &position,
+            uci("d1d5"),
+            Some(uci("d1d5").to()),
+            Evaluation::Centipawn(0),
+            Evaluation::Centipawn(0),
+            "d1d5"
+        ));
+
+        // A plain recapture that wins material back is no sacrifice either
+        let position = Position::from_fen("4k3/8/8/3b4/8/8/8/3QK3 w - - 0 1").unwrap();
+        assert!(!is_brilliant(
+            &position,
+            uci("d1d5"),
+            None,
+            Evaluation::Centipawn(0),
+            Evaluation::Centipawn(330),
+            "d1d5"
+        ));
+    }
+
     #[test]
     fn test_player_stats_from_empty_moves() {
         let stats = PlayerStats::from_moves(&[]);
@@ -365,6 +697,7 @@ mod tests {
                 engine_best_move: Some("e2e4".to_string()),
                 engine_pv: vec![],
                 centipawn_loss: Some(0),
+                forced: false,
             },
             MoveAnalysis {
                 uci: "d2d4".to_string(),
@@ -380,6 +713,7 @@ mod tests {
                 engine_best_move: Some("c2c4".to_string()),
                 engine_pv: vec![],
                 centipawn_loss: Some(20),
+                forced: false,
             },
             MoveAnalysis {
                 uci: "a2a4".to_string(),
@@ -395,6 +729,7 @@ mod tests {
                 engine_best_move: Some("b1c3".to_string()),
                 engine_pv: vec![],
                 centipawn_loss: Some(50),
+                forced: false,
             },
         ];
 
@@ -440,6 +775,7 @@ mod tests {
                 engine_best_move: None,
                 engine_pv: vec![],
                 centipawn_loss: Some(400),
+                forced: false,
             },
             MoveAnalysis {
                 uci: "d2d4".to_string(),
@@ -455,6 +791,7 @@ mod tests {
                 engine_best_move: None,
                 engine_pv: vec![],
                 centipawn_loss: Some(200),
+                forced: false,
             },
             MoveAnalysis {
                 uci: "b1c3".to_string(),
@@ -470,6 +807,7 @@ mod tests {
                 engine_best_move: None,
                 engine_pv: vec![],
                 centipawn_loss: Some(60),
+                forced: false,
             },
         ];
 
@@ -504,6 +842,7 @@ mod tests {
             engine_best_move: Some("e2e4".to_string()),
             engine_pv: vec!["e2e4".to_string(), "e7e5".to_string()],
             centipawn_loss: Some(0),
+            forced: false,
         };
 
         let json = serde_json::to_string(&analysis).unwrap();
@@ -525,6 +864,7 @@ mod tests {
             avg_nodes: 500000,
             avg_time_ms: 300,
             accuracy_percent: 75.5,
+            outcome: Some(Outcome::Loss),
         };
 
         let json = serde_json::to_string(&stats).unwrap();
@@ -534,6 +874,82 @@ mod tests {
         assert_eq!(parsed.blunders, 1);
         assert_eq!(parsed.mistakes, 2);
         assert_eq!(parsed.inaccuracies, 3);
+        assert_eq!(parsed.outcome, Some(Outcome::Loss));
+        assert!(json.contains(r#""outcome":"loss""#));
+    }
+
+    #[test]
+    fn test_outcome_from_recorded_result() {
+        assert_eq!(Outcome::for_white("1-0"), Some(Outcome::Win));
+        assert_eq!(Outcome::for_white("black"), Some(Outcome::Loss));
+        assert_eq!(Outcome::for_white("1/2-1/2"), Some(Outcome::Draw));
+        assert_eq!(Outcome::for_white("*"), None);
+        assert_eq!(Outcome::Loss.flipped(), Outcome::Win);
+        assert_eq!(Outcome::Draw.flipped(), Outcome::Draw);
+    }
+
+    fn game_with_timeline(white_pov: &[i32]) -> GameAnalysis {
+        GameAnalysis {
+            game_id: "game".to_string(),
+            white_bot: "white".to_string(),
+            black_bot: "black".to_string(),
+            opening: None,
+            result: "*".to_string(),
+            moves: vec![],
+            white_stats: PlayerStats::default(),
+            black_stats: PlayerStats::default(),
+            eval_timeline: white_pov
+                .iter()
+                .enumerate()
+                .map(|(ply, &cp)| (ply, Evaluation::Centipawn(cp)))
+                .collect(),
+            meta: AnalysisMeta::default(),
+            start_fen: None,
+        }
+    }
+
+    #[test]
+    fn test_turning_point_finds_black_blunder() {
+        // Black's second move (ply 3) throws away a level game
+        let game = game_with_timeline(&[20, 30, 25, 40, 650, 640, 700]);
+        let (ply, color, swing) = game.turning_point().unwrap();
+        assert_eq!((ply, color), (3, Color::Black));
+        let expected = Evaluation::Centipawn(650).win_probability()
+            - Evaluation::Centipawn(40).win_probability();
+        assert!((swing - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_turning_point_finds_white_blunder() {
+        // White hangs mate at ply 4 after a smaller slip at ply 2
+        let mut game = game_with_timeline(&[20, 10, 200, 0, 0, 0]);
+        game.eval_timeline[5].1 = Evaluation::Mate(-1);
+        let (ply, color, swing) = game.turning_point().unwrap();
+        assert_eq!((ply, color), (4, Color::White));
+        assert!((swing - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_turning_point_after_black_to_move_start() {
+        // Black moves first, so the blunder from ply 2 to 3 is black's
+        let mut game = game_with_timeline(&[-20, -30, -25, 600, 610]);
+        game.start_fen =
+            Some("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1".to_string());
+        assert_eq!(game.first_mover(), Color::Black);
+        assert_eq!(game.mover(1), Color::White);
+
+        let (ply, color, swing) = game.turning_point().unwrap();
+        assert_eq!((ply, color), (2, Color::Black));
+        let expected = Evaluation::Centipawn(600).win_probability()
+            - Evaluation::Centipawn(-25).win_probability();
+        assert!((swing - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_balanced_game_has_no_turning_point() {
+        let game = game_with_timeline(&[20, 35, 10, -15, 0, 40, 25]);
+        assert_eq!(game.turning_point(), None);
+        assert_eq!(game_with_timeline(&[]).turning_point(), None);
     }
 
     #[test]
@@ -547,6 +963,13 @@ mod tests {
             moves: vec![],
             white_stats: PlayerStats::default(),
             black_stats: PlayerStats::default(),
+            eval_timeline: vec![(0, Evaluation::Centipawn(20)), (1, Evaluation::Mate(-3))],
+            meta: AnalysisMeta {
+                positions_searched: 2,
+                nodes: 3000,
+                time_ms: 40,
+            },
+            start_fen: None,
         };
 
         let json = serde_json::to_string(&game).unwrap();
@@ -557,5 +980,17 @@ mod tests {
         assert_eq!(parsed.black_bot, "komodo-14");
         assert_eq!(parsed.opening, Some("Sicilian Defense".to_string()));
         assert_eq!(parsed.result, "1-0");
+        assert_eq!(parsed.eval_timeline, game.eval_timeline);
+        assert_eq!(parsed.meta, game.meta);
+    }
+
+    #[test]
+    fn test_analysis_meta_nps() {
+        let mut meta = AnalysisMeta::default();
+        assert_eq!(meta.nps(), None);
+        meta.record(150_000, 100);
+        meta.record(50_000, 100);
+        assert_eq!(meta.positions_searched, 2);
+        assert_eq!(meta.nps(), Some(1_000_000));
     }
 }
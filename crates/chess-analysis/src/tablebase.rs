@@ -0,0 +1,228 @@
+//! Syzygy endgame tablebase probing.
+//!
+//! Wraps the pure-Rust [`shakmaty_syzygy`] reader so positions from
+//! `chess-engine` can be probed for exact win/draw/loss results once few
+//! enough pieces remain on the board.
+
+use std::path::Path;
+
+use chess_engine::Position;
+use shakmaty::fen::Fen;
+use shakmaty::{CastlingMode, Chess};
+use thiserror::Error;
+
+use crate::evaluation::Evaluation;
+
+/// Centipawn score used for a tablebase win.
+///
+/// Large enough to dominate any positional evaluation, but below the
+/// values produced by [`Evaluation::to_centipawns`] for mate scores.
+pub const TB_WIN_CP: i32 = 5000;
+
+/// Errors that can occur when opening a tablebase.
+#[derive(Error, Debug)]
+pub enum TablebaseError {
+    /// The tablebase directory could not be read.
+    #[error("Failed to read tablebase directory: {0}")]
+    Io(#[from] std::io::Error),
+    /// The directory does not contain any Syzygy table files.
+    #[error("No Syzygy tables found in: {0}")]
+    NoTables(String),
+}
+
+/// Win/draw/loss result of a tablebase position, from the side to move's
+/// perspective, taking the 50-move rule into account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Wdl {
+    /// Unconditional loss.
+    Loss,
+    /// Loss that can be saved by the 50-move rule.
+    BlessedLoss,
+    /// Unconditional draw.
+    Draw,
+    /// Win that can be frustrated by the 50-move rule.
+    CursedWin,
+    /// Unconditional win.
+    Win,
+}
+
+impl Wdl {
+    /// Converts the result into an evaluation for the side to move.
+    ///
+    /// Cursed wins and blessed losses are drawn under the 50-move rule and
+    /// therefore evaluate as 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chess_analysis::{Evaluation, Wdl, TB_WIN_CP};
+    ///
+    /// assert_eq!(Wdl::Win.to_evaluation(), Evaluation::Centipawn(TB_WIN_CP));
+    /// assert_eq!(Wdl::CursedWin.to_evaluation(), Evaluation::Centipawn(0));
+    /// ```
+    pub fn to_evaluation(self) -> Evaluation {
+        match self {
+            Wdl::Win => Evaluation::Centipawn(TB_WIN_CP),
+            Wdl::Loss => Evaluation::Centipawn(-TB_WIN_CP),
+            Wdl::CursedWin | Wdl::Draw | Wdl::BlessedLoss => Evaluation::Centipawn(0),
+        }
+    }
+}
+
+impl From<shakmaty_syzygy::Wdl> for Wdl {
+    fn from(wdl: shakmaty_syzygy::Wdl) -> Self {
+        match wdl {
+            shakmaty_syzygy::Wdl::Loss => Wdl::Loss,
+            shakmaty_syzygy::Wdl::BlessedLoss => Wdl::BlessedLoss,
+            shakmaty_syzygy::Wdl::Draw => Wdl::Draw,
+            shakmaty_syzygy::Wdl::CursedWin => Wdl::CursedWin,
+            shakmaty_syzygy::Wdl::Win => Wdl::Win,
+        }
+    }
+}
+
+/// A set of Syzygy tablebase files.
+///
+/// Table files are opened lazily on first probe.
+pub struct Tablebase {
+    /// The underlying Syzygy reader.
+    inner: shakmaty_syzygy::Tablebase<Chess>,
+}
+
+impl Tablebase {
+    /// Opens all Syzygy tables (`*.rtbw` / `*.rtbz`) in a directory.
+    ///
+    /// # Errors
+    ///
+    /// - `TablebaseError::Io` if the directory cannot be read
+    /// - `TablebaseError::NoTables` if it contains no table files
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, TablebaseError> {
+        let path = path.as_ref();
+        let mut inner = shakmaty_syzygy::Tablebase::new();
+        if inner.add_directory(path)? == 0 {
+            return Err(TablebaseError::NoTables(path.display().to_string()));
+        }
+        Ok(Self { inner })
+    }
+
+    /// Returns the largest number of pieces (including kings) covered by the
+    /// loaded tables.
+    pub fn max_pieces(&self) -> usize {
+        self.inner.max_pieces()
+    }
+
+    /// Returns true if the position has few enough pieces to be probed.
+    pub fn covers(&self, position: &Position) -> bool {
+        position.occupied().count() as usize <= self.max_pieces()
+    }
+
+    /// Probes the win/draw/loss result for the side to move.
+    ///
+    /// Returns `None` if the position is not covered by the loaded tables,
+    /// has castling rights, or the result is ambiguous because of the
+    /// halfmove clock.
+    pub fn probe_wdl(&self, position: &Position) -> Option<Wdl> {
+        let pos = self.to_shakmaty(position)?;
+        let wdl = self.inner.probe_wdl(&pos).ok()?;
+        wdl.unambiguous().map(Wdl::from)
+    }
+
+    /// Probes the distance to zeroing (DTZ) in plies for the side to move.
+    ///
+    /// The value is positive for a win and negative for a loss, and counts
+    /// the plies until the next capture or pawn move on the optimal path.
+    /// Returns `None` if the position is not covered by the loaded tables.
+    pub fn probe_dtz(&self, position: &Position) -> Option<i32> {
+        let pos = self.to_shakmaty(position)?;
+        let dtz = self.inner.probe_dtz(&pos).ok()?;
+        Some(dtz.ignore_rounding().0)
+    }
+
+    /// Converts a position for probing, skipping positions with too many pieces.
+    fn to_shakmaty(&self, position: &Position) -> Option<Chess> {
+        if !self.covers(position) {
+            return None;
+        }
+        Fen::from_ascii(position.to_fen().as_bytes())
+            .ok()?
+            .into_position(CastlingMode::Standard)
+            .ok()
+    }
+}
+
+impl std::fmt::Debug for Tablebase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Tablebase")
+            .field("max_pieces", &self.max_pieces())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Directory with the KQvK tables checked in as test fixtures.
+    const SYZYGY_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/syzygy");
+
+    #[test]
+    fn test_wdl_to_evaluation() {
+        assert_eq!(Wdl::Win.to_evaluation(), Evaluation::Centipawn(TB_WIN_CP));
+        assert_eq!(Wdl::Loss.to_evaluation(), Evaluation::Centipawn(-TB_WIN_CP));
+        assert_eq!(Wdl::Draw.to_evaluation(), Evaluation::Centipawn(0));
+        assert_eq!(Wdl::CursedWin.to_evaluation(), Evaluation::Centipawn(0));
+        assert_eq!(Wdl::BlessedLoss.to_evaluation(), Evaluation::Centipawn(0));
+        assert!(TB_WIN_CP < Evaluation::Mate(100).to_centipawns());
+    }
+
+    #[test]
+    fn test_wdl_from_syzygy() {
+        assert_eq!(Wdl::from(shakmaty_syzygy::Wdl::Win), Wdl::Win);
+        assert_eq!(Wdl::from(shakmaty_syzygy::Wdl::CursedWin), Wdl::CursedWin);
+        assert_eq!(Wdl::from(shakmaty_syzygy::Wdl::Draw), Wdl::Draw);
+        assert_eq!(
+            Wdl::from(shakmaty_syzygy::Wdl::BlessedLoss),
+            Wdl::BlessedLoss
+        );
+        assert_eq!(Wdl::from(shakmaty_syzygy::Wdl::Loss), Wdl::Loss);
+    }
+
+    #[test]
+    fn test_open_missing_directory() {
+        let err = Tablebase::open("/nonexistent/syzygy").unwrap_err();
+        assert!(matches!(err, TablebaseError::Io(_)));
+    }
+
+    #[test]
+    fn test_open_directory_without_tables() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("README.txt"), "not a table").unwrap();
+
+        let err = Tablebase::open(dir.path()).unwrap_err();
+        assert!(matches!(err, TablebaseError::NoTables(_)));
+        assert!(err.to_string().contains("No Syzygy tables"));
+    }
+
+    #[test]
+    fn test_kqvk_is_win() {
+        let tb = Tablebase::open(SYZYGY_PATH).unwrap();
+        assert_eq!(tb.max_pieces(), 3);
+
+        let pos = Position::from_fen("4k3/8/8/8/8/8/8/3QK3 w - - 0 1").unwrap();
+        assert_eq!(tb.probe_wdl(&pos), Some(Wdl::Win));
+        assert!(tb.probe_dtz(&pos).unwrap() > 0);
+
+        // Same position with black to move is lost for black
+        let pos = Position::from_fen("4k3/8/8/8/8/8/8/3QK3 b - - 0 1").unwrap();
+        assert_eq!(tb.probe_wdl(&pos), Some(Wdl::Loss));
+    }
+
+    #[test]
+    fn test_too_many_pieces() {
+        let tb = Tablebase::open(SYZYGY_PATH).unwrap();
+        let pos = Position::startpos();
+        assert!(!tb.covers(&pos));
+        assert_eq!(tb.probe_wdl(&pos), None);
+        assert_eq!(tb.probe_dtz(&pos), None);
+    }
+}
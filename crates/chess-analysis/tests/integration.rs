@@ -140,14 +140,24 @@ fn test_scholars_mate_game_analysis() {
     // Use AnalysisConfig with depth 12
     let config = AnalysisConfig {
         depth: 12,
+        limit: None,
         opening_book_moves: 0,
+        tb_path: None,
+        forced_margin: None,
     };
 
     let mut analyzer =
         GameAnalyzer::new("stockfish", config).expect("Failed to create GameAnalyzer");
 
     let analysis = analyzer
-        .analyze_game("scholars_mate", "white_bot", "black_bot", &moves, "1-0")
+        .analyze_game(
+            "scholars_mate",
+            "white_bot",
+            "black_bot",
+            &moves,
+            "1-0",
+            None,
+        )
         .expect("Failed to analyze game");
 
     // Find the g8f6 (Nf6) move - it's move index 5 (0-indexed)
@@ -5,7 +5,7 @@ use thiserror::Error;
 /// Errors that can occur when parsing FEN strings.
 #[derive(Debug, Error, PartialEq, Eq)]
 pub enum FenError {
-    #[error("invalid FEN: expected 6 parts, got {0}")]
+    #[error("invalid FEN: expected 6 or 7 parts, got {0}")]
     InvalidPartCount(usize),
 
     #[error("invalid piece placement: {0}")]
@@ -25,6 +25,12 @@ pub enum FenError {
 
     #[error("invalid fullmove number: {0}")]
     InvalidFullmoveNumber(String),
+
+    #[error("invalid check counts: {0}")]
+    InvalidCheckCounts(String),
+
+    #[error("invalid pocket: {0}")]
+    InvalidPocket(String),
 }
 
 /// Parsed FEN data.
@@ -35,7 +41,12 @@ pub enum FenError {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FenParser {
     /// Piece placement string (e.g., "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR")
+    ///
+    /// Crazyhouse promoted pieces are marked with a trailing `~` (e.g., "Q~").
     pub piece_placement: String,
+    /// Crazyhouse pocket contents (e.g., "Nq"), from the bracketed suffix of
+    /// the piece placement field
+    pub pocket: Option<String>,
     /// Active color ('w' or 'b')
     pub active_color: char,
     /// Castling availability (e.g., "KQkq", "-")
@@ -46,6 +57,8 @@ pub struct FenParser {
     pub halfmove_clock: u32,
     /// Fullmove number
     pub fullmove_number: u32,
+    /// Checks delivered by (white, black), from the Three-check `+N+M` extension
+    pub check_counts: Option<(u8, u8)>,
 }
 
 impl FenParser {
@@ -53,15 +66,18 @@ impl FenParser {
     pub const STARTPOS: &'static str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
 
     /// Parses a FEN string.
+    ///
+    /// An optional seventh field in the lichess Three-check format (`+N+M`,
+    /// checks delivered by white and black) is accepted.
     pub fn parse(fen: &str) -> Result<Self, FenError> {
         let parts: Vec<&str> = fen.split_whitespace().collect();
 
-        if parts.len() != 6 {
+        if parts.len() != 6 && parts.len() != 7 {
             return Err(FenError::InvalidPartCount(parts.len()));
         }
 
-        // Validate piece placement
-        let piece_placement = parts[0];
+        // Validate piece placement, splitting off a Crazyhouse pocket
+        let (piece_placement, pocket) = Self::split_pocket(parts[0])?;
         Self::validate_piece_placement(piece_placement)?;
 
         // Validate active color
@@ -89,16 +105,39 @@ impl FenParser {
             .parse::<u32>()
             .map_err(|_| FenError::InvalidFullmoveNumber(parts[5].to_string()))?;
 
+        // Parse optional check counts
+        let check_counts = match parts.get(6) {
+            Some(counts) => Some(Self::parse_check_counts(counts)?),
+            None => None,
+        };
+
         Ok(FenParser {
             piece_placement: piece_placement.to_string(),
+            pocket: pocket.map(str::to_string),
             active_color,
             castling: castling.to_string(),
             en_passant: en_passant.to_string(),
             halfmove_clock,
             fullmove_number,
+            check_counts,
         })
     }
 
+    fn parse_check_counts(counts: &str) -> Result<(u8, u8), FenError> {
+        let invalid = || FenError::InvalidCheckCounts(counts.to_string());
+
+        let rest = counts.strip_prefix('+').ok_or_else(invalid)?;
+        let (white, black) = rest.split_once('+').ok_or_else(invalid)?;
+        let white = white.parse::<u8>().map_err(|_| invalid())?;
+        let black = black.parse::<u8>().map_err(|_| invalid())?;
+
+        if white > 3 || black > 3 {
+            return Err(invalid());
+        }
+
+        Ok((white, black))
+    }
+
     fn validate_piece_placement(placement: &str) -> Result<(), FenError> {
         let ranks: Vec<&str> = placement.split('/').collect();
         if ranks.len() != 8 {
@@ -110,11 +149,16 @@ impl FenParser {
 
         for (i, rank) in ranks.iter().enumerate() {
             let mut squares = 0;
+            let mut after_piece = false;
             for c in rank.chars() {
                 if c.is_ascii_digit() {
                     squares += c.to_digit(10).unwrap();
+                    after_piece = false;
                 } else if "pnbrqkPNBRQK".contains(c) {
                     squares += 1;
+                    after_piece = true;
+                } else if c == '~' && after_piece {
+                    after_piece = false;
                 } else {
                     return Err(FenError::InvalidPiecePlacement(format!(
                         "invalid character '{}' in rank {}",
@@ -135,13 +179,32 @@ impl FenParser {
         Ok(())
     }
 
+    fn split_pocket(placement: &str) -> Result<(&str, Option<&str>), FenError> {
+        let Some((board, rest)) = placement.split_once('[') else {
+            return Ok((placement, None));
+        };
+
+        let pocket = rest
+            .strip_suffix(']')
+            .ok_or_else(|| FenError::InvalidPocket(rest.to_string()))?;
+        if let Some(c) = pocket.chars().find(|c| !"pnbrqPNBRQ".contains(*c)) {
+            return Err(FenError::InvalidPocket(format!(
+                "invalid character '{}'",
+                c
+            )));
+        }
+
+        Ok((board, Some(pocket)))
+    }
+
     fn validate_castling(castling: &str) -> Result<(), FenError> {
         if castling == "-" {
             return Ok(());
         }
 
+        // Standard (KQkq) or Shredder-FEN rook files (A-H, a-h)
         for c in castling.chars() {
-            if !"KQkq".contains(c) {
+            if !"KQkq".contains(c) && !matches!(c.to_ascii_lowercase(), 'a'..='h') {
                 return Err(FenError::InvalidCastlingRights(format!(
                     "invalid character '{}'",
                     c
@@ -171,15 +234,23 @@ impl FenParser {
 
     /// Converts the parsed FEN back to a FEN string.
     pub fn to_fen(&self) -> String {
-        format!(
+        let placement = match &self.pocket {
+            Some(pocket) => format!("{}[{}]", self.piece_placement, pocket),
+            None => self.piece_placement.clone(),
+        };
+        let mut fen = format!(
             "{} {} {} {} {} {}",
-            self.piece_placement,
+            placement,
             self.active_color,
             self.castling,
             self.en_passant,
             self.halfmove_clock,
             self.fullmove_number
-        )
+        );
+        if let Some((white, black)) = self.check_counts {
+            fen.push_str(&format!(" +{}+{}", white, black));
+        }
+        fen
     }
 }
 
@@ -260,6 +331,13 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn shredder_castling_rights() {
+        let fen =
+            FenParser::parse("bqnbrkrn/pppppppp/8/8/8/8/PPPPPPPP/BQNBRKRN w GEge - 0 1").unwrap();
+        assert_eq!(fen.castling, "GEge");
+    }
+
     #[test]
     fn invalid_castling_rights() {
         assert!(matches!(
@@ -345,6 +423,66 @@ mod tests {
         assert_eq!(fen.en_passant, "d6");
     }
 
+    #[test]
+    fn parse_check_counts() {
+        let fen = FenParser::parse("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 +2+1")
+            .unwrap();
+        assert_eq!(fen.check_counts, Some((2, 1)));
+        assert_eq!(
+            fen.to_fen(),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 +2+1"
+        );
+
+        let fen = FenParser::parse(FenParser::STARTPOS).unwrap();
+        assert_eq!(fen.check_counts, None);
+    }
+
+    #[test]
+    fn invalid_check_counts() {
+        for counts in ["2+1", "+2", "+a+0", "+4+0", "+0+0+0"] {
+            let fen = format!("8/8/8/8/8/8/8/8 w - - 0 1 {}", counts);
+            assert!(
+                matches!(FenParser::parse(&fen), Err(FenError::InvalidCheckCounts(_))),
+                "{} should be rejected",
+                counts
+            );
+        }
+    }
+
+    #[test]
+    fn parse_crazyhouse_pocket() {
+        let original = "r1bqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKB1R[Nn] w KQkq - 0 4";
+        let fen = FenParser::parse(original).unwrap();
+        assert_eq!(fen.pocket.as_deref(), Some("Nn"));
+        assert_eq!(
+            fen.piece_placement,
+            "r1bqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKB1R"
+        );
+        assert_eq!(fen.to_fen(), original);
+
+        // Empty pocket and promoted piece marker
+        let fen = FenParser::parse("4k2Q~/8/8/8/8/8/8/4K3[] b - - 0 1").unwrap();
+        assert_eq!(fen.pocket.as_deref(), Some(""));
+        assert_eq!(fen.piece_placement, "4k2Q~/8/8/8/8/8/8/4K3");
+    }
+
+    #[test]
+    fn invalid_crazyhouse_pocket() {
+        assert!(matches!(
+            FenParser::parse("8/8/8/8/8/8/8/8[Nk] w - - 0 1"),
+            Err(FenError::InvalidPocket(_))
+        ));
+        assert!(matches!(
+            FenParser::parse("8/8/8/8/8/8/8/8[N w - - 0 1"),
+            Err(FenError::InvalidPocket(_))
+        ));
+        // A promotion marker must follow a piece
+        assert!(matches!(
+            FenParser::parse("~7/8/8/8/8/8/8/8 w - - 0 1"),
+            Err(FenError::InvalidPiecePlacement(_))
+        ));
+    }
+
     #[test]
     fn fen_error_display() {
         let err = FenError::InvalidPartCount(3);
@@ -367,5 +505,11 @@ mod tests {
 
         let err = FenError::InvalidFullmoveNumber("xyz".to_string());
         assert!(format!("{}", err).contains("xyz"));
+
+        let err = FenError::InvalidCheckCounts("+9+9".to_string());
+        assert!(format!("{}", err).contains("+9+9"));
+
+        let err = FenError::InvalidPocket("Kk".to_string());
+        assert!(format!("{}", err).contains("Kk"));
     }
 }
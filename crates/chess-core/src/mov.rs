@@ -25,6 +25,18 @@ pub enum MoveFlag {
     PromoteRook = 7,
     /// Pawn promotion to queen.
     PromoteQueen = 8,
+    /// Pawn dropped from the pocket (Crazyhouse).
+    DropPawn = 9,
+    /// Knight dropped from the pocket (Crazyhouse).
+    DropKnight = 10,
+    /// Bishop dropped from the pocket (Crazyhouse).
+    DropBishop = 11,
+    /// Rook dropped from the pocket (Crazyhouse).
+    DropRook = 12,
+    /// Queen dropped from the pocket (Crazyhouse).
+    DropQueen = 13,
+    /// Pawn promotion to king (Antichess).
+    PromoteKing = 14,
 }
 
 impl MoveFlag {
@@ -36,6 +48,7 @@ impl MoveFlag {
             MoveFlag::PromoteBishop => Some(Piece::Bishop),
             MoveFlag::PromoteRook => Some(Piece::Rook),
             MoveFlag::PromoteQueen => Some(Piece::Queen),
+            MoveFlag::PromoteKing => Some(Piece::King),
             _ => None,
         }
     }
@@ -49,6 +62,7 @@ impl MoveFlag {
                 | MoveFlag::PromoteBishop
                 | MoveFlag::PromoteRook
                 | MoveFlag::PromoteQueen
+                | MoveFlag::PromoteKing
         )
     }
 
@@ -57,6 +71,38 @@ impl MoveFlag {
     pub const fn is_castling(self) -> bool {
         matches!(self, MoveFlag::CastleKingside | MoveFlag::CastleQueenside)
     }
+
+    /// Returns the dropped piece if this is a drop move.
+    #[inline]
+    pub const fn drop_piece(self) -> Option<Piece> {
+        match self {
+            MoveFlag::DropPawn => Some(Piece::Pawn),
+            MoveFlag::DropKnight => Some(Piece::Knight),
+            MoveFlag::DropBishop => Some(Piece::Bishop),
+            MoveFlag::DropRook => Some(Piece::Rook),
+            MoveFlag::DropQueen => Some(Piece::Queen),
+            _ => None,
+        }
+    }
+
+    /// Returns true if this is a drop move.
+    #[inline]
+    pub const fn is_drop(self) -> bool {
+        self.drop_piece().is_some()
+    }
+
+    /// Returns the drop flag for the given piece, or `None` for the king.
+    #[inline]
+    pub const fn drop_of(piece: Piece) -> Option<MoveFlag> {
+        match piece {
+            Piece::Pawn => Some(MoveFlag::DropPawn),
+            Piece::Knight => Some(MoveFlag::DropKnight),
+            Piece::Bishop => Some(MoveFlag::DropBishop),
+            Piece::Rook => Some(MoveFlag::DropRook),
+            Piece::Queen => Some(MoveFlag::DropQueen),
+            Piece::King => None,
+        }
+    }
 }
 
 /// A chess move.
@@ -79,6 +125,18 @@ impl Move {
         Self::new(from, to, MoveFlag::Normal)
     }
 
+    /// Creates a drop move placing `piece` from the pocket on `to`.
+    ///
+    /// Drops are encoded with the source square equal to the destination.
+    /// Returns `None` for the king, which can never be dropped.
+    #[inline]
+    pub const fn drop(piece: Piece, to: Square) -> Option<Self> {
+        match MoveFlag::drop_of(piece) {
+            Some(flag) => Some(Self::new(to, to, flag)),
+            None => None,
+        }
+    }
+
     /// Returns the source square.
     #[inline]
     pub const fn from(self) -> Square {
@@ -106,17 +164,27 @@ impl Move {
             6 => MoveFlag::PromoteBishop,
             7 => MoveFlag::PromoteRook,
             8 => MoveFlag::PromoteQueen,
+            9 => MoveFlag::DropPawn,
+            10 => MoveFlag::DropKnight,
+            11 => MoveFlag::DropBishop,
+            12 => MoveFlag::DropRook,
+            13 => MoveFlag::DropQueen,
+            14 => MoveFlag::PromoteKing,
             _ => MoveFlag::Normal, // Should never happen
         }
     }
 
-    /// Returns the UCI notation for this move (e.g., "e2e4", "e7e8q").
+    /// Returns the UCI notation for this move (e.g., "e2e4", "e7e8q", "N@f3").
     pub fn to_uci(self) -> String {
+        if let Some(piece) = self.flag().drop_piece() {
+            return format!("{}@{}", piece.to_fen_char(crate::Color::White), self.to());
+        }
         let promo = match self.flag() {
             MoveFlag::PromoteKnight => "n",
             MoveFlag::PromoteBishop => "b",
             MoveFlag::PromoteRook => "r",
             MoveFlag::PromoteQueen => "q",
+            MoveFlag::PromoteKing => "k",
             _ => "",
         };
         format!("{}{}{}", self.from(), self.to(), promo)
@@ -127,6 +195,15 @@ impl Move {
     /// Note: This creates a basic move without full flag inference.
     /// The engine should validate and set proper flags based on position.
     pub fn from_uci(s: &str) -> Option<Self> {
+        // Drops, e.g. "N@f3"
+        if let Some((piece, to)) = s.split_once('@') {
+            let mut chars = piece.chars();
+            let (piece, _) = Piece::from_fen_char(chars.next()?.to_ascii_uppercase())?;
+            if chars.next().is_some() {
+                return None;
+            }
+            return Move::drop(piece, Square::from_algebraic(to)?);
+        }
         if s.len() < 4 || s.len() > 5 {
             return None;
         }
@@ -138,6 +215,7 @@ impl Move {
                 'b' | 'B' => MoveFlag::PromoteBishop,
                 'r' | 'R' => MoveFlag::PromoteRook,
                 'q' | 'Q' => MoveFlag::PromoteQueen,
+                'k' | 'K' => MoveFlag::PromoteKing,
                 _ => return None,
             }
         } else {
@@ -260,6 +338,7 @@ mod tests {
         assert_eq!(Move::new(e7, e8, MoveFlag::PromoteBishop).to_uci(), "e7e8b");
         assert_eq!(Move::new(e7, e8, MoveFlag::PromoteRook).to_uci(), "e7e8r");
         assert_eq!(Move::new(e7, e8, MoveFlag::PromoteQueen).to_uci(), "e7e8q");
+        assert_eq!(Move::new(e7, e8, MoveFlag::PromoteKing).to_uci(), "e7e8k");
     }
 
     #[test]
@@ -296,6 +375,10 @@ mod tests {
             Move::from_uci("e7e8Q").unwrap().flag(),
             MoveFlag::PromoteQueen
         );
+        assert_eq!(
+            Move::from_uci("e7e8k").unwrap().flag(),
+            MoveFlag::PromoteKing
+        );
         // Invalid promotion character
         assert!(Move::from_uci("e7e8x").is_none());
     }
@@ -316,6 +399,25 @@ mod tests {
         assert_eq!(format!("{}", m), "e2e4");
     }
 
+    #[test]
+    fn drop_moves() {
+        let f3 = Square::new(File::F, Rank::R3);
+        let m = Move::drop(Piece::Knight, f3).unwrap();
+        assert_eq!(m.flag(), MoveFlag::DropKnight);
+        assert_eq!(m.flag().drop_piece(), Some(Piece::Knight));
+        assert!(m.flag().is_drop());
+        assert_eq!(m.to(), f3);
+        assert_eq!(m.to_uci(), "N@f3");
+        assert_eq!(Move::from_uci("N@f3"), Some(m));
+        assert_eq!(Move::from_uci("p@e4").unwrap().to_uci(), "P@e4");
+
+        assert!(Move::drop(Piece::King, f3).is_none());
+        assert!(Move::from_uci("K@f3").is_none());
+        assert!(Move::from_uci("X@f3").is_none());
+        assert!(Move::from_uci("N@z9").is_none());
+        assert!(!MoveFlag::PromoteQueen.is_drop());
+    }
+
     #[test]
     fn move_from_uci_edge_cases() {
         // Too short
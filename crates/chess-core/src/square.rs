@@ -29,6 +29,11 @@ impl File {
         File::H,
     ];
 
+    /// Returns an iterator over all files from A to H.
+    pub fn all() -> impl Iterator<Item = File> {
+        Self::ALL.into_iter()
+    }
+
     /// Creates a file from index (0-7).
     #[inline]
     pub const fn from_index(index: u8) -> Option<Self> {
@@ -107,6 +112,11 @@ impl Rank {
         Rank::R8,
     ];
 
+    /// Returns an iterator over all ranks from 1 to 8.
+    pub fn all() -> impl Iterator<Item = Rank> {
+        Self::ALL.into_iter()
+    }
+
     /// Creates a rank from index (0-7).
     #[inline]
     pub const fn from_index(index: u8) -> Option<Self> {
@@ -193,6 +203,23 @@ impl Square {
         Square(index)
     }
 
+    /// Returns an iterator over all 64 squares in index order (a1, b1, ..., h8).
+    pub fn all() -> impl Iterator<Item = Square> {
+        (0..64).map(Square)
+    }
+
+    /// Returns the Chebyshev (king-move) distance to another square.
+    #[inline]
+    pub const fn distance(self, other: Square) -> u8 {
+        let files = (self.0 % 8).abs_diff(other.0 % 8);
+        let ranks = (self.0 / 8).abs_diff(other.0 / 8);
+        if files > ranks {
+            files
+        } else {
+            ranks
+        }
+    }
+
     /// Parses a square from algebraic notation (e.g., "e4").
     pub const fn from_algebraic(s: &str) -> Option<Self> {
         let bytes = s.as_bytes();
@@ -399,4 +426,30 @@ mod tests {
         assert_eq!(Square::A8.index(), 56);
         assert_eq!(Square::H8.index(), 63);
     }
+
+    #[test]
+    fn square_all_in_index_order() {
+        let squares: Vec<Square> = Square::all().collect();
+        assert_eq!(squares.len(), 64);
+        assert_eq!(squares[0], Square::A1);
+        assert_eq!(squares[63], Square::H8);
+        for (i, sq) in squares.iter().enumerate() {
+            assert_eq!(sq.index() as usize, i);
+        }
+    }
+
+    #[test]
+    fn file_and_rank_all() {
+        assert_eq!(File::all().collect::<Vec<_>>(), File::ALL);
+        assert_eq!(Rank::all().collect::<Vec<_>>(), Rank::ALL);
+    }
+
+    #[test]
+    fn square_distance() {
+        assert_eq!(Square::A1.distance(Square::H8), 7);
+        assert_eq!(Square::A1.distance(Square::A1), 0);
+        let e4 = Square::new(File::E, Rank::R4);
+        assert_eq!(e4.distance(Square::new(File::F, Rank::R6)), 2);
+        assert_eq!(Square::H1.distance(Square::A8), 7);
+    }
 }
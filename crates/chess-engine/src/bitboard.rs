@@ -68,6 +68,20 @@ impl Bitboard {
         self.0.count_ones()
     }
 
+    /// Returns true if every square set in `self` is also set in `other`.
+    #[inline]
+    pub const fn is_subset_of(self, other: Bitboard) -> bool {
+        self.0 & !other.0 == 0
+    }
+
+    /// Mirrors the bitboard vertically, so rank 1 becomes rank 8.
+    ///
+    /// Files are unchanged: a square on e2 moves to e7.
+    #[inline]
+    pub const fn flip_vertical(self) -> Bitboard {
+        Bitboard(self.0.swap_bytes())
+    }
+
     /// Returns true if the given square is set.
     #[inline]
     pub const fn contains(self, sq: Square) -> bool {
@@ -285,6 +299,35 @@ mod tests {
         assert_eq!(Bitboard::RANK_1.count(), 8);
     }
 
+    #[test]
+    fn start_position_occupancy_count() {
+        assert_eq!(crate::Position::startpos().occupied().count(), 32);
+    }
+
+    #[test]
+    fn bitboard_flip_vertical() {
+        assert_eq!(Bitboard::RANK_2.flip_vertical(), Bitboard::RANK_7);
+        assert_eq!(Bitboard::RANK_8.flip_vertical(), Bitboard::RANK_1);
+        assert_eq!(Bitboard::FILE_A.flip_vertical(), Bitboard::FILE_A);
+
+        let e2 = Bitboard::from_square(Square::new(File::E, Rank::R2));
+        assert_eq!(
+            e2.flip_vertical(),
+            Bitboard::from_square(Square::new(File::E, Rank::R7))
+        );
+        assert_eq!(e2.flip_vertical().flip_vertical(), e2);
+    }
+
+    #[test]
+    fn bitboard_is_subset_of() {
+        let a1 = Bitboard::from_square(Square::A1);
+        assert!(a1.is_subset_of(Bitboard::RANK_1));
+        assert!(a1.is_subset_of(Bitboard::FILE_A));
+        assert!(!Bitboard::RANK_1.is_subset_of(a1));
+        assert!(Bitboard::EMPTY.is_subset_of(a1));
+        assert!(Bitboard::RANK_2.is_subset_of(Bitboard::RANK_2));
+    }
+
     #[test]
     fn bitboard_shifts() {
         let a1 = Bitboard::from_square(Square::A1);
@@ -0,0 +1,248 @@
+//! Static evaluation: material plus piece-square tables.
+//!
+//! This is the evaluation used by the minimax bot, shared so that other
+//! front ends (such as the WASM bindings) can show the same numbers
+//! without running a search.
+
+use chess_core::{Color, Piece};
+
+use crate::Position;
+
+/// Piece values in centipawns
+pub const PAWN_VALUE: i32 = 100;
+pub const KNIGHT_VALUE: i32 = 320;
+pub const BISHOP_VALUE: i32 = 330;
+pub const ROOK_VALUE: i32 = 500;
+pub const QUEEN_VALUE: i32 = 900;
+
+/// Piece-square tables for positional evaluation (from white's perspective).
+/// Values are in centipawns, added to piece base value.
+pub const PAWN_PST: [i32; 64] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 50, 50, 50, 50, 50, 50, 50, 50, 10, 10, 20, 30, 30, 20, 10, 10, 5, 5,
+    10, 25, 25, 10, 5, 5, 0, 0, 0, 20, 20, 0, 0, 0, 5, -5, -10, 0, 0, -10, -5, 5, 5, 10, 10, -20,
+    -20, 10, 10, 5, 0, 0, 0, 0, 0, 0, 0, 0,
+];
+
+pub const KNIGHT_PST: [i32; 64] = [
+    -50, -40, -30, -30, -30, -30, -40, -50, -40, -20, 0, 0, 0, 0, -20, -40, -30, 0, 10, 15, 15, 10,
+    0, -30, -30, 5, 15, 20, 20, 15, 5, -30, -30, 0, 15, 20, 20, 15, 0, -30, -30, 5, 10, 15, 15, 10,
+    5, -30, -40, -20, 0, 5, 5, 0, -20, -40, -50, -40, -30, -30, -30, -30, -40, -50,
+];
+
+pub const BISHOP_PST: [i32; 64] = [
+    -20, -10, -10, -10, -10, -10, -10, -20, -10, 0, 0, 0, 0, 0, 0, -10, -10, 0, 5, 10, 10, 5, 0,
+    -10, -10, 5, 5, 10, 10, 5, 5, -10, -10, 0, 10, 10, 10, 10, 0, -10, -10, 10, 10, 10, 10, 10, 10,
+    -10, -10, 5, 0, 0, 0, 0, 5, -10, -20, -10, -10, -10, -10, -10, -10, -20,
+];
+
+pub const ROOK_PST: [i32; 64] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 5, 10, 10, 10, 10, 10, 10, 5, -5, 0, 0, 0, 0, 0, 0, -5, -5, 0, 0, 0, 0,
+    0, 0, -5, -5, 0, 0, 0, 0, 0, 0, -5, -5, 0, 0, 0, 0, 0, 0, -5, -5, 0, 0, 0, 0, 0, 0, -5, 0, 0,
+    0, 5, 5, 0, 0, 0,
+];
+
+pub const QUEEN_PST: [i32; 64] = [
+    -20, -10, -10, -5, -5, -10, -10, -20, -10, 0, 0, 0, 0, 0, 0, -10, -10, 0, 5, 5, 5, 5, 0, -10,
+    -5, 0, 5, 5, 5, 5, 0, -5, 0, 0, 5, 5, 5, 5, 0, -5, -10, 5, 5, 5, 5, 5, 0, -10, -10, 0, 5, 0, 0,
+    0, 0, -10, -20, -10, -10, -5, -5, -10, -10, -20,
+];
+
+pub const KING_MIDDLEGAME_PST: [i32; 64] = [
+    -30, -40, -40, -50, -50, -40, -40, -30, -30, -40, -40, -50, -50, -40, -40, -30, -30, -40, -40,
+    -50, -50, -40, -40, -30, -30, -40, -40, -50, -50, -40, -40, -30, -20, -30, -30, -40, -40, -30,
+    -30, -20, -10, -20, -20, -20, -20, -20, -20, -10, 20, 20, 0, 0, 0, 0, 20, 20, 20, 30, 10, 0, 0,
+    10, 30, 20,
+];
+
+/// Piece values and piece-square tables used by the evaluation.
+///
+/// Both arrays are indexed by [`Piece::index`]. The king's value is not
+/// counted, only its table. [`EvalParams::BUILTIN`] holds the constants
+/// above and is what [`evaluate`] and [`evaluate_white`] use.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvalParams {
+    /// Base value of each piece in centipawns.
+    pub values: [i32; 6],
+    /// Piece-square table of each piece, laid out from white's perspective.
+    pub tables: [[i32; 64]; 6],
+}
+
+impl EvalParams {
+    /// The built-in piece values and tables.
+    pub const BUILTIN: EvalParams = EvalParams {
+        values: [
+            PAWN_VALUE,
+            KNIGHT_VALUE,
+            BISHOP_VALUE,
+            ROOK_VALUE,
+            QUEEN_VALUE,
+            0,
+        ],
+        tables: [
+            PAWN_PST,
+            KNIGHT_PST,
+            BISHOP_PST,
+            ROOK_PST,
+            QUEEN_PST,
+            KING_MIDDLEGAME_PST,
+        ],
+    };
+
+    /// Evaluates the position in centipawns from white's perspective.
+    pub fn evaluate_white(&self, position: &Position) -> i32 {
+        let mut score = 0i32;
+
+        for color in [Color::White, Color::Black] {
+            let sign = if color == Color::White { 1 } else { -1 };
+
+            for piece in Piece::ALL {
+                let value = if piece == Piece::King {
+                    0
+                } else {
+                    self.values[piece.index()]
+                };
+                let table = &self.tables[piece.index()];
+                // Tables are laid out for white; black reads them mirrored
+                let pieces = match color {
+                    Color::White => position.pieces_of(piece, color),
+                    Color::Black => position.pieces_of(piece, color).flip_vertical(),
+                };
+                for sq in pieces {
+                    score += sign * (value + table[sq.index() as usize]);
+                }
+            }
+        }
+
+        score
+    }
+
+    /// Evaluates the position in centipawns from the side to move's
+    /// perspective.
+    pub fn evaluate(&self, position: &Position) -> i32 {
+        let score = self.evaluate_white(position);
+        if position.side_to_move == Color::White {
+            score
+        } else {
+            -score
+        }
+    }
+}
+
+impl Default for EvalParams {
+    fn default() -> Self {
+        Self::BUILTIN
+    }
+}
+
+/// Evaluates the position in centipawns from white's perspective.
+pub fn evaluate_white(position: &Position) -> i32 {
+    EvalParams::BUILTIN.evaluate_white(position)
+}
+
+/// Evaluates the position in centipawns from the side to move's perspective.
+pub fn evaluate(position: &Position) -> i32 {
+    EvalParams::BUILTIN.evaluate(position)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::RuleSet;
+    use crate::StandardChess;
+
+    #[test]
+    fn start_position_is_balanced() {
+        let position = StandardChess.initial_position();
+        assert_eq!(evaluate(&position), 0);
+        assert_eq!(evaluate_white(&position), 0);
+    }
+
+    #[test]
+    fn black_reads_tables_flipped_vertically() {
+        // The queen table is not symmetric between the wings, so black's
+        // queen on b3 must score like white's on b6, not like g6
+        let position = Position::from_fen("4k3/8/1Q6/8/8/1q6/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(evaluate_white(&position), 0);
+    }
+
+    #[test]
+    fn extra_queen_is_winning() {
+        // Black's queen is missing
+        let position =
+            Position::from_fen("rnb1kbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR b KQkq - 0 1").unwrap();
+        assert!(evaluate_white(&position) > QUEEN_VALUE - 100);
+        assert_eq!(evaluate(&position), -evaluate_white(&position));
+    }
+
+    /// Mirrors a FEN vertically and swaps the colors of everything on it.
+    fn mirrored_fen(fen: &str) -> String {
+        let fields: Vec<&str> = fen.split(' ').collect();
+        let swap_case = |s: &str| -> String {
+            s.chars()
+                .map(|c| {
+                    if c.is_ascii_uppercase() {
+                        c.to_ascii_lowercase()
+                    } else {
+                        c.to_ascii_uppercase()
+                    }
+                })
+                .collect()
+        };
+        let board: Vec<String> = fields[0].split('/').rev().map(swap_case).collect();
+        let side = if fields[1] == "w" { "b" } else { "w" };
+        let castling = if fields[2] == "-" {
+            "-".to_string()
+        } else {
+            let swapped = swap_case(fields[2]);
+            let mut rights: Vec<char> = swapped.chars().collect();
+            rights.sort_by_key(|c| "KQkq".find(*c));
+            rights.into_iter().collect()
+        };
+        let en_passant = match fields[3].as_bytes() {
+            [file, b'3'] => format!("{}6", *file as char),
+            [file, b'6'] => format!("{}3", *file as char),
+            _ => "-".to_string(),
+        };
+        format!(
+            "{} {} {} {} {} {}",
+            board.join("/"),
+            side,
+            castling,
+            en_passant,
+            fields[4],
+            fields[5]
+        )
+    }
+
+    #[test]
+    fn mirrored_position_negates_white_evaluation() {
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+            "r1bqk2r/pppp1ppp/2n2n2/2b1p3/2B1P3/3P1N2/PPP2PPP/RNBQK2R w KQkq - 1 5",
+            "4k3/8/1Q6/8/3N4/8/5q2/4K3 w - - 0 1",
+            "6k1/5ppp/8/8/8/8/r4PPP/3R2K1 b - - 0 30",
+        ] {
+            let position = Position::from_fen(fen).unwrap();
+            let mirror = Position::from_fen(&mirrored_fen(fen)).unwrap();
+            assert_eq!(
+                evaluate_white(&mirror),
+                -evaluate_white(&position),
+                "{}",
+                fen
+            );
+            assert_eq!(evaluate(&mirror), evaluate(&position), "{}", fen);
+        }
+    }
+
+    #[test]
+    fn params_change_the_evaluation() {
+        // White is a knight up
+        let position = Position::from_fen("4k3/8/8/8/3N4/8/8/4K3 w - - 0 1").unwrap();
+        let mut params = EvalParams::default();
+        params.values[Piece::Knight.index()] += 100;
+        assert_eq!(
+            params.evaluate_white(&position),
+            evaluate_white(&position) + 100
+        );
+    }
+}
@@ -69,6 +69,8 @@ pub struct Game {
     history: Vec<u64>,
     /// Move history with SAN notation.
     moves: Vec<GameMove>,
+    /// Position before each move in `moves`.
+    positions: Vec<Position>,
     /// Starting position.
     start_pos: Position,
     /// Game result if the game has ended.
@@ -92,6 +94,7 @@ impl Game {
             position: position.clone(),
             history: vec![hash],
             moves: Vec::new(),
+            positions: Vec::new(),
             start_pos: position,
             result: None,
             draw_claimed: false,
@@ -105,6 +108,7 @@ impl Game {
             position: position.clone(),
             history: vec![hash],
             moves: Vec::new(),
+            positions: Vec::new(),
             start_pos: position,
             result: None,
             draw_claimed: false,
@@ -135,12 +139,35 @@ impl Game {
         generate_moves(&self.position)
     }
 
+    /// Returns all legal moves in the current position in SAN.
+    pub fn legal_moves_san(&self) -> Vec<String> {
+        self.legal_moves()
+            .as_slice()
+            .iter()
+            .map(|&m| self.move_to_san(m))
+            .collect()
+    }
+
+    /// Returns all legal moves in the current position in UCI notation.
+    pub fn legal_moves_uci(&self) -> Vec<String> {
+        self.legal_moves()
+            .as_slice()
+            .iter()
+            .map(|m| m.to_uci())
+            .collect()
+    }
+
     /// Returns true if the side to move is in check.
     pub fn is_check(&self) -> bool {
         is_king_attacked(&self.position, self.position.side_to_move)
     }
 
     /// Returns the game result if the game is over.
+    ///
+    /// Only automatic endings are reported here: checkmate, stalemate,
+    /// insufficient material, fivefold repetition and the 75-move rule.
+    /// Threefold repetition and the 50-move rule only end the game once
+    /// claimed with [`Game::claim_draw`]; see [`Game::can_claim_draw`].
     pub fn result(&self) -> Option<GameResult> {
         self.result
     }
@@ -155,6 +182,15 @@ impl Game {
         &self.moves
     }
 
+    /// Returns the position before each move of the game, followed by the
+    /// current position.
+    ///
+    /// The iterator yields `ply_count() + 1` positions, starting with
+    /// [`start_position`](Self::start_position).
+    pub fn positions(&self) -> impl Iterator<Item = &Position> {
+        self.positions.iter().chain(std::iter::once(&self.position))
+    }
+
     /// Returns the number of half-moves (plies) played.
     pub fn ply_count(&self) -> usize {
         self.moves.len()
@@ -198,24 +234,13 @@ impl Game {
             return Err(GameError::GameAlreadyOver);
         }
 
-        let m = Move::from_uci(uci)
-            .ok_or_else(|| GameError::IllegalMove(format!("invalid UCI: {}", uci)))?;
+        if Move::from_uci(uci).is_none() {
+            return Err(GameError::IllegalMove(format!("invalid UCI: {}", uci)));
+        }
 
         // Find the matching legal move (to get correct flags)
-        let legal_moves = self.legal_moves();
-        let matching = legal_moves.as_slice().iter().find(|lm| {
-            lm.from() == m.from() && lm.to() == m.to() && {
-                // For promotions, also match the promotion piece
-                if m.flag().is_promotion() {
-                    lm.flag() == m.flag()
-                } else {
-                    true
-                }
-            }
-        });
-
-        match matching {
-            Some(&legal_move) => {
+        match self.legal_moves().find_uci(uci) {
+            Some(legal_move) => {
                 self.apply_move(legal_move);
                 Ok(())
             }
@@ -235,8 +260,10 @@ impl Game {
             hash_before,
         });
 
-        // Apply the move
-        self.position = make_move(&self.position, m);
+        // Apply the move, keeping the position it was played from
+        let next = make_move(&self.position, m);
+        self.positions
+            .push(std::mem::replace(&mut self.position, next));
 
         // Record position hash for repetition detection
         let new_hash = self.position.zobrist_hash();
@@ -288,12 +315,21 @@ impl Game {
         self.history.iter().filter(|&&h| h == current_hash).count()
     }
 
-    /// Returns true if a draw can be claimed (threefold repetition or 50-move rule).
-    pub fn can_claim_draw(&self) -> bool {
+    /// Returns the draw the side to move could claim, if any.
+    ///
+    /// Threefold repetition takes precedence over the 50-move rule when both
+    /// apply. Returns `None` once the game is over.
+    pub fn can_claim_draw(&self) -> Option<DrawReason> {
         if self.result.is_some() {
-            return false;
+            return None;
+        }
+        if self.position_count() >= 3 {
+            Some(DrawReason::ThreefoldRepetition)
+        } else if self.position.halfmove_clock >= 100 {
+            Some(DrawReason::FiftyMoveRule)
+        } else {
+            None
         }
-        self.position_count() >= 3 || self.position.halfmove_clock >= 100
     }
 
     /// Claims a draw if conditions are met.
@@ -302,19 +338,10 @@ impl Game {
             return Err(GameError::GameAlreadyOver);
         }
 
-        if self.position_count() >= 3 {
-            self.result = Some(GameResult::Draw(DrawReason::ThreefoldRepetition));
-            self.draw_claimed = true;
-            return Ok(());
-        }
-
-        if self.position.halfmove_clock >= 100 {
-            self.result = Some(GameResult::Draw(DrawReason::FiftyMoveRule));
-            self.draw_claimed = true;
-            return Ok(());
-        }
-
-        Err(GameError::CannotClaimDraw)
+        let reason = self.can_claim_draw().ok_or(GameError::CannotClaimDraw)?;
+        self.result = Some(GameResult::Draw(reason));
+        self.draw_claimed = true;
+        Ok(())
     }
 
     /// Agrees to a draw (both players must agree in real chess).
@@ -370,6 +397,20 @@ mod tests {
         assert!(!game.is_check());
     }
 
+    #[test]
+    fn legal_moves_san_and_uci_from_start() {
+        let game = Game::new();
+        let san = game.legal_moves_san();
+        assert_eq!(san.len(), 20);
+        assert!(san.contains(&"e4".to_string()));
+        assert!(san.contains(&"Nf3".to_string()));
+
+        let uci = game.legal_moves_uci();
+        assert_eq!(uci.len(), 20);
+        assert!(uci.contains(&"e2e4".to_string()));
+        assert!(uci.contains(&"g1f3".to_string()));
+    }
+
     #[test]
     fn make_move_uci() {
         let mut game = Game::new();
@@ -378,6 +419,27 @@ mod tests {
         assert_eq!(game.move_history()[0].san, "e4");
     }
 
+    #[test]
+    fn positions_replay_the_game() {
+        let mut game = Game::new();
+        for uci in ["e2e4", "e7e5", "g1f3", "b8c6"] {
+            game.make_move_uci(uci).unwrap();
+        }
+
+        let fens: Vec<String> = game.positions().map(|p| p.to_fen()).collect();
+        assert_eq!(
+            fens,
+            [
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+                "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+                "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2",
+                "rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2",
+                "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3",
+            ]
+        );
+        assert_eq!(game.positions().last(), Some(game.position()));
+    }
+
     #[test]
     fn make_move_san() {
         let mut game = Game::new();
@@ -422,7 +484,7 @@ mod tests {
         game.make_move_san("Ng8").unwrap();
         // Position repeated twice now
         assert_eq!(game.position_count(), 2);
-        assert!(!game.can_claim_draw());
+        assert_eq!(game.can_claim_draw(), None);
 
         game.make_move_san("Nf3").unwrap();
         game.make_move_san("Nf6").unwrap();
@@ -430,7 +492,7 @@ mod tests {
         game.make_move_san("Ng8").unwrap();
         // Position repeated three times
         assert_eq!(game.position_count(), 3);
-        assert!(game.can_claim_draw());
+        assert_eq!(game.can_claim_draw(), Some(DrawReason::ThreefoldRepetition));
 
         // Claim the draw
         game.claim_draw().unwrap();
@@ -450,7 +512,10 @@ mod tests {
         let a2 = Square::new(File::A, Rank::R2);
         let m = Move::normal(a1, a2);
         game.make_move(m).unwrap();
-        assert!(game.can_claim_draw());
+        assert_eq!(game.position().halfmove_clock, 100);
+        // Claimable but not automatic
+        assert_eq!(game.result(), None);
+        assert_eq!(game.can_claim_draw(), Some(DrawReason::FiftyMoveRule));
 
         game.claim_draw().unwrap();
         assert_eq!(
@@ -459,6 +524,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn seventy_five_move_rule_ends_game_on_reaching_150() {
+        let mut game = Game::from_fen("8/8/8/8/8/8/8/R3K2k w Q - 149 1").unwrap();
+        assert_eq!(game.result(), None);
+        assert_eq!(game.can_claim_draw(), Some(DrawReason::FiftyMoveRule));
+
+        let a1 = Square::new(File::A, Rank::R1);
+        let a2 = Square::new(File::A, Rank::R2);
+        game.make_move(Move::normal(a1, a2)).unwrap();
+        assert_eq!(game.position().halfmove_clock, 150);
+        assert_eq!(
+            game.result(),
+            Some(GameResult::Draw(DrawReason::SeventyFiveMoveRule))
+        );
+        // Nothing is left to claim once the game is over
+        assert_eq!(game.can_claim_draw(), None);
+        assert_eq!(game.claim_draw(), Err(GameError::GameAlreadyOver));
+    }
+
     #[test]
     fn seventy_five_move_rule_automatic() {
         // Position with rook so it's not insufficient material, 75-move rule takes precedence
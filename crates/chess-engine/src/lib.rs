@@ -7,6 +7,7 @@
 //! - [`RuleSet`] - Trait for implementing chess variants
 //! - Move generation and validation
 //! - SAN notation parsing and generation
+//! - Static evaluation ([`eval`])
 //!
 //! # Architecture
 //!
@@ -33,6 +34,7 @@
 //! ```
 
 mod bitboard;
+pub mod eval;
 mod game;
 pub mod movegen;
 mod position;
@@ -43,9 +45,12 @@ mod zobrist;
 pub use bitboard::Bitboard;
 pub use game::{Game, GameError, GameMove};
 pub use movegen::{
-    bishop_attacks, generate_moves, is_king_attacked, king_attacks, knight_attacks, make_move,
-    pawn_attacks, queen_attacks, rook_attacks, MoveList,
+    attackers_of, bishop_attacks, checkers, generate_captures, generate_moves, generate_quiets,
+    is_king_attacked, king_attacks, knight_attacks, make_move, pawn_attacks, queen_attacks,
+    rook_attacks, MoveList,
+};
+pub use position::{MaterialCount, Position};
+pub use rules::{
+    Antichess, Crazyhouse, DrawReason, GameResult, Horde, RuleSet, StandardChess, ThreeCheck,
 };
-pub use position::Position;
-pub use rules::{DrawReason, GameResult, RuleSet, StandardChess};
 pub use san::{move_to_san, san_to_move, SanError};
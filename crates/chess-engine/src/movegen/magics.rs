@@ -75,7 +75,7 @@ const BISHOP_MAGICS: [u64; 64] = [
     0x0188071040440a00,
     0x4800404002011c00,
     0x0104442040404200,
-    0x0511080200222104,
+    0x0302091000210220, // b5: replaces a constant that collided
     0x0004022401120400,
     0x80c0040400080120,
     0x8040010040820802,
@@ -207,69 +207,32 @@ impl AttackTables {
             offset: 0,
         });
 
-        // Initialize bishop tables
         for sq in 0..64 {
-            let mask = bishop_mask(sq);
-            let bits = BISHOP_BITS[sq as usize];
-            let shift = 64 - bits;
-            let table_size = 1 << bits;
-            let offset = bishop_attacks.len();
-
+            let (magic, table) = build_table(
+                bishop_mask(sq),
+                BISHOP_BITS[sq as usize],
+                BISHOP_MAGICS[sq as usize],
+                |blockers| bishop_attacks_slow(sq, blockers),
+            );
             bishop_magics[sq as usize] = Magic {
-                mask,
-                magic: BISHOP_MAGICS[sq as usize],
-                shift,
-                offset,
+                offset: bishop_attacks.len(),
+                ..magic
             };
-
-            // Initialize table entries
-            bishop_attacks.resize(offset + table_size, Bitboard::EMPTY);
-
-            // Fill attack table for all blocker configurations
-            let mut blockers = Bitboard::EMPTY;
-            loop {
-                let attacks = bishop_attacks_slow(sq, blockers);
-                let index = magic_index(&bishop_magics[sq as usize], blockers);
-                bishop_attacks[offset + index] = attacks;
-
-                // Carry-Rippler trick to enumerate all subsets
-                blockers = Bitboard((blockers.0.wrapping_sub(mask.0)) & mask.0);
-                if blockers.is_empty() {
-                    break;
-                }
-            }
+            bishop_attacks.extend(table);
         }
 
-        // Initialize rook tables
         for sq in 0..64 {
-            let mask = rook_mask(sq);
-            let bits = ROOK_BITS[sq as usize];
-            let shift = 64 - bits;
-            let table_size = 1 << bits;
-            let offset = rook_attacks.len();
-
+            let (magic, table) = build_table(
+                rook_mask(sq),
+                ROOK_BITS[sq as usize],
+                ROOK_MAGICS[sq as usize],
+                |blockers| rook_attacks_slow(sq, blockers),
+            );
             rook_magics[sq as usize] = Magic {
-                mask,
-                magic: ROOK_MAGICS[sq as usize],
-                shift,
-                offset,
+                offset: rook_attacks.len(),
+                ..magic
             };
-
-            // Initialize table entries
-            rook_attacks.resize(offset + table_size, Bitboard::EMPTY);
-
-            // Fill attack table for all blocker configurations
-            let mut blockers = Bitboard::EMPTY;
-            loop {
-                let attacks = rook_attacks_slow(sq, blockers);
-                let index = magic_index(&rook_magics[sq as usize], blockers);
-                rook_attacks[offset + index] = attacks;
-
-                blockers = Bitboard((blockers.0.wrapping_sub(mask.0)) & mask.0);
-                if blockers.is_empty() {
-                    break;
-                }
-            }
+            rook_attacks.extend(table);
         }
 
         AttackTables {
@@ -281,6 +244,58 @@ impl AttackTables {
     }
 }
 
+/// Builds the attack table for one square from its pre-computed magic.
+///
+/// Panics if `magic` maps two blocker subsets with different attacks to the
+/// same slot; `pre_computed_magics_have_no_collisions` checks every entry.
+fn build_table(
+    mask: Bitboard,
+    bits: u8,
+    magic: u64,
+    slow_attacks: impl Fn(Bitboard) -> Bitboard,
+) -> (Magic, Vec<Bitboard>) {
+    let magic = Magic {
+        mask,
+        magic,
+        shift: 64 - bits,
+        offset: 0,
+    };
+    let table = fill_table(&magic, bits, &blocker_subsets(mask, slow_attacks))
+        .expect("pre-computed magic has a destructive collision");
+    (magic, table)
+}
+
+/// Enumerates every blocker subset of `mask` with its attacks.
+fn blocker_subsets(
+    mask: Bitboard,
+    slow_attacks: impl Fn(Bitboard) -> Bitboard,
+) -> Vec<(Bitboard, Bitboard)> {
+    // Carry-Rippler trick
+    let mut subsets = Vec::with_capacity(1 << mask.count());
+    let mut blockers = Bitboard::EMPTY;
+    loop {
+        subsets.push((blockers, slow_attacks(blockers)));
+        blockers = Bitboard((blockers.0.wrapping_sub(mask.0)) & mask.0);
+        if blockers.is_empty() {
+            break;
+        }
+    }
+    subsets
+}
+
+/// Fills a table for `magic`, or returns `None` on a destructive collision.
+fn fill_table(magic: &Magic, bits: u8, subsets: &[(Bitboard, Bitboard)]) -> Option<Vec<Bitboard>> {
+    let mut table: Vec<Option<Bitboard>> = vec![None; 1 << bits];
+    for &(blockers, attacks) in subsets {
+        let slot = &mut table[magic_index(magic, blockers)];
+        match slot {
+            Some(existing) if *existing != attacks => return None,
+            _ => *slot = Some(attacks),
+        }
+    }
+    Some(table.into_iter().map(Option::unwrap_or_default).collect())
+}
+
 /// Computes the magic table index for a given blocker configuration.
 #[inline]
 fn magic_index(magic: &Magic, blockers: Bitboard) -> usize {
@@ -457,6 +472,77 @@ mod tests {
         assert!(!attacks.contains(Square::new(File::D, Rank::R7)));
     }
 
+    #[test]
+    fn magic_lookups_match_slow_attacks() {
+        for sq in 0..64u8 {
+            let square = Square::from_index(sq).unwrap();
+            for (mask, slow, fast) in [
+                (
+                    bishop_mask(sq),
+                    bishop_attacks_slow as fn(u8, Bitboard) -> Bitboard,
+                    bishop_attacks as fn(Square, Bitboard) -> Bitboard,
+                ),
+                (rook_mask(sq), rook_attacks_slow, rook_attacks),
+            ] {
+                let mut blockers = Bitboard::EMPTY;
+                loop {
+                    assert_eq!(fast(square, blockers), slow(sq, blockers), "{}", square);
+                    blockers = Bitboard((blockers.0.wrapping_sub(mask.0)) & mask.0);
+                    if blockers.is_empty() {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn pre_computed_magics_have_no_collisions() {
+        for sq in 0..64u8 {
+            for (kind, mask, bits, magic, slow) in [
+                (
+                    "bishop",
+                    bishop_mask(sq),
+                    BISHOP_BITS[sq as usize],
+                    BISHOP_MAGICS[sq as usize],
+                    bishop_attacks_slow as fn(u8, Bitboard) -> Bitboard,
+                ),
+                (
+                    "rook",
+                    rook_mask(sq),
+                    ROOK_BITS[sq as usize],
+                    ROOK_MAGICS[sq as usize],
+                    rook_attacks_slow,
+                ),
+            ] {
+                let entry = Magic {
+                    mask,
+                    magic,
+                    shift: 64 - bits,
+                    offset: 0,
+                };
+                let subsets = blocker_subsets(mask, |blockers| slow(sq, blockers));
+                assert!(
+                    fill_table(&entry, bits, &subsets).is_some(),
+                    "{} magic for square {} collides",
+                    kind,
+                    sq
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn bishop_b5_blocked_by_c4() {
+        // A bad magic for b5 used to let the bishop see through c4
+        let b5 = Square::new(File::B, Rank::R5);
+        let c4 = Square::new(File::C, Rank::R4);
+        let attacks = bishop_attacks(b5, Bitboard::from_square(c4));
+        assert!(attacks.contains(c4));
+        assert!(!attacks.contains(Square::new(File::D, Rank::R3)));
+        assert!(attacks.contains(Square::new(File::E, Rank::R8)));
+    }
+
     #[test]
     fn corner_bishop() {
         let attacks = bishop_attacks(Square::A1, Bitboard::EMPTY);
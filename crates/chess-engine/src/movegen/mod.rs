@@ -6,13 +6,16 @@
 mod attacks;
 mod magics;
 pub mod perft;
+mod see;
 
+use crate::zobrist::{castling_hash, en_passant_hash, ZOBRIST};
 use crate::{Bitboard, Position};
-use chess_core::{Color, Move, MoveFlag, Piece, Rank, Square};
+use chess_core::{Color, File, Move, MoveFlag, Piece, Rank, Square};
 
 pub use attacks::{
     bishop_attacks, king_attacks, knight_attacks, pawn_attacks, queen_attacks, rook_attacks,
 };
+pub use see::{see, SEE_VALUES};
 
 /// A list of moves with a fixed maximum capacity.
 ///
@@ -83,6 +86,77 @@ impl MoveList {
         }
         self.len = write;
     }
+
+    /// Sorts the moves with a comparator function.
+    ///
+    /// The sort is done in place and does not allocate, so it is not
+    /// stable: moves that compare equal may be reordered.
+    pub fn sort_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&Move, &Move) -> std::cmp::Ordering,
+    {
+        self.moves[..self.len].sort_unstable_by(compare);
+    }
+
+    /// Sorts the moves with a key extraction function.
+    ///
+    /// Like [`sort_by`](Self::sort_by), the sort is in place and not stable.
+    pub fn sort_by_key<K, F>(&mut self, f: F)
+    where
+        F: FnMut(&Move) -> K,
+        K: Ord,
+    {
+        self.moves[..self.len].sort_unstable_by_key(f);
+    }
+
+    /// Swaps the moves at indices `i` and `j`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is out of bounds.
+    #[inline]
+    pub fn swap(&mut self, i: usize, j: usize) {
+        self.moves[..self.len].swap(i, j);
+    }
+
+    /// Keeps the first `n` moves, e.g. the best `n` after sorting.
+    ///
+    /// Has no effect if the list holds `n` moves or fewer.
+    #[inline]
+    pub fn truncate(&mut self, n: usize) {
+        self.len = self.len.min(n);
+    }
+
+    /// Finds the move in this list that a UCI string such as `e7e8n` names.
+    ///
+    /// `Move::from_uci` cannot know flags such as double push, en passant or
+    /// castling, so moves are matched by their squares, promotion piece and
+    /// drop piece. A promotion without a suffix, such as `e7e8`, is taken to
+    /// be a queen promotion. Castling may also be written as the king taking
+    /// its own rook, such as `e1h1`, as Chess960 engines do.
+    pub fn find_uci(&self, uci: &str) -> Option<Move> {
+        let m = Move::from_uci(uci)?;
+        let promotion = m.flag().promotion_piece();
+        self.as_slice().iter().copied().find(|legal| {
+            legal.from() == m.from()
+                && (legal.to() == m.to() || castling_rook_square(*legal) == Some(m.to()))
+                && legal.flag().drop_piece() == m.flag().drop_piece()
+                && match legal.flag().promotion_piece() {
+                    Some(piece) => piece == promotion.unwrap_or(Piece::Queen),
+                    None => promotion.is_none(),
+                }
+        })
+    }
+}
+
+/// Returns the starting square of the rook that a castling move moves.
+fn castling_rook_square(m: Move) -> Option<Square> {
+    let rank = m.to().rank();
+    match m.flag() {
+        MoveFlag::CastleKingside => Some(Square::new(File::H, rank)),
+        MoveFlag::CastleQueenside => Some(Square::new(File::A, rank)),
+        _ => None,
+    }
 }
 
 impl Default for MoveList {
@@ -116,17 +190,53 @@ impl std::fmt::Debug for MoveList {
     }
 }
 
+/// Which moves a generation pass produces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    /// Captures, en passant and promotions.
+    Captures,
+    /// Every other move, including castling.
+    Quiets,
+    /// All moves.
+    All,
+}
+
+impl Stage {
+    /// Returns true if this stage includes captures and promotions.
+    const fn captures(self) -> bool {
+        matches!(self, Stage::Captures | Stage::All)
+    }
+
+    /// Returns true if this stage includes quiet moves.
+    const fn quiets(self) -> bool {
+        matches!(self, Stage::Quiets | Stage::All)
+    }
+}
+
 /// Generates all legal moves for the given position.
 pub fn generate_moves(position: &Position) -> MoveList {
-    let mut moves = MoveList::new();
+    legal_moves(position, Stage::All)
+}
+
+/// Generates the legal captures for the given position.
+///
+/// En passant and all promotions, including non-capturing ones, count as
+/// captures. Together with [`generate_quiets`] this partitions
+/// [`generate_moves`], so a search can try the tactical moves first without
+/// generating the quiet ones.
+pub fn generate_captures(position: &Position) -> MoveList {
+    legal_moves(position, Stage::Captures)
+}
 
-    generate_pawn_moves(position, &mut moves);
-    generate_knight_moves(position, &mut moves);
-    generate_bishop_moves(position, &mut moves);
-    generate_rook_moves(position, &mut moves);
-    generate_queen_moves(position, &mut moves);
-    generate_king_moves(position, &mut moves);
-    generate_castling_moves(position, &mut moves);
+/// Generates the legal moves that [`generate_captures`] leaves out: quiet
+/// piece moves, non-promoting pawn pushes and castling.
+pub fn generate_quiets(position: &Position) -> MoveList {
+    legal_moves(position, Stage::Quiets)
+}
+
+/// Generates the legal moves of one stage.
+fn legal_moves(position: &Position, stage: Stage) -> MoveList {
+    let mut moves = pseudo_legal_moves(position, stage);
 
     // Filter out moves that leave king in check
     let us = position.side_to_move;
@@ -138,8 +248,41 @@ pub fn generate_moves(position: &Position) -> MoveList {
     moves
 }
 
-/// Generates pseudo-legal pawn moves.
-fn generate_pawn_moves(position: &Position, moves: &mut MoveList) {
+/// Generates all pseudo-legal moves for the given position.
+///
+/// These moves follow piece movement rules but may leave the king in check.
+/// Variants without king safety (e.g. Antichess) use this directly.
+pub fn generate_pseudo_legal_moves(position: &Position) -> MoveList {
+    pseudo_legal_moves(position, Stage::All)
+}
+
+/// Generates the pseudo-legal moves of one stage.
+fn pseudo_legal_moves(position: &Position, stage: Stage) -> MoveList {
+    let mut moves = MoveList::new();
+
+    // Destination squares for piece moves
+    let us = position.side_to_move;
+    let targets = match stage {
+        Stage::Captures => position.colors[us.opposite().index()],
+        Stage::Quiets => !position.occupied(),
+        Stage::All => !position.colors[us.index()],
+    };
+
+    generate_pawn_moves(position, stage, &mut moves);
+    generate_knight_moves(position, targets, &mut moves);
+    generate_bishop_moves(position, targets, &mut moves);
+    generate_rook_moves(position, targets, &mut moves);
+    generate_queen_moves(position, targets, &mut moves);
+    generate_king_moves(position, targets, &mut moves);
+    if stage.quiets() {
+        generate_castling_moves(position, &mut moves);
+    }
+
+    moves
+}
+
+/// Generates the pseudo-legal pawn moves of one stage.
+fn generate_pawn_moves(position: &Position, stage: Stage, moves: &mut MoveList) {
     let us = position.side_to_move;
     let them = us.opposite();
     let our_pieces = position.colors[us.index()];
@@ -165,26 +308,47 @@ fn generate_pawn_moves(position: &Position, moves: &mut MoveList) {
         let from = unsafe { Square::from_index_unchecked((to.index() as i8 - push_dir) as u8) };
         if to.rank() == promo_rank {
             // Promotion
-            moves.push(Move::new(from, to, MoveFlag::PromoteQueen));
-            moves.push(Move::new(from, to, MoveFlag::PromoteRook));
-            moves.push(Move::new(from, to, MoveFlag::PromoteBishop));
-            moves.push(Move::new(from, to, MoveFlag::PromoteKnight));
-        } else {
+            if stage.captures() {
+                moves.push(Move::new(from, to, MoveFlag::PromoteQueen));
+                moves.push(Move::new(from, to, MoveFlag::PromoteRook));
+                moves.push(Move::new(from, to, MoveFlag::PromoteBishop));
+                moves.push(Move::new(from, to, MoveFlag::PromoteKnight));
+            }
+        } else if stage.quiets() {
             moves.push(Move::normal(from, to));
         }
     }
 
     // Double pushes
-    let double_pushes = if us == Color::White {
-        (pawns & Bitboard::RANK_2).north().north() & empty & (single_pushes.north())
-    } else {
-        (pawns & Bitboard::RANK_7).south().south() & empty & (single_pushes.south())
-    };
+    if stage.quiets() {
+        let double_pushes = if us == Color::White {
+            (pawns & Bitboard::RANK_2).north().north() & empty & (single_pushes.north())
+        } else {
+            (pawns & Bitboard::RANK_7).south().south() & empty & (single_pushes.south())
+        };
+
+        for to in double_pushes {
+            let from =
+                unsafe { Square::from_index_unchecked((to.index() as i8 - 2 * push_dir) as u8) };
+            moves.push(Move::new(from, to, MoveFlag::DoublePush));
+        }
+    }
 
-    for to in double_pushes {
-        let from = unsafe { Square::from_index_unchecked((to.index() as i8 - 2 * push_dir) as u8) };
-        moves.push(Move::new(from, to, MoveFlag::DoublePush));
+    if stage.captures() {
+        generate_pawn_captures(position, moves);
     }
+}
+
+/// Generates pseudo-legal pawn captures, including capturing promotions and
+/// en passant.
+fn generate_pawn_captures(position: &Position, moves: &mut MoveList) {
+    let us = position.side_to_move;
+    let their_pieces = position.colors[us.opposite().index()];
+    let pawns = position.pieces_of(Piece::Pawn, us);
+    let promo_rank = match us {
+        Color::White => Rank::R8,
+        Color::Black => Rank::R1,
+    };
 
     // Captures (left and right)
     for from in pawns {
@@ -212,13 +376,12 @@ fn generate_pawn_moves(position: &Position, moves: &mut MoveList) {
 }
 
 /// Generates pseudo-legal knight moves.
-fn generate_knight_moves(position: &Position, moves: &mut MoveList) {
+fn generate_knight_moves(position: &Position, targets: Bitboard, moves: &mut MoveList) {
     let us = position.side_to_move;
-    let our_pieces = position.colors[us.index()];
     let knights = position.pieces_of(Piece::Knight, us);
 
     for from in knights {
-        let attacks = knight_attacks(from) & !our_pieces;
+        let attacks = knight_attacks(from) & targets;
         for to in attacks {
             moves.push(Move::normal(from, to));
         }
@@ -226,14 +389,13 @@ fn generate_knight_moves(position: &Position, moves: &mut MoveList) {
 }
 
 /// Generates pseudo-legal bishop moves.
-fn generate_bishop_moves(position: &Position, moves: &mut MoveList) {
+fn generate_bishop_moves(position: &Position, targets: Bitboard, moves: &mut MoveList) {
     let us = position.side_to_move;
-    let our_pieces = position.colors[us.index()];
     let occupied = position.occupied();
     let bishops = position.pieces_of(Piece::Bishop, us);
 
     for from in bishops {
-        let attacks = bishop_attacks(from, occupied) & !our_pieces;
+        let attacks = bishop_attacks(from, occupied) & targets;
         for to in attacks {
             moves.push(Move::normal(from, to));
         }
@@ -241,14 +403,13 @@ fn generate_bishop_moves(position: &Position, moves: &mut MoveList) {
 }
 
 /// Generates pseudo-legal rook moves.
-fn generate_rook_moves(position: &Position, moves: &mut MoveList) {
+fn generate_rook_moves(position: &Position, targets: Bitboard, moves: &mut MoveList) {
     let us = position.side_to_move;
-    let our_pieces = position.colors[us.index()];
     let occupied = position.occupied();
     let rooks = position.pieces_of(Piece::Rook, us);
 
     for from in rooks {
-        let attacks = rook_attacks(from, occupied) & !our_pieces;
+        let attacks = rook_attacks(from, occupied) & targets;
         for to in attacks {
             moves.push(Move::normal(from, to));
         }
@@ -256,14 +417,13 @@ fn generate_rook_moves(position: &Position, moves: &mut MoveList) {
 }
 
 /// Generates pseudo-legal queen moves.
-fn generate_queen_moves(position: &Position, moves: &mut MoveList) {
+fn generate_queen_moves(position: &Position, targets: Bitboard, moves: &mut MoveList) {
     let us = position.side_to_move;
-    let our_pieces = position.colors[us.index()];
     let occupied = position.occupied();
     let queens = position.pieces_of(Piece::Queen, us);
 
     for from in queens {
-        let attacks = queen_attacks(from, occupied) & !our_pieces;
+        let attacks = queen_attacks(from, occupied) & targets;
         for to in attacks {
             moves.push(Move::normal(from, to));
         }
@@ -271,14 +431,12 @@ fn generate_queen_moves(position: &Position, moves: &mut MoveList) {
 }
 
 /// Generates pseudo-legal king moves (not including castling).
-fn generate_king_moves(position: &Position, moves: &mut MoveList) {
+fn generate_king_moves(position: &Position, targets: Bitboard, moves: &mut MoveList) {
     let us = position.side_to_move;
-    let our_pieces = position.colors[us.index()];
-    let king_sq = position.pieces_of(Piece::King, us).lsb();
 
-    if let Some(idx) = king_sq {
-        let from = unsafe { Square::from_index_unchecked(idx) };
-        let attacks = king_attacks(from) & !our_pieces;
+    // Antichess lets pawns promote to kings, so there may be more than one
+    for from in position.pieces_of(Piece::King, us) {
+        let attacks = king_attacks(from) & targets;
         for to in attacks {
             moves.push(Move::normal(from, to));
         }
@@ -393,6 +551,33 @@ pub fn is_square_attacked(position: &Position, sq: Square, by_color: Color) -> b
     false
 }
 
+/// Returns the pieces of `by_color` attacking the given square.
+pub fn attackers_of(position: &Position, sq: Square, by_color: Color) -> Bitboard {
+    let occupied = position.occupied();
+    let diagonal =
+        position.pieces_of(Piece::Bishop, by_color) | position.pieces_of(Piece::Queen, by_color);
+    let orthogonal =
+        position.pieces_of(Piece::Rook, by_color) | position.pieces_of(Piece::Queen, by_color);
+
+    (pawn_attacks(sq, by_color.opposite()) & position.pieces_of(Piece::Pawn, by_color))
+        | (knight_attacks(sq) & position.pieces_of(Piece::Knight, by_color))
+        | (king_attacks(sq) & position.pieces_of(Piece::King, by_color))
+        | (bishop_attacks(sq, occupied) & diagonal)
+        | (rook_attacks(sq, occupied) & orthogonal)
+}
+
+/// Returns the pieces giving check to the side to move.
+pub fn checkers(position: &Position) -> Bitboard {
+    let us = position.side_to_move;
+    match position.pieces_of(Piece::King, us).lsb() {
+        Some(king_idx) => {
+            let king_sq = unsafe { Square::from_index_unchecked(king_idx) };
+            attackers_of(position, king_sq, us.opposite())
+        }
+        None => Bitboard::EMPTY,
+    }
+}
+
 /// Returns true if the king of the given color is in check.
 pub fn is_king_attacked(position: &Position, king_color: Color) -> bool {
     let king_bb = position.pieces_of(Piece::King, king_color);
@@ -405,7 +590,15 @@ pub fn is_king_attacked(position: &Position, king_color: Color) -> bool {
 }
 
 /// Makes a move and returns the new position.
+///
+/// Drop moves (Crazyhouse) place a piece from the mover's pocket; capture
+/// bookkeeping for pockets is left to the [`Crazyhouse`](crate::rules::Crazyhouse)
+/// rule set.
 pub fn make_move(position: &Position, m: Move) -> Position {
+    if let Some(piece) = m.flag().drop_piece() {
+        return make_drop(position, piece, m.to());
+    }
+
     let mut new_pos = position.clone();
     let us = position.side_to_move;
     let them = us.opposite();
@@ -418,12 +611,14 @@ pub fn make_move(position: &Position, m: Move) -> Position {
     // Remove piece from source
     new_pos.pieces[piece.index()].clear(from);
     new_pos.colors[us.index()].clear(from);
+    let mut hash = position.hash ^ ZOBRIST.piece_key(piece, us, from);
 
     // Handle captures
     let mut is_capture = false;
     if let Some((captured, _)) = position.piece_at(to) {
         new_pos.pieces[captured.index()].clear(to);
         new_pos.colors[them.index()].clear(to);
+        hash ^= ZOBRIST.piece_key(captured, them, to);
         is_capture = true;
     }
 
@@ -435,6 +630,7 @@ pub fn make_move(position: &Position, m: Move) -> Position {
         };
         new_pos.pieces[Piece::Pawn.index()].clear(captured_sq);
         new_pos.colors[them.index()].clear(captured_sq);
+        hash ^= ZOBRIST.piece_key(Piece::Pawn, them, captured_sq);
         is_capture = true;
     }
 
@@ -444,6 +640,7 @@ pub fn make_move(position: &Position, m: Move) -> Position {
     // Place piece at destination
     new_pos.pieces[dest_piece.index()].set(to);
     new_pos.colors[us.index()].set(to);
+    hash ^= ZOBRIST.piece_key(dest_piece, us, to);
 
     // Handle castling - move the rook
     match m.flag() {
@@ -456,6 +653,8 @@ pub fn make_move(position: &Position, m: Move) -> Position {
             new_pos.colors[us.index()].clear(rook_from);
             new_pos.pieces[Piece::Rook.index()].set(rook_to);
             new_pos.colors[us.index()].set(rook_to);
+            hash ^= ZOBRIST.piece_key(Piece::Rook, us, rook_from)
+                ^ ZOBRIST.piece_key(Piece::Rook, us, rook_to);
         }
         MoveFlag::CastleQueenside => {
             let (rook_from, rook_to) = match us {
@@ -466,6 +665,8 @@ pub fn make_move(position: &Position, m: Move) -> Position {
             new_pos.colors[us.index()].clear(rook_from);
             new_pos.pieces[Piece::Rook.index()].set(rook_to);
             new_pos.colors[us.index()].set(rook_to);
+            hash ^= ZOBRIST.piece_key(Piece::Rook, us, rook_from)
+                ^ ZOBRIST.piece_key(Piece::Rook, us, rook_to);
         }
         _ => {}
     }
@@ -485,7 +686,10 @@ pub fn make_move(position: &Position, m: Move) -> Position {
             _ => {}
         }
     }
-    // Capture on rook starting square removes opponent's castling rights
+    // Capture on rook starting square removes opponent's castling rights.
+    // A right is only held while its rook is still on the corner, so a
+    // different piece there means the right is already gone and this is a
+    // no-op; it never touches the rights tied to the other corners.
     match to {
         sq if sq == Square::H1 => new_pos.castling.remove_kingside(Color::White),
         sq if sq == Square::A1 => new_pos.castling.remove_queenside(Color::White),
@@ -519,13 +723,40 @@ pub fn make_move(position: &Position, m: Move) -> Position {
     // Switch side to move
     new_pos.side_to_move = them;
 
+    hash ^= castling_hash(position.castling) ^ castling_hash(new_pos.castling);
+    hash ^= en_passant_hash(position.en_passant) ^ en_passant_hash(new_pos.en_passant);
+    new_pos.hash = hash ^ ZOBRIST.black_to_move;
+
+    new_pos
+}
+
+/// Places a piece from the side to move's pocket on an empty square.
+fn make_drop(position: &Position, piece: Piece, to: Square) -> Position {
+    let mut new_pos = position.clone();
+    let us = position.side_to_move;
+
+    new_pos.remove_from_pocket(us, piece);
+    new_pos.pieces[piece.index()].set(to);
+    new_pos.colors[us.index()].set(to);
+    new_pos.promoted.clear(to);
+    new_pos.hash ^= ZOBRIST.piece_key(piece, us, to)
+        ^ en_passant_hash(position.en_passant)
+        ^ ZOBRIST.black_to_move;
+
+    new_pos.en_passant = None;
+    new_pos.halfmove_clock += 1;
+    if us == Color::Black {
+        new_pos.fullmove_number += 1;
+    }
+    new_pos.side_to_move = us.opposite();
+
     new_pos
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chess_core::File;
+    use crate::StandardChess;
 
     #[test]
     fn movelist_push_and_iterate() {
@@ -548,6 +779,48 @@ mod tests {
         assert_eq!(list[1], m2);
     }
 
+    #[test]
+    fn find_uci_resolves_promotions_and_flags() {
+        let position = Position::from_fen("8/4P3/8/8/8/8/4K2k/8 w - - 0 1").unwrap();
+        let moves = generate_moves(&position);
+
+        let queen = moves.find_uci("e7e8").unwrap();
+        assert_eq!(queen.flag().promotion_piece(), Some(Piece::Queen));
+        assert_eq!(moves.find_uci("e7e8q"), Some(queen));
+
+        let knight = moves.find_uci("e7e8n").unwrap();
+        assert_eq!(knight.flag().promotion_piece(), Some(Piece::Knight));
+        assert_eq!(knight.to_uci(), "e7e8n");
+
+        let king = moves.find_uci("e2d3").unwrap();
+        assert_eq!(king.flag().promotion_piece(), None);
+        assert_eq!(moves.find_uci("e2e4"), None);
+        assert_eq!(moves.find_uci("nonsense"), None);
+
+        // The double push flag comes from the legal move, not the string
+        let start = generate_moves(&Position::startpos());
+        assert_eq!(start.find_uci("e2e4").unwrap().flag(), MoveFlag::DoublePush);
+        assert_eq!(start.find_uci("e2e4q"), None);
+    }
+
+    #[test]
+    fn find_uci_accepts_king_takes_rook_castling() {
+        let position = Position::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+        let moves = generate_moves(&position);
+
+        let kingside = moves.find_uci("e1g1").unwrap();
+        assert_eq!(kingside.flag(), MoveFlag::CastleKingside);
+        assert_eq!(moves.find_uci("e1h1"), Some(kingside));
+
+        let queenside = moves.find_uci("e1c1").unwrap();
+        assert_eq!(queenside.flag(), MoveFlag::CastleQueenside);
+        assert_eq!(moves.find_uci("e1a1"), Some(queenside));
+
+        // Without castling rights the rook square is just occupied
+        let position = Position::from_fen("r3k2r/8/8/8/8/8/8/R3K2R b - - 0 1").unwrap();
+        assert_eq!(generate_moves(&position).find_uci("e8h8"), None);
+    }
+
     #[test]
     fn movelist_default() {
         let list = MoveList::default();
@@ -584,6 +857,40 @@ mod tests {
         assert_eq!(list.len(), 2);
     }
 
+    #[test]
+    fn movelist_sort_swap_and_truncate() {
+        let e2 = Square::new(File::E, Rank::R2);
+        let targets = [
+            Square::new(File::H, Rank::R5),
+            Square::new(File::A, Rank::R6),
+            Square::new(File::E, Rank::R4),
+            Square::new(File::C, Rank::R3),
+        ];
+        let mut list = MoveList::new();
+        for &to in &targets {
+            list.push(Move::normal(e2, to));
+        }
+
+        list.sort_by_key(|m| m.to().index());
+        assert_eq!(list.len(), 4);
+        let sorted: Vec<u8> = list.as_slice().iter().map(|m| m.to().index()).collect();
+        assert!(sorted.windows(2).all(|w| w[0] < w[1]), "{:?}", sorted);
+
+        list.sort_by(|a, b| b.to().index().cmp(&a.to().index()));
+        assert_eq!(list.len(), 4);
+        assert_eq!(list[0].to(), targets[1]);
+        assert_eq!(list[3].to(), targets[3]);
+
+        list.swap(0, 3);
+        assert_eq!(list[0].to(), targets[3]);
+        assert_eq!(list[3].to(), targets[1]);
+
+        list.truncate(2);
+        assert_eq!(list.len(), 2);
+        list.truncate(5);
+        assert_eq!(list.len(), 2);
+    }
+
     #[test]
     fn generate_moves_startpos() {
         let position = Position::startpos();
@@ -617,6 +924,63 @@ mod tests {
         assert!(new_pos.piece_at(g1).is_none());
     }
 
+    #[test]
+    fn incremental_hash_matches_full_hash() {
+        use crate::rules::{Crazyhouse, RuleSet, ThreeCheck};
+
+        fn check(rules: &dyn RuleSet, position: &Position, depth: u32) {
+            assert_eq!(position.zobrist_hash(), position.compute_zobrist_hash());
+            if depth == 0 {
+                return;
+            }
+            for &m in rules.generate_moves(position).as_slice() {
+                let next = rules.make_move(position, m);
+                assert_eq!(
+                    next.zobrist_hash(),
+                    next.compute_zobrist_hash(),
+                    "{} after {}",
+                    position.to_fen(),
+                    m.to_uci()
+                );
+                check(rules, &next, depth - 1);
+            }
+        }
+
+        for fen in [
+            // Start position, castling both ways
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "r3k2r/pppq1ppp/2n2n2/3pp3/3PP3/2N2N2/PPPQ1PPP/R3K2R w KQkq - 0 1",
+            // En passant available
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+            // Promotions with and without capture
+            "n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1",
+            // Rooks on their corners can be captured
+            "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1",
+            "r3k2r/1B6/8/8/8/8/1b6/R3K2R b KQkq - 0 1",
+        ] {
+            check(&StandardChess, &Position::from_fen(fen).unwrap(), 2);
+        }
+
+        // Drops from the pocket, and captures that fill it
+        for fen in [
+            "4k3/8/8/8/8/8/8/4K3[Nq] w - - 0 1",
+            "4k3/8/8/3p4/4P3/8/8/4K3[Nq] w - - 0 1",
+        ] {
+            check(&Crazyhouse, &Position::from_fen(fen).unwrap(), 2);
+        }
+
+        // Checks counted by Three-check
+        let three_check = Position::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1 +1+0").unwrap();
+        check(&ThreeCheck, &three_check, 2);
+
+        // Null moves flip the side and clear en passant
+        let ep =
+            Position::from_fen("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3")
+                .unwrap()
+                .null_move();
+        assert_eq!(ep.zobrist_hash(), ep.compute_zobrist_hash());
+    }
+
     #[test]
     fn is_square_attacked_startpos() {
         let position = Position::startpos();
@@ -630,6 +994,25 @@ mod tests {
         assert!(!is_square_attacked(&position, e4, Color::White));
     }
 
+    #[test]
+    fn attackers_of_and_checkers() {
+        // The black knight on d3 checks the white king on e1. The e2 pawn and
+        // d1 queen attack it; the f1 bishop is blocked by the pawn
+        let position = Position::from_fen("4k3/8/8/8/8/3n4/4P3/3QKB2 w - - 0 1").unwrap();
+        let d3 = Square::new(File::D, Rank::R3);
+
+        assert_eq!(checkers(&position), Bitboard::from_square(d3));
+
+        let attackers: Vec<Square> = attackers_of(&position, d3, Color::White)
+            .into_iter()
+            .collect();
+        let e2 = Square::new(File::E, Rank::R2);
+        let d1 = Square::new(File::D, Rank::R1);
+        assert_eq!(attackers, vec![d1, e2]);
+
+        assert!(checkers(&Position::startpos()).is_empty());
+    }
+
     #[test]
     fn is_king_attacked_startpos() {
         let position = Position::startpos();
@@ -637,6 +1020,84 @@ mod tests {
         assert!(!is_king_attacked(&position, Color::Black));
     }
 
+    /// Plays `uci` from `fen` and returns the castling field of the result.
+    fn castling_after(fen: &str, uci: &str) -> String {
+        let position = Position::from_fen(fen).unwrap();
+        let m = *generate_moves(&position)
+            .as_slice()
+            .iter()
+            .find(|m| m.to_uci() == uci)
+            .unwrap();
+        make_move(&position, m)
+            .to_fen()
+            .split(' ')
+            .nth(2)
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn capturing_rook_on_corner_removes_its_right() {
+        let fen = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+        assert_eq!(castling_after(fen, "a1a8"), "Kk");
+        assert_eq!(castling_after(fen, "h1h8"), "Qq");
+    }
+
+    #[test]
+    fn capturing_non_rook_on_corner_keeps_other_rights() {
+        // Knights sit on the corners whose rights are already gone
+        assert_eq!(
+            castling_after("n3k2r/8/8/3Q4/8/8/8/R3K2R w KQk - 0 1", "d5a8"),
+            "KQk"
+        );
+        assert_eq!(
+            castling_after("r3k2n/4p3/8/4Q3/8/8/8/R3K2R w KQq - 0 1", "e5h8"),
+            "KQq"
+        );
+        assert_eq!(
+            castling_after("r3k2r/8/8/8/3q4/8/8/N3K2R b Kkq - 0 1", "d4a1"),
+            "Kkq"
+        );
+        assert_eq!(
+            castling_after("r3k2r/8/8/8/4q3/8/4P3/R3K2N b Qkq - 0 1", "e4h1"),
+            "Qkq"
+        );
+    }
+
+    #[test]
+    fn captures_and_quiets_partition_legal_moves() {
+        for fen in [
+            // Kiwipete: captures, castling and en passant candidates
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            // Promotions with and without capture, and en passant
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBPpP3/q4N2/Pp4PP/R2Q1RK1 b kq c3 0 1",
+            // In check from an adjacent rook: capture it or step away, no castling
+            "4k3/8/8/8/8/8/4r3/R3K3 w Q - 0 1",
+        ] {
+            let position = Position::from_fen(fen).unwrap();
+            let captures = generate_captures(&position);
+            let quiets = generate_quiets(&position);
+            for m in captures.as_slice() {
+                assert!(
+                    position.piece_at(m.to()).is_some()
+                        || m.flag() == MoveFlag::EnPassant
+                        || m.flag().is_promotion(),
+                    "{} in {}",
+                    m.to_uci(),
+                    fen
+                );
+            }
+
+            let mut staged: Vec<Move> = captures.as_slice().to_vec();
+            staged.extend_from_slice(quiets.as_slice());
+            let mut all: Vec<Move> = generate_moves(&position).as_slice().to_vec();
+            assert_eq!(staged.len(), all.len(), "{}", fen);
+            staged.sort_by_key(|m| m.to_uci());
+            all.sort_by_key(|m| m.to_uci());
+            assert_eq!(staged, all, "{}", fen);
+        }
+    }
+
     #[test]
     fn castling_kingside() {
         // Position where white can castle kingside
@@ -5,6 +5,9 @@
 
 use super::{generate_moves, make_move};
 use crate::Position;
+use chess_core::FenError;
+use std::fmt;
+use std::path::Path;
 
 /// Counts the number of leaf nodes at the given depth.
 ///
@@ -48,6 +51,108 @@ pub fn perft_divide(position: &Position, depth: u32) -> Vec<(String, u64)> {
     results
 }
 
+/// Error from running a perft suite.
+#[derive(Debug)]
+pub enum PerftSuiteError {
+    /// The suite file could not be read.
+    Io(std::io::Error),
+    /// A line is not of the form `FEN ;D1 n ;D2 n ...`.
+    InvalidLine { line: usize, message: String },
+    /// A line has an invalid FEN.
+    InvalidFen { line: usize, error: FenError },
+    /// The move generator produced the wrong node count.
+    Mismatch {
+        fen: String,
+        depth: u32,
+        expected: u64,
+        actual: u64,
+    },
+}
+
+impl fmt::Display for PerftSuiteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PerftSuiteError::Io(e) => write!(f, "cannot read perft suite: {}", e),
+            PerftSuiteError::InvalidLine { line, message } => {
+                write!(f, "line {}: {}", line, message)
+            }
+            PerftSuiteError::InvalidFen { line, error } => {
+                write!(f, "line {}: invalid FEN: {}", line, error)
+            }
+            PerftSuiteError::Mismatch {
+                fen,
+                depth,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "perft mismatch for {} at depth {}: expected {}, got {}",
+                fen, depth, expected, actual
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PerftSuiteError {}
+
+/// Runs every check in a perft suite file and returns how many passed.
+///
+/// See [`run_suite_str`] for the file format. Stops at the first mismatch.
+pub fn run_suite(path: impl AsRef<Path>) -> Result<usize, PerftSuiteError> {
+    let contents = std::fs::read_to_string(path).map_err(PerftSuiteError::Io)?;
+    run_suite_str(&contents)
+}
+
+/// Runs every check in a perft suite and returns how many passed.
+///
+/// Each line holds a FEN followed by expected node counts, in the EPD-like
+/// format `FEN ;D1 20 ;D2 400`. Blank lines and lines starting with `#` are
+/// skipped. Stops at the first mismatching FEN and depth.
+pub fn run_suite_str(suite: &str) -> Result<usize, PerftSuiteError> {
+    let mut checks = 0;
+
+    for (index, line) in suite.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut fields = line.split(';');
+        let fen = fields.next().unwrap_or_default().trim();
+        let position = Position::from_fen(fen).map_err(|error| PerftSuiteError::InvalidFen {
+            line: line_number,
+            error,
+        })?;
+
+        for field in fields {
+            let invalid = || PerftSuiteError::InvalidLine {
+                line: line_number,
+                message: format!("invalid depth entry '{}'", field.trim()),
+            };
+            let (depth, expected) = field.trim().split_once(' ').ok_or_else(invalid)?;
+            let depth: u32 = depth
+                .strip_prefix('D')
+                .and_then(|d| d.parse().ok())
+                .ok_or_else(invalid)?;
+            let expected: u64 = expected.trim().parse().map_err(|_| invalid())?;
+
+            let actual = perft(&position, depth);
+            if actual != expected {
+                return Err(PerftSuiteError::Mismatch {
+                    fen: fen.to_string(),
+                    depth,
+                    expected,
+                    actual,
+                });
+            }
+            checks += 1;
+        }
+    }
+
+    Ok(checks)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -195,4 +300,40 @@ mod tests {
         let total: u64 = results.iter().map(|(_, n)| n).sum();
         assert_eq!(total, 20);
     }
+
+    #[test]
+    fn suite_counts_checks_and_skips_comments() {
+        let suite = "# start position\n\nrnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 ;D1 20 ;D2 400\n";
+        assert_eq!(run_suite_str(suite).unwrap(), 2);
+    }
+
+    #[test]
+    fn suite_reports_first_mismatch() {
+        let suite = "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1 ;D1 14 ;D2 190 ;D3 2812";
+        match run_suite_str(suite) {
+            Err(PerftSuiteError::Mismatch {
+                depth,
+                expected,
+                actual,
+                ..
+            }) => {
+                assert_eq!(depth, 2);
+                assert_eq!(expected, 190);
+                assert_eq!(actual, 191);
+            }
+            other => panic!("expected mismatch, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn suite_rejects_malformed_lines() {
+        assert!(matches!(
+            run_suite_str("8/8/8/8/8/8/8/8 w - - 0 1 ;depth 1"),
+            Err(PerftSuiteError::InvalidLine { line: 1, .. })
+        ));
+        assert!(matches!(
+            run_suite_str("\nnot a fen ;D1 1"),
+            Err(PerftSuiteError::InvalidFen { line: 2, .. })
+        ));
+    }
 }
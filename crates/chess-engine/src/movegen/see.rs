@@ -0,0 +1,203 @@
+//! Static exchange evaluation.
+//!
+//! SEE estimates the material outcome of a capture sequence on a single
+//! square, assuming both sides always recapture with their least valuable
+//! attacker and may stop whenever continuing would lose material. Pins and
+//! checks are ignored.
+
+use super::{bishop_attacks, king_attacks, knight_attacks, pawn_attacks, rook_attacks};
+use crate::{Bitboard, Position};
+use chess_core::{Color, Move, MoveFlag, Piece, Square};
+
+/// Piece values used by [`see`], in centipawns.
+pub const SEE_VALUES: [i32; 6] = [100, 320, 330, 500, 900, 20_000];
+
+/// Returns the SEE value of a piece.
+#[inline]
+const fn value(piece: Piece) -> i32 {
+    SEE_VALUES[piece as usize]
+}
+
+/// Returns the static exchange evaluation of `m` in centipawns, from the
+/// point of view of the side making the move.
+///
+/// Positive values win material, negative values lose it. Quiet moves score
+/// the loss of the moved piece if the destination is attacked, so a
+/// non-capture to a square where it can be taken for free returns minus its
+/// value. Drops score 0.
+///
+/// # Examples
+///
+/// ```
+/// use chess_core::Move;
+/// use chess_engine::{movegen::see, Position};
+///
+/// // A knight takes a pawn defended by a pawn: wins 100, loses 320
+/// let position = Position::from_fen("4k3/8/2p5/3p4/8/4N3/8/4K3 w - - 0 1").unwrap();
+/// let m = Move::from_uci("e3d5").unwrap();
+/// assert_eq!(see(&position, m), -220);
+/// ```
+pub fn see(position: &Position, m: Move) -> i32 {
+    if m.flag().drop_piece().is_some() {
+        return 0;
+    }
+    let Some((moved, us)) = position.piece_at(m.from()) else {
+        return 0;
+    };
+    let to = m.to();
+
+    let mut occupied = position.occupied();
+    occupied.clear(m.from());
+
+    let mut gain = [0i32; 32];
+    gain[0] = if m.flag() == MoveFlag::EnPassant {
+        let captured_sq = match us {
+            Color::White => Square::from_index(to.index() - 8),
+            Color::Black => Square::from_index(to.index() + 8),
+        };
+        if let Some(sq) = captured_sq {
+            occupied.clear(sq);
+        }
+        value(Piece::Pawn)
+    } else {
+        position.piece_at(to).map_or(0, |(piece, _)| value(piece))
+    };
+
+    // The piece standing on the target square, which the next capture wins
+    let mut on_square = value(moved);
+    if let Some(promoted) = m.flag().promotion_piece() {
+        gain[0] += value(promoted) - value(Piece::Pawn);
+        on_square = value(promoted);
+    }
+
+    let mut side = us.opposite();
+    let mut depth = 0;
+    while depth + 1 < gain.len() {
+        let Some((sq, piece)) = least_valuable_attacker(position, to, occupied, side) else {
+            break;
+        };
+        // The king may only capture if the square is no longer defended
+        if piece == Piece::King {
+            let mut after = occupied;
+            after.clear(sq);
+            if least_valuable_attacker(position, to, after, side.opposite()).is_some() {
+                break;
+            }
+        }
+
+        depth += 1;
+        gain[depth] = on_square - gain[depth - 1];
+        occupied.clear(sq);
+        on_square = value(piece);
+        side = side.opposite();
+    }
+
+    // Either side may decline to continue the exchange
+    while depth > 0 {
+        gain[depth - 1] = -(-gain[depth - 1]).max(gain[depth]);
+        depth -= 1;
+    }
+    gain[0]
+}
+
+/// Returns the least valuable piece of `color` attacking `sq`, considering
+/// only pieces still in `occupied` and sliding attacks through it.
+fn least_valuable_attacker(
+    position: &Position,
+    sq: Square,
+    occupied: Bitboard,
+    color: Color,
+) -> Option<(Square, Piece)> {
+    let diagonal = bishop_attacks(sq, occupied);
+    let orthogonal = rook_attacks(sq, occupied);
+
+    for piece in Piece::ALL {
+        let attacks = match piece {
+            Piece::Pawn => pawn_attacks(sq, color.opposite()),
+            Piece::Knight => knight_attacks(sq),
+            Piece::Bishop => diagonal,
+            Piece::Rook => orthogonal,
+            Piece::Queen => diagonal | orthogonal,
+            Piece::King => king_attacks(sq),
+        };
+        let mut attackers = attacks & position.pieces_of(piece, color) & occupied;
+        if let Some(from) = attackers.pop_lsb() {
+            return Some((from, piece));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::movegen::generate_moves;
+
+    fn see_uci(fen: &str, uci: &str) -> i32 {
+        let position = Position::from_fen(fen).unwrap();
+        let m = generate_moves(&position)
+            .as_slice()
+            .iter()
+            .copied()
+            .find(|m| m.to_uci() == uci)
+            .unwrap();
+        see(&position, m)
+    }
+
+    #[test]
+    fn undefended_capture_wins_the_piece() {
+        assert_eq!(see_uci("4k3/8/8/3r4/8/8/8/3RK3 w - - 0 1", "d1d5"), 500);
+    }
+
+    #[test]
+    fn defended_capture_loses_the_attacker() {
+        // QxP, PxQ
+        assert_eq!(
+            see_uci("4k3/8/4p3/3p4/8/8/8/3QK3 w - - 0 1", "d1d5"),
+            100 - 900
+        );
+    }
+
+    #[test]
+    fn even_trade_scores_zero() {
+        // NxN, PxN
+        assert_eq!(see_uci("4k3/8/4p3/3n4/8/4N3/8/4K3 w - - 0 1", "e3d5"), 0);
+    }
+
+    #[test]
+    fn xray_attackers_join_the_exchange() {
+        // RxP, RxR, RxR with doubled rooks on each side: white's second rook
+        // stands behind the first and wins the exchange back
+        assert_eq!(see_uci("3rk3/8/8/3p4/8/8/3R4/3RK3 w - - 0 1", "d2d5"), 100);
+        // Black has two rooks behind the pawn, so the capture loses the rook
+        assert_eq!(
+            see_uci("3rk3/3r4/8/3p4/8/8/8/3RK3 w - - 0 1", "d1d5"),
+            100 - 500
+        );
+    }
+
+    #[test]
+    fn quiet_move_to_attacked_square_loses_the_piece() {
+        assert_eq!(see_uci("4k3/8/4p3/8/8/8/8/3QK3 w - - 0 1", "d1d5"), -900);
+        assert_eq!(see_uci("4k3/8/8/8/8/8/8/3QK3 w - - 0 1", "d1d5"), 0);
+    }
+
+    #[test]
+    fn king_recaptures_only_when_safe() {
+        // QxN, RxQ, KxR
+        assert_eq!(
+            see_uci("4r1k1/8/8/8/8/8/4n3/3QK3 w - - 0 1", "d1e2"),
+            320 - 900 + 500
+        );
+        // QxN, BxQ and the king cannot take back with the rook behind
+        assert_eq!(
+            see_uci("4r1k1/8/8/7b/8/8/4n3/3QK3 w - - 0 1", "d1e2"),
+            320 - 900
+        );
+    }
+
+    #[test]
+    fn en_passant_wins_a_pawn() {
+        assert_eq!(see_uci("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "e5d6"), 100);
+    }
+}
@@ -79,6 +79,47 @@ impl CastlingRights {
     }
 }
 
+/// Number of pieces of each type that one side has on the board.
+///
+/// Kings are not counted, since each side always has exactly one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MaterialCount {
+    /// Number of pawns.
+    pub pawns: u32,
+    /// Number of knights.
+    pub knights: u32,
+    /// Number of bishops.
+    pub bishops: u32,
+    /// Number of rooks.
+    pub rooks: u32,
+    /// Number of queens.
+    pub queens: u32,
+}
+
+impl MaterialCount {
+    /// Returns the number of pieces of the given type (0 for kings).
+    pub const fn count(&self, piece: Piece) -> u32 {
+        match piece {
+            Piece::Pawn => self.pawns,
+            Piece::Knight => self.knights,
+            Piece::Bishop => self.bishops,
+            Piece::Rook => self.rooks,
+            Piece::Queen => self.queens,
+            Piece::King => 0,
+        }
+    }
+
+    /// Returns the number of knights and bishops.
+    pub const fn minor_pieces(&self) -> u32 {
+        self.knights + self.bishops
+    }
+
+    /// Returns the number of rooks and queens.
+    pub const fn major_pieces(&self) -> u32 {
+        self.rooks + self.queens
+    }
+}
+
 /// Complete chess position state.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Position {
@@ -102,12 +143,31 @@ pub struct Position {
 
     /// Fullmove number (starts at 1, increments after Black's move).
     pub fullmove_number: u32,
+
+    /// Number of checks delivered by White, counted by [`ThreeCheck`](crate::ThreeCheck).
+    pub white_checks: u8,
+
+    /// Number of checks delivered by Black, counted by [`ThreeCheck`](crate::ThreeCheck).
+    pub black_checks: u8,
+
+    /// Pieces in hand for each color, indexed by color then piece (pawn to
+    /// queen) (used by Crazyhouse).
+    pub pockets: [[u8; 5]; 2],
+
+    /// Squares holding promoted pieces, which demote to pawns when captured
+    /// (used by Crazyhouse).
+    pub promoted: Bitboard,
+
+    /// Zobrist hash, updated incrementally as moves are made. Code that
+    /// edits the public fields directly must call
+    /// [`refresh_hash`](Self::refresh_hash) afterwards.
+    pub(crate) hash: u64,
 }
 
 impl Position {
     /// Creates an empty position.
     pub fn empty() -> Self {
-        Position {
+        let mut position = Position {
             pieces: [Bitboard::EMPTY; 6],
             colors: [Bitboard::EMPTY; 2],
             side_to_move: Color::White,
@@ -115,7 +175,14 @@ impl Position {
             en_passant: None,
             halfmove_clock: 0,
             fullmove_number: 1,
-        }
+            white_checks: 0,
+            black_checks: 0,
+            pockets: [[0; 5]; 2],
+            promoted: Bitboard::EMPTY,
+            hash: 0,
+        };
+        position.refresh_hash();
+        position
     }
 
     /// Creates the standard starting position.
@@ -137,6 +204,10 @@ impl Position {
             for c in rank_str.chars() {
                 if let Some(digit) = c.to_digit(10) {
                     file += digit as usize;
+                } else if c == '~' {
+                    // Crazyhouse marker: the previous piece was promoted
+                    let sq = unsafe { Square::from_index_unchecked((rank * 8 + file - 1) as u8) };
+                    position.promoted.set(sq);
                 } else if let Some((piece, color)) = Piece::from_fen_char(c) {
                     let sq = unsafe { Square::from_index_unchecked((rank * 8 + file) as u8) };
                     position.pieces[piece.index()].set(sq);
@@ -153,7 +224,7 @@ impl Position {
             _ => unreachable!("FEN parser validated this"),
         };
 
-        // Castling rights
+        // Castling rights, either KQkq or Shredder-FEN rook files
         let mut castling = 0u8;
         for c in parsed.castling.chars() {
             match c {
@@ -161,7 +232,21 @@ impl Position {
                 'Q' => castling |= CastlingRights::WHITE_QUEENSIDE,
                 'k' => castling |= CastlingRights::BLACK_KINGSIDE,
                 'q' => castling |= CastlingRights::BLACK_QUEENSIDE,
-                '-' => {}
+                'A'..='H' | 'a'..='h' => {
+                    let color = if c.is_ascii_uppercase() {
+                        Color::White
+                    } else {
+                        Color::Black
+                    };
+                    let rook_file = c.to_ascii_lowercase() as u8 - b'a';
+                    let kingside = rook_file > position.king_file(color);
+                    castling |= match (color, kingside) {
+                        (Color::White, true) => CastlingRights::WHITE_KINGSIDE,
+                        (Color::White, false) => CastlingRights::WHITE_QUEENSIDE,
+                        (Color::Black, true) => CastlingRights::BLACK_KINGSIDE,
+                        (Color::Black, false) => CastlingRights::BLACK_QUEENSIDE,
+                    };
+                }
                 _ => {}
             }
         }
@@ -177,35 +262,32 @@ impl Position {
         position.halfmove_clock = parsed.halfmove_clock;
         position.fullmove_number = parsed.fullmove_number;
 
+        // Three-check counters
+        if let Some((white, black)) = parsed.check_counts {
+            position.white_checks = white;
+            position.black_checks = black;
+        }
+
+        // Crazyhouse pockets
+        if let Some(pocket) = &parsed.pocket {
+            for c in pocket.chars() {
+                if let Some((piece, color)) = Piece::from_fen_char(c) {
+                    position.add_to_pocket(color, piece);
+                }
+            }
+        }
+
+        position.refresh_hash();
         Ok(position)
     }
 
     /// Converts the position to a FEN string.
+    ///
+    /// Check counters are not included; use
+    /// [`to_fen_with_checks`](Self::to_fen_with_checks) for Three-check positions.
     pub fn to_fen(&self) -> String {
-        let mut fen = String::new();
-
         // Piece placement
-        for rank in (0..8).rev() {
-            let mut empty_count = 0;
-            for file in 0..8 {
-                let sq = unsafe { Square::from_index_unchecked(rank * 8 + file) };
-                if let Some((piece, color)) = self.piece_at(sq) {
-                    if empty_count > 0 {
-                        fen.push_str(&empty_count.to_string());
-                        empty_count = 0;
-                    }
-                    fen.push(piece.to_fen_char(color));
-                } else {
-                    empty_count += 1;
-                }
-            }
-            if empty_count > 0 {
-                fen.push_str(&empty_count.to_string());
-            }
-            if rank > 0 {
-                fen.push('/');
-            }
-        }
+        let mut fen = self.piece_placement(false);
 
         // Active color
         fen.push(' ');
@@ -216,22 +298,7 @@ impl Position {
 
         // Castling
         fen.push(' ');
-        if self.castling.raw() == 0 {
-            fen.push('-');
-        } else {
-            if self.castling.can_castle_kingside(Color::White) {
-                fen.push('K');
-            }
-            if self.castling.can_castle_queenside(Color::White) {
-                fen.push('Q');
-            }
-            if self.castling.can_castle_kingside(Color::Black) {
-                fen.push('k');
-            }
-            if self.castling.can_castle_queenside(Color::Black) {
-                fen.push('q');
-            }
-        }
+        fen.push_str(&self.castling_field(false));
 
         // En passant
         fen.push(' ');
@@ -249,6 +316,207 @@ impl Position {
         fen
     }
 
+    /// Converts the position to a Shredder-FEN string, which names castling
+    /// rights by the castling rook's file (e.g. `HAha`) instead of `KQkq`.
+    ///
+    /// This is the unambiguous notation for Chess960. [`from_fen`](Self::from_fen)
+    /// accepts both forms.
+    pub fn to_shredder_fen(&self) -> String {
+        let fen = self.to_fen();
+        let fields: Vec<&str> = fen.split(' ').collect();
+        format!(
+            "{} {} {} {}",
+            fields[0],
+            fields[1],
+            self.castling_field(true),
+            fields[3..].join(" ")
+        )
+    }
+
+    /// Writes the FEN castling field, as rook files if `shredder` is set.
+    fn castling_field(&self, shredder: bool) -> String {
+        if self.castling.raw() == 0 {
+            return "-".to_string();
+        }
+
+        let mut field = String::new();
+        for color in [Color::White, Color::Black] {
+            for kingside in [true, false] {
+                let allowed = if kingside {
+                    self.castling.can_castle_kingside(color)
+                } else {
+                    self.castling.can_castle_queenside(color)
+                };
+                if !allowed {
+                    continue;
+                }
+                let c = if shredder {
+                    (b'a' + self.castling_rook_file(color, kingside)) as char
+                } else if kingside {
+                    'k'
+                } else {
+                    'q'
+                };
+                field.push(match color {
+                    Color::White => c.to_ascii_uppercase(),
+                    Color::Black => c,
+                });
+            }
+        }
+        field
+    }
+
+    /// Returns the file index of `color`'s king on its back rank, or the
+    /// e-file if it is elsewhere.
+    fn king_file(&self, color: Color) -> u8 {
+        self.pieces_of(Piece::King, color)
+            .into_iter()
+            .find(|sq| sq.rank().index() == color.back_rank())
+            .map_or(4, |sq| sq.file().index())
+    }
+
+    /// Returns the file index of the rook `color` castles with on the given
+    /// side: the outermost back-rank rook beyond the king, or the corner file
+    /// if there is none.
+    fn castling_rook_file(&self, color: Color, kingside: bool) -> u8 {
+        let king_file = self.king_file(color);
+        let rook_files = self
+            .pieces_of(Piece::Rook, color)
+            .into_iter()
+            .filter(|sq| sq.rank().index() == color.back_rank())
+            .map(|sq| sq.file().index());
+        if kingside {
+            rook_files.filter(|&f| f > king_file).max().unwrap_or(7)
+        } else {
+            rook_files.filter(|&f| f < king_file).min().unwrap_or(0)
+        }
+    }
+
+    /// Converts the position to a FEN string in the Crazyhouse format, with
+    /// the pocket in brackets after the piece placement and promoted pieces
+    /// marked with `~`.
+    pub fn to_fen_with_pockets(&self) -> String {
+        let mut placement = self.piece_placement(true);
+
+        placement.push('[');
+        for color in [Color::White, Color::Black] {
+            for piece in &Piece::ALL[..5] {
+                for _ in 0..self.pocket_count(color, *piece) {
+                    placement.push(piece.to_fen_char(color));
+                }
+            }
+        }
+        placement.push(']');
+
+        let fen = self.to_fen();
+        let (_, rest) = fen.split_once(' ').expect("FEN has several fields");
+        format!("{} {}", placement, rest)
+    }
+
+    /// Writes the FEN piece placement field, optionally marking promoted pieces.
+    fn piece_placement(&self, mark_promoted: bool) -> String {
+        let mut placement = String::new();
+        for rank in (0..8).rev() {
+            let mut empty_count = 0;
+            for file in 0..8 {
+                let sq = unsafe { Square::from_index_unchecked(rank * 8 + file) };
+                if let Some((piece, color)) = self.piece_at(sq) {
+                    if empty_count > 0 {
+                        placement.push_str(&empty_count.to_string());
+                        empty_count = 0;
+                    }
+                    placement.push(piece.to_fen_char(color));
+                    if mark_promoted && self.promoted.contains(sq) {
+                        placement.push('~');
+                    }
+                } else {
+                    empty_count += 1;
+                }
+            }
+            if empty_count > 0 {
+                placement.push_str(&empty_count.to_string());
+            }
+            if rank > 0 {
+                placement.push('/');
+            }
+        }
+        placement
+    }
+
+    /// Returns how many pieces of the given type `color` holds in its pocket.
+    ///
+    /// Always zero for the king.
+    #[inline]
+    pub fn pocket_count(&self, color: Color, piece: Piece) -> u8 {
+        match piece {
+            Piece::King => 0,
+            _ => self.pockets[color.index()][piece.index()],
+        }
+    }
+
+    /// Adds a piece to `color`'s pocket. Kings are ignored.
+    #[inline]
+    pub fn add_to_pocket(&mut self, color: Color, piece: Piece) {
+        use crate::zobrist::ZOBRIST;
+
+        if piece != Piece::King {
+            let count = self.pockets[color.index()][piece.index()];
+            let new_count = count.saturating_add(1);
+            self.pockets[color.index()][piece.index()] = new_count;
+            self.hash ^= ZOBRIST.pocket_key(color, piece, count)
+                ^ ZOBRIST.pocket_key(color, piece, new_count);
+        }
+    }
+
+    /// Removes a piece from `color`'s pocket, returning false if none was held.
+    #[inline]
+    pub fn remove_from_pocket(&mut self, color: Color, piece: Piece) -> bool {
+        use crate::zobrist::ZOBRIST;
+
+        let count = self.pocket_count(color, piece);
+        if count == 0 {
+            return false;
+        }
+        self.pockets[color.index()][piece.index()] = count - 1;
+        self.hash ^=
+            ZOBRIST.pocket_key(color, piece, count) ^ ZOBRIST.pocket_key(color, piece, count - 1);
+        true
+    }
+
+    /// Counts a check delivered by `color` (used by Three-check).
+    #[inline]
+    pub fn add_check(&mut self, color: Color) {
+        use crate::zobrist::ZOBRIST;
+
+        let checks = match color {
+            Color::White => &mut self.white_checks,
+            Color::Black => &mut self.black_checks,
+        };
+        let count = *checks;
+        *checks = count.saturating_add(1);
+        self.hash ^= ZOBRIST.checks_key(color, count) ^ ZOBRIST.checks_key(color, *checks);
+    }
+
+    /// Converts the position to a FEN string with the Three-check `+N+M`
+    /// extension appended.
+    pub fn to_fen_with_checks(&self) -> String {
+        format!(
+            "{} +{}+{}",
+            self.to_fen(),
+            self.white_checks,
+            self.black_checks
+        )
+    }
+
+    /// Returns the number of checks delivered by the given color.
+    #[inline]
+    pub fn checks_given(&self, color: Color) -> u8 {
+        match color {
+            Color::White => self.white_checks,
+            Color::Black => self.black_checks,
+        }
+    }
+
     /// Returns the piece and color at the given square, if any.
     pub fn piece_at(&self, sq: Square) -> Option<(Piece, Color)> {
         let bb = Bitboard::from_square(sq);
@@ -290,12 +558,73 @@ impl Position {
         self.pieces[piece.index()] & self.colors[color.index()]
     }
 
-    /// Computes the Zobrist hash for this position.
+    /// Returns the number of pieces of each type that `color` has on the
+    /// board. Pieces in Crazyhouse pockets are not included.
+    pub fn material_count(&self, color: Color) -> MaterialCount {
+        let count = |piece| self.pieces_of(piece, color).count();
+        MaterialCount {
+            pawns: count(Piece::Pawn),
+            knights: count(Piece::Knight),
+            bishops: count(Piece::Bishop),
+            rooks: count(Piece::Rook),
+            queens: count(Piece::Queen),
+        }
+    }
+
+    /// Returns the value in centipawns of `color`'s knights, bishops, rooks
+    /// and queens, using the piece values from [`eval`](crate::eval).
+    pub fn non_pawn_material(&self, color: Color) -> i32 {
+        use crate::eval::{BISHOP_VALUE, KNIGHT_VALUE, QUEEN_VALUE, ROOK_VALUE};
+
+        let material = self.material_count(color);
+        material.knights as i32 * KNIGHT_VALUE
+            + material.bishops as i32 * BISHOP_VALUE
+            + material.rooks as i32 * ROOK_VALUE
+            + material.queens as i32 * QUEEN_VALUE
+    }
+
+    /// Returns the position after the side to move passes.
+    ///
+    /// The side to move flips and the en passant square is cleared; pieces
+    /// and castling rights are unchanged. A null move is not legal chess but
+    /// is used by searches for null-move pruning, so it must not be played
+    /// while in check.
+    pub fn null_move(&self) -> Position {
+        use crate::zobrist::{en_passant_hash, ZOBRIST};
+
+        let mut position = self.clone();
+        position.hash ^= en_passant_hash(self.en_passant) ^ ZOBRIST.black_to_move;
+        position.en_passant = None;
+        position.halfmove_clock += 1;
+        if self.side_to_move == Color::Black {
+            position.fullmove_number += 1;
+        }
+        position.side_to_move = self.side_to_move.opposite();
+        position
+    }
+
+    /// Returns the Zobrist hash for this position.
     ///
     /// The hash uniquely identifies the position (with very high probability)
-    /// and is used for repetition detection.
+    /// and is used for repetition detection. It covers the pieces, side to
+    /// move, castling rights, en passant file, pockets and checks delivered,
+    /// but not which pieces were promoted.
+    ///
+    /// The hash is kept up to date by [`make_move`](crate::movegen::make_move)
+    /// and the pocket and check methods; after editing the public fields
+    /// directly, call [`refresh_hash`](Self::refresh_hash).
     pub fn zobrist_hash(&self) -> u64 {
-        use crate::zobrist::ZOBRIST;
+        self.hash
+    }
+
+    /// Recomputes the cached Zobrist hash from the position's fields.
+    pub fn refresh_hash(&mut self) {
+        self.hash = self.compute_zobrist_hash();
+    }
+
+    /// Computes the Zobrist hash for this position from scratch.
+    pub fn compute_zobrist_hash(&self) -> u64 {
+        use crate::zobrist::{castling_hash, en_passant_hash, pockets_hash, ZOBRIST};
 
         let mut hash = 0u64;
 
@@ -314,24 +643,11 @@ impl Position {
             hash ^= ZOBRIST.black_to_move;
         }
 
-        // Hash castling rights
-        if self.castling.can_castle_kingside(Color::White) {
-            hash ^= ZOBRIST.castling_key(0);
-        }
-        if self.castling.can_castle_queenside(Color::White) {
-            hash ^= ZOBRIST.castling_key(1);
-        }
-        if self.castling.can_castle_kingside(Color::Black) {
-            hash ^= ZOBRIST.castling_key(2);
-        }
-        if self.castling.can_castle_queenside(Color::Black) {
-            hash ^= ZOBRIST.castling_key(3);
-        }
-
-        // Hash en passant file
-        if let Some(ep_square) = self.en_passant {
-            hash ^= ZOBRIST.en_passant_key(ep_square.file().index() as usize);
-        }
+        hash ^= castling_hash(self.castling);
+        hash ^= en_passant_hash(self.en_passant);
+        hash ^= pockets_hash(&self.pockets);
+        hash ^= ZOBRIST.checks_key(Color::White, self.white_checks);
+        hash ^= ZOBRIST.checks_key(Color::Black, self.black_checks);
 
         hash
     }
@@ -347,6 +663,38 @@ impl Default for Position {
 mod tests {
     use super::*;
 
+    #[test]
+    fn startpos_material_is_equal() {
+        let pos = Position::startpos();
+        let white = pos.material_count(Color::White);
+        assert_eq!(white, pos.material_count(Color::Black));
+        assert_eq!(
+            white,
+            MaterialCount {
+                pawns: 8,
+                knights: 2,
+                bishops: 2,
+                rooks: 2,
+                queens: 1,
+            }
+        );
+        assert_eq!(white.count(Piece::King), 0);
+        assert_eq!(white.minor_pieces(), 4);
+        assert_eq!(white.major_pieces(), 3);
+        // 2 * 320 + 2 * 330 + 2 * 500 + 900
+        assert_eq!(pos.non_pawn_material(Color::White), 3200);
+        assert_eq!(pos.non_pawn_material(Color::Black), 3200);
+    }
+
+    #[test]
+    fn material_counts_each_side_separately() {
+        let pos = Position::from_fen("4k3/pp6/8/8/8/8/3Q4/1N2K2R w K - 0 1").unwrap();
+        assert_eq!(pos.material_count(Color::White).count(Piece::Knight), 1);
+        assert_eq!(pos.material_count(Color::Black).pawns, 2);
+        assert_eq!(pos.non_pawn_material(Color::White), 320 + 500 + 900);
+        assert_eq!(pos.non_pawn_material(Color::Black), 0);
+    }
+
     #[test]
     fn startpos_fen_roundtrip() {
         let pos = Position::startpos();
@@ -360,6 +708,108 @@ mod tests {
         assert_eq!(pos.to_fen(), fen);
     }
 
+    #[test]
+    fn check_counts_fen_roundtrip() {
+        let fen = "rnbqkbnr/ppp2ppp/8/3pp3/4P3/5Q2/PPPP1PPP/RNB1KBNR w KQkq - 0 3 +1+2";
+        let pos = Position::from_fen(fen).unwrap();
+        assert_eq!(pos.white_checks, 1);
+        assert_eq!(pos.black_checks, 2);
+        assert_eq!(pos.checks_given(Color::Black), 2);
+        assert_eq!(pos.to_fen_with_checks(), fen);
+
+        // Plain FEN output omits the counters
+        assert_eq!(
+            pos.to_fen(),
+            "rnbqkbnr/ppp2ppp/8/3pp3/4P3/5Q2/PPPP1PPP/RNB1KBNR w KQkq - 0 3"
+        );
+    }
+
+    #[test]
+    fn pocket_fen_roundtrip() {
+        let fen = "rnbqkb1r/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKB1R[NPn] w KQkq - 0 4";
+        let pos = Position::from_fen(fen).unwrap();
+        assert_eq!(pos.pocket_count(Color::White, Piece::Knight), 1);
+        assert_eq!(pos.pocket_count(Color::White, Piece::Pawn), 1);
+        assert_eq!(pos.pocket_count(Color::Black, Piece::Knight), 1);
+        assert_eq!(pos.pocket_count(Color::Black, Piece::Queen), 0);
+        // Pockets are written in a canonical order
+        assert_eq!(
+            pos.to_fen_with_pockets(),
+            "rnbqkb1r/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKB1R[PNn] w KQkq - 0 4"
+        );
+        assert_eq!(
+            pos.to_fen(),
+            "rnbqkb1r/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKB1R w KQkq - 0 4"
+        );
+
+        let promoted = "4k2Q~/8/8/8/8/8/8/4K3[] b - - 0 1";
+        let pos = Position::from_fen(promoted).unwrap();
+        assert!(pos.promoted.contains(Square::H8));
+        assert_eq!(pos.to_fen_with_pockets(), promoted);
+    }
+
+    #[test]
+    fn pocket_add_remove() {
+        let mut pos = Position::startpos();
+        assert!(!pos.remove_from_pocket(Color::White, Piece::Rook));
+        pos.add_to_pocket(Color::White, Piece::Rook);
+        pos.add_to_pocket(Color::White, Piece::King);
+        assert_eq!(pos.pocket_count(Color::White, Piece::Rook), 1);
+        assert_eq!(pos.pocket_count(Color::White, Piece::King), 0);
+        assert!(pos.remove_from_pocket(Color::White, Piece::Rook));
+        assert_eq!(pos.pocket_count(Color::White, Piece::Rook), 0);
+    }
+
+    #[test]
+    fn hash_covers_pockets_and_checks() {
+        let hash = |fen: &str| Position::from_fen(fen).unwrap().zobrist_hash();
+        let hashes = [
+            hash("4k3/8/8/8/8/8/8/4K3 w - - 0 1"),
+            hash("4k3/8/8/8/8/8/8/4K3[N] w - - 0 1"),
+            hash("4k3/8/8/8/8/8/8/4K3[NN] w - - 0 1"),
+            hash("4k3/8/8/8/8/8/8/4K3[n] w - - 0 1"),
+            hash("4k3/8/8/8/8/8/8/4K3 w - - 0 1 +1+0"),
+            hash("4k3/8/8/8/8/8/8/4K3 w - - 0 1 +0+1"),
+            hash("4k3/8/8/8/8/8/8/4K3 w - - 0 1 +2+0"),
+        ];
+        for (i, a) in hashes.iter().enumerate() {
+            for b in &hashes[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+
+        // Empty pockets and no checks hash like a standard position
+        assert_eq!(hashes[0], hash("4k3/8/8/8/8/8/8/4K3[] w - - 0 1 +0+0"));
+
+        // The pocket and check methods keep the hash up to date
+        let mut pos = Position::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        pos.add_to_pocket(Color::White, Piece::Knight);
+        pos.add_check(Color::Black);
+        assert_eq!(
+            pos.zobrist_hash(),
+            hash("4k3/8/8/8/8/8/8/4K3[N] w - - 0 1 +0+1")
+        );
+        assert!(pos.remove_from_pocket(Color::White, Piece::Knight));
+        assert_eq!(pos.zobrist_hash(), hashes[5]);
+    }
+
+    #[test]
+    fn refresh_hash_after_editing_fields() {
+        let mut pos = Position::startpos();
+        pos.side_to_move = Color::Black;
+        pos.castling = CastlingRights::NONE;
+        assert_ne!(pos.zobrist_hash(), pos.compute_zobrist_hash());
+
+        pos.refresh_hash();
+        let expected =
+            Position::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR b - - 0 1").unwrap();
+        assert_eq!(pos.zobrist_hash(), expected.zobrist_hash());
+        assert_eq!(
+            Position::empty().zobrist_hash(),
+            Position::empty().compute_zobrist_hash()
+        );
+    }
+
     #[test]
     fn piece_at() {
         let pos = Position::startpos();
@@ -471,4 +921,56 @@ mod tests {
         let pos = Position::from_fen(fen).unwrap();
         assert_eq!(pos.side_to_move, Color::Black);
     }
+
+    #[test]
+    fn null_move_passes_the_turn() {
+        let pos = Position::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 5").unwrap();
+        let passed = pos.null_move();
+        assert_eq!(passed.side_to_move, Color::Black);
+        assert_eq!(passed.en_passant, None);
+        assert_eq!(passed.pieces, pos.pieces);
+        assert_eq!(passed.to_fen(), "4k3/8/8/3pP3/8/8/8/4K3 b - - 1 5");
+        assert_eq!(
+            passed.null_move().to_fen(),
+            "4k3/8/8/3pP3/8/8/8/4K3 w - - 2 6"
+        );
+    }
+
+    #[test]
+    fn shredder_fen_roundtrip_chess960() {
+        let fen = "bqnbrkrn/pppppppp/8/8/8/8/PPPPPPPP/BQNBRKRN w GEge - 0 1";
+        let pos = Position::from_fen(fen).unwrap();
+        assert_eq!(pos.castling, CastlingRights::ALL);
+        assert_eq!(pos.to_shredder_fen(), fen);
+        assert_eq!(
+            pos.to_fen(),
+            "bqnbrkrn/pppppppp/8/8/8/8/PPPPPPPP/BQNBRKRN w KQkq - 0 1"
+        );
+
+        // Standard castling notation reads the same rights
+        let from_standard = Position::from_fen(&pos.to_fen()).unwrap();
+        assert_eq!(from_standard.to_shredder_fen(), fen);
+    }
+
+    #[test]
+    fn shredder_fen_partial_rights() {
+        let pos = Position::from_fen("rk2r3/8/8/8/8/8/8/1R2KR2 w Fa - 0 1").unwrap();
+        assert!(pos.castling.can_castle_kingside(Color::White));
+        assert!(!pos.castling.can_castle_queenside(Color::White));
+        assert!(pos.castling.can_castle_queenside(Color::Black));
+        assert!(!pos.castling.can_castle_kingside(Color::Black));
+        assert_eq!(pos.to_shredder_fen(), "rk2r3/8/8/8/8/8/8/1R2KR2 w Fa - 0 1");
+        assert_eq!(pos.to_fen(), "rk2r3/8/8/8/8/8/8/1R2KR2 w Kq - 0 1");
+    }
+
+    #[test]
+    fn shredder_fen_standard_position() {
+        let pos = Position::startpos();
+        assert_eq!(
+            pos.to_shredder_fen(),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0 1"
+        );
+        assert_eq!(pos.to_fen(), FenParser::STARTPOS);
+        assert_eq!(Position::from_fen(&pos.to_shredder_fen()).unwrap(), pos);
+    }
 }
@@ -0,0 +1,189 @@
+//! Antichess (giveaway) rules implementation.
+
+use super::{contains_move, DrawReason, GameResult, RuleSet};
+use crate::movegen::{generate_pseudo_legal_moves, make_move};
+use crate::{MoveList, Position};
+use chess_core::{Color, Move, MoveFlag};
+
+/// Antichess, also known as giveaway or losing chess.
+///
+/// The goal is to lose all of your pieces:
+/// - Captures are mandatory when available
+/// - There is no check; the king is an ordinary piece and can be captured
+/// - There is no castling, even from a FEN with castling rights
+/// - Pawns may also promote to a king
+/// - A side wins when it has no pieces left or no legal moves
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Antichess;
+
+impl Antichess {
+    /// The Antichess starting position (standard setup without castling rights).
+    pub const STARTPOS: &'static str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1";
+}
+
+/// Returns true if the move captures a piece.
+fn is_capture(position: &Position, m: Move) -> bool {
+    m.flag() == MoveFlag::EnPassant || position.occupied().contains(m.to())
+}
+
+impl RuleSet for Antichess {
+    fn initial_position(&self) -> Position {
+        Position::from_fen(Self::STARTPOS).expect("STARTPOS is valid")
+    }
+
+    fn generate_moves(&self, position: &Position) -> MoveList {
+        let mut moves = generate_pseudo_legal_moves(position);
+        moves.retain(|m| !m.flag().is_castling());
+
+        // Every queen promotion also stands for a king promotion
+        let king_promotions: Vec<Move> = moves
+            .as_slice()
+            .iter()
+            .filter(|m| m.flag() == MoveFlag::PromoteQueen)
+            .map(|m| Move::new(m.from(), m.to(), MoveFlag::PromoteKing))
+            .collect();
+        for m in king_promotions {
+            moves.push(m);
+        }
+
+        // Captures are compulsory
+        if moves.as_slice().iter().any(|m| is_capture(position, *m)) {
+            moves.retain(|m| is_capture(position, *m));
+        }
+
+        moves
+    }
+
+    fn is_legal(&self, position: &Position, m: Move) -> bool {
+        contains_move(&self.generate_moves(position), m)
+    }
+
+    fn make_move(&self, position: &Position, m: Move) -> Position {
+        make_move(position, m)
+    }
+
+    fn is_check(&self, _position: &Position) -> bool {
+        false
+    }
+
+    fn game_result(&self, position: &Position) -> Option<GameResult> {
+        let to_move_wins = match position.side_to_move {
+            Color::White => GameResult::WhiteWins,
+            Color::Black => GameResult::BlackWins,
+        };
+
+        // Losing every piece, or being stalemated, wins the game
+        if position.colors[position.side_to_move.index()].is_empty() {
+            return Some(to_move_wins);
+        }
+
+        if position.halfmove_clock >= 150 {
+            return Some(GameResult::Draw(DrawReason::SeventyFiveMoveRule));
+        }
+
+        if self.generate_moves(position).is_empty() {
+            return Some(to_move_wins);
+        }
+
+        None
+    }
+
+    fn is_insufficient_material(&self, _position: &Position) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chess_core::{Piece, Square};
+
+    #[test]
+    fn initial_position_has_no_castling() {
+        let pos = Antichess.initial_position();
+        assert_eq!(pos.castling.raw(), 0);
+        assert_eq!(Antichess.generate_moves(&pos).len(), 20);
+        assert!(Antichess.game_result(&pos).is_none());
+    }
+
+    #[test]
+    fn captures_are_forced() {
+        // After 1. e4 d5, exd5 is the only legal move
+        let pos = Position::from_fen("rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w - - 0 2")
+            .unwrap();
+        let moves = Antichess.generate_moves(&pos);
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].to_uci(), "e4d5");
+        assert!(!Antichess.is_legal(&pos, Move::from_uci("g1f3").unwrap()));
+    }
+
+    #[test]
+    fn all_captures_are_offered() {
+        // Both the knight and the rook can capture the queen on d4; the king can take e2
+        let pos = Position::from_fen("8/8/8/8/3q4/1N6/4p3/3RK3 w - - 0 1").unwrap();
+        let mut uci: Vec<String> = Antichess
+            .generate_moves(&pos)
+            .as_slice()
+            .iter()
+            .map(|m| m.to_uci())
+            .collect();
+        uci.sort();
+        assert_eq!(uci, vec!["b3d4", "d1d4", "e1e2"]);
+    }
+
+    #[test]
+    fn king_can_be_captured() {
+        let pos = Position::from_fen("R7/8/8/8/8/8/8/k1K5 w - - 0 1").unwrap();
+        assert!(!Antichess.is_check(&pos));
+
+        let pos = Antichess.make_move(&pos, Move::from_uci("a8a1").unwrap());
+        assert!(pos.pieces_of(Piece::King, Color::Black).is_empty());
+        // Black has lost every piece and therefore wins
+        assert_eq!(Antichess.game_result(&pos), Some(GameResult::BlackWins));
+    }
+
+    #[test]
+    fn king_capture_is_forced() {
+        let pos = Position::from_fen("7k/8/8/8/8/8/r7/K7 w - - 0 1").unwrap();
+        // Kxa2 is the only capture and therefore forced
+        let moves = Antichess.generate_moves(&pos);
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].to_uci(), "a1a2");
+    }
+
+    #[test]
+    fn castling_rights_are_ignored() {
+        let pos = Position::from_fen("r3k2r/2P5/8/8/8/8/P6P/R3K2R w KQkq - 0 1").unwrap();
+        let castles = |moves: &MoveList| moves.as_slice().iter().any(|m| m.flag().is_castling());
+        assert!(castles(&crate::movegen::generate_moves(&pos)));
+        assert!(!castles(&Antichess.generate_moves(&pos)));
+        assert!(!Antichess.is_legal(
+            &pos,
+            Move::new(Square::E1, Square::G1, MoveFlag::CastleKingside)
+        ));
+    }
+
+    #[test]
+    fn pawns_promote_to_kings() {
+        let pos = Position::from_fen("r3k2r/2P5/8/8/8/8/P6P/R3K2R w KQkq - 0 1").unwrap();
+        let promotion = Antichess.generate_moves(&pos).find_uci("c7c8k").unwrap();
+        assert_eq!(promotion.flag(), MoveFlag::PromoteKing);
+
+        let pos = Antichess.make_move(&pos, promotion);
+        assert_eq!(pos.pieces_of(Piece::King, Color::White).count(), 2);
+    }
+
+    #[test]
+    fn every_king_moves() {
+        let pos = Position::from_fen("8/8/8/8/8/8/8/K1k4K w - - 0 1").unwrap();
+        assert_eq!(Antichess.generate_moves(&pos).len(), 6);
+    }
+
+    #[test]
+    fn stalemated_side_wins() {
+        // White pawn blocked by a black pawn: white has no moves and wins
+        let pos = Position::from_fen("8/8/8/8/8/p7/P7/8 w - - 0 1").unwrap();
+        assert!(Antichess.generate_moves(&pos).is_empty());
+        assert_eq!(Antichess.game_result(&pos), Some(GameResult::WhiteWins));
+    }
+}
@@ -0,0 +1,241 @@
+//! Crazyhouse rules implementation.
+
+use super::{contains_move, DrawReason, GameResult, RuleSet, StandardChess};
+use crate::movegen::{generate_moves, is_king_attacked, make_move};
+use crate::{Bitboard, MoveList, Position};
+use chess_core::{Color, Move, MoveFlag, Piece};
+
+/// Crazyhouse chess.
+///
+/// Standard chess rules, plus:
+/// - Captured pieces go to the captor's pocket, with promoted pieces
+///   reverting to pawns
+/// - Instead of moving, a player may drop a piece from their pocket onto
+///   any empty square (pawns not on the first or last rank)
+///
+/// Pockets are stored in [`Position::pockets`] and written to FEN in
+/// brackets after the piece placement (see [`Position::to_fen_with_pockets`]).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Crazyhouse;
+
+impl Crazyhouse {
+    /// The Crazyhouse starting position (standard setup, empty pockets).
+    pub const STARTPOS: &'static str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR[] w KQkq - 0 1";
+}
+
+/// Pieces that can be held in a pocket.
+const POCKET_PIECES: [Piece; 5] = [
+    Piece::Pawn,
+    Piece::Knight,
+    Piece::Bishop,
+    Piece::Rook,
+    Piece::Queen,
+];
+
+/// Adds all legal drop moves to `moves`.
+fn generate_drops(position: &Position, moves: &mut MoveList) {
+    let us = position.side_to_move;
+    let empty = position.empty_squares();
+
+    for piece in POCKET_PIECES {
+        if position.pocket_count(us, piece) == 0 {
+            continue;
+        }
+
+        let targets = match piece {
+            Piece::Pawn => empty & !(Bitboard::RANK_1 | Bitboard::RANK_8),
+            _ => empty,
+        };
+
+        for to in targets {
+            let m = Move::drop(piece, to).expect("pocket pieces can be dropped");
+            // A drop must not leave our king in check
+            if !is_king_attacked(&make_move(position, m), us) {
+                moves.push(m);
+            }
+        }
+    }
+}
+
+impl RuleSet for Crazyhouse {
+    fn initial_position(&self) -> Position {
+        Position::from_fen(Self::STARTPOS).expect("STARTPOS is valid")
+    }
+
+    fn generate_moves(&self, position: &Position) -> MoveList {
+        let mut moves = generate_moves(position);
+        generate_drops(position, &mut moves);
+        moves
+    }
+
+    fn is_legal(&self, position: &Position, m: Move) -> bool {
+        contains_move(&self.generate_moves(position), m)
+    }
+
+    fn make_move(&self, position: &Position, m: Move) -> Position {
+        if m.flag().is_drop() {
+            return make_move(position, m);
+        }
+
+        let us = position.side_to_move;
+        let from = m.from();
+        let to = m.to();
+
+        // Captured piece, demoted to a pawn if it had been promoted
+        let captured = if m.flag() == MoveFlag::EnPassant {
+            Some(Piece::Pawn)
+        } else {
+            position.piece_at(to).map(|(piece, _)| {
+                if position.promoted.contains(to) {
+                    Piece::Pawn
+                } else {
+                    piece
+                }
+            })
+        };
+
+        let mut new_pos = make_move(position, m);
+
+        if let Some(piece) = captured {
+            new_pos.add_to_pocket(us, piece);
+        }
+
+        // Track promoted pieces as they move
+        new_pos.promoted.clear(to);
+        if position.promoted.contains(from) {
+            new_pos.promoted.clear(from);
+            new_pos.promoted.set(to);
+        }
+        if m.flag().is_promotion() {
+            new_pos.promoted.set(to);
+        }
+
+        new_pos
+    }
+
+    fn is_check(&self, position: &Position) -> bool {
+        StandardChess.is_check(position)
+    }
+
+    fn game_result(&self, position: &Position) -> Option<GameResult> {
+        if self.is_insufficient_material(position) {
+            return Some(GameResult::Draw(DrawReason::InsufficientMaterial));
+        }
+
+        if self.generate_moves(position).is_empty() {
+            if self.is_check(position) {
+                return Some(match position.side_to_move {
+                    Color::White => GameResult::BlackWins,
+                    Color::Black => GameResult::WhiteWins,
+                });
+            }
+            return Some(GameResult::Draw(DrawReason::Stalemate));
+        }
+
+        None
+    }
+
+    fn is_insufficient_material(&self, position: &Position) -> bool {
+        // Material never leaves the game, so only bare kings with empty
+        // pockets are a draw
+        position.occupied() == position.pieces[Piece::King.index()]
+            && position.pockets.iter().flatten().all(|&count| count == 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chess_core::Square;
+
+    fn play(position: &Position, uci: &str) -> Position {
+        let m = Move::from_uci(uci).unwrap();
+        assert!(Crazyhouse.is_legal(position, m), "{} should be legal", uci);
+        Crazyhouse.make_move(position, m)
+    }
+
+    #[test]
+    fn initial_position() {
+        let pos = Crazyhouse.initial_position();
+        assert_eq!(pos.to_fen_with_pockets(), Crazyhouse::STARTPOS);
+        assert_eq!(Crazyhouse.generate_moves(&pos).len(), 20);
+        assert!(Crazyhouse.game_result(&pos).is_none());
+    }
+
+    #[test]
+    fn capture_then_drop_knight_mate() {
+        let pos = Position::from_fen("6rk/1p4pp/n7/8/8/8/8/Q3K3 w - - 0 1").unwrap();
+
+        // Qxa6 puts the knight in white's pocket
+        let pos = play(&pos, "a1a6");
+        assert_eq!(pos.pocket_count(Color::White, Piece::Knight), 1);
+        let pos = play(&pos, "b7b5");
+
+        // N@f7 is a smothered mate
+        let pos = play(&pos, "N@f7");
+        assert_eq!(pos.pocket_count(Color::White, Piece::Knight), 0);
+        assert_eq!(
+            pos.piece_at(Square::from_algebraic("f7").unwrap()),
+            Some((Piece::Knight, Color::White))
+        );
+        assert!(Crazyhouse.is_check(&pos));
+        assert_eq!(Crazyhouse.game_result(&pos), Some(GameResult::WhiteWins));
+    }
+
+    #[test]
+    fn drops_only_with_pocket_pieces() {
+        let pos = Position::from_fen("4k3/8/8/8/8/8/8/4K3[N] w - - 0 1").unwrap();
+        assert!(Crazyhouse.is_legal(&pos, Move::from_uci("N@d4").unwrap()));
+        assert!(!Crazyhouse.is_legal(&pos, Move::from_uci("P@d4").unwrap()));
+        assert!(!Crazyhouse.is_legal(&pos, Move::from_uci("N@e8").unwrap()));
+        // 5 king moves + 62 knight drops
+        assert_eq!(Crazyhouse.generate_moves(&pos).len(), 67);
+    }
+
+    #[test]
+    fn pawns_cannot_be_dropped_on_back_ranks() {
+        let pos = Position::from_fen("4k3/8/8/8/8/8/8/4K3[P] w - - 0 1").unwrap();
+        assert!(Crazyhouse.is_legal(&pos, Move::from_uci("P@a2").unwrap()));
+        assert!(!Crazyhouse.is_legal(&pos, Move::from_uci("P@a1").unwrap()));
+        assert!(!Crazyhouse.is_legal(&pos, Move::from_uci("P@a8").unwrap()));
+    }
+
+    #[test]
+    fn drop_can_block_check() {
+        let pos = Position::from_fen("4k3/8/8/8/8/8/8/K3R3[n] b - - 0 1").unwrap();
+        assert!(Crazyhouse.is_check(&pos));
+        assert!(Crazyhouse.is_legal(&pos, Move::from_uci("n@e5").unwrap()));
+        assert!(!Crazyhouse.is_legal(&pos, Move::from_uci("n@a5").unwrap()));
+    }
+
+    #[test]
+    fn captured_promoted_piece_becomes_pawn() {
+        let pos = Position::from_fen("3qk3/4P3/8/8/8/8/8/4K3[] w - - 0 1").unwrap();
+
+        // e7xd8=Q, and the black king recaptures the promoted queen
+        let pos = play(&pos, "e7d8q");
+        assert!(pos.promoted.contains(Square::D8));
+        assert_eq!(pos.pocket_count(Color::White, Piece::Queen), 1);
+        assert_eq!(
+            pos.to_fen_with_pockets(),
+            "3Q~k3/8/8/8/8/8/8/4K3[Q] b - - 0 1"
+        );
+
+        let pos = play(&pos, "e8d8");
+        assert_eq!(pos.pocket_count(Color::Black, Piece::Pawn), 1);
+        assert_eq!(pos.pocket_count(Color::Black, Piece::Queen), 0);
+        assert!(pos.promoted.is_empty());
+    }
+
+    #[test]
+    fn bare_kings_with_empty_pockets_draw() {
+        let pos = Position::from_fen("4k3/8/8/8/8/8/8/4K3[] w - - 0 1").unwrap();
+        assert_eq!(
+            Crazyhouse.game_result(&pos),
+            Some(GameResult::Draw(DrawReason::InsufficientMaterial))
+        );
+
+        let pos = Position::from_fen("4k3/8/8/8/8/8/8/4K3[p] w - - 0 1").unwrap();
+        assert!(Crazyhouse.game_result(&pos).is_none());
+    }
+}
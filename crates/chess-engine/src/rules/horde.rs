@@ -0,0 +1,153 @@
+//! Horde rules implementation.
+
+use super::{contains_move, DrawReason, GameResult, RuleSet};
+use crate::movegen::{generate_moves, is_king_attacked, make_move};
+use crate::{Bitboard, MoveList, Position};
+use chess_core::{Color, Move, Piece, Square};
+
+/// Horde chess.
+///
+/// White plays with a horde of 36 pawns and no king against the standard
+/// black army:
+/// - White pawns on the first rank may advance one or two squares
+/// - Black wins by capturing every white piece
+/// - White wins by checkmating black
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Horde;
+
+impl Horde {
+    /// The Horde starting position.
+    pub const STARTPOS: &'static str =
+        "rnbqkbnr/pppppppp/8/1PP2PP1/PPPPPPPP/PPPPPPPP/PPPPPPPP/PPPPPPPP w kq - 0 1";
+}
+
+impl RuleSet for Horde {
+    fn initial_position(&self) -> Position {
+        Position::from_fen(Self::STARTPOS).expect("STARTPOS is valid")
+    }
+
+    fn generate_moves(&self, position: &Position) -> MoveList {
+        let mut moves = generate_moves(position);
+
+        // First-rank horde pawns may also move two squares. These pushes do
+        // not create an en passant square.
+        if position.side_to_move == Color::White {
+            let empty = position.empty_squares();
+            let pawns = position.pieces_of(Piece::Pawn, Color::White) & Bitboard::RANK_1;
+            let targets = (pawns.north() & empty).north() & empty;
+            for to in targets {
+                let from = unsafe { Square::from_index_unchecked(to.index() - 16) };
+                moves.push(Move::normal(from, to));
+            }
+        }
+
+        moves
+    }
+
+    fn is_legal(&self, position: &Position, m: Move) -> bool {
+        contains_move(&self.generate_moves(position), m)
+    }
+
+    fn make_move(&self, position: &Position, m: Move) -> Position {
+        make_move(position, m)
+    }
+
+    fn is_check(&self, position: &Position) -> bool {
+        is_king_attacked(position, position.side_to_move)
+    }
+
+    fn game_result(&self, position: &Position) -> Option<GameResult> {
+        // The horde loses once every white piece is gone
+        if position.colors[Color::White.index()].is_empty() {
+            return Some(GameResult::BlackWins);
+        }
+
+        if position.halfmove_clock >= 150 {
+            return Some(GameResult::Draw(DrawReason::SeventyFiveMoveRule));
+        }
+
+        if self.generate_moves(position).is_empty() {
+            if self.is_check(position) {
+                return Some(match position.side_to_move {
+                    Color::White => GameResult::BlackWins,
+                    Color::Black => GameResult::WhiteWins,
+                });
+            }
+            return Some(GameResult::Draw(DrawReason::Stalemate));
+        }
+
+        None
+    }
+
+    fn is_insufficient_material(&self, _position: &Position) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn initial_position() {
+        let pos = Horde.initial_position();
+        assert_eq!(pos.to_fen(), Horde::STARTPOS);
+        assert_eq!(pos.pieces_of(Piece::Pawn, Color::White).count(), 36);
+        assert!(pos.pieces_of(Piece::King, Color::White).is_empty());
+        assert_eq!(pos.colors[Color::Black.index()].count(), 16);
+        assert!(Horde.game_result(&pos).is_none());
+    }
+
+    #[test]
+    fn starting_moves() {
+        // Only the front pawns on ranks 4 and 5 can move
+        let pos = Horde.initial_position();
+        let moves = Horde.generate_moves(&pos);
+        assert_eq!(moves.len(), 8);
+        assert!(moves
+            .as_slice()
+            .iter()
+            .all(|m| pos.piece_at(m.from()) == Some((Piece::Pawn, Color::White))));
+    }
+
+    #[test]
+    fn black_starting_moves_are_standard() {
+        let pos = Horde.initial_position();
+        let pos = Horde.make_move(&pos, Move::from_uci("b5b6").unwrap());
+        // a7xb6 and c7xb6 in addition to the usual pawn and knight moves
+        let moves = Horde.generate_moves(&pos);
+        assert!(Horde.is_legal(&pos, Move::from_uci("a7b6").unwrap()));
+        assert!(Horde.is_legal(&pos, Move::from_uci("c7b6").unwrap()));
+        assert!(Horde.is_legal(&pos, Move::from_uci("g8f6").unwrap()));
+        assert!(moves.len() > 2);
+    }
+
+    #[test]
+    fn first_rank_pawn_double_push() {
+        let pos = Position::from_fen("4k3/8/8/8/8/8/8/P7 w - - 0 1").unwrap();
+        assert!(Horde.is_legal(&pos, Move::from_uci("a1a2").unwrap()));
+        assert!(Horde.is_legal(&pos, Move::from_uci("a1a3").unwrap()));
+        assert_eq!(Horde.generate_moves(&pos).len(), 2);
+
+        let pos = Horde.make_move(&pos, Move::from_uci("a1a3").unwrap());
+        assert_eq!(pos.en_passant, None);
+
+        // Blocked on the second rank
+        let pos = Position::from_fen("4k3/8/8/8/8/8/n7/P7 w - - 0 1").unwrap();
+        assert!(!Horde.is_legal(&pos, Move::from_uci("a1a3").unwrap()));
+    }
+
+    #[test]
+    fn black_wins_when_horde_is_captured() {
+        let pos = Position::from_fen("4k3/8/8/8/8/8/8/8 w - - 0 1").unwrap();
+        assert_eq!(Horde.game_result(&pos), Some(GameResult::BlackWins));
+    }
+
+    #[test]
+    fn white_wins_by_checkmate() {
+        // g7 gives check, h7 covers g8, and f6/g6 defend the checking pawns
+        let pos = Position::from_fen("7k/6PP/5PP1/8/8/8/8/8 b - - 0 1").unwrap();
+        assert!(Horde.is_check(&pos));
+        assert_eq!(Horde.game_result(&pos), Some(GameResult::WhiteWins));
+    }
+}
@@ -4,9 +4,17 @@
 //! chess variants. The engine is rule-agnostic - it delegates game-specific
 //! logic to the active rule set.
 
+mod antichess;
+mod crazyhouse;
+mod horde;
 mod standard;
+mod three_check;
 
+pub use antichess::Antichess;
+pub use crazyhouse::Crazyhouse;
+pub use horde::Horde;
 pub use standard::StandardChess;
+pub use three_check::{ThreeCheck, CHECKS_TO_WIN};
 
 use crate::{MoveList, Position};
 use chess_core::Move;
@@ -101,3 +109,17 @@ pub trait RuleSet {
     /// Returns true if neither side has sufficient material to checkmate.
     fn is_insufficient_material(&self, position: &Position) -> bool;
 }
+
+/// Returns true if `m` matches one of the generated `moves`.
+///
+/// Compares by from/to squares plus promotion and drop piece only, because
+/// `Move::from_uci` doesn't set proper flags (e.g., DoublePush, EnPassant,
+/// Castling) - it defaults to Normal.
+fn contains_move(moves: &MoveList, m: Move) -> bool {
+    moves.as_slice().iter().any(|legal| {
+        legal.from() == m.from()
+            && legal.to() == m.to()
+            && legal.flag().promotion_piece() == m.flag().promotion_piece()
+            && legal.flag().drop_piece() == m.flag().drop_piece()
+    })
+}
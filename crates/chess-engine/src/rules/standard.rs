@@ -1,6 +1,6 @@
 //! Standard chess rules implementation.
 
-use super::{DrawReason, GameResult, RuleSet};
+use super::{contains_move, DrawReason, GameResult, RuleSet};
 use crate::movegen::{generate_moves, is_king_attacked, make_move};
 use crate::{Bitboard, MoveList, Position};
 use chess_core::{Color, Move, Piece};
@@ -27,15 +27,7 @@ impl RuleSet for StandardChess {
     }
 
     fn is_legal(&self, position: &Position, m: Move) -> bool {
-        // Compare by from/to squares and promotion piece only.
-        // This is needed because Move::from_uci doesn't set proper flags
-        // (e.g., DoublePush, EnPassant, Castling) - it defaults to Normal.
-        let moves = self.generate_moves(position);
-        moves.as_slice().iter().any(|legal| {
-            legal.from() == m.from()
-                && legal.to() == m.to()
-                && legal.flag().promotion_piece() == m.flag().promotion_piece()
-        })
+        contains_move(&self.generate_moves(position), m)
     }
 
     fn make_move(&self, position: &Position, m: Move) -> Position {
@@ -74,32 +66,26 @@ impl RuleSet for StandardChess {
         None
     }
 
+    /// Returns true only for dead positions, where no sequence of legal
+    /// moves can end in checkmate: K vs K, K+N vs K, K+B vs K, and K+B vs
+    /// K+B with both bishops on the same color.
+    ///
+    /// Material that can mate only with the defender's help is not a draw.
+    /// K+N+N vs K cannot force mate, but a mate is possible, so under FIDE
+    /// rules it is played on; the same goes for K+N vs K+N and K+B vs K+N.
     fn is_insufficient_material(&self, position: &Position) -> bool {
-        // Count all pieces for each side
-        let white_pawns = position.pieces_of(Piece::Pawn, Color::White).count();
-        let black_pawns = position.pieces_of(Piece::Pawn, Color::Black).count();
-        let white_knights = position.pieces_of(Piece::Knight, Color::White).count();
-        let black_knights = position.pieces_of(Piece::Knight, Color::Black).count();
-        let white_bishops = position.pieces_of(Piece::Bishop, Color::White);
-        let black_bishops = position.pieces_of(Piece::Bishop, Color::Black);
-        let white_rooks = position.pieces_of(Piece::Rook, Color::White).count();
-        let black_rooks = position.pieces_of(Piece::Rook, Color::Black).count();
-        let white_queens = position.pieces_of(Piece::Queen, Color::White).count();
-        let black_queens = position.pieces_of(Piece::Queen, Color::Black).count();
+        let white = position.material_count(Color::White);
+        let black = position.material_count(Color::Black);
 
         // If any pawns, rooks, or queens exist, not insufficient
-        if white_pawns > 0
-            || black_pawns > 0
-            || white_rooks > 0
-            || black_rooks > 0
-            || white_queens > 0
-            || black_queens > 0
-        {
+        if white.pawns + white.major_pieces() + black.pawns + black.major_pieces() > 0 {
             return false;
         }
 
-        let white_bishop_count = white_bishops.count();
-        let black_bishop_count = black_bishops.count();
+        let white_knights = white.knights;
+        let black_knights = black.knights;
+        let white_bishop_count = white.bishops;
+        let black_bishop_count = black.bishops;
 
         // K vs K
         if white_knights == 0
@@ -129,6 +115,8 @@ impl RuleSet for StandardChess {
 
             // K+B vs K+B with bishops on same color
             if white_bishop_count == 1 && black_bishop_count == 1 {
+                let white_bishops = position.pieces_of(Piece::Bishop, Color::White);
+                let black_bishops = position.pieces_of(Piece::Bishop, Color::Black);
                 let white_on_light = (white_bishops & Bitboard::LIGHT_SQUARES).is_not_empty();
                 let black_on_light = (black_bishops & Bitboard::LIGHT_SQUARES).is_not_empty();
                 if white_on_light == black_on_light {
@@ -261,6 +249,26 @@ mod tests {
         // Two knights can technically checkmate (with opponent cooperation)
         let pos = Position::from_fen("8/8/8/8/8/8/8/3NKN1k w - - 0 1").unwrap();
         assert!(!StandardChess.is_insufficient_material(&pos));
+        assert_eq!(StandardChess.game_result(&pos), None);
+    }
+
+    #[test]
+    fn insufficient_material_minor_piece_for_black() {
+        let pos = Position::from_fen("4k1n1/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        assert!(StandardChess.is_insufficient_material(&pos));
+
+        let pos = Position::from_fen("4k1b1/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        assert!(StandardChess.is_insufficient_material(&pos));
+    }
+
+    #[test]
+    fn sufficient_material_minor_piece_each() {
+        // A cornered king can be mated by the other side's minor piece
+        let pos = Position::from_fen("4k1n1/8/8/8/8/8/8/4KN2 w - - 0 1").unwrap();
+        assert!(!StandardChess.is_insufficient_material(&pos));
+
+        let pos = Position::from_fen("4k1n1/8/8/8/8/8/8/4KB2 w - - 0 1").unwrap();
+        assert!(!StandardChess.is_insufficient_material(&pos));
     }
 
     #[test]
@@ -0,0 +1,157 @@
+//! Three-check rules implementation.
+
+use super::{DrawReason, GameResult, RuleSet, StandardChess};
+use crate::movegen::is_king_attacked;
+use crate::{MoveList, Position};
+use chess_core::{Color, Move, Piece};
+
+/// Number of checks needed to win a Three-check game.
+pub const CHECKS_TO_WIN: u8 = 3;
+
+/// Three-check chess.
+///
+/// Standard chess rules, with one extra win condition: the first side to
+/// deliver three checks wins the game. The number of checks given by each
+/// side is tracked in [`Position::white_checks`] and [`Position::black_checks`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ThreeCheck;
+
+impl RuleSet for ThreeCheck {
+    fn initial_position(&self) -> Position {
+        Position::startpos()
+    }
+
+    fn generate_moves(&self, position: &Position) -> MoveList {
+        StandardChess.generate_moves(position)
+    }
+
+    fn is_legal(&self, position: &Position, m: Move) -> bool {
+        StandardChess.is_legal(position, m)
+    }
+
+    fn make_move(&self, position: &Position, m: Move) -> Position {
+        let mut new_pos = StandardChess.make_move(position, m);
+
+        // Count the check, if the move gave one
+        let us = position.side_to_move;
+        if is_king_attacked(&new_pos, us.opposite()) {
+            new_pos.add_check(us);
+        }
+        new_pos
+    }
+
+    fn is_check(&self, position: &Position) -> bool {
+        StandardChess.is_check(position)
+    }
+
+    fn game_result(&self, position: &Position) -> Option<GameResult> {
+        if position.white_checks >= CHECKS_TO_WIN {
+            return Some(GameResult::WhiteWins);
+        }
+        if position.black_checks >= CHECKS_TO_WIN {
+            return Some(GameResult::BlackWins);
+        }
+
+        if position.halfmove_clock >= 150 {
+            return Some(GameResult::Draw(DrawReason::SeventyFiveMoveRule));
+        }
+
+        if self.is_insufficient_material(position) {
+            return Some(GameResult::Draw(DrawReason::InsufficientMaterial));
+        }
+
+        if self.generate_moves(position).is_empty() {
+            if self.is_check(position) {
+                return Some(match position.side_to_move {
+                    Color::White => GameResult::BlackWins,
+                    Color::Black => GameResult::WhiteWins,
+                });
+            }
+            return Some(GameResult::Draw(DrawReason::Stalemate));
+        }
+
+        None
+    }
+
+    fn is_insufficient_material(&self, position: &Position) -> bool {
+        // Any piece can still deliver checks, so only bare kings are a draw
+        let kings = position.pieces[Piece::King.index()];
+        position.occupied() == kings
+            && position.pieces_of(Piece::King, Color::White).is_not_empty()
+            && position.pieces_of(Piece::King, Color::Black).is_not_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn play(position: &Position, uci: &str) -> Position {
+        let m = Move::from_uci(uci).unwrap();
+        assert!(ThreeCheck.is_legal(position, m), "{} should be legal", uci);
+        ThreeCheck.make_move(position, m)
+    }
+
+    #[test]
+    fn initial_position() {
+        let pos = ThreeCheck.initial_position();
+        assert_eq!(pos.white_checks, 0);
+        assert_eq!(pos.black_checks, 0);
+        assert!(ThreeCheck.game_result(&pos).is_none());
+    }
+
+    #[test]
+    fn three_checks_win() {
+        let pos = Position::from_fen("7k/8/8/8/8/8/8/R3K3 w - - 0 1").unwrap();
+
+        let pos = play(&pos, "a1a8");
+        assert_eq!(pos.white_checks, 1);
+        assert!(ThreeCheck.game_result(&pos).is_none());
+        let pos = play(&pos, "h8h7");
+
+        let pos = play(&pos, "a8a7");
+        assert_eq!(pos.white_checks, 2);
+        assert!(ThreeCheck.game_result(&pos).is_none());
+        let pos = play(&pos, "h7g6");
+
+        let pos = play(&pos, "a7a6");
+        assert_eq!(pos.white_checks, 3);
+        assert_eq!(pos.black_checks, 0);
+        assert_eq!(ThreeCheck.game_result(&pos), Some(GameResult::WhiteWins));
+        assert!(ThreeCheck.is_game_over(&pos));
+
+        // The same position is still in progress under standard rules
+        assert!(StandardChess.game_result(&pos).is_none());
+    }
+
+    #[test]
+    fn black_wins_with_counters_from_fen() {
+        let pos = Position::from_fen("4k3/8/8/8/8/8/8/4K2r w - - 0 1 +0+3").unwrap();
+        assert_eq!(ThreeCheck.game_result(&pos), Some(GameResult::BlackWins));
+    }
+
+    #[test]
+    fn non_checking_move_keeps_counters() {
+        let pos = Position::from_fen("7k/8/8/8/8/8/8/R3K3 w - - 0 1 +2+1").unwrap();
+        let pos = play(&pos, "a1a2");
+        assert_eq!(pos.white_checks, 2);
+        assert_eq!(pos.black_checks, 1);
+        assert_eq!(
+            pos.to_fen_with_checks(),
+            "7k/8/8/8/8/8/R7/4K3 b - - 1 1 +2+1"
+        );
+    }
+
+    #[test]
+    fn insufficient_material_only_bare_kings() {
+        let pos = Position::from_fen("8/8/8/8/8/8/8/4K2k w - - 0 1").unwrap();
+        assert_eq!(
+            ThreeCheck.game_result(&pos),
+            Some(GameResult::Draw(DrawReason::InsufficientMaterial))
+        );
+
+        // A lone knight can still give checks
+        let pos = Position::from_fen("8/8/8/8/8/8/8/4KN1k w - - 0 1").unwrap();
+        assert!(!ThreeCheck.is_insufficient_material(&pos));
+    }
+}
@@ -96,14 +96,36 @@ pub fn move_to_san(position: &Position, m: Move) -> String {
 }
 
 /// Parses a SAN string and returns the corresponding legal move.
+///
+/// Parsing is lenient with notation found in real PGN files: castling may
+/// use zeros (`0-0`), an `e.p.` suffix is ignored, the `=` before a
+/// promotion piece may be omitted (`e8Q`), and trailing `+`, `#`, `!` and
+/// `?` annotations are skipped. [`move_to_san`] always produces strict SAN.
 pub fn san_to_move(position: &Position, san: &str) -> Result<Move, SanError> {
     let san = san.trim();
     if san.is_empty() {
         return Err(SanError::Empty);
     }
 
-    // Remove check/checkmate suffix for parsing
-    let san = san.trim_end_matches('#').trim_end_matches('+');
+    // Remove check/checkmate suffixes, annotations and the en passant marker
+    let is_suffix = |c: char| matches!(c, '+' | '#' | '!' | '?');
+    let san = san.trim_end_matches(is_suffix);
+    let san = san
+        .strip_suffix("e.p.")
+        .map_or(san, |s| s.trim_end().trim_end_matches(is_suffix));
+    if san.is_empty() {
+        return Err(SanError::Empty);
+    }
+
+    // Insert the `=` of a promotion written without it ("e8Q")
+    let mut chars = san.chars().rev();
+    let san = match (chars.next(), chars.next()) {
+        (Some(p @ ('Q' | 'R' | 'B' | 'N')), Some(rank)) if rank.is_ascii_digit() => {
+            format!("{}={}", &san[..san.len() - 1], p)
+        }
+        _ => san.to_string(),
+    };
+    let san = san.as_str();
 
     // Handle castling
     if san == "O-O" || san == "0-0" {
@@ -563,6 +585,56 @@ mod tests {
         assert_eq!(m.to(), Square::new(File::H, Rank::R2));
     }
 
+    #[test]
+    fn parse_san_castling_with_zeros() {
+        let pos = Position::from_fen("r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1").unwrap();
+        assert_eq!(
+            san_to_move(&pos, "0-0").unwrap(),
+            san_to_move(&pos, "O-O").unwrap()
+        );
+        assert_eq!(
+            san_to_move(&pos, "0-0-0").unwrap(),
+            san_to_move(&pos, "O-O-O").unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_san_en_passant_suffix() {
+        let pos =
+            Position::from_fen("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3")
+                .unwrap();
+        let expected = san_to_move(&pos, "exf6").unwrap();
+        assert_eq!(expected.flag(), MoveFlag::EnPassant);
+        assert_eq!(san_to_move(&pos, "exf6e.p.").unwrap(), expected);
+        assert_eq!(san_to_move(&pos, "exf6 e.p.").unwrap(), expected);
+    }
+
+    #[test]
+    fn parse_san_promotion_without_equals() {
+        let pos = Position::from_fen("1r6/P7/8/8/8/8/8/4K1k1 w - - 0 1").unwrap();
+        let m = san_to_move(&pos, "a8Q").unwrap();
+        assert_eq!(m, san_to_move(&pos, "a8=Q").unwrap());
+        assert_eq!(m.flag().promotion_piece(), Some(Piece::Queen));
+
+        let m = san_to_move(&pos, "axb8N").unwrap();
+        assert_eq!(m.to(), Square::new(File::B, Rank::R8));
+        assert_eq!(m.flag().promotion_piece(), Some(Piece::Knight));
+    }
+
+    #[test]
+    fn parse_san_with_annotations() {
+        let pos = Position::startpos();
+        let e4 = san_to_move(&pos, "e4").unwrap();
+        for san in ["e4!", "e4?", "e4!!", "e4?!", "e4!?"] {
+            assert_eq!(san_to_move(&pos, san).unwrap(), e4, "{}", san);
+        }
+
+        let pos = Position::from_fen("8/8/8/8/8/8/8/4K1Qk w - - 0 1").unwrap();
+        let m = san_to_move(&pos, "Qh2+!").unwrap();
+        assert_eq!(m.to(), Square::new(File::H, Rank::R2));
+        assert!(san_to_move(&pos, "!?").is_err());
+    }
+
     #[test]
     fn parse_san_invalid() {
         let pos = Position::startpos();
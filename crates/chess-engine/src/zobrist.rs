@@ -6,14 +6,20 @@
 //! - Side to move
 //! - Castling rights (4 values)
 //! - En passant file (8 values)
+//! - Pocket counts for Crazyhouse (160 values)
+//! - Checks delivered for Three-check (6 values)
 //!
 //! This allows efficient incremental updates when making moves.
 
-// Allow dead code - this module is scaffolded for future use in move generation
-#![allow(dead_code)]
-
+use crate::position::CastlingRights;
 use chess_core::{Color, Piece, Square};
 
+/// Highest pocket count with its own key; larger counts share its key.
+const MAX_POCKET_COUNT: u8 = 16;
+
+/// Highest check count with its own key; Three-check ends at three.
+const MAX_CHECKS: u8 = 3;
+
 /// Zobrist hash keys.
 ///
 /// Generated using a fixed seed for reproducibility.
@@ -26,6 +32,10 @@ pub struct ZobristKeys {
     pub castling: [u64; 4],
     /// Keys for en passant file.
     pub en_passant: [u64; 8],
+    /// Keys for pocket counts: [color][piece][count - 1], pawn to queen.
+    pub pockets: [[[u64; MAX_POCKET_COUNT as usize]; 5]; 2],
+    /// Keys for checks delivered: [color][checks - 1].
+    pub checks: [[u64; MAX_CHECKS as usize]; 2],
 }
 
 impl ZobristKeys {
@@ -46,6 +56,8 @@ impl ZobristKeys {
         let mut pieces = [[[0u64; 64]; 2]; 6];
         let mut castling = [0u64; 4];
         let mut en_passant = [0u64; 8];
+        let mut pockets = [[[0u64; MAX_POCKET_COUNT as usize]; 5]; 2];
+        let mut checks = [[0u64; MAX_CHECKS as usize]; 2];
 
         // Initialize piece keys
         let mut piece = 0;
@@ -86,11 +98,43 @@ impl ZobristKeys {
             i += 1;
         }
 
+        // Initialize pocket keys
+        let mut color = 0;
+        while color < 2 {
+            let mut piece = 0;
+            while piece < 5 {
+                let mut count = 0;
+                while count < MAX_POCKET_COUNT as usize {
+                    let (new_state, value) = next_random(state);
+                    state = new_state;
+                    pockets[color][piece][count] = value;
+                    count += 1;
+                }
+                piece += 1;
+            }
+            color += 1;
+        }
+
+        // Initialize check keys
+        let mut color = 0;
+        while color < 2 {
+            let mut count = 0;
+            while count < MAX_CHECKS as usize {
+                let (new_state, value) = next_random(state);
+                state = new_state;
+                checks[color][count] = value;
+                count += 1;
+            }
+            color += 1;
+        }
+
         ZobristKeys {
             pieces,
             black_to_move,
             castling,
             en_passant,
+            pockets,
+            checks,
         }
     }
 
@@ -111,11 +155,75 @@ impl ZobristKeys {
     pub const fn en_passant_key(&self, file: usize) -> u64 {
         self.en_passant[file]
     }
+
+    /// Returns the key for `color` holding `count` pieces of one type
+    /// (pawn to queen) in its pocket, or 0 for an empty pocket slot.
+    #[inline]
+    pub const fn pocket_key(&self, color: Color, piece: Piece, count: u8) -> u64 {
+        if count == 0 {
+            return 0;
+        }
+        let count = if count > MAX_POCKET_COUNT {
+            MAX_POCKET_COUNT
+        } else {
+            count
+        };
+        self.pockets[color.index()][piece.index()][count as usize - 1]
+    }
+
+    /// Returns the key for `color` having delivered `checks` checks, or 0
+    /// for none.
+    #[inline]
+    pub const fn checks_key(&self, color: Color, checks: u8) -> u64 {
+        if checks == 0 {
+            return 0;
+        }
+        let checks = if checks > MAX_CHECKS {
+            MAX_CHECKS
+        } else {
+            checks
+        };
+        self.checks[color.index()][checks as usize - 1]
+    }
 }
 
 /// Global Zobrist keys (initialized at compile time).
 pub static ZOBRIST: ZobristKeys = ZobristKeys::new();
 
+/// Returns the combined key of the rights in `castling`.
+pub fn castling_hash(castling: CastlingRights) -> u64 {
+    let mut hash = 0;
+    if castling.can_castle_kingside(Color::White) {
+        hash ^= ZOBRIST.castling_key(0);
+    }
+    if castling.can_castle_queenside(Color::White) {
+        hash ^= ZOBRIST.castling_key(1);
+    }
+    if castling.can_castle_kingside(Color::Black) {
+        hash ^= ZOBRIST.castling_key(2);
+    }
+    if castling.can_castle_queenside(Color::Black) {
+        hash ^= ZOBRIST.castling_key(3);
+    }
+    hash
+}
+
+/// Returns the key for an en passant square's file, or 0 without one.
+pub fn en_passant_hash(square: Option<Square>) -> u64 {
+    square.map_or(0, |sq| ZOBRIST.en_passant_key(sq.file().index() as usize))
+}
+
+/// Returns the combined key of the pieces held in `pockets`.
+pub fn pockets_hash(pockets: &[[u8; 5]; 2]) -> u64 {
+    let mut hash = 0;
+    for color in [Color::White, Color::Black] {
+        for piece in &Piece::ALL[..5] {
+            hash ^= ZOBRIST.pocket_key(color, *piece, pockets[color.index()][piece.index()]);
+        }
+    }
+    hash
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
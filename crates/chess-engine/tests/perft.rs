@@ -0,0 +1,14 @@
+//! Perft regression suite for the move generator.
+//!
+//! Runs every position in `tests/perft.epd` against its known node counts.
+
+use chess_engine::movegen::perft::run_suite;
+
+#[test]
+fn perft_suite() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/perft.epd");
+    match run_suite(path) {
+        Ok(checks) => assert_eq!(checks, 36),
+        Err(e) => panic!("{}", e),
+    }
+}
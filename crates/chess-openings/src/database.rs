@@ -1,12 +1,15 @@
 //! Opening database storage and lookup.
 
 use std::collections::HashMap;
+use std::path::Path;
 
+use chess_engine::Game;
 use rand::seq::{IndexedRandom, SliceRandom};
 use rand::{Rng, RngExt};
 use thiserror::Error;
 
 use crate::opening::{Opening, OpeningMove, OpeningSource};
+use crate::pgn::ParsedGame;
 
 /// Errors that can occur when working with opening databases.
 #[derive(Debug, Error)]
@@ -22,6 +25,19 @@ pub enum DatabaseError {
     /// JSON deserialization error.
     #[error("JSON error: {0}")]
     JsonError(#[from] serde_json::Error),
+
+    /// The opening database file is empty.
+    #[error("opening database file is empty: {0}")]
+    EmptyFile(String),
+
+    /// The opening database file does not contain valid opening JSON.
+    #[error("malformed opening database {path}: {source}")]
+    MalformedFile {
+        /// Path of the file that failed to parse.
+        path: String,
+        /// The underlying JSON error.
+        source: serde_json::Error,
+    },
 }
 
 /// A move database that maps positions to candidate moves.
@@ -53,6 +69,36 @@ impl MoveDatabase {
         self.positions.len()
     }
 
+    /// Builds a weighted move database from a collection of games.
+    ///
+    /// For each game, the first `max_ply` moves are walked. Each position is
+    /// keyed by the space-separated move history leading to it (the same
+    /// convention as [`MoveDatabase::add_position`]), and every time a move is
+    /// played from a position its [`OpeningMove::weight`] is incremented.
+    /// Candidate moves are ordered by descending weight.
+    #[must_use]
+    pub fn from_pgn_games(games: impl Iterator<Item = ParsedGame>, max_ply: usize) -> Self {
+        let mut positions: HashMap<String, Vec<OpeningMove>> = HashMap::new();
+
+        for game in games {
+            for ply in 0..game.moves.len().min(max_ply) {
+                let key = game.moves[..ply].join(" ");
+                let uci = &game.moves[ply];
+                let candidates = positions.entry(key).or_default();
+                match candidates.iter_mut().find(|m| &m.uci == uci) {
+                    Some(existing) => existing.weight += 1,
+                    None => candidates.push(OpeningMove::new(uci.clone(), 1)),
+                }
+            }
+        }
+
+        for candidates in positions.values_mut() {
+            candidates.sort_by_key(|m| std::cmp::Reverse(m.weight));
+        }
+
+        Self { positions }
+    }
+
     /// Adds a position with its candidate moves to the database.
     pub fn add_position(&mut self, position_key: impl Into<String>, moves: Vec<OpeningMove>) {
         self.positions.insert(position_key.into(), moves);
@@ -102,6 +148,16 @@ impl MoveDatabase {
 pub struct OpeningDatabase {
     /// All openings stored in the database.
     openings: Vec<Opening>,
+    /// Indices into `openings` by the [`position_key`] of their FEN.
+    by_fen: HashMap<String, Vec<usize>>,
+}
+
+/// Returns the part of a FEN that identifies the position for transposition
+/// lookups: piece placement, side to move, castling rights and en passant
+/// square, without the move counters.
+#[must_use]
+pub fn position_key(fen: &str) -> String {
+    fen.split_whitespace().take(4).collect::<Vec<_>>().join(" ")
 }
 
 impl OpeningDatabase {
@@ -114,7 +170,11 @@ impl OpeningDatabase {
     /// Creates a new opening database with the given openings.
     #[must_use]
     pub fn with_openings(openings: Vec<Opening>) -> Self {
-        Self { openings }
+        let mut db = Self::new();
+        for opening in openings {
+            db.add(opening);
+        }
+        db
     }
 
     /// Returns the number of openings in the database.
@@ -131,6 +191,10 @@ impl OpeningDatabase {
 
     /// Adds an opening to the database.
     pub fn add(&mut self, opening: Opening) {
+        self.by_fen
+            .entry(position_key(&opening.fen))
+            .or_default()
+            .push(self.openings.len());
         self.openings.push(opening);
     }
 
@@ -140,6 +204,43 @@ impl OpeningDatabase {
         &self.openings
     }
 
+    /// Saves all openings to a JSON file.
+    ///
+    /// The file contains a JSON array of [`Opening`] objects and can be read
+    /// back with [`OpeningDatabase::load_from_file`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the openings cannot be serialized or the file
+    /// cannot be written.
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> Result<(), DatabaseError> {
+        let json = serde_json::to_string_pretty(&self.openings)?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Loads openings from a JSON file written by [`OpeningDatabase::save_to_file`].
+    ///
+    /// # Errors
+    ///
+    /// - [`DatabaseError::IoError`] if the file cannot be read
+    /// - [`DatabaseError::EmptyFile`] if the file is empty
+    /// - [`DatabaseError::MalformedFile`] if the file is not a valid JSON array of openings
+    pub fn load_from_file(path: impl AsRef<Path>) -> Result<Self, DatabaseError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)?;
+        if contents.trim().is_empty() {
+            return Err(DatabaseError::EmptyFile(path.display().to_string()));
+        }
+
+        let openings =
+            serde_json::from_str(&contents).map_err(|source| DatabaseError::MalformedFile {
+                path: path.display().to_string(),
+                source,
+            })?;
+        Ok(Self::with_openings(openings))
+    }
+
     /// Finds an opening by its unique ID.
     ///
     /// Returns `None` if no opening with the given ID exists.
@@ -316,6 +417,56 @@ impl OpeningDatabase {
             .max_by_key(|o| o.moves.len())
     }
 
+    /// Classifies a game by the most specific ECO opening it reached.
+    ///
+    /// Only openings with an ECO code are considered. Among the ECO lines that
+    /// the game followed in full, the deepest one is returned, together with
+    /// the ply at which the game left book: the length of the longest prefix
+    /// the game shares with any ECO line. This can be deeper than the
+    /// returned opening when the game followed a longer line part of the way.
+    ///
+    /// Returns `None` if the game did not complete any ECO line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chess_openings::{Opening, OpeningDatabase};
+    ///
+    /// let db = OpeningDatabase::with_openings(vec![
+    ///     Opening::new("kings-pawn", "King's Pawn", vec!["e2e4".into()], "fen1").with_eco("B00"),
+    ///     Opening::new("open-game", "Open Game", vec!["e2e4".into(), "e7e5".into(), "g1f3".into()], "fen2").with_eco("C40"),
+    /// ]);
+    ///
+    /// let moves = vec!["e2e4".into(), "e7e5".into(), "d2d4".into()];
+    /// let (opening, divergence) = db.classify_eco(&moves).unwrap();
+    /// assert_eq!(opening.id, "kings-pawn");
+    /// assert_eq!(divergence, 2); // 1...e5 was still in the Open Game line
+    /// ```
+    #[must_use]
+    pub fn classify_eco(&self, moves: &[String]) -> Option<(&Opening, usize)> {
+        let eco_lines = self.openings.iter().filter(|o| o.has_eco());
+
+        let mut best: Option<&Opening> = None;
+        let mut divergence = 0;
+        for opening in eco_lines {
+            let shared = opening
+                .moves
+                .iter()
+                .zip(moves.iter())
+                .take_while(|(a, b)| a == b)
+                .count();
+            divergence = divergence.max(shared);
+
+            // Only lines the game followed to the end name the opening
+            let reached = shared == opening.moves.len();
+            if reached && best.is_none_or(|b| opening.moves.len() > b.moves.len()) {
+                best = Some(opening);
+            }
+        }
+
+        best.map(|opening| (opening, divergence))
+    }
+
     /// Returns all openings whose moves are a prefix of the provided sequence.
     ///
     /// Unlike [`find_by_moves`](Self::find_by_moves), this returns all matching openings, not just the longest.
@@ -358,10 +509,69 @@ impl OpeningDatabase {
             .collect();
 
         // Sort by move count descending (longest first)
-        matches.sort_by(|a, b| b.moves.len().cmp(&a.moves.len()));
+        matches.sort_by_key(|a| std::cmp::Reverse(a.moves.len()));
         matches
     }
 
+    /// Returns the openings whose resulting position matches `fen`.
+    ///
+    /// Positions are compared by [`position_key`], so the move counters of
+    /// `fen` don't matter. Openings are returned in database order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chess_openings::{Opening, OpeningDatabase};
+    ///
+    /// let db = OpeningDatabase::with_openings(vec![Opening::new(
+    ///     "kings-pawn",
+    ///     "King's Pawn",
+    ///     vec!["e2e4".into()],
+    ///     "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+    /// )]);
+    ///
+    /// let found = db.by_position_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 5 9");
+    /// assert_eq!(found[0].id, "kings-pawn");
+    /// ```
+    #[must_use]
+    pub fn by_position_fen(&self, fen: &str) -> Vec<&Opening> {
+        self.by_fen
+            .get(&position_key(fen))
+            .map(|indices| indices.iter().map(|&i| &self.openings[i]).collect())
+            .unwrap_or_default()
+    }
+
+    /// Classifies a game by the last opening position it reached, whatever
+    /// the move order.
+    ///
+    /// Replays `moves` (UCI, from the starting position) and looks up every
+    /// position reached with [`by_position_fen`](Self::by_position_fen), so a
+    /// game that transposes into an opening is recognized even though
+    /// [`find_by_moves`](Self::find_by_moves) would not match its moves.
+    /// Among several openings at the same position, the one with the most
+    /// moves wins. Replay stops at the first illegal move.
+    #[must_use]
+    pub fn classify_by_position(&self, moves: &[String]) -> Option<&Opening> {
+        let mut game = Game::new();
+        let mut best = self.deepest_at(&game.to_fen());
+        for uci in moves {
+            if game.make_move_uci(uci).is_err() {
+                break;
+            }
+            if let Some(opening) = self.deepest_at(&game.to_fen()) {
+                best = Some(opening);
+            }
+        }
+        best
+    }
+
+    /// Returns the opening with the most moves at the position `fen`.
+    fn deepest_at(&self, fen: &str) -> Option<&Opening> {
+        self.by_position_fen(fen)
+            .into_iter()
+            .max_by_key(|o| o.moves.len())
+    }
+
     /// Returns openings that could follow from the given position.
     ///
     /// An opening is a continuation if the provided moves are a prefix of the opening's moves,
@@ -1077,4 +1287,226 @@ mod tests {
         assert!(result.is_some());
         assert_eq!(result.unwrap().id, "queens-pawn");
     }
+
+    #[test]
+    fn test_save_and_load_builtin_openings() {
+        let db = OpeningDatabase::with_openings(crate::builtin::builtin_openings());
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("openings.json");
+
+        db.save_to_file(&path).unwrap();
+        let loaded = OpeningDatabase::load_from_file(&path).unwrap();
+
+        assert_eq!(loaded.len(), db.len());
+        assert_eq!(loaded.by_id("italian-game"), db.by_id("italian-game"));
+        assert_eq!(loaded.by_id("ruy-lopez"), db.by_id("ruy-lopez"));
+        assert!(loaded.by_id("ruy-lopez").is_some());
+    }
+
+    #[test]
+    fn test_load_empty_database_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("empty.json");
+        std::fs::write(&path, "  \n").unwrap();
+
+        let err = OpeningDatabase::load_from_file(&path).unwrap_err();
+        assert!(matches!(err, DatabaseError::EmptyFile(_)));
+        assert!(err.to_string().contains("empty"));
+    }
+
+    #[test]
+    fn test_load_malformed_database_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bad.json");
+        std::fs::write(&path, "[{\"id\": \"broken\"").unwrap();
+
+        let err = OpeningDatabase::load_from_file(&path).unwrap_err();
+        assert!(matches!(err, DatabaseError::MalformedFile { .. }));
+        assert!(err.to_string().contains("bad.json"));
+    }
+
+    #[test]
+    fn test_load_missing_database_file() {
+        let err = OpeningDatabase::load_from_file("/nonexistent/openings.json").unwrap_err();
+        assert!(matches!(err, DatabaseError::IoError(_)));
+    }
+
+    fn uci_game(moves: &str) -> ParsedGame {
+        ParsedGame::from_moves(moves.split_whitespace().map(String::from).collect())
+    }
+
+    #[test]
+    fn test_move_database_from_pgn_games() {
+        let games = vec![
+            uci_game("e2e4 e7e5 g1f3 b8c6"),
+            uci_game("e2e4 c7c5 g1f3"),
+            uci_game("d2d4 d7d5"),
+        ];
+
+        let db = MoveDatabase::from_pgn_games(games.into_iter(), 2);
+
+        // Shared first move is merged and ranked first
+        let first = db.lookup("").unwrap();
+        assert_eq!(
+            first,
+            &[OpeningMove::new("e2e4", 2), OpeningMove::new("d2d4", 1)]
+        );
+
+        let replies = db.lookup("e2e4").unwrap();
+        assert_eq!(replies.len(), 2);
+        assert!(replies.iter().all(|m| m.weight == 1));
+
+        // Moves beyond max_ply are not recorded
+        assert!(db.lookup("e2e4 e7e5").is_none());
+        assert_eq!(db.len(), 3);
+    }
+
+    #[test]
+    fn test_move_database_from_pgn_games_empty() {
+        let db = MoveDatabase::from_pgn_games(std::iter::empty(), 10);
+        assert!(db.is_empty());
+
+        let db = MoveDatabase::from_pgn_games(vec![uci_game("e2e4")].into_iter(), 0);
+        assert!(db.is_empty());
+    }
+
+    fn ruy_lopez_test_openings() -> Vec<Opening> {
+        let line = |moves: &str| moves.split_whitespace().map(String::from).collect();
+        vec![
+            Opening::new("kings-pawn", "King's Pawn", line("e2e4"), "fen").with_eco("B00"),
+            Opening::new(
+                "ruy-lopez",
+                "Ruy Lopez",
+                line("e2e4 e7e5 g1f3 b8c6 f1b5"),
+                "fen",
+            )
+            .with_eco("C60"),
+            Opening::new(
+                "ruy-lopez-berlin",
+                "Ruy Lopez: Berlin Defense",
+                line("e2e4 e7e5 g1f3 b8c6 f1b5 g8f6"),
+                "fen",
+            )
+            .with_eco("C65"),
+            Opening::new(
+                "ruy-lopez-morphy",
+                "Ruy Lopez: Morphy Defense",
+                line("e2e4 e7e5 g1f3 b8c6 f1b5 a7a6 b5a4 g8f6"),
+                "fen",
+            )
+            .with_eco("C70"),
+            // Lines without an ECO code are ignored
+            Opening::new(
+                "custom-berlin",
+                "Custom Berlin",
+                line("e2e4 e7e5 g1f3 b8c6 f1b5 g8f6 e1g1 f6e4"),
+                "fen",
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_classify_eco_divergence_after_move_six() {
+        let db = OpeningDatabase::with_openings(ruy_lopez_test_openings());
+
+        // Berlin Defense, then 4.Nc3 leaves book
+        let moves: Vec<String> = "e2e4 e7e5 g1f3 b8c6 f1b5 g8f6 b1c3 f8c5"
+            .split_whitespace()
+            .map(String::from)
+            .collect();
+
+        let (opening, divergence) = db.classify_eco(&moves).unwrap();
+        assert_eq!(opening.id, "ruy-lopez-berlin");
+        assert_eq!(opening.eco.as_deref(), Some("C65"));
+        assert_eq!(divergence, 6);
+    }
+
+    #[test]
+    fn test_classify_eco_prefers_deepest_reached_line() {
+        let db = OpeningDatabase::with_openings(ruy_lopez_test_openings());
+
+        // Morphy Defense up to 4.Ba4, then 4...d6 instead of 4...Nf6
+        let moves: Vec<String> = "e2e4 e7e5 g1f3 b8c6 f1b5 a7a6 b5a4 d7d6"
+            .split_whitespace()
+            .map(String::from)
+            .collect();
+
+        // The Morphy line was never completed, so the game is a Ruy Lopez
+        // that left book after 4.Ba4
+        let (opening, divergence) = db.classify_eco(&moves).unwrap();
+        assert_eq!(opening.id, "ruy-lopez");
+        assert_eq!(divergence, 7);
+
+        // find_by_moves agrees on the opening but cannot report the divergence
+        assert_eq!(db.find_by_moves(&moves).unwrap().id, "ruy-lopez");
+    }
+
+    #[test]
+    fn test_classify_eco_no_match() {
+        let db = OpeningDatabase::with_openings(ruy_lopez_test_openings());
+
+        let moves: Vec<String> = vec!["d2d4".into(), "d7d5".into()];
+        assert!(db.classify_eco(&moves).is_none());
+        assert!(db.classify_eco(&[]).is_none());
+    }
+
+    fn uci_line(moves: &str) -> Vec<String> {
+        moves.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_classify_by_position_recognizes_transpositions() {
+        let db = OpeningDatabase::with_openings(crate::builtin::builtin_openings());
+        let main_line = uci_line("e2e4 e7e5 g1f3 b8c6 f1c4");
+        let transposed = uci_line("e2e4 e7e5 f1c4 b8c6 g1f3");
+
+        assert_eq!(
+            db.classify_by_position(&main_line).unwrap().id,
+            "italian-game"
+        );
+        assert_eq!(
+            db.classify_by_position(&transposed).unwrap().id,
+            "italian-game"
+        );
+        assert_ne!(db.find_by_moves(&transposed).unwrap().id, "italian-game");
+
+        // Moves after the last book position keep its classification
+        let mut continued = transposed.clone();
+        continued.extend(uci_line("a7a6 a2a3"));
+        assert_eq!(
+            db.classify_by_position(&continued).unwrap().id,
+            "italian-game"
+        );
+    }
+
+    #[test]
+    fn test_by_position_fen_ignores_move_counters() {
+        let db = OpeningDatabase::with_openings(crate::builtin::builtin_openings());
+        let italian = db.by_id("italian-game").unwrap();
+        let key = position_key(&italian.fen);
+        assert!(!key.ends_with(" 3 3"));
+
+        let found = db.by_position_fen(&format!("{} 0 40", key));
+        assert!(found.iter().any(|o| o.id == "italian-game"));
+
+        // 1. a3 is not in the book
+        let after_a3 = "rnbqkbnr/pppppppp/8/8/8/P7/1PPPPPPP/RNBQKBNR b KQkq - 0 1";
+        assert!(db.by_position_fen(after_a3).is_empty());
+    }
+
+    #[test]
+    fn test_builtin_fens_match_their_moves() {
+        for opening in crate::builtin::builtin_openings() {
+            let mut game = Game::new();
+            for uci in &opening.moves {
+                game.make_move_uci(uci).unwrap();
+            }
+            assert_eq!(
+                position_key(&game.to_fen()),
+                position_key(&opening.fen),
+                "{}",
+                opening.id
+            );
+        }
+    }
 }
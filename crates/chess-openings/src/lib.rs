@@ -7,6 +7,8 @@
 pub mod builtin;
 pub mod database;
 pub mod opening;
+pub mod pgn;
 
-pub use database::{MoveDatabase, OpeningDatabase};
+pub use database::{position_key, DatabaseError, MoveDatabase, OpeningDatabase};
 pub use opening::{Opening, OpeningMove, OpeningSource, OpeningStats, STARTING_FEN};
+pub use pgn::ParsedGame;
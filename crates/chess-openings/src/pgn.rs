@@ -0,0 +1,32 @@
+//! Games parsed from PGN, used as input for building opening books.
+
+use std::collections::HashMap;
+
+/// A game read from a PGN file.
+///
+/// Moves are stored in UCI notation, matching the position keys used by
+/// [`MoveDatabase`](crate::MoveDatabase).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParsedGame {
+    /// PGN tag pairs (e.g., "White", "Result", "ECO").
+    pub tags: HashMap<String, String>,
+    /// The moves of the game in UCI notation.
+    pub moves: Vec<String>,
+}
+
+impl ParsedGame {
+    /// Creates a game from a list of UCI moves, with no tags.
+    #[must_use]
+    pub fn from_moves(moves: Vec<String>) -> Self {
+        Self {
+            tags: HashMap::new(),
+            moves,
+        }
+    }
+
+    /// Returns the value of a PGN tag, if present.
+    #[must_use]
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags.get(name).map(String::as_str)
+    }
+}
@@ -20,15 +20,17 @@
 //! console.log(game.to_fen());
 //! ```
 
-use chess_engine::rules::RuleSet;
-use chess_engine::{Position, StandardChess};
+use serde::Serialize;
+use std::collections::BTreeMap;
 use wasm_bindgen::prelude::*;
 
 /// A chess game that can be manipulated from JavaScript.
+///
+/// Wraps a [`chess_engine::Game`], so the move history is kept and draws by
+/// repetition and the fifty-move rule are detected as moves are played.
 #[wasm_bindgen]
 pub struct Game {
-    position: Position,
-    rules: StandardChess,
+    game: chess_engine::Game,
 }
 
 #[wasm_bindgen]
@@ -37,8 +39,7 @@ impl Game {
     #[wasm_bindgen(constructor)]
     pub fn new() -> Self {
         Game {
-            position: StandardChess.initial_position(),
-            rules: StandardChess,
+            game: chess_engine::Game::new(),
         }
     }
 
@@ -47,85 +48,110 @@ impl Game {
     /// Returns an error if the FEN is invalid.
     #[wasm_bindgen(js_name = fromFen)]
     pub fn from_fen(fen: &str) -> Result<Game, JsError> {
-        let position = Position::from_fen(fen).map_err(|e| JsError::new(&e.to_string()))?;
-        Ok(Game {
-            position,
-            rules: StandardChess,
-        })
+        let mut game = Game {
+            game: chess_engine::Game::from_fen(fen).map_err(|e| JsError::new(&e.to_string()))?,
+        };
+        game.claim_draw_if_possible();
+        Ok(game)
     }
 
     /// Returns the current position as a FEN string.
     #[wasm_bindgen(js_name = toFen)]
     pub fn to_fen(&self) -> String {
-        self.position.to_fen()
+        self.game.to_fen()
     }
 
     /// Returns an array of legal moves in UCI format.
     #[wasm_bindgen(js_name = legalMoves)]
     pub fn legal_moves(&self) -> Vec<String> {
-        self.rules
-            .generate_moves(&self.position)
-            .as_slice()
-            .iter()
-            .map(|m| m.to_uci())
-            .collect()
+        self.game.legal_moves_uci()
     }
 
     /// Makes a move given in UCI format (e.g., "e2e4", "e7e8q").
     ///
-    /// Returns an error if the move is invalid or illegal.
+    /// Returns an error if the move is invalid or illegal, or if the game is
+    /// already over.
     #[wasm_bindgen(js_name = makeMove)]
     pub fn make_move(&mut self, uci: &str) -> Result<(), JsError> {
-        let m = chess_core::Move::from_uci(uci)
-            .ok_or_else(|| JsError::new(&format!("Invalid move format: {}", uci)))?;
-
-        // Find the matching legal move with correct flags (DoublePush, EnPassant, etc.)
-        // since from_uci doesn't set these flags properly.
-        let legal_move = self
-            .rules
-            .generate_moves(&self.position)
-            .as_slice()
-            .iter()
-            .find(|legal| {
-                legal.from() == m.from()
-                    && legal.to() == m.to()
-                    && legal.flag().promotion_piece() == m.flag().promotion_piece()
-            })
-            .copied()
-            .ok_or_else(|| JsError::new(&format!("Illegal move: {}", uci)))?;
+        self.play(uci).map_err(|e| JsError::new(&e))
+    }
 
-        self.position = self.rules.make_move(&self.position, legal_move);
-        Ok(())
+    /// Applies a list of moves in UCI format, like the UCI
+    /// `position ... moves ...` command.
+    ///
+    /// If a move is invalid or illegal the game is left unchanged and the
+    /// error names the index of the failing move.
+    #[wasm_bindgen(js_name = playMoves)]
+    pub fn play_moves(&mut self, moves: Vec<String>) -> Result<(), JsError> {
+        self.apply_moves(&moves).map_err(|e| JsError::new(&e))
+    }
+
+    /// Creates a game from a FEN string followed by a list of UCI moves.
+    ///
+    /// Returns an error if the FEN is invalid or any move is illegal.
+    #[wasm_bindgen(js_name = fromFenWithMoves)]
+    pub fn from_fen_with_moves(fen: &str, moves: Vec<String>) -> Result<Game, JsError> {
+        let mut game = Game::from_fen(fen)?;
+        game.play_moves(moves)?;
+        Ok(game)
     }
 
     /// Returns true if the current side to move is in check.
     #[wasm_bindgen(js_name = isCheck)]
     pub fn is_check(&self) -> bool {
-        self.rules.is_check(&self.position)
+        self.game.is_check()
     }
 
     /// Returns true if the game is over (checkmate, stalemate, or draw).
     #[wasm_bindgen(js_name = isGameOver)]
     pub fn is_game_over(&self) -> bool {
-        self.rules.is_game_over(&self.position)
+        self.game.is_game_over()
     }
 
     /// Returns the game result if the game is over.
     ///
     /// Returns one of: "white_wins", "black_wins", "draw", or null if game is ongoing.
+    /// Threefold repetition and the fifty-move rule count as draws as soon
+    /// as they occur.
     #[wasm_bindgen]
     pub fn result(&self) -> Option<String> {
-        self.rules.game_result(&self.position).map(|r| match r {
+        self.game.result().map(|r| match r {
             chess_engine::GameResult::WhiteWins => "white_wins".to_string(),
             chess_engine::GameResult::BlackWins => "black_wins".to_string(),
             chess_engine::GameResult::Draw(_) => "draw".to_string(),
         })
     }
 
+    /// Returns why the game was drawn, or null if it is not a draw.
+    ///
+    /// Returns one of: "stalemate", "insufficient_material", "fifty_move",
+    /// "seventy_five_move", "threefold_repetition", "fivefold_repetition" or
+    /// "agreement".
+    #[wasm_bindgen(js_name = drawReason)]
+    pub fn draw_reason(&self) -> Option<String> {
+        use chess_engine::DrawReason;
+
+        match self.game.result()? {
+            chess_engine::GameResult::Draw(reason) => Some(
+                match reason {
+                    DrawReason::Stalemate => "stalemate",
+                    DrawReason::InsufficientMaterial => "insufficient_material",
+                    DrawReason::FiftyMoveRule => "fifty_move",
+                    DrawReason::SeventyFiveMoveRule => "seventy_five_move",
+                    DrawReason::ThreefoldRepetition => "threefold_repetition",
+                    DrawReason::FivefoldRepetition => "fivefold_repetition",
+                    DrawReason::Agreement => "agreement",
+                }
+                .to_string(),
+            ),
+            _ => None,
+        }
+    }
+
     /// Returns the side to move ("white" or "black").
     #[wasm_bindgen(js_name = sideToMove)]
     pub fn side_to_move(&self) -> String {
-        match self.position.side_to_move {
+        match self.position().side_to_move {
             chess_core::Color::White => "white".to_string(),
             chess_core::Color::Black => "black".to_string(),
         }
@@ -138,13 +164,102 @@ impl Game {
     #[wasm_bindgen(js_name = pieceAt)]
     pub fn piece_at(&self, square: &str) -> Option<String> {
         let sq = chess_core::Square::from_algebraic(square)?;
-        let (piece, color) = self.position.piece_at(sq)?;
-        Some(piece.to_fen_char(color).to_string())
+        self.piece_at_square(sq).map(String::from)
+    }
+
+    /// Returns the squares attacked by `color` ("white" or "black") in
+    /// algebraic notation, ordered a1, b1, ..., h8.
+    ///
+    /// Returns an error if the color is not recognized.
+    #[wasm_bindgen(js_name = attackedSquares)]
+    pub fn attacked_squares(&self, color: &str) -> Result<Vec<String>, JsError> {
+        let color = match color {
+            "white" => chess_core::Color::White,
+            "black" => chess_core::Color::Black,
+            _ => return Err(JsError::new(&format!("Invalid color: {}", color))),
+        };
+        Ok(chess_core::Square::all()
+            .filter(|&sq| chess_engine::movegen::is_square_attacked(self.position(), sq, color))
+            .map(|sq| sq.to_algebraic())
+            .collect())
+    }
+
+    /// Returns the squares of the pieces giving check to the side to move.
+    ///
+    /// Returns an empty array when the side to move is not in check.
+    pub fn checkers(&self) -> Vec<String> {
+        chess_engine::checkers(self.position())
+            .into_iter()
+            .map(|sq| sq.to_algebraic())
+            .collect()
+    }
+
+    /// Returns a static material and piece-square evaluation in centipawns,
+    /// from the side to move's perspective.
+    pub fn evaluate(&self) -> i32 {
+        chess_engine::eval::evaluate(self.position())
+    }
+
+    /// Returns the static evaluation in centipawns from white's perspective.
+    #[wasm_bindgen(js_name = evaluateWhite)]
+    pub fn evaluate_white(&self) -> i32 {
+        chess_engine::eval::evaluate_white(self.position())
+    }
+
+    /// Counts the leaf nodes of the move tree to `depth` from the current
+    /// position.
+    ///
+    /// Counts are exact up to 2^53, far beyond any depth that finishes in a
+    /// browser.
+    pub fn perft(&self, depth: u32) -> f64 {
+        chess_engine::movegen::perft::perft(self.position(), depth) as f64
+    }
+
+    /// Returns an object mapping each legal move (UCI) to its perft count at
+    /// `depth - 1`.
+    #[wasm_bindgen(js_name = perftDivide)]
+    pub fn perft_divide(&self, depth: u32) -> Result<JsValue, JsError> {
+        let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+        self.divide(depth)
+            .serialize(&serializer)
+            .map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    /// Returns the board as 64 FEN piece characters in a single call.
+    ///
+    /// Index 0 is a8 and index 63 is h1, so squares run rank by rank from
+    /// white's far side as in FEN and on a board drawn from white's view.
+    /// Empty squares are empty strings.
+    #[wasm_bindgen(js_name = boardArray)]
+    pub fn board_array(&self) -> Vec<String> {
+        board_squares()
+            .map(|sq| {
+                self.piece_at_square(sq)
+                    .map(String::from)
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+
+    /// Returns an ASCII diagram of the board for debugging, with "." for
+    /// empty squares and rank/file labels.
+    #[wasm_bindgen(js_name = toAscii)]
+    pub fn to_ascii(&self) -> String {
+        let mut out = String::new();
+        for (i, sq) in board_squares().enumerate() {
+            if i % 8 == 0 {
+                out.push_str(&format!("{} ", 8 - i / 8));
+            }
+            out.push(self.piece_at_square(sq).unwrap_or('.'));
+            out.push(if i % 8 == 7 { '\n' } else { ' ' });
+        }
+        out.push_str("  a b c d e f g h\n");
+        out
     }
 
     /// Resets the game to the starting position.
     pub fn reset(&mut self) {
-        self.position = StandardChess.initial_position();
+        self.game = chess_engine::Game::new();
     }
 
     /// Converts a UCI move to Standard Algebraic Notation (SAN).
@@ -152,122 +267,84 @@ impl Game {
     /// Must be called before making the move since it needs the current position.
     #[wasm_bindgen(js_name = moveToSan)]
     pub fn move_to_san(&self, uci: &str) -> Result<String, JsError> {
-        use chess_core::{Move, MoveFlag, Piece};
+        let legal_move = self.legal_move(uci).map_err(|e| JsError::new(&e))?;
+        Ok(self.game.move_to_san(legal_move))
+    }
+}
 
-        let m = Move::from_uci(uci)
-            .ok_or_else(|| JsError::new(&format!("Invalid move format: {}", uci)))?;
+impl Game {
+    /// The current position.
+    fn position(&self) -> &chess_engine::Position {
+        self.game.position()
+    }
 
-        // Find the legal move with correct flags
-        let legal_moves = self.rules.generate_moves(&self.position);
-        let legal_move = legal_moves
-            .as_slice()
-            .iter()
-            .find(|legal| {
-                legal.from() == m.from()
-                    && legal.to() == m.to()
-                    && legal.flag().promotion_piece() == m.flag().promotion_piece()
-            })
-            .ok_or_else(|| JsError::new(&format!("Illegal move: {}", uci)))?;
-
-        let from = legal_move.from();
-        let to = legal_move.to();
-        let flag = legal_move.flag();
-
-        // Get piece at from square
-        let (piece, _color) = self
-            .position
-            .piece_at(from)
-            .ok_or_else(|| JsError::new("No piece at from square"))?;
-
-        let mut san = String::new();
-
-        // Handle castling
-        if flag == MoveFlag::CastleKingside {
-            san.push_str("O-O");
-        } else if flag == MoveFlag::CastleQueenside {
-            san.push_str("O-O-O");
-        } else {
-            // Piece letter (except pawns)
-            if piece != Piece::Pawn {
-                san.push(match piece {
-                    Piece::Knight => 'N',
-                    Piece::Bishop => 'B',
-                    Piece::Rook => 'R',
-                    Piece::Queen => 'Q',
-                    Piece::King => 'K',
-                    Piece::Pawn => unreachable!(),
-                });
-
-                // Check for disambiguation - other pieces of same type that can reach the target
-                let same_piece_moves: Vec<_> = legal_moves
-                    .as_slice()
-                    .iter()
-                    .filter(|mv| {
-                        mv.to() == to
-                            && mv.from() != from
-                            && self
-                                .position
-                                .piece_at(mv.from())
-                                .map(|(p, _)| p == piece)
-                                .unwrap_or(false)
-                    })
-                    .collect();
-
-                if !same_piece_moves.is_empty() {
-                    let same_file = same_piece_moves
-                        .iter()
-                        .any(|mv| mv.from().file() == from.file());
-                    let same_rank = same_piece_moves
-                        .iter()
-                        .any(|mv| mv.from().rank() == from.rank());
-
-                    if !same_file {
-                        san.push(from.to_algebraic().chars().next().unwrap());
-                    } else if !same_rank {
-                        san.push(from.to_algebraic().chars().nth(1).unwrap());
-                    } else {
-                        san.push_str(&from.to_algebraic());
-                    }
-                }
-            }
+    /// Finds the legal move matching a UCI string.
+    ///
+    /// `Move::from_uci` does not set flags such as double push or en
+    /// passant, so the move is looked up among the legal moves.
+    fn legal_move(&self, uci: &str) -> Result<chess_core::Move, String> {
+        if chess_core::Move::from_uci(uci).is_none() {
+            return Err(format!("Invalid move format: {}", uci));
+        }
 
-            // Capture indicator
-            let is_capture = self.position.piece_at(to).is_some() || flag == MoveFlag::EnPassant;
-            if is_capture {
-                if piece == Piece::Pawn {
-                    san.push(from.to_algebraic().chars().next().unwrap());
-                }
-                san.push('x');
-            }
+        self.game
+            .legal_moves()
+            .find_uci(uci)
+            .ok_or_else(|| format!("Illegal move: {}", uci))
+    }
 
-            // Destination square
-            san.push_str(&to.to_algebraic());
-
-            // Promotion
-            if let Some(promo_piece) = flag.promotion_piece() {
-                san.push('=');
-                san.push(match promo_piece {
-                    Piece::Queen => 'Q',
-                    Piece::Rook => 'R',
-                    Piece::Bishop => 'B',
-                    Piece::Knight => 'N',
-                    _ => 'Q',
-                });
-            }
+    /// Plays one UCI move, then ends the game if a draw can be claimed.
+    fn play(&mut self, uci: &str) -> Result<(), String> {
+        let m = self.legal_move(uci)?;
+        self.game.make_move(m).map_err(|e| e.to_string())?;
+        self.claim_draw_if_possible();
+        Ok(())
+    }
+
+    /// Ends the game by threefold repetition or the fifty-move rule once
+    /// either applies. There is no one to claim the draw in the browser, so
+    /// it is taken as soon as it is available.
+    fn claim_draw_if_possible(&mut self) {
+        if self.game.can_claim_draw().is_some() {
+            self.game
+                .claim_draw()
+                .expect("a claimable draw can be claimed");
         }
+    }
 
-        // Check for check or checkmate after the move
-        let new_pos = self.rules.make_move(&self.position, *legal_move);
-        if self.rules.is_check(&new_pos) {
-            if self.rules.is_game_over(&new_pos) {
-                san.push('#');
-            } else {
-                san.push('+');
+    /// Applies UCI moves in order, leaving the game unchanged on error.
+    fn apply_moves(&mut self, moves: &[String]) -> Result<(), String> {
+        let start = self.game.clone();
+        for (index, uci) in moves.iter().enumerate() {
+            if let Err(e) = self.play(uci) {
+                self.game = start;
+                return Err(format!("Move at index {}: {}", index, e));
             }
         }
+        Ok(())
+    }
 
-        Ok(san)
+    /// FEN character of the piece on `sq`, if any.
+    fn piece_at_square(&self, sq: chess_core::Square) -> Option<char> {
+        let (piece, color) = self.position().piece_at(sq)?;
+        Some(piece.to_fen_char(color))
     }
+
+    /// Perft divide counts keyed by UCI move.
+    fn divide(&self, depth: u32) -> BTreeMap<String, f64> {
+        chess_engine::movegen::perft::perft_divide(self.position(), depth)
+            .into_iter()
+            .map(|(uci, nodes)| (uci, nodes as f64))
+            .collect()
+    }
+}
+
+/// Squares in FEN order: a8, b8, ..., h8, a7, ..., h1.
+fn board_squares() -> impl Iterator<Item = chess_core::Square> {
+    chess_core::Rank::ALL
+        .into_iter()
+        .rev()
+        .flat_map(|rank| chess_core::File::ALL.map(|file| chess_core::Square::new(file, rank)))
 }
 
 impl Default for Game {
@@ -301,6 +378,30 @@ mod tests {
         assert_eq!(game.side_to_move(), "black");
     }
 
+    #[test]
+    fn san_suffix_marks_only_checkmate() {
+        let mate = Game::from_fen("rnbqkbnr/pppp1ppp/8/4p3/6P1/5P2/PPPPP2P/RNBQKBNR b KQkq - 0 2")
+            .unwrap();
+        assert_eq!(mate.move_to_san("d8h4").unwrap(), "Qh4#");
+
+        let stalemate = Game::from_fen("7k/8/6Q1/8/8/8/8/K7 w - - 0 1").unwrap();
+        assert_eq!(stalemate.move_to_san("g6f7").unwrap(), "Qf7");
+
+        let check = Game::from_fen("4k3/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+        assert_eq!(check.move_to_san("h1h8").unwrap(), "Rh8+");
+    }
+
+    #[test]
+    fn san_ignores_pinned_piece_for_disambiguation() {
+        // Both rooks could reach c2, but the e2 rook is pinned to the king
+        let game = Game::from_fen("4r2k/8/8/8/8/8/R3R3/4K3 w - - 0 1").unwrap();
+        assert_eq!(game.move_to_san("a2c2").unwrap(), "Rc2");
+
+        // Without the pin the move needs the file
+        let game = Game::from_fen("7k/8/8/8/8/8/R3R3/4K3 w - - 0 1").unwrap();
+        assert_eq!(game.move_to_san("a2c2").unwrap(), "Rac2");
+    }
+
     #[test]
     fn piece_at() {
         let game = Game::new();
@@ -308,4 +409,163 @@ mod tests {
         assert_eq!(game.piece_at("e8"), Some("k".to_string()));
         assert_eq!(game.piece_at("e4"), None);
     }
+
+    #[test]
+    fn attacked_squares_from_fen() {
+        // The rook covers its rank and file up to and including the h1 king
+        let game = Game::from_fen("7k/8/8/8/8/8/8/R6K w - - 0 1").unwrap();
+        let attacked = game.attacked_squares("white").unwrap();
+
+        let mut expected: Vec<String> = ["a2", "a3", "a4", "a5", "a6", "a7", "a8"]
+            .iter()
+            .chain(&["b1", "c1", "d1", "e1", "f1", "g1", "h1"])
+            .chain(&["g2", "h2"])
+            .map(|s| s.to_string())
+            .collect();
+        expected.sort_by_key(|s| chess_core::Square::from_algebraic(s).unwrap().index());
+        assert_eq!(attacked, expected);
+
+        let black = game.attacked_squares("black").unwrap();
+        assert_eq!(black, vec!["g7", "h7", "g8"]);
+    }
+
+    #[test]
+    fn checkers_returns_checking_knight() {
+        let game = Game::from_fen("4k3/8/8/8/8/5n2/8/4K3 w - - 0 1").unwrap();
+        assert!(game.is_check());
+        assert_eq!(game.checkers(), vec!["f3"]);
+
+        assert!(Game::new().checkers().is_empty());
+    }
+
+    fn uci_moves(moves: &str) -> Vec<String> {
+        moves.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn threefold_repetition_is_a_draw() {
+        let mut game = Game::new();
+        // The start position occurs for the third time after the last move
+        let moves = uci_moves("g1f3 g8f6 f3g1 f6g8 g1f3 g8f6 f3g1 f6g8");
+        game.play_moves(moves[..7].to_vec()).unwrap();
+        assert_eq!(game.result(), None);
+        assert!(!game.is_game_over());
+
+        game.make_move(&moves[7]).unwrap();
+        assert_eq!(game.result(), Some("draw".to_string()));
+        assert!(game.is_game_over());
+        assert!(game.play("e2e4").is_err());
+        assert_eq!(game.draw_reason(), Some("threefold_repetition".to_string()));
+    }
+
+    #[test]
+    fn draw_reason_names_the_draw() {
+        let stalemate = Game::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+        assert_eq!(stalemate.draw_reason(), Some("stalemate".to_string()));
+
+        let bare_kings = Game::from_fen("4k3/8/8/8/8/8/8/4KB2 w - - 0 1").unwrap();
+        assert_eq!(
+            bare_kings.draw_reason(),
+            Some("insufficient_material".to_string())
+        );
+
+        let mate = Game::from_fen("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3")
+            .unwrap();
+        assert_eq!(mate.result(), Some("black_wins".to_string()));
+        assert_eq!(mate.draw_reason(), None);
+        assert_eq!(Game::new().draw_reason(), None);
+    }
+
+    #[test]
+    fn fifty_move_rule_is_a_draw() {
+        let mut game = Game::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 99 80").unwrap();
+        assert_eq!(game.result(), None);
+        game.make_move("a1a2").unwrap();
+        assert_eq!(game.result(), Some("draw".to_string()));
+        assert_eq!(game.draw_reason(), Some("fifty_move".to_string()));
+
+        let game = Game::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 100 80").unwrap();
+        assert!(game.is_game_over());
+    }
+
+    #[test]
+    fn play_moves_ruy_lopez() {
+        let mut game = Game::new();
+        game.apply_moves(&uci_moves(
+            "e2e4 e7e5 g1f3 b8c6 f1b5 a7a6 b5a4 g8f6 e1g1 f8e7",
+        ))
+        .unwrap();
+        assert_eq!(
+            game.to_fen(),
+            "r1bqk2r/1pppbppp/p1n2n2/4p3/B3P3/5N2/PPPP1PPP/RNBQ1RK1 w kq - 4 6"
+        );
+    }
+
+    #[test]
+    fn play_moves_reports_failing_index() {
+        let mut game = Game::new();
+        let err = game
+            .apply_moves(&uci_moves("e2e4 e7e5 g1f3 e1e3 b8c6"))
+            .unwrap_err();
+        assert_eq!(err, "Move at index 3: Illegal move: e1e3");
+        assert_eq!(game.to_fen(), Game::new().to_fen());
+    }
+
+    #[test]
+    fn board_array_start_position() {
+        let board = Game::new().board_array();
+        assert_eq!(board.len(), 64);
+        assert_eq!(board[..8].concat(), "rnbqkbnr");
+        assert_eq!(board[8..16].concat(), "pppppppp");
+        assert!(board[16..48].iter().all(String::is_empty));
+        assert_eq!(board[48..56].concat(), "PPPPPPPP");
+        assert_eq!(board[56..].concat(), "RNBQKBNR");
+    }
+
+    #[test]
+    fn to_ascii_start_position() {
+        let ascii = Game::new().to_ascii();
+        let lines: Vec<&str> = ascii.lines().collect();
+        assert_eq!(lines.len(), 9);
+        assert_eq!(lines[0], "8 r n b q k b n r");
+        assert_eq!(lines[4], "4 . . . . . . . .");
+        assert_eq!(lines[7], "1 R N B Q K B N R");
+        assert_eq!(lines[8], "  a b c d e f g h");
+    }
+
+    #[test]
+    fn perft_start_position() {
+        let game = Game::new();
+        assert_eq!(game.perft(1), 20.0);
+        assert_eq!(game.perft(3), 8902.0);
+
+        let divide = game.divide(3);
+        assert_eq!(divide.len(), 20);
+        assert_eq!(divide["e2e4"], 600.0);
+        assert_eq!(divide.values().sum::<f64>(), 8902.0);
+    }
+
+    #[test]
+    fn perft_promotions() {
+        let game = Game::from_fen("n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1").unwrap();
+        assert_eq!(game.perft(1), 24.0);
+        assert_eq!(game.perft(2), 496.0);
+        assert_eq!(game.perft(3), 9483.0);
+    }
+
+    #[test]
+    fn evaluate_start_position() {
+        let game = Game::new();
+        assert_eq!(game.evaluate(), 0);
+        assert_eq!(game.evaluate_white(), 0);
+    }
+
+    #[test]
+    fn evaluate_queen_up() {
+        // White has no queen, black to move
+        let game =
+            Game::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNB1KBNR b KQkq - 0 1").unwrap();
+        assert!(game.evaluate() > 800);
+        assert!(game.evaluate_white() < -800);
+    }
 }
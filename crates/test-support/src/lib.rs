@@ -0,0 +1,155 @@
+//! Test helpers shared by the workspace crates.
+//!
+//! [`FakeEngine`] writes a shell script that speaks just enough UCI for
+//! tests that spawn an engine process, so they don't need a real engine.
+
+use std::path::{Path, PathBuf};
+
+/// Builds an executable shell script that acts as a UCI engine.
+///
+/// The script answers `uci` and `isready` and keeps the last `position`
+/// command in `$position`. For every `go` it runs a shell snippet, with the
+/// command itself in `$line`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use test_support::FakeEngine;
+///
+/// let dir = std::env::temp_dir();
+/// let engine = FakeEngine::new("mover")
+///     .on_go(r#"echo "info depth 1 score cp 20 pv e2e4""#)
+///     .bestmove("e2e4")
+///     .write(&dir);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FakeEngine<'a> {
+    name: &'a str,
+    on_go: &'a str,
+    bestmove: Option<&'a str>,
+    options_log: Option<PathBuf>,
+    silent: bool,
+}
+
+impl<'a> FakeEngine<'a> {
+    /// An engine that answers the handshake and ignores `go`.
+    ///
+    /// `name` is both the script's file name and the engine's `id name`.
+    pub fn new(name: &'a str) -> Self {
+        Self {
+            name,
+            on_go: ":",
+            bestmove: None,
+            options_log: None,
+            silent: false,
+        }
+    }
+
+    /// Runs the shell snippet `on_go` for every `go` command.
+    pub fn on_go(mut self, on_go: &'a str) -> Self {
+        self.on_go = on_go;
+        self
+    }
+
+    /// Replies `bestmove <uci>` once the `go` snippet has run.
+    pub fn bestmove(mut self, uci: &'a str) -> Self {
+        self.bestmove = Some(uci);
+        self
+    }
+
+    /// Appends every `setoption` command to the file at `path`.
+    pub fn log_options(mut self, path: impl AsRef<Path>) -> Self {
+        self.options_log = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Reads commands but never replies to any of them.
+    pub fn silent(mut self) -> Self {
+        self.silent = true;
+        self
+    }
+
+    /// Returns the script's source.
+    pub fn script(&self) -> String {
+        if self.silent {
+            return "#!/bin/sh\nwhile read -r line; do :; done\n".to_string();
+        }
+
+        let setoption = match &self.options_log {
+            Some(log) => format!("echo \"$line\" >> '{}'", log.display()),
+            None => ":".to_string(),
+        };
+        let bestmove = match self.bestmove {
+            Some(uci) => format!("\n         echo \"bestmove {}\"", uci),
+            None => String::new(),
+        };
+        format!(
+            r#"#!/bin/sh
+while read -r line; do
+  case "$line" in
+    uci) echo "id name {name}"; echo "uciok" ;;
+    isready) echo "readyok" ;;
+    setoption*) {setoption} ;;
+    position*) position="$line" ;;
+    go*) {on_go}{bestmove} ;;
+    quit) exit 0 ;;
+  esac
+done
+"#,
+            name = self.name,
+            on_go = self.on_go,
+        )
+    }
+
+    /// Writes the script to `dir`, named after the engine, and makes it
+    /// executable. Returns its path.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the script cannot be written.
+    #[cfg(unix)]
+    pub fn write(self, dir: &Path) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = dir.join(self.name);
+        std::fs::write(&path, self.script()).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+}
+
+/// Writes a [`FakeEngine`] named `name` that runs `on_go` for every `go`.
+#[cfg(unix)]
+pub fn fake_engine(dir: &Path, name: &str, on_go: &str) -> PathBuf {
+    FakeEngine::new(name).on_go(on_go).write(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn script_runs_go_snippet_then_bestmove() {
+        let script = FakeEngine::new("mover")
+            .on_go("echo 'info depth 1'")
+            .bestmove("e2e4")
+            .script();
+        assert!(script.contains("echo \"id name mover\""));
+        assert!(script.contains("go*) echo 'info depth 1'\n         echo \"bestmove e2e4\" ;;"));
+        assert!(script.contains("setoption*) : ;;"));
+    }
+
+    #[test]
+    fn script_logs_options() {
+        let script = FakeEngine::new("e")
+            .log_options("/tmp/options.log")
+            .script();
+        assert!(script.contains("setoption*) echo \"$line\" >> '/tmp/options.log' ;;"));
+    }
+
+    #[test]
+    fn silent_script_never_replies() {
+        let script = FakeEngine::new("e").on_go("echo x").silent().script();
+        assert!(!script.contains("echo"));
+    }
+}
@@ -11,21 +11,50 @@ pub enum GuiCommand {
     Extensions,
     /// Check if engine is ready.
     IsReady,
-    /// Set up position.
+    /// Set up position (`position startpos|fen <fen> [moves <move>...]`).
     Position {
-        fen: Option<String>,
+        start: StartPosition,
         moves: Vec<String>,
     },
+    /// Set an engine option (`setoption name <name> [value <value>]`).
+    SetOption { name: String, value: Option<String> },
     /// Start calculating.
     Go(GoOptions),
     /// Stop calculating.
     Stop,
+    /// The expected move was played; continue the ponder search normally.
+    PonderHit,
+    /// Switch debug mode on or off (`debug on|off`).
+    Debug(bool),
+    /// Registration (`register later` or `register name <name> code <code>`).
+    Register(Registration),
     /// Quit the engine.
     Quit,
     /// Unknown command (for forward compatibility).
     Unknown(String),
 }
 
+/// The position a `position` command starts from, before its moves.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StartPosition {
+    /// The standard starting position (`position startpos`).
+    StartPos,
+    /// A position given as FEN (`position fen <fen>`).
+    Fen(String),
+}
+
+/// Arguments of the `register` command.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Registration {
+    /// Postpone registration (`register later`).
+    Later,
+    /// Register with the given details; either may be missing.
+    With {
+        name: Option<String>,
+        code: Option<String>,
+    },
+}
+
 /// Options for the `go` command.
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct GoOptions {
@@ -45,6 +74,8 @@ pub struct GoOptions {
     pub movestogo: Option<u32>,
     /// Search indefinitely until `stop`.
     pub infinite: bool,
+    /// Search in ponder mode, on the position after the expected reply.
+    pub ponder: bool,
 }
 
 impl GuiCommand {
@@ -60,22 +91,35 @@ impl GuiCommand {
             "extensions" => Ok(GuiCommand::Extensions),
             "isready" => Ok(GuiCommand::IsReady),
             "stop" => Ok(GuiCommand::Stop),
+            "ponderhit" => Ok(GuiCommand::PonderHit),
             "quit" => Ok(GuiCommand::Quit),
             "position" => Self::parse_position(parts),
             "go" => Self::parse_go(parts),
+            "setoption" => Self::parse_setoption(parts),
+            "debug" => match parts.next() {
+                Some("on") => Ok(GuiCommand::Debug(true)),
+                Some("off") => Ok(GuiCommand::Debug(false)),
+                _ => Err(UciError::ParseError(
+                    "Expected 'on' or 'off' after 'debug'".to_string(),
+                )),
+            },
+            "register" => Self::parse_register(parts),
             "" => Ok(GuiCommand::Unknown(String::new())),
             _ => Ok(GuiCommand::Unknown(input.to_string())),
         }
     }
 
     fn parse_position<'a>(mut parts: impl Iterator<Item = &'a str>) -> Result<Self, UciError> {
-        let mut fen = None;
-        let mut moves = Vec::new();
-
         // Parse "startpos" or "fen <fen>"
-        match parts.next() {
+        let start = match parts.next() {
             Some("startpos") => {
-                fen = None; // Use default starting position
+                // Skip ahead to "moves", if present
+                for part in parts.by_ref() {
+                    if part == "moves" {
+                        break;
+                    }
+                }
+                StartPosition::StartPos
             }
             Some("fen") => {
                 // Collect FEN parts until "moves" or end
@@ -86,9 +130,12 @@ impl GuiCommand {
                     }
                     fen_parts.push(part);
                 }
-                if !fen_parts.is_empty() {
-                    fen = Some(fen_parts.join(" "));
+                if fen_parts.is_empty() {
+                    return Err(UciError::ParseError(
+                        "Expected a FEN after 'fen'".to_string(),
+                    ));
                 }
+                StartPosition::Fen(fen_parts.join(" "))
             }
             Some(other) => {
                 return Err(UciError::ParseError(format!(
@@ -101,21 +148,77 @@ impl GuiCommand {
                     "Expected 'startpos' or 'fen'".to_string(),
                 ));
             }
+        };
+
+        let moves = parts.map(|s| s.to_string()).collect();
+
+        Ok(GuiCommand::Position { start, moves })
+    }
+
+    fn parse_setoption<'a>(mut parts: impl Iterator<Item = &'a str>) -> Result<Self, UciError> {
+        if parts.next() != Some("name") {
+            return Err(UciError::ParseError(
+                "Expected 'name' after 'setoption'".to_string(),
+            ));
         }
 
-        // Check if we're at "moves" or need to find it
-        // (for startpos case, need to advance to moves)
-        let remaining: Vec<&str> = parts.collect();
-        let moves_start = remaining.iter().position(|&s| s == "moves");
+        // Option names and values may contain spaces
+        let mut name = Vec::new();
+        let mut value: Option<Vec<&str>> = None;
+        for part in parts {
+            match &mut value {
+                Some(value) => value.push(part),
+                None if part == "value" => value = Some(Vec::new()),
+                None => name.push(part),
+            }
+        }
 
-        if let Some(idx) = moves_start {
-            moves = remaining[idx + 1..].iter().map(|s| s.to_string()).collect();
-        } else if fen.is_some() {
-            // FEN case already consumed "moves" in the loop
-            moves = remaining.iter().map(|s| s.to_string()).collect();
+        if name.is_empty() {
+            return Err(UciError::ParseError("Missing option name".to_string()));
         }
 
-        Ok(GuiCommand::Position { fen, moves })
+        Ok(GuiCommand::SetOption {
+            name: name.join(" "),
+            value: value.map(|v| v.join(" ")),
+        })
+    }
+
+    fn parse_register<'a>(mut parts: impl Iterator<Item = &'a str>) -> Result<Self, UciError> {
+        let first = parts.next();
+        if first == Some("later") {
+            return Ok(GuiCommand::Register(Registration::Later));
+        }
+
+        // Names and codes may contain spaces
+        let mut name: Option<Vec<&str>> = None;
+        let mut code: Option<Vec<&str>> = None;
+        let mut current = None;
+        for part in first.into_iter().chain(parts) {
+            match part {
+                "name" => current = Some(name.insert(Vec::new())),
+                "code" => current = Some(code.insert(Vec::new())),
+                _ => match current.as_mut() {
+                    Some(field) => field.push(part),
+                    None => {
+                        return Err(UciError::ParseError(format!(
+                            "Expected 'later', 'name' or 'code' after 'register', got '{}'",
+                            part
+                        )))
+                    }
+                },
+            }
+        }
+
+        if name.is_none() && code.is_none() {
+            return Err(UciError::ParseError(
+                "Expected 'later', 'name' or 'code' after 'register'".to_string(),
+            ));
+        }
+
+        Ok(GuiCommand::Register(Registration::With {
+            name: name.map(|n| n.join(" ")),
+            code: code.map(|c| c.join(" ")),
+        }))
     }
 
     fn parse_go<'a>(parts: impl Iterator<Item = &'a str>) -> Result<Self, UciError> {
@@ -170,6 +273,9 @@ impl GuiCommand {
                 "infinite" => {
                     opts.infinite = true;
                 }
+                "ponder" => {
+                    opts.ponder = true;
+                }
                 _ => {}
             }
             i += 1;
@@ -199,7 +305,7 @@ mod tests {
         assert_eq!(
             cmd,
             GuiCommand::Position {
-                fen: None,
+                start: StartPosition::StartPos,
                 moves: vec![]
             }
         );
@@ -211,7 +317,7 @@ mod tests {
         assert_eq!(
             cmd,
             GuiCommand::Position {
-                fen: None,
+                start: StartPosition::StartPos,
                 moves: vec!["e2e4".to_string(), "e7e5".to_string()]
             }
         );
@@ -226,7 +332,7 @@ mod tests {
         assert_eq!(
             cmd,
             GuiCommand::Position {
-                fen: Some(
+                start: StartPosition::Fen(
                     "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1".to_string()
                 ),
                 moves: vec![]
@@ -234,6 +340,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_position_startpos_and_fen_moves_differ() {
+        let startpos = GuiCommand::parse("position startpos moves e2e4").unwrap();
+        assert_eq!(
+            startpos,
+            GuiCommand::Position {
+                start: StartPosition::StartPos,
+                moves: vec!["e2e4".to_string()]
+            }
+        );
+
+        let fen = GuiCommand::parse(
+            "position fen rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 moves e2e4",
+        )
+        .unwrap();
+        assert_eq!(
+            fen,
+            GuiCommand::Position {
+                start: StartPosition::Fen(
+                    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1".to_string()
+                ),
+                moves: vec!["e2e4".to_string()]
+            }
+        );
+        assert_ne!(startpos, fen);
+    }
+
+    #[test]
+    fn parse_position_fen_without_fen_is_an_error() {
+        assert!(GuiCommand::parse("position fen moves e2e4").is_err());
+        assert!(GuiCommand::parse("position").is_err());
+    }
+
     #[test]
     fn parse_go_movetime() {
         let cmd = GuiCommand::parse("go movetime 1000").unwrap();
@@ -263,4 +402,92 @@ mod tests {
             panic!("Expected Go command");
         }
     }
+
+    #[test]
+    fn parse_setoption_with_value() {
+        let cmd = GuiCommand::parse("setoption name Move Overhead value 30").unwrap();
+        assert_eq!(
+            cmd,
+            GuiCommand::SetOption {
+                name: "Move Overhead".to_string(),
+                value: Some("30".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_setoption_button() {
+        let cmd = GuiCommand::parse("setoption name Clear Hash").unwrap();
+        assert_eq!(
+            cmd,
+            GuiCommand::SetOption {
+                name: "Clear Hash".to_string(),
+                value: None,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_go_ponder() {
+        let cmd = GuiCommand::parse("go ponder wtime 1000 btime 2000").unwrap();
+        if let GuiCommand::Go(opts) = cmd {
+            assert!(opts.ponder);
+            assert_eq!(opts.wtime, Some(1000));
+            assert_eq!(opts.btime, Some(2000));
+        } else {
+            panic!("Expected Go command");
+        }
+        assert_eq!(
+            GuiCommand::parse("ponderhit").unwrap(),
+            GuiCommand::PonderHit
+        );
+    }
+
+    #[test]
+    fn parse_debug() {
+        assert_eq!(
+            GuiCommand::parse("debug on").unwrap(),
+            GuiCommand::Debug(true)
+        );
+        assert_eq!(
+            GuiCommand::parse("debug off").unwrap(),
+            GuiCommand::Debug(false)
+        );
+        assert!(GuiCommand::parse("debug").is_err());
+    }
+
+    #[test]
+    fn parse_register_later() {
+        assert_eq!(
+            GuiCommand::parse("register later").unwrap(),
+            GuiCommand::Register(Registration::Later)
+        );
+    }
+
+    #[test]
+    fn parse_register_name_and_code() {
+        assert_eq!(
+            GuiCommand::parse("register name Stefan MK code 4359874324").unwrap(),
+            GuiCommand::Register(Registration::With {
+                name: Some("Stefan MK".to_string()),
+                code: Some("4359874324".to_string()),
+            })
+        );
+        assert_eq!(
+            GuiCommand::parse("register code 123").unwrap(),
+            GuiCommand::Register(Registration::With {
+                name: None,
+                code: Some("123".to_string()),
+            })
+        );
+        assert!(GuiCommand::parse("register").is_err());
+        assert!(GuiCommand::parse("register soon").is_err());
+    }
+
+    #[test]
+    fn parse_setoption_requires_name() {
+        assert!(GuiCommand::parse("setoption").is_err());
+        assert!(GuiCommand::parse("setoption value 1").is_err());
+        assert!(GuiCommand::parse("setoption name").is_err());
+    }
 }
@@ -21,6 +21,8 @@ pub struct EngineInfo {
     pub seldepth: Option<u32>,
     /// Score evaluation.
     pub score: Option<Score>,
+    /// Win/draw/loss expectation in per mille, from the engine's side.
+    pub wdl: Option<(u32, u32, u32)>,
     /// Nodes searched.
     pub nodes: Option<u64>,
     /// Nodes per second.
@@ -35,6 +37,8 @@ pub struct EngineInfo {
     pub currmovenumber: Option<u32>,
     /// Hash table usage (per mille).
     pub hashfull: Option<u32>,
+    /// Positions found in endgame tablebases.
+    pub tbhits: Option<u64>,
     /// Arbitrary string info.
     pub string: Option<String>,
     /// Custom extension data.
@@ -48,6 +52,10 @@ impl EngineInfo {
     }
 
     /// Format as UCI info string.
+    ///
+    /// Fields appear in the order engines conventionally use: depth,
+    /// seldepth, score, wdl, nodes, nps, hashfull, tbhits, time, currmove,
+    /// currmovenumber, pv, then string. Unset fields are omitted.
     pub fn to_uci(&self) -> String {
         let mut parts = vec!["info".to_string()];
 
@@ -63,26 +71,32 @@ impl EngineInfo {
                 Score::Mate(m) => parts.push(format!("score mate {}", m)),
             }
         }
+        if let Some((w, d, l)) = self.wdl {
+            parts.push(format!("wdl {} {} {}", w, d, l));
+        }
         if let Some(n) = self.nodes {
             parts.push(format!("nodes {}", n));
         }
         if let Some(n) = self.nps {
             parts.push(format!("nps {}", n));
         }
+        if let Some(h) = self.hashfull {
+            parts.push(format!("hashfull {}", h));
+        }
+        if let Some(n) = self.tbhits {
+            parts.push(format!("tbhits {}", n));
+        }
         if let Some(t) = self.time {
             parts.push(format!("time {}", t));
         }
-        if !self.pv.is_empty() {
-            parts.push(format!("pv {}", self.pv.join(" ")));
-        }
         if let Some(ref m) = self.currmove {
             parts.push(format!("currmove {}", m));
         }
         if let Some(n) = self.currmovenumber {
             parts.push(format!("currmovenumber {}", n));
         }
-        if let Some(h) = self.hashfull {
-            parts.push(format!("hashfull {}", h));
+        if !self.pv.is_empty() {
+            parts.push(format!("pv {}", self.pv.join(" ")));
         }
         if let Some(ref s) = self.string {
             parts.push(format!("string {}", s));
@@ -144,6 +158,17 @@ impl EngineInfo {
                         }
                     }
                 }
+                "wdl" => {
+                    let values: Vec<u32> = parts[i + 1..]
+                        .iter()
+                        .take(3)
+                        .map_while(|p| p.parse().ok())
+                        .collect();
+                    if let [w, d, l] = values[..] {
+                        info.wdl = Some((w, d, l));
+                    }
+                    i += values.len();
+                }
                 "nodes" => {
                     i += 1;
                     if i < parts.len() {
@@ -189,6 +214,12 @@ impl EngineInfo {
                         info.hashfull = parts[i].parse().ok();
                     }
                 }
+                "tbhits" => {
+                    i += 1;
+                    if i < parts.len() {
+                        info.tbhits = parts[i].parse().ok();
+                    }
+                }
                 "string" => {
                     i += 1;
                     // Rest of line is the string
@@ -223,6 +254,7 @@ fn is_info_keyword(s: &str) -> bool {
         "depth"
             | "seldepth"
             | "score"
+            | "wdl"
             | "nodes"
             | "nps"
             | "time"
@@ -230,6 +262,7 @@ fn is_info_keyword(s: &str) -> bool {
             | "currmove"
             | "currmovenumber"
             | "hashfull"
+            | "tbhits"
             | "string"
     )
 }
@@ -265,6 +298,12 @@ impl InfoBuilder {
         self
     }
 
+    /// Win/draw/loss expectation in per mille.
+    pub fn wdl(mut self, win: u32, draw: u32, loss: u32) -> Self {
+        self.info.wdl = Some((win, draw, loss));
+        self
+    }
+
     pub fn nodes(mut self, n: u64) -> Self {
         self.info.nodes = Some(n);
         self
@@ -290,6 +329,22 @@ impl InfoBuilder {
         self
     }
 
+    pub fn currmovenumber(mut self, n: u32) -> Self {
+        self.info.currmovenumber = Some(n);
+        self
+    }
+
+    /// Hash table usage in per mille (0-1000).
+    pub fn hashfull(mut self, per_mille: u32) -> Self {
+        self.info.hashfull = Some(per_mille);
+        self
+    }
+
+    pub fn tbhits(mut self, n: u64) -> Self {
+        self.info.tbhits = Some(n);
+        self
+    }
+
     pub fn string(mut self, s: &str) -> Self {
         self.info.string = Some(s.to_string());
         self
@@ -300,7 +355,14 @@ impl InfoBuilder {
         self
     }
 
-    pub fn build(self) -> EngineInfo {
+    /// Finishes the info. If `nps` was not set it is computed from
+    /// `nodes` and a non-zero `time`.
+    pub fn build(mut self) -> EngineInfo {
+        if self.info.nps.is_none() {
+            if let (Some(nodes), Some(time)) = (self.info.nodes, self.info.time) {
+                self.info.nps = nodes.saturating_mul(1000).checked_div(time);
+            }
+        }
         self.info
     }
 }
@@ -325,6 +387,53 @@ mod tests {
         assert!(uci.contains("pv e2e4 e7e5"));
     }
 
+    #[test]
+    fn info_to_uci_extended_fields() {
+        let info = InfoBuilder::new()
+            .depth(20)
+            .seldepth(25)
+            .score_cp(12)
+            .nodes(1_800_000)
+            .nps(900_000)
+            .hashfull(120)
+            .tbhits(7)
+            .time(2000)
+            .currmove("e2e4")
+            .currmovenumber(1)
+            .pv(vec!["e2e4".to_string()])
+            .build();
+
+        assert_eq!(
+            info.to_uci(),
+            "info depth 20 seldepth 25 score cp 12 nodes 1800000 nps 900000 \
+             hashfull 120 tbhits 7 time 2000 currmove e2e4 currmovenumber 1 pv e2e4"
+        );
+        assert_eq!(EngineInfo::parse(&info.to_uci()), Some(info));
+    }
+
+    #[test]
+    fn nps_is_computed_from_nodes_and_time() {
+        let info = InfoBuilder::new().nodes(450_000).time(500).build();
+        assert_eq!(info.nps, Some(900_000));
+
+        // No time, or zero time, leaves nps unset
+        assert_eq!(InfoBuilder::new().nodes(10).build().nps, None);
+        assert_eq!(InfoBuilder::new().nodes(10).time(0).build().nps, None);
+
+        // An explicit value wins
+        let info = InfoBuilder::new().nodes(10).time(10).nps(5).build();
+        assert_eq!(info.nps, Some(5));
+    }
+
+    #[test]
+    fn omitted_fields_are_skipped() {
+        let uci = InfoBuilder::new().depth(3).build().to_uci();
+        assert_eq!(uci, "info depth 3");
+        for field in ["seldepth", "nps", "hashfull", "tbhits", "currmove"] {
+            assert!(!uci.contains(field), "{}", uci);
+        }
+    }
+
     #[test]
     fn parse_info() {
         let line = "info depth 12 score cp 30 nodes 125000 nps 500000 pv e2e4 e7e5 g1f3";
@@ -337,6 +446,27 @@ mod tests {
         assert_eq!(info.pv, vec!["e2e4", "e7e5", "g1f3"]);
     }
 
+    #[test]
+    fn wdl_round_trips() {
+        let line = "info depth 18 score cp 35 wdl 600 350 50 nodes 1000 pv e2e4";
+        let info = EngineInfo::parse(line).unwrap();
+
+        assert_eq!(info.score, Some(Score::Cp(35)));
+        assert_eq!(info.wdl, Some((600, 350, 50)));
+        assert_eq!(info.nodes, Some(1000));
+        assert_eq!(info.to_uci(), line);
+
+        let built = InfoBuilder::new().score_cp(35).wdl(600, 350, 50).build();
+        assert_eq!(built.to_uci(), "info score cp 35 wdl 600 350 50");
+    }
+
+    #[test]
+    fn incomplete_wdl_is_ignored() {
+        let info = EngineInfo::parse("info score cp 10 wdl 600 350 nodes 5").unwrap();
+        assert_eq!(info.wdl, None);
+        assert_eq!(info.nodes, Some(5));
+    }
+
     #[test]
     fn parse_mate_score() {
         let line = "info depth 20 score mate 3 pv e2e4";
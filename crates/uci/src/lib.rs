@@ -5,11 +5,15 @@
 //!
 //! # Standard UCI Commands
 //!
-//! - `uci` - Initialize engine, get id and options
+//! - `uci` - Initialize engine, get id and options (`option name ...`)
 //! - `isready` / `readyok` - Synchronization
-//! - `position fen <fen> [moves <move>...]` - Set position
+//! - `setoption name <name> [value <value>]` - Configure an option
+//! - `position startpos|fen <fen> [moves <move>...]` - Set position
 //! - `go [movetime <ms>] [depth <d>]` - Start search
 //! - `stop` - Stop search
+//! - `ponderhit` - The pondered move was played
+//! - `debug on|off` - Toggle debug output
+//! - `register later | name <name> code <code>` - Registration
 //! - `quit` - Exit engine
 //!
 //! # Extensions
@@ -20,10 +24,12 @@
 mod command;
 mod extension;
 mod info;
+mod option;
 
-pub use command::{GoOptions, GuiCommand};
+pub use command::{GoOptions, GuiCommand, Registration, StartPosition};
 pub use extension::{Extension, ExtensionValue};
 pub use info::{EngineInfo, InfoBuilder, Score};
+pub use option::OptionType;
 
 use std::io::{BufRead, Write};
 use thiserror::Error;
@@ -51,13 +57,26 @@ pub enum EngineMessage {
     /// Engine is ready.
     ReadyOk,
     /// Search information.
-    Info(EngineInfo),
+    Info(Box<EngineInfo>),
     /// Best move found.
     BestMove { mv: String, ponder: Option<String> },
     /// Extension declaration.
     Extension(Extension),
     /// Extensions query complete.
     ExtensionsOk,
+    /// Declaration of a configurable option, sent in reply to `uci`.
+    Opt {
+        name: String,
+        opt_type: OptionType,
+        /// Default value; an empty string is sent as `<empty>`.
+        default: Option<String>,
+        /// Lower bound of a spin option.
+        min: Option<i64>,
+        /// Upper bound of a spin option.
+        max: Option<i64>,
+        /// Allowed values of a combo option.
+        var: Vec<String>,
+    },
 }
 
 impl EngineMessage {
@@ -85,6 +104,30 @@ impl EngineMessage {
                 format!("extension {} description \"{}\"", ext.name, ext.description)
             }
             EngineMessage::ExtensionsOk => "extensionsok".to_string(),
+            EngineMessage::Opt {
+                name,
+                opt_type,
+                default,
+                min,
+                max,
+                var,
+            } => {
+                let mut line = format!("option name {} type {}", name, opt_type);
+                if let Some(d) = default {
+                    let d = if d.is_empty() { "<empty>" } else { d };
+                    line.push_str(&format!(" default {}", d));
+                }
+                if let Some(m) = min {
+                    line.push_str(&format!(" min {}", m));
+                }
+                if let Some(m) = max {
+                    line.push_str(&format!(" max {}", m));
+                }
+                for v in var {
+                    line.push_str(&format!(" var {}", v));
+                }
+                line
+            }
         }
     }
 }
@@ -142,7 +185,7 @@ impl<R: BufRead, W: Write> UciEngine<R, W> {
 
     /// Send search info.
     pub fn send_info(&mut self, info: EngineInfo) -> Result<(), UciError> {
-        self.send(&EngineMessage::Info(info))
+        self.send(&EngineMessage::Info(Box::new(info)))
     }
 
     /// Declare a supported extension.
@@ -153,6 +196,26 @@ impl<R: BufRead, W: Write> UciEngine<R, W> {
         }))
     }
 
+    /// Declare a configurable option.
+    pub fn send_option(
+        &mut self,
+        name: &str,
+        opt_type: OptionType,
+        default: Option<&str>,
+        min: Option<i64>,
+        max: Option<i64>,
+        var: &[&str],
+    ) -> Result<(), UciError> {
+        self.send(&EngineMessage::Opt {
+            name: name.to_string(),
+            opt_type,
+            default: default.map(str::to_string),
+            min,
+            max,
+            var: var.iter().map(|v| v.to_string()).collect(),
+        })
+    }
+
     /// Send extensionsok.
     pub fn send_extensionsok(&mut self) -> Result<(), UciError> {
         self.send(&EngineMessage::ExtensionsOk)
@@ -163,3 +226,96 @@ impl<R: BufRead, W: Write> UciEngine<R, W> {
 pub fn stdio_engine() -> UciEngine<std::io::BufReader<std::io::Stdin>, std::io::Stdout> {
     UciEngine::new(std::io::BufReader::new(std::io::stdin()), std::io::stdout())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn option(
+        name: &str,
+        opt_type: OptionType,
+        default: Option<&str>,
+        range: Option<(i64, i64)>,
+        var: &[&str],
+    ) -> String {
+        EngineMessage::Opt {
+            name: name.to_string(),
+            opt_type,
+            default: default.map(str::to_string),
+            min: range.map(|(min, _)| min),
+            max: range.map(|(_, max)| max),
+            var: var.iter().map(|v| v.to_string()).collect(),
+        }
+        .to_uci()
+    }
+
+    #[test]
+    fn spin_option() {
+        assert_eq!(
+            option("Hash", OptionType::Spin, Some("16"), Some((1, 1024)), &[]),
+            "option name Hash type spin default 16 min 1 max 1024"
+        );
+    }
+
+    #[test]
+    fn check_option() {
+        assert_eq!(
+            option("OwnBook", OptionType::Check, Some("true"), None, &[]),
+            "option name OwnBook type check default true"
+        );
+    }
+
+    #[test]
+    fn combo_option() {
+        assert_eq!(
+            option(
+                "Style",
+                OptionType::Combo,
+                Some("Normal"),
+                None,
+                &["Solid", "Normal", "Risky"]
+            ),
+            "option name Style type combo default Normal var Solid var Normal var Risky"
+        );
+    }
+
+    #[test]
+    fn string_option() {
+        assert_eq!(
+            option("EvalFile", OptionType::String, Some("eval.toml"), None, &[]),
+            "option name EvalFile type string default eval.toml"
+        );
+        assert_eq!(
+            option("SyzygyPath", OptionType::String, Some(""), None, &[]),
+            "option name SyzygyPath type string default <empty>"
+        );
+    }
+
+    #[test]
+    fn button_option() {
+        assert_eq!(
+            option("Clear Hash", OptionType::Button, None, None, &[]),
+            "option name Clear Hash type button"
+        );
+    }
+
+    #[test]
+    fn send_option_writes_line() {
+        let mut output = Vec::new();
+        let mut engine = UciEngine::new(std::io::empty(), &mut output);
+        engine
+            .send_option(
+                "Threads",
+                OptionType::Spin,
+                Some("1"),
+                Some(1),
+                Some(1),
+                &[],
+            )
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "option name Threads type spin default 1 min 1 max 1\n"
+        );
+    }
+}
@@ -0,0 +1,37 @@
+//! UCI engine option types.
+
+use std::fmt;
+
+/// Type of an engine option declared with `option name <name> type <type>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionType {
+    /// Boolean option (`true` or `false`).
+    Check,
+    /// Integer option within `min`..=`max`.
+    Spin,
+    /// One of a fixed list of `var` values.
+    Combo,
+    /// Action without a value, triggered by `setoption name <name>`.
+    Button,
+    /// Free-form text.
+    String,
+}
+
+impl OptionType {
+    /// Returns the UCI name of the type.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OptionType::Check => "check",
+            OptionType::Spin => "spin",
+            OptionType::Combo => "combo",
+            OptionType::Button => "button",
+            OptionType::String => "string",
+        }
+    }
+}
+
+impl fmt::Display for OptionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}